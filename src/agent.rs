@@ -3,6 +3,9 @@
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
+use crate::types::Score;
+use crate::visibility::Visibility;
+
 /// Agent color for UI display
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "lowercase")]
@@ -126,11 +129,11 @@ pub struct ConsensusRole {
     pub can_veto: bool,
     /// Required approval threshold (0.0-1.0)
     #[serde(default = "default_vote_threshold")]
-    pub vote_threshold: f64,
+    pub vote_threshold: Score,
 }
 
-fn default_vote_threshold() -> f64 {
-    0.67
+fn default_vote_threshold() -> Score {
+    Score::clamped(0.67)
 }
 
 impl Default for ConsensusRole {
@@ -138,7 +141,7 @@ impl Default for ConsensusRole {
         Self {
             priority: 50,
             can_veto: false,
-            vote_threshold: 0.67,
+            vote_threshold: default_vote_threshold(),
         }
     }
 }
@@ -148,7 +151,7 @@ impl ConsensusRole {
         Self {
             priority,
             can_veto: false,
-            vote_threshold: 0.67,
+            vote_threshold: default_vote_threshold(),
         }
     }
 
@@ -157,8 +160,9 @@ impl ConsensusRole {
         self
     }
 
+    /// Clamped into `[0, 1]`.
     pub fn with_threshold(mut self, threshold: f64) -> Self {
-        self.vote_threshold = threshold;
+        self.vote_threshold = Score::clamped(threshold);
         self
     }
 }
@@ -226,6 +230,9 @@ pub struct Agent {
     /// Example interactions
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub examples: Vec<AgentExample>,
+    /// Highest visibility of rules and known issues this agent may receive
+    #[serde(default, skip_serializing_if = "Visibility::is_public")]
+    pub access: Visibility,
 }
 
 impl Agent {
@@ -246,6 +253,7 @@ impl Agent {
             consensus: None,
             prompt: prompt.into(),
             examples: Vec::new(),
+            access: Visibility::Public,
         }
     }
 
@@ -279,6 +287,11 @@ impl Agent {
         self
     }
 
+    pub fn with_access(mut self, access: Visibility) -> Self {
+        self.access = access;
+        self
+    }
+
     pub fn with_consensus(mut self, consensus: ConsensusRole) -> Self {
         self.consensus = Some(consensus);
         self
@@ -320,7 +333,7 @@ mod tests {
         let role = ConsensusRole::new(80).with_veto().with_threshold(0.75);
         assert_eq!(role.priority, 80);
         assert!(role.can_veto);
-        assert!((role.vote_threshold - 0.75).abs() < f64::EPSILON);
+        assert!((role.vote_threshold.get() - 0.75).abs() < f64::EPSILON);
     }
 
     #[test]
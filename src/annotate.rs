@@ -0,0 +1,337 @@
+//! Per-file pull request annotations derived from the map
+
+use std::fmt::Write;
+
+use serde::Serialize;
+
+use crate::module_map::ModuleMap;
+use crate::types::IssueSeverity;
+
+/// Known issue touching an annotated file
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct IssueNote {
+    pub id: String,
+    pub severity: IssueSeverity,
+    pub description: String,
+    /// First evidence line inside the file; `None` for module-wide issues
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub line: Option<u32>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct FileAnnotation {
+    pub path: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub module_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub group_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub domain_id: Option<String>,
+    /// Group rules first, then domain rules
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub boundary_rules: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub issues: Vec<IssueNote>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub reviewers: Vec<String>,
+    /// Generated file: issues are not attributed to it
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub generated: bool,
+}
+
+impl FileAnnotation {
+    pub fn is_empty(&self) -> bool {
+        self.boundary_rules.is_empty() && self.issues.is_empty() && !self.generated
+    }
+
+    fn body(&self) -> String {
+        let mut body = String::new();
+        if let Some(module_id) = &self.module_id {
+            let _ = write!(body, "Module `{module_id}`");
+            if let Some(domain_id) = &self.domain_id {
+                let _ = write!(body, " (domain `{domain_id}`)");
+            }
+            body.push('\n');
+        }
+        if self.generated {
+            body.push_str("Generated code: do not edit by hand\n");
+        }
+        for rule in &self.boundary_rules {
+            let _ = writeln!(body, "- Boundary: {rule}");
+        }
+        for issue in &self.issues {
+            let _ = writeln!(
+                body,
+                "- [{}] {}: {}",
+                issue.severity, issue.id, issue.description
+            );
+        }
+        body
+    }
+}
+
+/// GitHub pull request review comment
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ReviewComment {
+    pub path: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub line: Option<u32>,
+    /// `"line"` or `"file"`
+    pub subject_type: &'static str,
+    pub body: String,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct PrAnnotations {
+    pub files: Vec<FileAnnotation>,
+    /// Owners of every touched domain, deduplicated
+    pub reviewers: Vec<String>,
+    /// Changed files outside every module
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub unowned: Vec<String>,
+    /// Modules transitively depending on the changed ones
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub impacted_modules: Vec<String>,
+}
+
+impl PrAnnotations {
+    /// One comment per annotated file, anchored at the first issue line if any.
+    pub fn to_review_comments(&self) -> Vec<ReviewComment> {
+        self.files
+            .iter()
+            .filter(|f| !f.is_empty())
+            .map(|f| {
+                let line = f.issues.iter().find_map(|i| i.line);
+                ReviewComment {
+                    path: f.path.clone(),
+                    line,
+                    subject_type: if line.is_some() { "line" } else { "file" },
+                    body: f.body(),
+                }
+            })
+            .collect()
+    }
+
+    /// Markdown summary for a check run.
+    pub fn to_check_summary(&self) -> String {
+        let mut out = format!("{} file(s) changed\n", self.files.len());
+        if !self.reviewers.is_empty() {
+            let _ = writeln!(out, "\nSuggested reviewers: {}", self.reviewers.join(", "));
+        }
+        if !self.impacted_modules.is_empty() {
+            let _ = writeln!(
+                out,
+                "\nImpacted modules: {}",
+                self.impacted_modules.join(", ")
+            );
+        }
+        let issues: Vec<&IssueNote> = self.files.iter().flat_map(|f| &f.issues).collect();
+        if !issues.is_empty() {
+            out.push_str("\n## Known issues\n\n");
+            let mut seen = Vec::new();
+            for issue in issues {
+                if !seen.contains(&&issue.id) {
+                    seen.push(&issue.id);
+                    let _ = writeln!(
+                        out,
+                        "- [{}] {}: {}",
+                        issue.severity, issue.id, issue.description
+                    );
+                }
+            }
+        }
+        if !self.unowned.is_empty() {
+            out.push_str("\n## Unowned files\n\n");
+            for path in &self.unowned {
+                let _ = writeln!(out, "- `{path}`");
+            }
+        }
+        out
+    }
+}
+
+impl ModuleMap {
+    /// Owning module/domain, boundary rules, related issues and reviewers per changed file.
+    pub fn annotate_pr(&self, changed_files: &[&str]) -> PrAnnotations {
+        let mut annotations = PrAnnotations::default();
+        for path in changed_files {
+            let Some(module) = self.modules.iter().find(|m| m.contains_file(path)) else {
+                annotations.unowned.push(path.to_string());
+                annotations.files.push(FileAnnotation {
+                    path: path.to_string(),
+                    ..Default::default()
+                });
+                continue;
+            };
+            let group = self.find_group_containing(&module.id);
+            let domain = self.find_domain_containing_module(&module.id);
+
+            let mut boundary_rules: Vec<String> = Vec::new();
+            for rule in group
+                .into_iter()
+                .flat_map(|g| &g.boundary_rules)
+                .chain(domain.into_iter().flat_map(|d| &d.boundary_rules))
+            {
+                if !boundary_rules.contains(rule) {
+                    boundary_rules.push(rule.clone());
+                }
+            }
+
+            let generated = self.is_generated(path);
+            let issues = module
+                .known_issues
+                .iter()
+                .filter(|_| !generated)
+                .filter_map(|issue| {
+                    let line = if issue.evidence.is_empty() {
+                        None
+                    } else {
+                        let location = issue.evidence.iter().find(|e| e.file == *path)?;
+                        location.is_line_level().then_some(location.start_line)
+                    };
+                    Some(IssueNote {
+                        id: issue.id.clone(),
+                        severity: self.effective_severity(issue, module),
+                        description: issue.description.clone(),
+                        line,
+                    })
+                })
+                .collect();
+
+            let reviewers: Vec<String> = self
+                .find_module_owner(&module.id)
+                .map(String::from)
+                .into_iter()
+                .collect();
+            for reviewer in &reviewers {
+                if !annotations.reviewers.contains(reviewer) {
+                    annotations.reviewers.push(reviewer.clone());
+                }
+            }
+
+            annotations.files.push(FileAnnotation {
+                path: path.to_string(),
+                module_id: Some(module.id.clone()),
+                group_id: group.map(|g| g.id.clone()),
+                domain_id: domain.map(|d| d.id.clone()),
+                boundary_rules,
+                issues,
+                reviewers,
+                generated,
+            });
+        }
+
+        let changed: Vec<&str> = annotations
+            .files
+            .iter()
+            .filter_map(|f| f.module_id.as_deref())
+            .collect();
+        annotations.impacted_modules = self
+            .impacted_modules(changed_files)
+            .into_iter()
+            .filter(|m| !changed.contains(&m.id.as_str()))
+            .map(|m| m.id.clone())
+            .collect();
+        annotations
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        Domain, EvidenceLocation, GeneratedCode, GeneratorInfo, IssueCategory, KnownIssue, Module,
+        ModuleDependency, ModuleGroup, ProjectMetadata, TechStack,
+    };
+
+    fn module(id: &str, dependencies: &[&str]) -> Module {
+        Module::new(id, id, vec![format!("src/{id}/")])
+            .with_dependencies(
+                dependencies
+                    .iter()
+                    .map(|d| ModuleDependency::runtime(*d))
+                    .collect(),
+            )
+            .with_primary_language("rust")
+    }
+
+    #[test]
+    fn test_annotate_pr() {
+        let mut map = ModuleMap::new(
+            GeneratorInfo::new("test", "1.0.0"),
+            ProjectMetadata::new("shop", TechStack::new("rust")),
+            vec![module("auth", &[]), module("api", &["auth"])],
+            vec![
+                ModuleGroup::new("core", "Core", vec!["auth".into()])
+                    .with_boundary_rules(vec!["No HTTP types".into()]),
+            ],
+        )
+        .with_domains(vec![
+            Domain::new("identity", "Identity", vec!["core".into()])
+                .with_owner("@acme/identity")
+                .with_boundary_rules(vec!["Tokens never leave identity".into()]),
+        ]);
+        map.modules[0].known_issues = vec![
+            KnownIssue::new(
+                "token-leak",
+                "Tokens logged",
+                IssueSeverity::High,
+                IssueCategory::Security,
+            )
+            .with_evidence(vec![EvidenceLocation::new("src/auth/token.rs", 42)]),
+            KnownIssue::new(
+                "slow-hash",
+                "bcrypt cost too high",
+                IssueSeverity::Low,
+                IssueCategory::Performance,
+            ),
+            KnownIssue::new(
+                "other-file",
+                "Elsewhere",
+                IssueSeverity::Medium,
+                IssueCategory::Correctness,
+            )
+            .with_evidence(vec![EvidenceLocation::new("src/auth/session.rs", 3)]),
+        ];
+
+        let pr = map.annotate_pr(&["src/auth/token.rs", "README.md"]);
+        let token = &pr.files[0];
+        assert_eq!(token.domain_id.as_deref(), Some("identity"));
+        assert_eq!(
+            token.boundary_rules,
+            vec!["No HTTP types", "Tokens never leave identity"]
+        );
+        let ids: Vec<_> = token.issues.iter().map(|i| i.id.as_str()).collect();
+        assert_eq!(ids, vec!["token-leak", "slow-hash"]);
+        assert_eq!(token.issues[0].line, Some(42));
+        assert_eq!(pr.reviewers, vec!["@acme/identity"]);
+        assert_eq!(pr.unowned, vec!["README.md"]);
+        assert_eq!(pr.impacted_modules, vec!["api"]);
+
+        let comments = pr.to_review_comments();
+        assert_eq!(comments.len(), 1);
+        assert_eq!(comments[0].line, Some(42));
+        assert!(
+            comments[0]
+                .body
+                .contains("- [HIGH] token-leak: Tokens logged")
+        );
+
+        let summary = pr.to_check_summary();
+        assert!(summary.contains("Suggested reviewers: @acme/identity"));
+        assert!(summary.contains("- `README.md`"));
+
+        let json = serde_json::to_value(&comments[0]).unwrap();
+        assert_eq!(json["subject_type"], "line");
+
+        map.modules[0].generated_paths = vec![GeneratedCode::new(vec!["src/auth/gen/**".into()])];
+        let pr = map.annotate_pr(&["src/auth/gen/token.rs"]);
+        assert!(pr.files[0].generated);
+        assert!(pr.files[0].issues.is_empty());
+        assert!(
+            pr.to_review_comments()[0]
+                .body
+                .contains("Generated code: do not edit by hand")
+        );
+    }
+}
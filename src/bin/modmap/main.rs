@@ -0,0 +1,304 @@
+//! `modmap` command-line tool (feature: cli)
+
+mod tui;
+
+use std::error::Error;
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+use clap::{Parser, Subcommand, ValueEnum};
+use modmap::{EntityRef, ModuleMap, ProjectManifest, SchemaRegistry};
+
+#[derive(Debug, Parser)]
+#[command(name = "modmap", version, about = "Inspect module maps and manifests")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Browse domains, groups and modules interactively
+    Explore {
+        /// Path to the project manifest JSON
+        manifest: PathBuf,
+    },
+    /// Check schema version and context consistency of a manifest
+    Validate {
+        manifest: PathBuf,
+        /// Also check that listed rule, skill and agent files exist under this directory
+        #[arg(long)]
+        root: Option<PathBuf>,
+    },
+    /// Compare two manifests or module maps
+    Diff {
+        old: PathBuf,
+        new: PathBuf,
+        #[arg(long, value_enum, default_value_t = DiffFormat::Markdown)]
+        format: DiffFormat,
+    },
+    /// Render the dependency graph or module table
+    Export {
+        /// Manifest or module map JSON
+        input: PathBuf,
+        #[arg(long, value_enum)]
+        format: ExportFormat,
+    },
+    /// Print one module, group, domain, convention or issue as JSON
+    Show {
+        /// Manifest or module map JSON
+        input: PathBuf,
+        /// e.g. `module:auth`, `group:payments/checkout`, `issue:token-leak`
+        entity: EntityRef,
+    },
+    /// List the cargo features compiled into this binary
+    Features,
+    /// Serve the manifest over a read-only REST API
+    #[cfg(feature = "server")]
+    Serve {
+        manifest: PathBuf,
+        #[arg(long, default_value = "127.0.0.1:8080")]
+        addr: String,
+    },
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum DiffFormat {
+    Markdown,
+    Json,
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum ExportFormat {
+    Mermaid,
+    /// Mermaid with one subgraph per domain
+    MermaidDomains,
+    Dot,
+    Csv,
+}
+
+fn read(path: &Path) -> Result<String, Box<dyn Error>> {
+    Ok(fs::read_to_string(path).map_err(|e| format!("{}: {e}", path.display()))?)
+}
+
+fn load_manifest(path: &Path) -> Result<ProjectManifest, Box<dyn Error>> {
+    Ok(SchemaRegistry::new()
+        .load(&read(path)?)
+        .map_err(|e| format!("{}: {e}", path.display()))?)
+}
+
+/// Accepts either a full manifest or a bare module map.
+fn load_map(path: &Path) -> Result<ModuleMap, Box<dyn Error>> {
+    let json = read(path)?;
+    if let Ok(manifest) = ProjectManifest::from_json(&json) {
+        return Ok(manifest.project);
+    }
+    Ok(serde_json::from_str(&json).map_err(|e| format!("{}: {e}", path.display()))?)
+}
+
+/// Closest existing module, group or domain to a reference that does not resolve.
+fn suggest(map: &ModuleMap, entity: &EntityRef) -> Option<EntityRef> {
+    Some(match entity {
+        EntityRef::Module(id) => {
+            EntityRef::Module(map.find_module_fuzzy(id).first()?.item.id.clone())
+        }
+        EntityRef::Group(id) => EntityRef::Group(map.find_group_fuzzy(id).first()?.item.id.clone()),
+        EntityRef::Domain(id) => {
+            EntityRef::Domain(map.find_domain_fuzzy(id).first()?.item.id.clone())
+        }
+        _ => return None,
+    })
+}
+
+fn run(command: Command, out: &mut impl Write) -> Result<ExitCode, Box<dyn Error>> {
+    match command {
+        Command::Explore { manifest } => {
+            tui::run(&load_manifest(&manifest)?)?;
+        }
+        Command::Validate { manifest, root } => {
+            let manifest = load_manifest(&manifest)?;
+            let issues = manifest.validate_contexts(root.as_deref());
+            for issue in &issues {
+                writeln!(out, "{issue}")?;
+            }
+            if !issues.is_empty() {
+                writeln!(out, "{} issue(s) found", issues.len())?;
+                return Ok(ExitCode::FAILURE);
+            }
+            writeln!(
+                out,
+                "ok: {} modules, no issues",
+                manifest.project.modules.len()
+            )?;
+        }
+        Command::Diff { old, new, format } => {
+            let diff = ModuleMap::diff(&load_map(&old)?, &load_map(&new)?);
+            match format {
+                DiffFormat::Markdown => write!(out, "{}", diff.to_markdown())?,
+                DiffFormat::Json => writeln!(out, "{}", serde_json::to_string_pretty(&diff)?)?,
+            }
+        }
+        Command::Export { input, format } => {
+            let map = load_map(&input)?;
+            let rendered = match format {
+                ExportFormat::Mermaid => map.to_mermaid(),
+                ExportFormat::MermaidDomains => map.to_mermaid_by_domain(),
+                ExportFormat::Dot => map.to_dot(),
+                ExportFormat::Csv => map.to_csv(),
+            };
+            write!(out, "{rendered}")?;
+        }
+        Command::Show { input, entity } => {
+            let map = load_map(&input)?;
+            let value = map
+                .pointer_of(&entity)
+                .and_then(|pointer| map.resolve_pointer(&pointer))
+                .ok_or_else(|| {
+                    let mut message = format!("{entity} is not in {}", input.display());
+                    if let Some(similar) = suggest(&map, &entity) {
+                        message.push_str(&format!(", did you mean {similar}?"));
+                    }
+                    message
+                })?;
+            writeln!(out, "{}", serde_json::to_string_pretty(&value)?)?;
+        }
+        Command::Features => {
+            for feature in modmap::features().all() {
+                let mark = if feature.enabled { '+' } else { '-' };
+                writeln!(out, "{mark} {:<18} {}", feature.name, feature.description)?;
+            }
+        }
+        #[cfg(feature = "server")]
+        Command::Serve { manifest, addr } => {
+            let manifest = load_manifest(&manifest)?;
+            writeln!(out, "listening on http://{addr}")?;
+            out.flush()?;
+            tokio::runtime::Runtime::new()?.block_on(modmap::server::serve(manifest, addr))?;
+        }
+    }
+    Ok(ExitCode::SUCCESS)
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    match run(cli.command, &mut io::stdout().lock()) {
+        Ok(code) => code,
+        Err(e) => {
+            eprintln!("error: {e}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use modmap::{GeneratorInfo, ModuleContext, ModuleGroup, ProjectMetadata, TechStack};
+
+    fn write_manifest(name: &str, manifest: &ProjectManifest) -> PathBuf {
+        let path = std::env::temp_dir().join(format!("modmap-cli-{}-{name}", std::process::id()));
+        fs::write(&path, manifest.to_json().unwrap()).unwrap();
+        path
+    }
+
+    fn manifest() -> ProjectManifest {
+        ProjectManifest::new(ModuleMap::new(
+            GeneratorInfo::new("test", "1.0.0"),
+            ProjectMetadata::new("test", TechStack::new("rust")),
+            vec![],
+            vec![],
+        ))
+    }
+
+    fn output(command: Command) -> (ExitCode, String) {
+        let mut out = Vec::new();
+        let code = run(command, &mut out).unwrap();
+        (code, String::from_utf8(out).unwrap())
+    }
+
+    #[test]
+    fn test_validate() {
+        let valid = write_manifest("valid.json", &manifest());
+        let (code, text) = output(Command::Validate {
+            manifest: valid.clone(),
+            root: None,
+        });
+        assert_eq!(code, ExitCode::SUCCESS);
+        assert_eq!(text, "ok: 0 modules, no issues\n");
+
+        let invalid = write_manifest(
+            "invalid.json",
+            &manifest().with_modules([("ghost".to_string(), ModuleContext::new())].into()),
+        );
+        let (code, text) = output(Command::Validate {
+            manifest: invalid.clone(),
+            root: None,
+        });
+        assert_eq!(code, ExitCode::FAILURE);
+        assert!(text.contains("ghost"));
+
+        fs::remove_file(valid).unwrap();
+        fs::remove_file(invalid).unwrap();
+    }
+
+    #[test]
+    fn test_diff_and_export() {
+        let path = write_manifest("same.json", &manifest());
+        let (_, text) = output(Command::Diff {
+            old: path.clone(),
+            new: path.clone(),
+            format: DiffFormat::Markdown,
+        });
+        assert!(text.contains("No changes."));
+
+        let (_, text) = output(Command::Export {
+            input: path.clone(),
+            format: ExportFormat::Mermaid,
+        });
+        assert_eq!(text, "graph TD\n");
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_features() {
+        let (code, text) = output(Command::Features);
+        assert_eq!(code, ExitCode::SUCCESS);
+        assert!(text.contains("+ cli "));
+        assert_eq!(text.lines().count(), modmap::features().all().len());
+    }
+
+    #[test]
+    fn test_show() {
+        let mut with_group = manifest();
+        with_group.project.groups = vec![ModuleGroup::new("core", "Core", vec![])];
+        let path = write_manifest("show.json", &with_group);
+        let (_, text) = output(Command::Show {
+            input: path.clone(),
+            entity: "group:core".parse().unwrap(),
+        });
+        assert!(text.contains("\"name\": \"Core\""));
+
+        let err = run(
+            Command::Show {
+                input: path.clone(),
+                entity: "domain:core".parse().unwrap(),
+            },
+            &mut Vec::new(),
+        )
+        .unwrap_err();
+        assert!(err.to_string().starts_with("domain:core is not in"));
+
+        let err = run(
+            Command::Show {
+                input: path.clone(),
+                entity: "group:CORE".parse().unwrap(),
+            },
+            &mut Vec::new(),
+        )
+        .unwrap_err();
+        assert!(err.to_string().ends_with(", did you mean group:core?"));
+        fs::remove_file(path).unwrap();
+    }
+}
@@ -0,0 +1,431 @@
+//! Read-only terminal explorer for a project manifest
+
+use std::io;
+
+use modmap::{ContextResolver, ModuleMap, ProjectManifest};
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use ratatui::layout::{Constraint, Layout};
+use ratatui::style::{Modifier, Style};
+use ratatui::widgets::{Block, Borders, List, ListItem, ListState, Paragraph, Wrap};
+use ratatui::{DefaultTerminal, Frame};
+
+/// Row in the domain → group → module tree
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Entry {
+    Domain(usize),
+    Group(usize),
+    Module(usize),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Mode {
+    Browse,
+    /// Typing a path to resolve
+    Input(String),
+    /// Showing context and impact for a path
+    Path(String),
+}
+
+struct App<'a> {
+    manifest: &'a ProjectManifest,
+    entries: Vec<(usize, Entry)>,
+    state: ListState,
+    mode: Mode,
+}
+
+impl<'a> App<'a> {
+    fn new(manifest: &'a ProjectManifest) -> Self {
+        let entries = tree(&manifest.project);
+        let mut state = ListState::default();
+        if !entries.is_empty() {
+            state.select(Some(0));
+        }
+        Self {
+            manifest,
+            entries,
+            state,
+            mode: Mode::Browse,
+        }
+    }
+
+    fn map(&self) -> &'a ModuleMap {
+        &self.manifest.project
+    }
+
+    fn selected(&self) -> Option<Entry> {
+        self.state.selected().map(|i| self.entries[i].1)
+    }
+
+    fn step(&mut self, delta: isize) {
+        let Some(current) = self.state.selected() else {
+            return;
+        };
+        let last = self.entries.len() as isize - 1;
+        self.state
+            .select(Some((current as isize + delta).clamp(0, last) as usize));
+    }
+
+    /// Returns false when the app should exit.
+    fn handle(&mut self, code: KeyCode) -> bool {
+        match &mut self.mode {
+            Mode::Input(input) => match code {
+                KeyCode::Enter => self.mode = Mode::Path(input.trim().to_string()),
+                KeyCode::Esc => self.mode = Mode::Browse,
+                KeyCode::Backspace => {
+                    input.pop();
+                }
+                KeyCode::Char(c) => input.push(c),
+                _ => {}
+            },
+            _ => match code {
+                KeyCode::Char('q') => return false,
+                KeyCode::Esc => self.mode = Mode::Browse,
+                KeyCode::Char('/') => self.mode = Mode::Input(String::new()),
+                KeyCode::Down | KeyCode::Char('j') => {
+                    self.mode = Mode::Browse;
+                    self.step(1);
+                }
+                KeyCode::Up | KeyCode::Char('k') => {
+                    self.mode = Mode::Browse;
+                    self.step(-1);
+                }
+                _ => {}
+            },
+        }
+        true
+    }
+
+    fn label(&self, depth: usize, entry: Entry) -> String {
+        let map = self.map();
+        let indent = "  ".repeat(depth);
+        match entry {
+            Entry::Domain(i) => format!("{indent}◆ {}", map.domains[i].name),
+            Entry::Group(i) => format!("{indent}▸ {}", map.groups[i].name),
+            Entry::Module(i) => {
+                let module = &map.modules[i];
+                let issues = module.known_issues.len();
+                if issues > 0 {
+                    format!("{indent}{} ({issues} issues)", module.id)
+                } else {
+                    format!("{indent}{}", module.id)
+                }
+            }
+        }
+    }
+
+    fn detail(&self) -> Vec<String> {
+        match &self.mode {
+            Mode::Path(path) | Mode::Input(path) => self.path_detail(path),
+            Mode::Browse => self
+                .selected()
+                .map_or_else(Vec::new, |e| self.entry_detail(e)),
+        }
+    }
+
+    fn entry_detail(&self, entry: Entry) -> Vec<String> {
+        let map = self.map();
+        match entry {
+            Entry::Domain(i) => {
+                let domain = &map.domains[i];
+                let mut lines = vec![
+                    format!("Domain: {} ({})", domain.name, domain.id),
+                    domain.responsibility.clone(),
+                ];
+                if let Some(owner) = &domain.owner {
+                    lines.push(format!("Owner: {owner}"));
+                }
+                section(&mut lines, "Groups", domain.group_ids.clone());
+                section(
+                    &mut lines,
+                    "Interfaces",
+                    domain.interfaces.iter().map(|i| i.name.clone()).collect(),
+                );
+                section(&mut lines, "Boundary rules", domain.boundary_rules.clone());
+                lines
+            }
+            Entry::Group(i) => {
+                let group = &map.groups[i];
+                let mut lines = vec![
+                    format!("Group: {} ({})", group.name, group.id),
+                    group.responsibility.clone(),
+                ];
+                section(&mut lines, "Modules", group.module_ids.clone());
+                section(&mut lines, "Boundary rules", group.boundary_rules.clone());
+                lines
+            }
+            Entry::Module(i) => {
+                let module = &map.modules[i];
+                let mut lines = vec![
+                    format!("Module: {} ({})", module.name, module.id),
+                    module.responsibility.clone(),
+                    format!(
+                        "Risk {:.2}  Value {:.2}  Coverage {:.2}",
+                        module.metrics.risk_score,
+                        module.metrics.value_score,
+                        module.metrics.coverage_ratio
+                    ),
+                ];
+                section(&mut lines, "Paths", module.paths.clone());
+                section(
+                    &mut lines,
+                    "Dependencies",
+                    module
+                        .dependencies
+                        .iter()
+                        .map(|d| d.module_id.clone())
+                        .collect(),
+                );
+                section(&mut lines, "Dependents", module.dependents.clone());
+                section(
+                    &mut lines,
+                    "Issues",
+                    module
+                        .known_issues
+                        .iter()
+                        .map(|issue| {
+                            format!(
+                                "[{}] {}: {}",
+                                map.effective_severity(issue, module),
+                                issue.id,
+                                issue.description
+                            )
+                        })
+                        .collect(),
+                );
+                if let Some(path) = module.paths.first() {
+                    let resolved = ContextResolver::new(self.manifest).resolve(path);
+                    section(&mut lines, "Rules", resolved.rules);
+                }
+                let paths: Vec<&str> = module.paths.iter().map(String::as_str).collect();
+                section(&mut lines, "Impact", impact(map, &paths));
+                lines
+            }
+        }
+    }
+
+    fn path_detail(&self, path: &str) -> Vec<String> {
+        let resolved = ContextResolver::new(self.manifest).resolve(path);
+        let mut lines = vec![format!("Path: {path}")];
+        if resolved.is_empty() {
+            lines.push("No module owns this path".into());
+            return lines;
+        }
+        section(
+            &mut lines,
+            "Modules",
+            resolved.modules.iter().map(|m| m.id.clone()).collect(),
+        );
+        section(&mut lines, "Rules", resolved.rules);
+        section(&mut lines, "Skills", resolved.skills);
+        section(&mut lines, "Impact", impact(self.map(), &[path]));
+        lines
+    }
+
+    fn draw(&mut self, frame: &mut Frame) {
+        let [body, status] =
+            Layout::vertical([Constraint::Min(1), Constraint::Length(1)]).areas(frame.area());
+        let [left, right] =
+            Layout::horizontal([Constraint::Percentage(40), Constraint::Percentage(60)])
+                .areas(body);
+
+        let items: Vec<ListItem> = self
+            .entries
+            .iter()
+            .map(|&(depth, entry)| ListItem::new(self.label(depth, entry)))
+            .collect();
+        let list = List::new(items)
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title(self.map().project.name.as_str()),
+            )
+            .highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+        frame.render_stateful_widget(list, left, &mut self.state);
+
+        let detail = Paragraph::new(self.detail().join("\n"))
+            .block(Block::default().borders(Borders::ALL).title("Details"))
+            .wrap(Wrap { trim: false });
+        frame.render_widget(detail, right);
+
+        let hint = match &self.mode {
+            Mode::Input(input) => format!("path: {input}▏"),
+            _ => "↑/↓ move  / lookup path  esc back  q quit".to_string(),
+        };
+        frame.render_widget(Paragraph::new(hint), status);
+    }
+}
+
+fn section(lines: &mut Vec<String>, title: &str, items: Vec<String>) {
+    if items.is_empty() {
+        return;
+    }
+    lines.push(String::new());
+    lines.push(format!("{title}:"));
+    lines.extend(items.into_iter().map(|item| format!("  {item}")));
+}
+
+fn impact(map: &ModuleMap, paths: &[&str]) -> Vec<String> {
+    map.impacted_modules(paths)
+        .iter()
+        .map(|m| m.id.clone())
+        .collect()
+}
+
+/// Flattens the map into indented rows; ungrouped groups and modules come last.
+fn tree(map: &ModuleMap) -> Vec<(usize, Entry)> {
+    let group_index = |id: &str| map.group_position_of(id);
+    let module_index = |id: &str| map.position_of(id);
+    let push_group = |entries: &mut Vec<(usize, Entry)>, depth: usize, index: usize| {
+        entries.push((depth, Entry::Group(index)));
+        for id in &map.groups[index].module_ids {
+            if let Some(m) = module_index(id) {
+                entries.push((depth + 1, Entry::Module(m)));
+            }
+        }
+    };
+
+    let mut entries = Vec::new();
+    for (i, domain) in map.domains.iter().enumerate() {
+        entries.push((0, Entry::Domain(i)));
+        for group in map.find_groups_in_domain(&domain.id) {
+            if let Some(g) = group_index(&group.id) {
+                push_group(&mut entries, 1, g);
+            }
+        }
+    }
+    for (g, group) in map.groups.iter().enumerate() {
+        let in_domain = map
+            .domains
+            .iter()
+            .any(|d| map.domain_lists_group(d, &group.id));
+        if !in_domain {
+            push_group(&mut entries, 0, g);
+        }
+    }
+    for (m, module) in map.modules.iter().enumerate() {
+        if map.find_group_containing(&module.id).is_none() {
+            entries.push((0, Entry::Module(m)));
+        }
+    }
+    entries
+}
+
+pub fn run(manifest: &ProjectManifest) -> io::Result<()> {
+    let mut terminal = ratatui::init();
+    let result = event_loop(&mut terminal, App::new(manifest));
+    ratatui::restore();
+    result
+}
+
+fn event_loop(terminal: &mut DefaultTerminal, mut app: App) -> io::Result<()> {
+    loop {
+        terminal.draw(|frame| app.draw(frame))?;
+        if let Event::Key(key) = event::read()?
+            && key.kind == KeyEventKind::Press
+            && !app.handle(key.code)
+        {
+            return Ok(());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use modmap::{
+        Domain, GeneratorInfo, Module, ModuleContext, ModuleDependency, ModuleGroup,
+        ProjectMetadata, TechStack,
+    };
+
+    fn module(id: &str, dependencies: &[&str]) -> Module {
+        Module::new(id, id, vec![format!("src/{id}/")])
+            .with_dependencies(
+                dependencies
+                    .iter()
+                    .map(|d| ModuleDependency::runtime(*d))
+                    .collect(),
+            )
+            .with_responsibility(format!("{id} module"))
+            .with_primary_language("rust")
+    }
+
+    fn sample_manifest() -> ProjectManifest {
+        let map = ModuleMap::new(
+            GeneratorInfo::new("test", "1.0.0"),
+            ProjectMetadata::new("shop", TechStack::new("rust")),
+            vec![
+                module("auth", &[]),
+                module("api", &["auth"]),
+                module("cli", &[]),
+            ],
+            vec![ModuleGroup::new(
+                "core",
+                "Core",
+                vec!["auth".into(), "api".into()],
+            )],
+        )
+        .with_domains(vec![Domain::new(
+            "identity",
+            "Identity",
+            vec!["core".into()],
+        )]);
+        ProjectManifest::new(map).with_modules(
+            [(
+                "auth".to_string(),
+                ModuleContext::new().with_rules(vec!["auth-rules".into()]),
+            )]
+            .into(),
+        )
+    }
+
+    #[test]
+    fn test_tree_order() {
+        let manifest = sample_manifest();
+        let app = App::new(&manifest);
+        assert_eq!(
+            app.entries,
+            vec![
+                (0, Entry::Domain(0)),
+                (1, Entry::Group(0)),
+                (2, Entry::Module(0)),
+                (2, Entry::Module(1)),
+                (0, Entry::Module(2)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_navigation_and_detail() {
+        let manifest = sample_manifest();
+        let mut app = App::new(&manifest);
+
+        app.handle(KeyCode::Up);
+        assert_eq!(app.selected(), Some(Entry::Domain(0)));
+        app.handle(KeyCode::Down);
+        app.handle(KeyCode::Char('j'));
+        assert_eq!(app.selected(), Some(Entry::Module(0)));
+
+        let detail = app.detail();
+        assert!(detail.contains(&"  auth-rules".to_string()));
+        assert!(detail.contains(&"Impact:".to_string()));
+        assert!(detail.contains(&"  api".to_string()));
+        assert!(!app.handle(KeyCode::Char('q')));
+    }
+
+    #[test]
+    fn test_path_lookup() {
+        let manifest = sample_manifest();
+        let mut app = App::new(&manifest);
+
+        app.handle(KeyCode::Char('/'));
+        for c in "src/auth/x.rs".chars() {
+            app.handle(KeyCode::Char(c));
+        }
+        app.handle(KeyCode::Enter);
+        assert_eq!(app.mode, Mode::Path("src/auth/x.rs".into()));
+        assert!(app.detail().contains(&"  auth-rules".to_string()));
+
+        app.handle(KeyCode::Esc);
+        assert_eq!(app.mode, Mode::Browse);
+    }
+}
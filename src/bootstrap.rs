@@ -0,0 +1,299 @@
+//! First-cut map from CODEOWNERS and the directory tree
+
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::Path;
+
+use crate::module_map::{Domain, Module, ModuleGroup, ModuleMap, ProjectMetadata, VendoredPath};
+use crate::normalize::canonical_id;
+use crate::registry::SchemaError;
+use crate::types::{DetectedLanguage, GeneratorInfo, ProjectType, TechStack};
+
+/// Directories never scanned for language detection
+const SKIPPED_DIRS: &[&str] = &[
+    "node_modules",
+    "target",
+    "vendor",
+    "third_party",
+    "dist",
+    "build",
+];
+
+/// Directory names holding third-party code rather than modules
+const VENDOR_DIRS: &[&str] = &["vendor", "third_party", "node_modules"];
+
+fn is_vendor_dir(dir: &str) -> bool {
+    dir.split('/').any(|seg| VENDOR_DIRS.contains(&seg))
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CodeownersEntry {
+    pub pattern: String,
+    pub owners: Vec<String>,
+}
+
+impl CodeownersEntry {
+    /// Directory owned by this entry (`src/auth/`), or `None` for file globs.
+    pub fn directory(&self) -> Option<String> {
+        let path = self.pattern.trim_start_matches('/');
+        let path = path
+            .strip_suffix("/**")
+            .or_else(|| path.strip_suffix("/*"))
+            .unwrap_or(path)
+            .trim_end_matches('/');
+        if path.is_empty() || path.contains(['*', '?', '[']) {
+            return None;
+        }
+        Some(format!("{path}/"))
+    }
+}
+
+/// Parses CODEOWNERS lines, skipping comments and blank lines.
+pub fn parse_codeowners(text: &str) -> Vec<CodeownersEntry> {
+    text.lines()
+        .map(|line| line.split('#').next().unwrap_or_default().trim())
+        .filter(|line| !line.is_empty())
+        .filter_map(|line| {
+            let mut parts = line.split_whitespace();
+            Some(CodeownersEntry {
+                pattern: parts.next()?.to_string(),
+                owners: parts.map(String::from).collect(),
+            })
+        })
+        .collect()
+}
+
+/// `@org/payments-team` → `payments-team`
+fn team_id(owner: &str) -> String {
+    let name = owner.trim_start_matches('@');
+    canonical_id(name.rsplit('/').next().unwrap_or(name))
+}
+
+fn language_for(extension: &str) -> Option<&'static str> {
+    Some(match extension {
+        "rs" => "rust",
+        "ts" | "tsx" => "typescript",
+        "js" | "jsx" | "mjs" | "cjs" => "javascript",
+        "py" => "python",
+        "go" => "go",
+        "java" => "java",
+        "kt" | "kts" => "kotlin",
+        "rb" => "ruby",
+        "swift" => "swift",
+        "c" | "h" => "c",
+        "cc" | "cpp" | "cxx" | "hpp" => "cpp",
+        "cs" => "csharp",
+        "php" => "php",
+        "scala" => "scala",
+        "tf" | "tfvars" => "hcl",
+        _ => return None,
+    })
+}
+
+/// Counts source files per language under `dir`, returning the total file count.
+fn scan(dir: &Path, counts: &mut HashMap<&'static str, usize>) -> Result<usize, SchemaError> {
+    let mut total = 0;
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        let path = entry.path();
+        let name = entry.file_name();
+        let name = name.to_string_lossy();
+        if entry.file_type()?.is_dir() {
+            if !name.starts_with('.') && !SKIPPED_DIRS.contains(&name.as_ref()) {
+                total += scan(&path, counts)?;
+            }
+        } else {
+            total += 1;
+            if let Some(language) = path
+                .extension()
+                .and_then(|e| e.to_str())
+                .and_then(language_for)
+            {
+                *counts.entry(language).or_default() += 1;
+            }
+        }
+    }
+    Ok(total)
+}
+
+fn dominant(counts: &HashMap<&'static str, usize>) -> &'static str {
+    counts
+        .iter()
+        .max_by(|a, b| a.1.cmp(b.1).then_with(|| b.0.cmp(a.0)))
+        .map_or("unknown", |(language, _)| language)
+}
+
+impl ModuleMap {
+    /// Builds a starting map: one module per owned directory, one domain and
+    /// group per owning team, languages from file extensions.
+    ///
+    /// Later CODEOWNERS entries win, as in GitHub. Entries for file globs or
+    /// directories missing under `root` are skipped, and owned directories
+    /// under `vendor/`, `third_party/` or `node_modules/` become vendored
+    /// paths instead of modules. Mostly-Terraform repos
+    /// become infrastructure projects with module-source dependencies.
+    pub fn bootstrap_from(root: &Path, codeowners: &str) -> Result<ModuleMap, SchemaError> {
+        let mut owned: BTreeMap<String, String> = BTreeMap::new();
+        let mut vendored: Vec<VendoredPath> = Vec::new();
+        for entry in parse_codeowners(codeowners) {
+            let (Some(dir), Some(owner)) = (entry.directory(), entry.owners.first()) else {
+                continue;
+            };
+            if !root.join(&dir).is_dir() {
+                continue;
+            }
+            if is_vendor_dir(&dir) {
+                if !vendored.iter().any(|v| v.path == dir) {
+                    vendored.push(VendoredPath::new(dir));
+                }
+            } else {
+                owned.insert(dir, owner.clone());
+            }
+        }
+
+        let mut modules = Vec::new();
+        let mut teams: BTreeMap<String, (String, Vec<String>)> = BTreeMap::new();
+        for (dir, owner) in &owned {
+            let mut counts = HashMap::new();
+            scan(&root.join(dir), &mut counts)?;
+
+            let leaf = dir.trim_end_matches('/').rsplit('/').next().unwrap_or(dir);
+            let leaf_taken = owned
+                .keys()
+                .filter(|d| d.trim_end_matches('/').rsplit('/').next() == Some(leaf))
+                .count()
+                > 1;
+            let id = if leaf_taken {
+                canonical_id(&dir.trim_end_matches('/').replace('/', "-"))
+            } else {
+                canonical_id(leaf)
+            };
+
+            teams
+                .entry(team_id(owner))
+                .or_insert_with(|| (owner.clone(), Vec::new()))
+                .1
+                .push(id.clone());
+            modules.push(
+                Module::new(id, leaf, vec![dir.clone()]).with_primary_language(dominant(&counts)),
+            );
+        }
+
+        let mut groups = Vec::new();
+        let mut domains = Vec::new();
+        for (team, (owner, module_ids)) in teams {
+            groups
+                .push(ModuleGroup::new(team.clone(), team.clone(), module_ids).with_domain(&team));
+            domains.push(Domain::new(team.clone(), team.clone(), vec![team]).with_owner(owner));
+        }
+
+        let mut counts = HashMap::new();
+        let total_files = scan(root, &mut counts)?;
+        let source_files: usize = counts.values().sum();
+        let mut languages: Vec<DetectedLanguage> = counts
+            .iter()
+            .map(|(name, count)| {
+                DetectedLanguage::new(*name)
+                    .with_percentage(*count as f64 * 100.0 / source_files as f64)
+            })
+            .collect();
+        languages.sort_by(|a, b| {
+            b.percentage
+                .total_cmp(&a.percentage)
+                .then_with(|| a.name.cmp(&b.name))
+        });
+
+        let name = root.file_name().map_or_else(
+            || "project".to_string(),
+            |n| n.to_string_lossy().into_owned(),
+        );
+        let mut project = ProjectMetadata::new(name, TechStack::new(dominant(&counts)))
+            .with_languages(languages)
+            .with_total_files(total_files)
+            .with_vendored_paths(vendored);
+        let infrastructure = project.tech_stack.primary_language == "hcl";
+        if infrastructure {
+            project = project.with_type(ProjectType::Infrastructure);
+        }
+
+        let mut map = ModuleMap::new(
+            GeneratorInfo::new("modmap-bootstrap", env!("CARGO_PKG_VERSION")),
+            project,
+            modules,
+            groups,
+        )
+        .with_domains(domains);
+        if infrastructure {
+            map.link_terraform_modules(root)?;
+        }
+        Ok(map)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CODEOWNERS: &str = "\
+# Default owners
+*                 @acme/platform
+*.md              @acme/docs
+/services/auth/   @acme/identity-team @alice
+/services/billing/** @acme/payments
+/libs/auth/       @acme/identity-team
+/third_party/zlib/ @acme/platform
+/missing/         @acme/ghosts
+";
+
+    #[test]
+    fn test_parse_codeowners() {
+        let entries = parse_codeowners(CODEOWNERS);
+        assert_eq!(entries.len(), 7);
+        assert_eq!(entries[2].owners, vec!["@acme/identity-team", "@alice"]);
+        assert_eq!(entries[0].directory(), None);
+        assert_eq!(entries[1].directory(), None);
+        assert_eq!(entries[3].directory().as_deref(), Some("services/billing/"));
+    }
+
+    #[test]
+    fn test_bootstrap_from() {
+        let root = std::env::temp_dir().join(format!("modmap-bootstrap-{}", std::process::id()));
+        for (file, body) in [
+            ("services/auth/main.go", "package main"),
+            ("services/auth/util.go", "package main"),
+            ("services/billing/app.py", "print()"),
+            ("libs/auth/index.ts", "export {}"),
+            ("third_party/zlib/zlib.c", "int main;"),
+            ("README.md", "# demo"),
+        ] {
+            let path = root.join(file);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, body).unwrap();
+        }
+
+        let map = ModuleMap::bootstrap_from(&root, CODEOWNERS).unwrap();
+        fs::remove_dir_all(&root).unwrap();
+
+        let ids: Vec<_> = map.modules.iter().map(|m| m.id.as_str()).collect();
+        assert_eq!(ids, vec!["libs-auth", "services-auth", "billing"]);
+        assert_eq!(
+            map.find_module("services-auth").unwrap().primary_language,
+            "go"
+        );
+        assert_eq!(
+            map.find_module("billing").unwrap().primary_language,
+            "python"
+        );
+
+        let identity = map.find_domain("identity-team").unwrap();
+        assert_eq!(identity.owner.as_deref(), Some("@acme/identity-team"));
+        assert_eq!(
+            map.find_group("identity-team").unwrap().module_ids,
+            vec!["libs-auth", "services-auth"]
+        );
+        assert_eq!(map.project.tech_stack.primary_language, "go");
+        assert_eq!(map.project.total_files, 5);
+        assert_eq!(map.project.vendored_paths[0].path, "third_party/zlib/");
+        assert!(map.is_vendored("third_party/zlib/zlib.c"));
+    }
+}
@@ -0,0 +1,718 @@
+//! Cross-domain boundary checks over declared interfaces
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fmt;
+
+use chrono::NaiveDate;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::entity::EntityRef;
+use crate::module_map::{AccessMode, DomainInterface, ModuleMap, Stability};
+use crate::ownership::PathOwner;
+use crate::types::EvidenceLocation;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BoundaryViolation {
+    /// More than one domain owns or writes the same table
+    ConflictingTableOwnership { table: String, domains: Vec<String> },
+    /// Module consumes a topic no domain exports through an event interface
+    UnexportedTopic { module_id: String, topic: String },
+    /// Interface lists a consumer that has no dependency on the domain
+    UnbackedConsumer {
+        domain_id: String,
+        interface: String,
+        /// The consuming module or domain
+        consumer: EntityRef,
+    },
+    /// Cross-domain dependency not covered by any interface of the target domain
+    UndeclaredDependency {
+        from: String,
+        to: String,
+        from_domain: String,
+        to_domain: String,
+    },
+    /// Module lists a shared path (or one inside it) as its own
+    ClaimedSharedPath { module_id: String, path: String },
+    /// Module lists a vendored directory (or one inside it) as its own
+    ClaimedVendoredPath { module_id: String, path: String },
+}
+
+impl BoundaryViolation {
+    /// Stable id of the check that raised this, as used by waivers
+    pub fn check_id(&self) -> &'static str {
+        match self {
+            Self::ConflictingTableOwnership { .. } => "conflicting-table-ownership",
+            Self::UnexportedTopic { .. } => "unexported-topic",
+            Self::UnbackedConsumer { .. } => "unbacked-consumer",
+            Self::UndeclaredDependency { .. } => "undeclared-dependency",
+            Self::ClaimedSharedPath { .. } => "claimed-shared-path",
+            Self::ClaimedVendoredPath { .. } => "claimed-vendored-path",
+        }
+    }
+
+    /// Module, domain or table the violation is about
+    pub fn target(&self) -> EntityRef {
+        match self {
+            Self::ConflictingTableOwnership { table, .. } => EntityRef::Table(table.clone()),
+            Self::UnexportedTopic { module_id, .. }
+            | Self::ClaimedSharedPath { module_id, .. }
+            | Self::ClaimedVendoredPath { module_id, .. } => EntityRef::Module(module_id.clone()),
+            Self::UnbackedConsumer { consumer, .. } => consumer.clone(),
+            Self::UndeclaredDependency { from, .. } => EntityRef::Module(from.clone()),
+        }
+    }
+}
+
+impl fmt::Display for BoundaryViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ConflictingTableOwnership { table, domains } => write!(
+                f,
+                "table '{table}' is written by several domains: {}",
+                domains.join(", ")
+            ),
+            Self::UnexportedTopic { module_id, topic } => write!(
+                f,
+                "module '{module_id}' consumes topic '{topic}' that no domain exports"
+            ),
+            Self::UnbackedConsumer {
+                domain_id,
+                interface,
+                consumer,
+            } => write!(
+                f,
+                "'{}' is a declared consumer of {domain_id}/{interface} but does not depend on it",
+                consumer.id()
+            ),
+            Self::UndeclaredDependency {
+                from,
+                to,
+                from_domain,
+                to_domain,
+            } => write!(
+                f,
+                "'{from}' ({from_domain}) depends on '{to}' ({to_domain}) without a declared interface"
+            ),
+            Self::ClaimedSharedPath { module_id, path } => write!(
+                f,
+                "module '{module_id}' claims '{path}', which is declared shared"
+            ),
+            Self::ClaimedVendoredPath { module_id, path } => write!(
+                f,
+                "module '{module_id}' claims '{path}', which is vendored third-party code"
+            ),
+        }
+    }
+}
+
+/// A deprecated interface and who still uses it
+#[derive(Debug, Clone)]
+pub struct DeprecatedUsage<'a> {
+    pub domain_id: &'a str,
+    pub interface: &'a DomainInterface,
+    pub consumers: Vec<&'a str>,
+}
+
+impl DeprecatedUsage<'_> {
+    pub fn is_past_sunset(&self, today: NaiveDate) -> bool {
+        self.interface.sunset_date.is_some_and(|date| date < today)
+    }
+}
+
+/// Checks declared interface consumers against real dependency edges across
+/// maps composed from several repositories.
+///
+/// Every consumer (module or domain id) listed on an interface must have a
+/// dependency into that domain, and every dependency between modules of
+/// different domains must be covered by an interface of the target domain
+/// listing the source module or its domain.
+pub fn verify_consumers(maps: &[&ModuleMap]) -> Vec<BoundaryViolation> {
+    let mut domain_of: HashMap<&str, &str> = HashMap::new();
+    for map in maps {
+        for module in &map.modules {
+            if let Some(domain) = map.find_domain_containing_module(&module.id) {
+                domain_of.insert(&module.id, &domain.id);
+            }
+        }
+    }
+    let edges: Vec<_> = maps.iter().flat_map(|m| m.dependency_edges()).collect();
+    let domains: Vec<_> = maps.iter().flat_map(|m| &m.domains).collect();
+
+    let mut violations = Vec::new();
+    for domain in &domains {
+        for interface in &domain.interfaces {
+            for consumer in interface.all_consumers() {
+                let consumer = canonical(maps, consumer);
+                let backed = edges.iter().any(|e| {
+                    domain_of.get(e.to.as_str()) == Some(&domain.id.as_str())
+                        && (e.from == consumer || domain_of.get(e.from.as_str()) == Some(&consumer))
+                });
+                if !backed {
+                    let consumer = if domains.iter().any(|d| d.id == consumer) {
+                        EntityRef::Domain(consumer.to_string())
+                    } else {
+                        EntityRef::Module(consumer.to_string())
+                    };
+                    violations.push(BoundaryViolation::UnbackedConsumer {
+                        domain_id: domain.id.clone(),
+                        interface: interface.name.clone(),
+                        consumer,
+                    });
+                }
+            }
+        }
+    }
+
+    for edge in &edges {
+        let (Some(&from_domain), Some(&to_domain)) = (
+            domain_of.get(edge.from.as_str()),
+            domain_of.get(edge.to.as_str()),
+        ) else {
+            continue;
+        };
+        if from_domain == to_domain {
+            continue;
+        }
+        let declared = domains
+            .iter()
+            .filter(|d| d.id == to_domain)
+            .flat_map(|d| &d.interfaces)
+            .any(|i| {
+                i.all_consumers()
+                    .iter()
+                    .map(|c| canonical(maps, c))
+                    .any(|c| c == edge.from || c == from_domain)
+            });
+        if !declared {
+            violations.push(BoundaryViolation::UndeclaredDependency {
+                from: edge.from.clone(),
+                to: edge.to.clone(),
+                from_domain: from_domain.to_string(),
+                to_domain: to_domain.to_string(),
+            });
+        }
+    }
+    violations
+}
+
+/// Current id of the domain or module `name` refers to, since consumers
+/// may still use a former id.
+fn canonical<'a>(maps: &[&'a ModuleMap], name: &'a str) -> &'a str {
+    maps.iter()
+        .find_map(|m| {
+            m.find_domain(name)
+                .map(|d| d.id.as_str())
+                .or_else(|| m.find_module(name).map(|m| m.id.as_str()))
+        })
+        .unwrap_or(name)
+}
+
+impl ModuleMap {
+    /// `verify_consumers` for this map alone.
+    pub fn verify_consumers(&self) -> Vec<BoundaryViolation> {
+        verify_consumers(&[self])
+    }
+
+    /// Deprecated interfaces with remaining consumers, earliest sunset first
+    /// (interfaces without a sunset date last).
+    pub fn deprecated_interface_consumers(&self) -> Vec<DeprecatedUsage<'_>> {
+        let mut usages: Vec<_> = self
+            .domains
+            .iter()
+            .flat_map(|d| d.interfaces.iter().map(move |i| (d, i)))
+            .filter(|(_, i)| i.stability == Stability::Deprecated)
+            .map(|(domain, interface)| DeprecatedUsage {
+                domain_id: &domain.id,
+                interface,
+                consumers: interface.all_consumers(),
+            })
+            .filter(|usage| !usage.consumers.is_empty())
+            .collect();
+        usages.sort_by_key(|u| (u.interface.sunset_date.is_none(), u.interface.sunset_date));
+        usages
+    }
+
+    /// Domain a consumer id refers to: the domain itself, or the domain of a module.
+    fn consumer_domain<'a>(&'a self, consumer: &'a str) -> &'a str {
+        if self.find_domain(consumer).is_some() {
+            return consumer;
+        }
+        self.find_domain_containing_module(consumer)
+            .map_or(consumer, |d| d.id.as_str())
+    }
+
+    /// Checks declared domain interfaces for conflicting claims.
+    ///
+    /// A domain declaring a database interface owns its tables; consumers
+    /// with write access also claim them. Each table may have one writer.
+    /// Every topic a module consumes must be exported by some domain, and no
+    /// module may claim a shared path as exclusively its own.
+    pub fn check_boundaries(&self) -> Vec<BoundaryViolation> {
+        let mut writers: BTreeMap<String, BTreeSet<&str>> = BTreeMap::new();
+        for domain in &self.domains {
+            for database in domain.interfaces.iter().filter_map(|i| i.database.as_ref()) {
+                for table in &database.tables {
+                    let claims = writers.entry(database.qualified_table(table)).or_default();
+                    claims.insert(&domain.id);
+                    for consumer in &database.consumers {
+                        if consumer.access == AccessMode::Write {
+                            claims.insert(self.consumer_domain(&consumer.consumer));
+                        }
+                    }
+                }
+            }
+        }
+
+        let mut violations: Vec<_> = writers
+            .into_iter()
+            .filter(|(_, domains)| domains.len() > 1)
+            .map(
+                |(table, domains)| BoundaryViolation::ConflictingTableOwnership {
+                    table,
+                    domains: domains.into_iter().map(String::from).collect(),
+                },
+            )
+            .collect();
+
+        let exported: BTreeSet<&str> = self
+            .domains
+            .iter()
+            .flat_map(|d| &d.interfaces)
+            .filter_map(|i| i.event.as_ref())
+            .map(|e| e.topic.as_str())
+            .collect();
+        for module in &self.modules {
+            for topic in &module.consumed_topics {
+                if !exported.contains(topic.as_str()) {
+                    violations.push(BoundaryViolation::UnexportedTopic {
+                        module_id: module.id.clone(),
+                        topic: topic.clone(),
+                    });
+                }
+            }
+            for path in &module.paths {
+                if self.shared_paths.iter().any(|s| s.contains_file(path)) {
+                    violations.push(BoundaryViolation::ClaimedSharedPath {
+                        module_id: module.id.clone(),
+                        path: path.clone(),
+                    });
+                }
+                if self.is_vendored(path) {
+                    violations.push(BoundaryViolation::ClaimedVendoredPath {
+                        module_id: module.id.clone(),
+                        path: path.clone(),
+                    });
+                }
+            }
+        }
+        violations
+    }
+}
+
+/// One file-level import reported by a language analyzer
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct ImportEdge {
+    pub from_file: String,
+    pub to_file: String,
+    /// Where the import statement is
+    pub location: EvidenceLocation,
+}
+
+impl ImportEdge {
+    pub fn new(from_file: impl Into<String>, to_file: impl Into<String>, line: u32) -> Self {
+        let from_file = from_file.into();
+        Self {
+            location: EvidenceLocation::new(from_file.clone(), line),
+            from_file,
+            to_file: to_file.into(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ImportViolationKind {
+    /// Importing module does not list the imported module as a dependency
+    UndeclaredDependency,
+    /// Cross-domain import not covered by an interface of the target domain
+    UncoveredCrossDomain {
+        from_domain: String,
+        to_domain: String,
+    },
+    /// Imported shared path is restricted to other modules
+    RestrictedSharedPath { shared_path: String },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImportViolation {
+    pub from_module: String,
+    /// `None` when the target is a shared path
+    pub to_module: Option<String>,
+    pub kind: ImportViolationKind,
+    pub location: EvidenceLocation,
+}
+
+impl ImportViolation {
+    /// Stable check id, as used by waivers
+    pub fn check_id(&self) -> &'static str {
+        "imports"
+    }
+}
+
+impl fmt::Display for ImportViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let to = self.to_module.as_deref().unwrap_or_default();
+        write!(f, "{}:{}: ", self.location.file, self.location.start_line)?;
+        match &self.kind {
+            ImportViolationKind::UndeclaredDependency => write!(
+                f,
+                "'{}' imports '{to}' without declaring the dependency",
+                self.from_module
+            ),
+            ImportViolationKind::UncoveredCrossDomain {
+                from_domain,
+                to_domain,
+            } => write!(
+                f,
+                "'{}' ({from_domain}) imports '{to}' ({to_domain}) without a declared interface",
+                self.from_module
+            ),
+            ImportViolationKind::RestrictedSharedPath { shared_path } => write!(
+                f,
+                "'{}' imports from '{shared_path}', which is not shared with it",
+                self.from_module
+            ),
+        }
+    }
+}
+
+impl ModuleMap {
+    /// Validates extracted imports against the map, as an architecture test.
+    ///
+    /// An import into another module needs a declared dependency and, across
+    /// domains, an interface listing the importer or its domain. Imports into
+    /// shared paths need only be allowed by the shared path. Imports from
+    /// unowned or shared files, and into unowned files, are not checked.
+    pub fn check_imports(&self, edges: &[ImportEdge]) -> Vec<ImportViolation> {
+        let mut violations = Vec::new();
+        for edge in edges {
+            let PathOwner::Module(from) = self.owner_of(&edge.from_file) else {
+                continue;
+            };
+            let violation = |to_module: Option<&str>, kind| ImportViolation {
+                from_module: from.id.clone(),
+                to_module: to_module.map(String::from),
+                kind,
+                location: edge.location.clone(),
+            };
+            let to = match self.owner_of(&edge.to_file) {
+                PathOwner::Module(to) if to.id != from.id => to,
+                PathOwner::Shared { shared, .. } => {
+                    if !shared.allows(&from.id) {
+                        violations.push(violation(
+                            None,
+                            ImportViolationKind::RestrictedSharedPath {
+                                shared_path: shared.path.clone(),
+                            },
+                        ));
+                    }
+                    continue;
+                }
+                _ => continue,
+            };
+
+            if !from.dependencies.iter().any(|d| d.module_id == to.id) {
+                violations.push(violation(
+                    Some(&to.id),
+                    ImportViolationKind::UndeclaredDependency,
+                ));
+            }
+            let (Some(from_domain), Some(to_domain)) = (
+                self.find_domain_containing_module(&from.id),
+                self.find_domain_containing_module(&to.id),
+            ) else {
+                continue;
+            };
+            let covered = from_domain.id == to_domain.id
+                || to_domain.interfaces.iter().any(|i| {
+                    i.all_consumers()
+                        .iter()
+                        .any(|c| *c == from.id || *c == from_domain.id)
+                });
+            if !covered {
+                violations.push(violation(
+                    Some(&to.id),
+                    ImportViolationKind::UncoveredCrossDomain {
+                        from_domain: from_domain.id.clone(),
+                        to_domain: to_domain.id.clone(),
+                    },
+                ));
+            }
+        }
+        violations
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        DatabaseInterface, Domain, DomainInterface, EventInterface, GeneratorInfo, InterfaceType,
+        Module, ModuleGroup, ProjectMetadata, TechStack,
+    };
+
+    fn map(domains: Vec<Domain>) -> ModuleMap {
+        ModuleMap::new(
+            GeneratorInfo::new("test", "1.0.0"),
+            ProjectMetadata::new("shop", TechStack::new("rust")),
+            vec![],
+            vec![ModuleGroup::new(
+                "billing-core",
+                "Billing",
+                vec!["invoices".into()],
+            )],
+        )
+        .with_domains(domains)
+    }
+
+    fn db(tables: &[&str]) -> DatabaseInterface {
+        DatabaseInterface::new("postgres")
+            .with_schema("public")
+            .with_tables(tables.iter().map(|t| t.to_string()).collect())
+    }
+
+    fn domain(id: &str, database: DatabaseInterface) -> Domain {
+        Domain::new(id, id, vec![format!("{id}-core")]).with_interfaces(vec![
+            DomainInterface::new(format!("{id}-db"), InterfaceType::Database)
+                .with_database(database),
+        ])
+    }
+
+    #[test]
+    fn test_read_consumers_do_not_conflict() {
+        let map = map(vec![
+            domain(
+                "identity",
+                db(&["users"]).with_consumer("billing", AccessMode::Read),
+            ),
+            domain("billing", db(&["invoices"])),
+        ]);
+        assert!(map.check_boundaries().is_empty());
+    }
+
+    #[test]
+    fn test_conflicting_table_ownership() {
+        let map = map(vec![
+            domain("identity", db(&["users", "public.sessions"])),
+            domain("auth", db(&["sessions"])),
+            domain(
+                "catalog",
+                db(&["products"]).with_consumer("invoices", AccessMode::Write),
+            ),
+            domain("billing", db(&["invoices"])),
+        ]);
+
+        let violations = map.check_boundaries();
+        assert_eq!(
+            violations,
+            vec![
+                BoundaryViolation::ConflictingTableOwnership {
+                    table: "public.products".into(),
+                    domains: vec!["billing".into(), "catalog".into()],
+                },
+                BoundaryViolation::ConflictingTableOwnership {
+                    table: "public.sessions".into(),
+                    domains: vec!["auth".into(), "identity".into()],
+                },
+            ]
+        );
+        assert_eq!(
+            violations[1].to_string(),
+            "table 'public.sessions' is written by several domains: auth, identity"
+        );
+    }
+
+    #[test]
+    fn test_unexported_topic() {
+        let consumer = |id: &str, topics: &[&str]| Module {
+            consumed_topics: topics.iter().map(|t| t.to_string()).collect(),
+            ..Module::new(id, id, vec![]).with_primary_language("rust")
+        };
+        let mut map = map(vec![
+            Domain::new("identity", "Identity", vec![]).with_interfaces(vec![
+                DomainInterface::new("UserEvents", InterfaceType::Event).with_event(
+                    EventInterface::new("user.created")
+                        .with_producers(vec!["users".into()])
+                        .with_consumers(vec!["invoices".into()]),
+                ),
+            ]),
+        ]);
+        map.modules = vec![
+            consumer("invoices", &["user.created"]),
+            consumer("mailer", &["user.created", "order.paid"]),
+        ];
+
+        assert_eq!(
+            map.check_boundaries(),
+            vec![BoundaryViolation::UnexportedTopic {
+                module_id: "mailer".into(),
+                topic: "order.paid".into(),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_deprecated_interface_consumers() {
+        let date = |d: &str| NaiveDate::parse_from_str(d, "%Y-%m-%d").ok();
+        let map = map(vec![
+            Domain::new("identity", "Identity", vec![]).with_interfaces(vec![
+                DomainInterface::new("LegacyAuth", InterfaceType::Api)
+                    .with_consumers(vec!["billing".into()])
+                    .deprecated(None),
+                DomainInterface::new("UsersV1", InterfaceType::Event)
+                    .with_event(
+                        EventInterface::new("user.v1").with_consumers(vec!["mailer".into()]),
+                    )
+                    .with_consumers(vec!["mailer".into(), "search".into()])
+                    .deprecated(date("2026-01-31")),
+                DomainInterface::new("Unused", InterfaceType::Api).deprecated(None),
+                DomainInterface::new("UsersV2", InterfaceType::Api)
+                    .with_consumers(vec!["billing".into()]),
+            ]),
+        ]);
+
+        let usages = map.deprecated_interface_consumers();
+        let names: Vec<_> = usages.iter().map(|u| u.interface.name.as_str()).collect();
+        assert_eq!(names, vec!["UsersV1", "LegacyAuth"]);
+        assert_eq!(usages[0].consumers, vec!["mailer", "search"]);
+        assert!(usages[0].is_past_sunset(date("2026-02-01").unwrap()));
+        assert!(!usages[1].is_past_sunset(date("2026-02-01").unwrap()));
+    }
+
+    fn module(id: &str, dependencies: &[&str]) -> Module {
+        Module::new(id, id, vec![])
+            .with_dependencies(
+                dependencies
+                    .iter()
+                    .map(|d| crate::ModuleDependency::runtime(*d))
+                    .collect(),
+            )
+            .with_primary_language("rust")
+    }
+
+    fn repo(module: Module, domain: Domain) -> ModuleMap {
+        let group = format!("{}-core", domain.id);
+        ModuleMap::new(
+            GeneratorInfo::new("test", "1.0.0"),
+            ProjectMetadata::new(domain.id.clone(), TechStack::new("rust")),
+            vec![module.clone()],
+            vec![ModuleGroup::new(&group, &group, vec![module.id])],
+        )
+        .with_domains(vec![domain])
+    }
+
+    #[test]
+    fn test_verify_consumers_across_maps() {
+        let identity = repo(
+            module("users", &[]),
+            Domain::new("identity", "Identity", vec!["identity-core".into()]).with_interfaces(
+                vec![
+                    DomainInterface::new("UsersAPI", InterfaceType::Api)
+                        .with_consumers(vec!["billing".into(), "search".into()]),
+                ],
+            ),
+        );
+        let billing = repo(
+            module("invoices", &["users"]),
+            Domain::new("billing", "Billing", vec!["billing-core".into()]),
+        );
+        let shipping = repo(
+            module("parcels", &["users"]),
+            Domain::new("shipping", "Shipping", vec!["shipping-core".into()]),
+        );
+
+        let violations = verify_consumers(&[&identity, &billing, &shipping]);
+        assert_eq!(
+            violations,
+            vec![
+                BoundaryViolation::UnbackedConsumer {
+                    domain_id: "identity".into(),
+                    interface: "UsersAPI".into(),
+                    consumer: EntityRef::Module("search".into()),
+                },
+                BoundaryViolation::UndeclaredDependency {
+                    from: "parcels".into(),
+                    to: "users".into(),
+                    from_domain: "shipping".into(),
+                    to_domain: "identity".into(),
+                },
+            ]
+        );
+        assert_eq!(verify_consumers(&[&identity, &billing]).len(), 1);
+
+        let mut billing = billing;
+        billing.domains[0].id = "payments".into();
+        billing.domains[0].aliases = vec!["billing".into()];
+        assert_eq!(verify_consumers(&[&identity, &billing]).len(), 1);
+    }
+
+    #[test]
+    fn test_check_imports() {
+        let mut api = module("api", &["users"]);
+        api.paths = vec!["src/api/".into()];
+        let mut users = module("users", &[]);
+        users.paths = vec!["src/users/".into()];
+        let mut invoices = module("invoices", &[]);
+        invoices.paths = vec!["src/invoices/".into()];
+        let map = ModuleMap::new(
+            GeneratorInfo::new("test", "1.0.0"),
+            ProjectMetadata::new("shop", TechStack::new("rust")),
+            vec![api, users, invoices],
+            vec![
+                ModuleGroup::new("identity-core", "Identity", vec!["users".into()]),
+                ModuleGroup::new("edge", "Edge", vec!["api".into()]),
+                ModuleGroup::new("billing-core", "Billing", vec!["invoices".into()]),
+            ],
+        )
+        .with_domains(vec![
+            Domain::new("identity", "Identity", vec!["identity-core".into()]).with_interfaces(
+                vec![
+                    DomainInterface::new("UsersAPI", InterfaceType::Api)
+                        .with_consumers(vec!["api".into()]),
+                ],
+            ),
+            Domain::new("billing", "Billing", vec!["billing-core".into()]),
+            Domain::new("platform", "Platform", vec!["edge".into()]),
+        ])
+        .with_shared_paths(vec![crate::SharedPath::new("proto/", vec!["api".into()])]);
+
+        let violations = map.check_imports(&[
+            ImportEdge::new("src/api/routes.rs", "src/users/lib.rs", 3),
+            ImportEdge::new("src/api/routes.rs", "src/api/util.rs", 4),
+            ImportEdge::new("src/api/routes.rs", "proto/users.rs", 5),
+            ImportEdge::new("src/invoices/a.rs", "src/users/lib.rs", 7),
+            ImportEdge::new("src/invoices/a.rs", "proto/users.rs", 8),
+            ImportEdge::new("src/invoices/a.rs", "vendor/zlib.rs", 9),
+        ]);
+
+        let kinds: Vec<_> = violations.iter().map(|v| v.kind.clone()).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                ImportViolationKind::UndeclaredDependency,
+                ImportViolationKind::UncoveredCrossDomain {
+                    from_domain: "billing".into(),
+                    to_domain: "identity".into(),
+                },
+                ImportViolationKind::RestrictedSharedPath {
+                    shared_path: "proto/".into(),
+                },
+            ]
+        );
+        assert_eq!(
+            violations[0].to_string(),
+            "src/invoices/a.rs:7: 'invoices' imports 'users' without declaring the dependency"
+        );
+        assert_eq!(violations[2].location.start_line, 8);
+    }
+}
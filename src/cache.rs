@@ -0,0 +1,145 @@
+//! On-disk cache of derived data keyed by the manifest content hash
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+
+use crate::graph::CompiledGraph;
+use crate::manifest::ProjectManifest;
+use crate::store::ContentStore;
+
+/// Cache directory relative to the manifest directory
+pub const CACHE_DIR: &str = ".modmap/cache";
+
+impl ProjectManifest {
+    /// SHA-256 of the canonical JSON form; changes whenever any field does.
+    pub fn content_hash(&self) -> serde_json::Result<String> {
+        // `Value` objects are key-sorted, so map ordering does not leak in.
+        let canonical = serde_json::to_value(self)?.to_string();
+        Ok(ContentStore::hash(&canonical))
+    }
+}
+
+/// Derived artifacts stored as `.modmap/cache/<content hash>/<kind>.json`.
+/// A changed manifest hashes differently, so stale entries are never read.
+#[derive(Debug, Clone)]
+pub struct DerivedCache {
+    dir: PathBuf,
+}
+
+impl DerivedCache {
+    pub fn new(base: impl AsRef<Path>) -> Self {
+        Self {
+            dir: base.as_ref().join(CACHE_DIR),
+        }
+    }
+
+    pub fn entry_path(&self, hash: &str, kind: &str) -> PathBuf {
+        self.dir.join(hash).join(format!("{kind}.json"))
+    }
+
+    /// Cached value, or `None` when missing or unreadable.
+    pub fn get<T: DeserializeOwned>(&self, hash: &str, kind: &str) -> Option<T> {
+        let data = fs::read_to_string(self.entry_path(hash, kind)).ok()?;
+        serde_json::from_str(&data).ok()
+    }
+
+    pub fn put<T: Serialize>(&self, hash: &str, kind: &str, value: &T) -> io::Result<()> {
+        let path = self.entry_path(hash, kind);
+        fs::create_dir_all(self.dir.join(hash))?;
+        let tmp = path.with_extension(format!("tmp.{}", std::process::id()));
+        fs::write(&tmp, serde_json::to_vec(value)?)?;
+        fs::rename(&tmp, &path)
+    }
+
+    /// Cached value, computing and storing it on a miss.
+    pub fn get_or_insert_with<T: Serialize + DeserializeOwned>(
+        &self,
+        hash: &str,
+        kind: &str,
+        compute: impl FnOnce() -> T,
+    ) -> io::Result<T> {
+        if let Some(value) = self.get(hash, kind) {
+            return Ok(value);
+        }
+        let value = compute();
+        self.put(hash, kind, &value)?;
+        Ok(value)
+    }
+
+    /// `ModuleMap::compile_graph` for `manifest`, reused across runs.
+    pub fn compiled_graph(&self, manifest: &ProjectManifest) -> io::Result<CompiledGraph> {
+        let hash = manifest.content_hash()?;
+        self.get_or_insert_with(&hash, "compiled_graph", || manifest.project.compile_graph())
+    }
+
+    /// Removes entries for every hash except `keep`; returns how many were removed.
+    pub fn prune(&self, keep: &str) -> io::Result<usize> {
+        let entries = match fs::read_dir(&self.dir) {
+            Ok(entries) => entries,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(0),
+            Err(err) => return Err(err),
+        };
+        let mut removed = 0;
+        for entry in entries {
+            let entry = entry?;
+            if entry.file_name() != keep && entry.file_type()?.is_dir() {
+                fs::remove_dir_all(entry.path())?;
+                removed += 1;
+            }
+        }
+        Ok(removed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{GeneratorInfo, ModuleMap, ProjectMetadata, TechStack};
+
+    fn manifest(name: &str) -> ProjectManifest {
+        ProjectManifest::new(ModuleMap::new(
+            GeneratorInfo::new("test", "1.0.0"),
+            ProjectMetadata::new(name, TechStack::new("rust")),
+            vec![],
+            vec![],
+        ))
+    }
+
+    #[test]
+    fn test_cache_keyed_by_content_hash() {
+        let dir = std::env::temp_dir().join(format!("modmap-cache-{}", std::process::id()));
+        let cache = DerivedCache::new(&dir);
+        let first = manifest("shop");
+        let hash = first.content_hash().unwrap();
+        assert_eq!(hash, first.clone().content_hash().unwrap());
+
+        let mut calls = 0;
+        for _ in 0..2 {
+            let value = cache
+                .get_or_insert_with(&hash, "rule_bundle", || {
+                    calls += 1;
+                    vec!["auth-rules".to_string()]
+                })
+                .unwrap();
+            assert_eq!(value, vec!["auth-rules"]);
+        }
+        assert_eq!(calls, 1);
+        assert_eq!(
+            cache.compiled_graph(&first).unwrap(),
+            first.project.compile_graph()
+        );
+
+        let second = manifest("shop-renamed");
+        let other = second.content_hash().unwrap();
+        assert_ne!(hash, other);
+        assert_eq!(cache.get::<Vec<String>>(&other, "rule_bundle"), None);
+        cache.put(&other, "rule_bundle", &vec!["x"]).unwrap();
+        assert_eq!(cache.prune(&other).unwrap(), 1);
+        assert_eq!(cache.get::<Vec<String>>(&hash, "rule_bundle"), None);
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,261 @@
+//! Conventional-commit scopes for a set of changed files
+
+use std::collections::HashMap;
+
+use serde::Serialize;
+
+use crate::module_map::ModuleMap;
+use crate::normalize::canonical_id;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ScopeLevel {
+    #[default]
+    Module,
+    Group,
+}
+
+/// How scope names are derived from map ids
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScopePolicy {
+    pub level: ScopeLevel,
+    /// Prefixes removed from ids, e.g. `"pkg-"`
+    pub strip_prefixes: Vec<String>,
+    /// More distinct module scopes than this fall back to group scopes
+    pub max_scopes: usize,
+}
+
+impl Default for ScopePolicy {
+    fn default() -> Self {
+        Self {
+            level: ScopeLevel::Module,
+            strip_prefixes: Vec::new(),
+            max_scopes: 3,
+        }
+    }
+}
+
+impl ScopePolicy {
+    pub fn with_level(mut self, level: ScopeLevel) -> Self {
+        self.level = level;
+        self
+    }
+
+    pub fn with_strip_prefixes(mut self, prefixes: Vec<String>) -> Self {
+        self.strip_prefixes = prefixes;
+        self
+    }
+
+    pub fn with_max_scopes(mut self, max_scopes: usize) -> Self {
+        self.max_scopes = max_scopes;
+        self
+    }
+
+    fn short_name(&self, id: &str) -> String {
+        let id = canonical_id(id);
+        self.strip_prefixes
+            .iter()
+            .find_map(|p| id.strip_prefix(p.as_str()).filter(|rest| !rest.is_empty()))
+            .map_or_else(|| id.clone(), String::from)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct CommitScope {
+    pub scope: String,
+    pub level: ScopeLevel,
+    /// Module or group id the scope stands for
+    pub entity_id: String,
+    /// Changed files under this scope
+    pub files: usize,
+}
+
+impl ModuleMap {
+    /// Scopes for `changed_files`, most touched first. Names are unique across
+    /// the whole map so the same module always gets the same scope.
+    pub fn suggest_commit_scopes(
+        &self,
+        changed_files: &[&str],
+        policy: &ScopePolicy,
+    ) -> Vec<CommitScope> {
+        let scopes = self.collect_scopes(changed_files, policy, policy.level);
+        if policy.level == ScopeLevel::Module && scopes.len() > policy.max_scopes {
+            let grouped = self.collect_scopes(changed_files, policy, ScopeLevel::Group);
+            if !grouped.is_empty() {
+                return grouped;
+            }
+        }
+        scopes
+    }
+
+    fn collect_scopes(
+        &self,
+        changed_files: &[&str],
+        policy: &ScopePolicy,
+        level: ScopeLevel,
+    ) -> Vec<CommitScope> {
+        let names = self.scope_names(policy, level);
+        let mut scopes: Vec<CommitScope> = Vec::new();
+        for path in changed_files {
+            let Some(module) = self.modules.iter().find(|m| m.contains_file(path)) else {
+                continue;
+            };
+            let entity_id = match level {
+                ScopeLevel::Module => Some(module.id.as_str()),
+                ScopeLevel::Group => self
+                    .find_group_containing(&module.id)
+                    .map(|g| g.id.as_str()),
+            };
+            let Some(entity_id) = entity_id else {
+                continue;
+            };
+            match scopes.iter_mut().find(|s| s.entity_id == entity_id) {
+                Some(scope) => scope.files += 1,
+                None => scopes.push(CommitScope {
+                    scope: names[entity_id].clone(),
+                    level,
+                    entity_id: entity_id.to_string(),
+                    files: 1,
+                }),
+            }
+        }
+        scopes.sort_by(|a, b| b.files.cmp(&a.files).then_with(|| a.scope.cmp(&b.scope)));
+        scopes
+    }
+
+    /// Short names for every entity at `level`; colliding names are qualified
+    /// with the parent group (or domain), then fall back to the full id.
+    fn scope_names(&self, policy: &ScopePolicy, level: ScopeLevel) -> HashMap<&str, String> {
+        let entities: Vec<(&str, Option<&str>)> = match level {
+            ScopeLevel::Module => self
+                .modules
+                .iter()
+                .map(|m| {
+                    let parent = self.find_group_containing(&m.id).map(|g| g.id.as_str());
+                    (m.id.as_str(), parent)
+                })
+                .collect(),
+            ScopeLevel::Group => self
+                .groups
+                .iter()
+                .map(|g| {
+                    let parent = g.domain_id.as_deref().or_else(|| {
+                        self.find_domain_containing_group(&g.id)
+                            .map(|d| d.id.as_str())
+                    });
+                    (g.id.as_str(), parent)
+                })
+                .collect(),
+        };
+
+        let short: Vec<String> = entities
+            .iter()
+            .map(|(id, _)| policy.short_name(id))
+            .collect();
+        let count = |name: &str, names: &[String]| names.iter().filter(|n| *n == name).count();
+
+        let qualified: Vec<String> = entities
+            .iter()
+            .zip(&short)
+            .map(|((id, parent), name)| match parent {
+                Some(parent) if count(name, &short) > 1 => {
+                    format!("{}-{name}", policy.short_name(parent))
+                }
+                None if count(name, &short) > 1 => canonical_id(id),
+                _ => name.clone(),
+            })
+            .collect();
+
+        entities
+            .iter()
+            .zip(qualified.iter())
+            .map(|((id, _), name)| {
+                let name = if count(name, &qualified) > 1 {
+                    canonical_id(id)
+                } else {
+                    name.clone()
+                };
+                (*id, name)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{GeneratorInfo, Module, ModuleGroup, ProjectMetadata, TechStack};
+
+    fn module(id: &str, path: &str) -> Module {
+        Module::new(id, id, vec![path.into()]).with_primary_language("rust")
+    }
+
+    fn sample() -> ModuleMap {
+        ModuleMap::new(
+            GeneratorInfo::new("test", "1.0.0"),
+            ProjectMetadata::new("shop", TechStack::new("rust")),
+            vec![
+                module("pkg-auth", "packages/auth/"),
+                module("pkg-billing", "packages/billing/"),
+                module("web-api", "apps/web/api/"),
+                module("admin-api", "apps/admin/api/"),
+                module("pkg-ui", "packages/ui/"),
+            ],
+            vec![
+                ModuleGroup::new(
+                    "core",
+                    "Core",
+                    vec!["pkg-auth".into(), "pkg-billing".into(), "pkg-ui".into()],
+                ),
+                ModuleGroup::new("web", "Web", vec!["web-api".into()]),
+                ModuleGroup::new("admin", "Admin", vec!["admin-api".into()]),
+            ],
+        )
+    }
+
+    #[test]
+    fn test_suggest_commit_scopes() {
+        let map = sample();
+        let policy = ScopePolicy::default().with_strip_prefixes(vec![
+            "pkg-".into(),
+            "web-".into(),
+            "admin-".into(),
+        ]);
+
+        let scopes = map.suggest_commit_scopes(
+            &[
+                "packages/auth/src/lib.rs",
+                "packages/auth/src/token.rs",
+                "apps/web/api/routes.ts",
+                "apps/admin/api/routes.ts",
+                "README.md",
+            ],
+            &policy,
+        );
+        let names: Vec<_> = scopes.iter().map(|s| s.scope.as_str()).collect();
+        // "api" collides, so both are qualified with their group
+        assert_eq!(names, vec!["auth", "admin-api", "web-api"]);
+        assert_eq!(scopes[0].files, 2);
+        assert_eq!(scopes[0].entity_id, "pkg-auth");
+    }
+
+    #[test]
+    fn test_group_fallback() {
+        let map = sample();
+        let policy = ScopePolicy::default()
+            .with_strip_prefixes(vec!["pkg-".into()])
+            .with_max_scopes(2);
+        let scopes = map.suggest_commit_scopes(
+            &[
+                "packages/auth/a.rs",
+                "packages/billing/b.rs",
+                "packages/ui/c.rs",
+            ],
+            &policy,
+        );
+        assert_eq!(scopes.len(), 1);
+        assert_eq!(scopes[0].scope, "core");
+        assert_eq!(scopes[0].level, ScopeLevel::Group);
+        assert_eq!(scopes[0].files, 3);
+    }
+}
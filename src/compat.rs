@@ -0,0 +1,228 @@
+//! Compatibility checks against a corpus of historical manifest fixtures
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::registry::{SchemaError, SchemaRegistry};
+
+/// Outcome of loading one fixture
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CompatStatus {
+    /// Loaded, validated cleanly and survived a save/load round trip
+    Compatible,
+    /// Loaded, but validation reported issues
+    Degraded,
+    /// Loaded, but the re-serialized manifest failed to load again
+    RoundTripFailed,
+    /// The registry refused the file
+    Rejected,
+}
+
+/// Result for one fixture file
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct CompatEntry {
+    pub fixture: String,
+    /// `project.schema_version`, when the file is readable JSON
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub schema_version: Option<String>,
+    pub status: CompatStatus,
+    /// Load error or validation issues
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub messages: Vec<String>,
+}
+
+/// Per-fixture results for a corpus, in file name order
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct CompatReport {
+    pub entries: Vec<CompatEntry>,
+}
+
+impl CompatReport {
+    /// Whether every fixture loaded and round-tripped (validation issues allowed).
+    pub fn is_compatible(&self) -> bool {
+        self.entries
+            .iter()
+            .all(|e| matches!(e.status, CompatStatus::Compatible | CompatStatus::Degraded))
+    }
+
+    /// Status counts per schema version; unreadable files count under `unknown`.
+    pub fn matrix(&self) -> BTreeMap<String, BTreeMap<String, usize>> {
+        let mut matrix: BTreeMap<String, BTreeMap<String, usize>> = BTreeMap::new();
+        for entry in &self.entries {
+            let version = entry.schema_version.as_deref().unwrap_or("unknown");
+            *matrix
+                .entry(version.to_string())
+                .or_default()
+                .entry(entry.status.as_str().to_string())
+                .or_default() += 1;
+        }
+        matrix
+    }
+
+    /// Markdown table of the matrix, one row per schema version.
+    pub fn to_markdown(&self) -> String {
+        const COLUMNS: [CompatStatus; 4] = [
+            CompatStatus::Compatible,
+            CompatStatus::Degraded,
+            CompatStatus::RoundTripFailed,
+            CompatStatus::Rejected,
+        ];
+        let mut out = String::from("| schema_version |");
+        for status in &COLUMNS {
+            out.push_str(&format!(" {} |", status.as_str()));
+        }
+        out.push_str("\n|---|---|---|---|---|\n");
+        for (version, counts) in self.matrix() {
+            out.push_str(&format!("| {version} |"));
+            for status in &COLUMNS {
+                let count = counts.get(status.as_str()).copied().unwrap_or(0);
+                out.push_str(&format!(" {count} |"));
+            }
+            out.push('\n');
+        }
+        out
+    }
+}
+
+impl CompatStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Compatible => "compatible",
+            Self::Degraded => "degraded",
+            Self::RoundTripFailed => "round_trip_failed",
+            Self::Rejected => "rejected",
+        }
+    }
+}
+
+impl SchemaRegistry {
+    /// Loads, validates and round-trips one fixture.
+    pub fn check_compat(&self, fixture: impl Into<String>, data: &str) -> CompatEntry {
+        let schema_version = serde_json::from_str::<Value>(data).ok().and_then(|v| {
+            v.pointer("/project/schema_version")
+                .and_then(Value::as_str)
+                .map(str::to_string)
+        });
+        let (status, messages) = match self.load(data) {
+            Err(err) => (CompatStatus::Rejected, vec![err.to_string()]),
+            Ok(manifest) => {
+                let reloaded = serde_json::to_string(&manifest)
+                    .map_err(SchemaError::from)
+                    .and_then(|json| self.load(&json));
+                match reloaded {
+                    Err(err) => (CompatStatus::RoundTripFailed, vec![err.to_string()]),
+                    Ok(_) => {
+                        let issues: Vec<String> = manifest
+                            .validate_contexts(None)
+                            .iter()
+                            .map(ToString::to_string)
+                            .collect();
+                        if issues.is_empty() {
+                            (CompatStatus::Compatible, issues)
+                        } else {
+                            (CompatStatus::Degraded, issues)
+                        }
+                    }
+                }
+            }
+        };
+        CompatEntry {
+            fixture: fixture.into(),
+            schema_version,
+            status,
+            messages,
+        }
+    }
+
+    /// Runs `check_compat` over every `.json` file in `dir`, sorted by name.
+    pub fn check_corpus(&self, dir: &Path) -> Result<CompatReport, SchemaError> {
+        let mut paths: Vec<_> = fs::read_dir(dir)?
+            .map(|entry| entry.map(|e| e.path()))
+            .collect::<Result<_, _>>()?;
+        paths.retain(|p| p.is_file() && p.extension().is_some_and(|e| e == "json"));
+        paths.sort();
+
+        let mut report = CompatReport::default();
+        for path in paths {
+            let data = fs::read_to_string(&path)?;
+            let name = path
+                .file_name()
+                .map(|n| n.to_string_lossy().into_owned())
+                .unwrap_or_default();
+            report.entries.push(self.check_compat(name, &data));
+        }
+        Ok(report)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fixture(schema_version: &str, extra: &str) -> String {
+        format!(
+            r#"{{
+                "version": "2.0.0",
+                "created_at": "2026-01-29T00:00:00Z",
+                "generator": "claudegen",
+                "project": {{
+                    "schema_version": "{schema_version}",
+                    "generator": {{"name": "claudegen", "version": "0.3.0"}},
+                    "project": {{
+                        "name": "legacy",
+                        "workspace": {{}},
+                        "tech_stack": {{"primary_language": "rust"}},
+                        "languages": [],
+                        "total_files": 0
+                    }},
+                    "modules": [],
+                    "generated_at": "2026-01-29T00:00:00Z"
+                }}{extra}
+            }}"#
+        )
+    }
+
+    #[test]
+    fn test_check_corpus() {
+        let dir = std::env::temp_dir().join(format!("modmap-compat-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("a-1.0.json"), fixture("1.0.0", "")).unwrap();
+        fs::write(
+            dir.join("b-1.2.json"),
+            fixture("1.2.0", r#", "modules": {"ghost": {}}"#),
+        )
+        .unwrap();
+        fs::write(dir.join("c-0.2.json"), fixture("0.2.0", "")).unwrap();
+        fs::write(dir.join("d-broken.json"), "{\"project\":").unwrap();
+        fs::write(dir.join("notes.txt"), "ignored").unwrap();
+
+        let report = SchemaRegistry::new().check_corpus(&dir).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+
+        let statuses: Vec<_> = report.entries.iter().map(|e| &e.status).collect();
+        assert_eq!(
+            statuses,
+            vec![
+                &CompatStatus::Compatible,
+                &CompatStatus::Degraded,
+                &CompatStatus::Rejected,
+                &CompatStatus::Rejected,
+            ]
+        );
+        assert_eq!(
+            report.entries[1].messages,
+            vec!["context for unknown module 'ghost'"]
+        );
+        assert!(!report.is_compatible());
+
+        let matrix = report.matrix();
+        assert_eq!(matrix["0.2.0"]["rejected"], 1);
+        assert_eq!(matrix["unknown"]["rejected"], 1);
+        assert!(report.to_markdown().contains("| 1.0.0 | 1 | 0 | 0 | 0 |"));
+    }
+}
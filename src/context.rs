@@ -0,0 +1,471 @@
+//! Path-based context resolution and change impact
+
+use std::collections::HashSet;
+
+use serde::Serialize;
+
+use crate::explain::Explanation;
+use crate::manifest::ProjectManifest;
+use crate::module_map::{GeneratedCode, Module, ModuleMap};
+use crate::rule::Rule;
+use crate::visibility::{Visibility, rule_visibility};
+
+/// Everything injected for a single file
+#[derive(Debug, Clone, Serialize)]
+pub struct ResolvedContext<'a> {
+    pub path: String,
+    /// Modules whose paths contain the file
+    #[serde(serialize_with = "serialize_ids")]
+    pub modules: Vec<&'a Module>,
+    /// Rule names, module rules first, then group and domain rules
+    pub rules: Vec<String>,
+    pub skills: Vec<String>,
+    /// Set for generated files, which get no rules or skills: do not hand-edit
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub generated: Option<&'a GeneratedCode>,
+    /// Why each rule and skill was selected, in explain mode
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub explanations: Vec<Explanation>,
+}
+
+fn serialize_ids<S: serde::Serializer>(modules: &[&Module], s: S) -> Result<S::Ok, S::Error> {
+    s.collect_seq(modules.iter().map(|m| &m.id))
+}
+
+impl ResolvedContext<'_> {
+    pub fn is_empty(&self) -> bool {
+        self.modules.is_empty()
+    }
+}
+
+/// Context of a file set: what every owned file shares, once, plus per-file extras
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct BatchContext<'a> {
+    /// Union of modules owning any of the files
+    #[serde(serialize_with = "serialize_ids")]
+    pub modules: Vec<&'a Module>,
+    /// Rules applying to every file owned by a module
+    pub rules: Vec<String>,
+    pub skills: Vec<String>,
+    pub files: Vec<FileContext>,
+}
+
+/// Per-file remainder of a `BatchContext`
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct FileContext {
+    pub path: String,
+    pub module_ids: Vec<String>,
+    /// Rules not already in the batch's shared rules
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub rules: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub skills: Vec<String>,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub generated: bool,
+}
+
+/// Resolves module, group and domain contexts of a manifest for file paths
+#[derive(Debug, Clone, Copy)]
+pub struct ContextResolver<'a> {
+    manifest: &'a ProjectManifest,
+    explain: bool,
+    access: Option<(Visibility, &'a [Rule])>,
+}
+
+impl<'a> ContextResolver<'a> {
+    pub fn new(manifest: &'a ProjectManifest) -> Self {
+        Self {
+            manifest,
+            explain: false,
+            access: None,
+        }
+    }
+
+    /// Injects only rules visible at `level`, looking visibility up in `rules`.
+    /// Withheld rules leave no explanation behind.
+    pub fn with_access(mut self, level: Visibility, rules: &'a [Rule]) -> Self {
+        self.access = Some((level, rules));
+        self
+    }
+
+    /// Records in `ResolvedContext::explanations` where every rule and skill came from.
+    pub fn with_explanations(mut self) -> Self {
+        self.explain = true;
+        self
+    }
+
+    pub fn resolve(&self, path: &str) -> ResolvedContext<'a> {
+        let map = &self.manifest.project;
+        let modules: Vec<&Module> = map
+            .modules
+            .iter()
+            .filter(|m| m.contains_file(path))
+            .collect();
+
+        let generated = map.generated_code_for(path);
+        let mut rules = Vec::new();
+        let mut skills = Vec::new();
+        let mut explanations = Vec::new();
+        let mut why =
+            |kind: &str, items: &[String], scope: &str, id: &str, list: &mut Vec<String>| {
+                for item in items {
+                    let withheld = self.access.is_some_and(|(level, rules)| {
+                        kind == "rule" && !rule_visibility(rules, item).visible_to(level)
+                    });
+                    if withheld || list.contains(item) {
+                        continue;
+                    }
+                    list.push(item.clone());
+                    if self.explain {
+                        explanations.push(
+                            Explanation::new(format!(
+                                "{kind} '{item}' from {scope} context '{id}'"
+                            ))
+                            .with_data("path", path)
+                            .with_data(scope, id),
+                        );
+                    }
+                }
+            };
+        for module in modules.iter().filter(|_| generated.is_none()) {
+            let ctx = self.manifest.get_module_context(&module.id);
+            if let Some(ctx) = ctx {
+                why("rule", &ctx.rules, "module", &module.id, &mut rules);
+                why("skill", &ctx.skills, "module", &module.id, &mut skills);
+            }
+
+            let group_id = ctx
+                .and_then(|c| c.group_id.clone())
+                .or_else(|| map.find_group_containing(&module.id).map(|g| g.id.clone()));
+            if let Some(id) = group_id
+                && let Some(group) = self.manifest.get_group_context(&id)
+            {
+                why("rule", &group.rules, "group", &id, &mut rules);
+            }
+
+            let domain_id = ctx.and_then(|c| c.domain_id.clone()).or_else(|| {
+                map.find_domain_containing_module(&module.id)
+                    .map(|d| d.id.clone())
+            });
+            if let Some(id) = domain_id
+                && let Some(domain) = self.manifest.get_domain_context(&id)
+            {
+                why("rule", &domain.rules, "domain", &id, &mut rules);
+            }
+        }
+        if self.explain {
+            if let Some(generated) = generated {
+                let mut explanation =
+                    Explanation::new("generated file: no rules or skills injected")
+                        .with_data("path", path);
+                if let Some(generator) = &generated.generator {
+                    explanation = explanation.with_data("generator", generator.as_str());
+                }
+                explanations.push(explanation);
+            } else if modules.is_empty() {
+                explanations
+                    .push(Explanation::new("no module owns this path").with_data("path", path));
+            }
+        }
+
+        ResolvedContext {
+            path: path.to_string(),
+            modules,
+            rules,
+            skills,
+            generated,
+            explanations,
+        }
+    }
+
+    /// Resolves many files at once, hoisting rules and skills common to all
+    /// owned files into the shared part. Duplicate paths are resolved once.
+    pub fn resolve_batch(&self, paths: &[&str]) -> BatchContext<'a> {
+        let mut seen = HashSet::new();
+        let resolved: Vec<ResolvedContext<'a>> = paths
+            .iter()
+            .filter(|p| seen.insert(**p))
+            .map(|p| self.resolve(p))
+            .collect();
+
+        let mut owned = resolved
+            .iter()
+            .filter(|ctx| !ctx.is_empty() && ctx.generated.is_none());
+        let (mut rules, mut skills) = owned
+            .next()
+            .map(|ctx| (ctx.rules.clone(), ctx.skills.clone()))
+            .unwrap_or_default();
+        for ctx in owned {
+            rules.retain(|r| ctx.rules.contains(r));
+            skills.retain(|s| ctx.skills.contains(s));
+        }
+
+        let mut modules: Vec<&Module> = Vec::new();
+        let mut files = Vec::with_capacity(resolved.len());
+        for ctx in resolved {
+            for module in &ctx.modules {
+                if !modules.iter().any(|m| m.id == module.id) {
+                    modules.push(module);
+                }
+            }
+            files.push(FileContext {
+                module_ids: ctx.modules.iter().map(|m| m.id.clone()).collect(),
+                rules: ctx
+                    .rules
+                    .into_iter()
+                    .filter(|r| !rules.contains(r))
+                    .collect(),
+                skills: ctx
+                    .skills
+                    .into_iter()
+                    .filter(|s| !skills.contains(s))
+                    .collect(),
+                generated: ctx.generated.is_some(),
+                path: ctx.path,
+            });
+        }
+
+        BatchContext {
+            modules,
+            rules,
+            skills,
+            files,
+        }
+    }
+}
+
+impl ModuleMap {
+    /// Modules owning any of `paths`, followed by everything that transitively
+    /// depends on them, nearest first. Walks the same edges as
+    /// `transitive_dependents`.
+    pub fn impacted_modules(&self, paths: &[&str]) -> Vec<&Module> {
+        let owners: Vec<&str> = self
+            .modules
+            .iter()
+            .filter(|m| paths.iter().any(|p| m.contains_file(p)))
+            .map(|m| m.id.as_str())
+            .collect();
+        self.compile_graph()
+            .transitive_dependents(&owners)
+            .into_iter()
+            .filter_map(|id| self.find_module(id))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        Domain, DomainContext, GeneratorInfo, GroupContext, ModuleContext, ModuleDependency,
+        ModuleGroup, ProjectMetadata, TechStack,
+    };
+
+    fn module(id: &str, dependencies: &[&str]) -> Module {
+        Module::new(id, id, vec![format!("src/{id}/")])
+            .with_dependencies(
+                dependencies
+                    .iter()
+                    .map(|d| ModuleDependency::runtime(*d))
+                    .collect(),
+            )
+            .with_primary_language("rust")
+    }
+
+    fn sample_manifest() -> ProjectManifest {
+        let map = ModuleMap::new(
+            GeneratorInfo::new("test", "1.0.0"),
+            ProjectMetadata::new("test", TechStack::new("rust")),
+            vec![
+                module("auth", &[]),
+                module("api", &["auth"]),
+                module("cli", &["api"]),
+                module("docs", &[]),
+            ],
+            vec![
+                ModuleGroup::new("core", "Core", vec!["auth".into(), "api".into()])
+                    .with_domain("identity"),
+            ],
+        )
+        .with_domains(vec![Domain::new(
+            "identity",
+            "Identity",
+            vec!["core".into()],
+        )]);
+        ProjectManifest::new(map)
+            .with_modules(
+                [(
+                    "auth".to_string(),
+                    ModuleContext::new()
+                        .with_rules(vec!["auth-rules".into(), "shared".into()])
+                        .with_skills(vec!["login-flow".into()]),
+                )]
+                .into(),
+            )
+            .with_groups(
+                [(
+                    "core".to_string(),
+                    GroupContext::new().with_rules(vec!["shared".into()]),
+                )]
+                .into(),
+            )
+            .with_domains(
+                [(
+                    "identity".to_string(),
+                    DomainContext::new().with_rules(vec!["identity-rules".into()]),
+                )]
+                .into(),
+            )
+    }
+
+    #[test]
+    fn test_resolve_path() {
+        let manifest = sample_manifest();
+        let resolver = ContextResolver::new(&manifest);
+
+        let ctx = resolver.resolve("src/auth/token.rs");
+        assert_eq!(ctx.modules[0].id, "auth");
+        assert_eq!(ctx.rules, vec!["auth-rules", "shared", "identity-rules"]);
+        assert_eq!(ctx.skills, vec!["login-flow"]);
+
+        let json = serde_json::to_value(&ctx).unwrap();
+        assert_eq!(json["modules"][0], "auth");
+
+        assert!(resolver.resolve("README.md").is_empty());
+    }
+
+    #[test]
+    fn test_resolve_through_contexts_keyed_by_alias() {
+        let mut manifest = sample_manifest();
+        let map = &mut manifest.project;
+        map.modules[0].id = "identity-auth".into();
+        map.modules[0].aliases = vec!["auth".into()];
+        map.groups[0].id = "identity-core".into();
+        map.groups[0].aliases = vec!["core".into()];
+
+        let ctx = ContextResolver::new(&manifest).resolve("src/auth/token.rs");
+        assert_eq!(ctx.modules[0].id, "identity-auth");
+        assert_eq!(ctx.rules, vec!["auth-rules", "shared", "identity-rules"]);
+        assert_eq!(ctx.skills, vec!["login-flow"]);
+        assert!(manifest.get_module_context("identity-auth").is_some());
+        assert!(manifest.get_group_context("identity-core").is_some());
+        assert_eq!(
+            manifest
+                .get_module_context_refs("identity-auth")
+                .unwrap()
+                .module_id,
+            "identity-auth"
+        );
+    }
+
+    #[test]
+    fn test_resolve_with_explanations() {
+        let manifest = sample_manifest();
+        let resolver = ContextResolver::new(&manifest).with_explanations();
+
+        let ctx = resolver.resolve("src/auth/token.rs");
+        assert_eq!(ctx.explanations.len(), ctx.rules.len() + ctx.skills.len());
+        assert_eq!(
+            ctx.explanations[0].to_string(),
+            "rule 'auth-rules' from module context 'auth' (module=\"auth\", path=\"src/auth/token.rs\")"
+        );
+        assert_eq!(
+            resolver.resolve("README.md").explanations[0].reason,
+            "no module owns this path"
+        );
+        assert!(
+            ContextResolver::new(&manifest)
+                .resolve("src/auth/token.rs")
+                .explanations
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_generated_files_get_no_rules() {
+        let mut manifest = sample_manifest();
+        manifest.project.project.generated_paths =
+            vec![GeneratedCode::new(vec!["src/auth/gen/**".into()]).with_generator("protoc")];
+        let resolver = ContextResolver::new(&manifest);
+
+        let ctx = resolver.resolve("src/auth/gen/token.pb.rs");
+        assert_eq!(ctx.modules[0].id, "auth");
+        assert!(ctx.rules.is_empty() && ctx.skills.is_empty());
+        assert_eq!(ctx.generated.unwrap().generator.as_deref(), Some("protoc"));
+
+        let batch = resolver.resolve_batch(&["src/auth/gen/token.pb.rs", "src/auth/token.rs"]);
+        assert!(batch.files[0].generated);
+        assert_eq!(batch.rules, vec!["auth-rules", "shared", "identity-rules"]);
+    }
+
+    #[test]
+    fn test_resolve_batch() {
+        let manifest = sample_manifest();
+        let resolver = ContextResolver::new(&manifest);
+
+        let batch = resolver.resolve_batch(&[
+            "src/auth/token.rs",
+            "src/api/routes.rs",
+            "src/auth/token.rs",
+            "README.md",
+        ]);
+        let ids: Vec<_> = batch.modules.iter().map(|m| m.id.as_str()).collect();
+        assert_eq!(ids, vec!["auth", "api"]);
+        assert_eq!(batch.rules, vec!["shared", "identity-rules"]);
+        assert!(batch.skills.is_empty());
+
+        assert_eq!(batch.files.len(), 3);
+        assert_eq!(batch.files[0].rules, vec!["auth-rules"]);
+        assert_eq!(batch.files[0].skills, vec!["login-flow"]);
+        assert!(batch.files[1].rules.is_empty());
+        assert!(batch.files[2].module_ids.is_empty());
+
+        let json = serde_json::to_value(&batch).unwrap();
+        assert_eq!(json["modules"], serde_json::json!(["auth", "api"]));
+        assert!(json["files"][1].get("rules").is_none());
+    }
+
+    #[test]
+    fn test_impacted_modules() {
+        let manifest = sample_manifest();
+        let ids: Vec<_> = manifest
+            .project
+            .impacted_modules(&["src/auth/lib.rs"])
+            .iter()
+            .map(|m| m.id.as_str())
+            .collect();
+        assert_eq!(ids, vec!["auth", "api", "cli"]);
+
+        assert!(
+            manifest
+                .project
+                .impacted_modules(&["other/x.rs"])
+                .is_empty()
+        );
+
+        // An explicit graph takes precedence, as for `transitive_dependents`
+        let map = manifest
+            .project
+            .clone()
+            .with_dependency_graph(crate::DependencyGraph {
+                edges: vec![crate::DependencyEdge {
+                    from: "cli".into(),
+                    to: "auth".into(),
+                    edge_type: Default::default(),
+                }],
+                layers: vec![],
+            });
+        let impacted: Vec<_> = map
+            .impacted_modules(&["src/auth/lib.rs"])
+            .iter()
+            .map(|m| m.id.as_str())
+            .collect();
+        assert_eq!(impacted, ["auth", "cli"]);
+        let dependents: Vec<_> = map
+            .transitive_dependents("auth")
+            .iter()
+            .map(|m| m.id.as_str())
+            .collect();
+        assert_eq!(dependents, ["cli"]);
+    }
+}
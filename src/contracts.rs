@@ -0,0 +1,280 @@
+//! Checks interface contract files and counts their operations (feature: contracts)
+
+use std::fmt;
+use std::fs;
+use std::path::Path;
+
+use crate::module_map::{ContractKind, ContractRef, ModuleMap};
+
+const HTTP_METHODS: &[&str] = &[
+    "get", "put", "post", "delete", "patch", "head", "options", "trace",
+];
+
+/// Operation count of one contract file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContractMetrics {
+    pub domain_id: String,
+    pub interface: String,
+    pub contract: ContractRef,
+    /// HTTP operations, RPC methods or root GraphQL fields
+    pub operations: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ContractIssue {
+    Missing {
+        domain_id: String,
+        interface: String,
+        path: String,
+    },
+    Unreadable {
+        domain_id: String,
+        interface: String,
+        path: String,
+        error: String,
+    },
+}
+
+impl fmt::Display for ContractIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Missing {
+                domain_id,
+                interface,
+                path,
+            } => write!(f, "{domain_id}/{interface}: contract '{path}' not found"),
+            Self::Unreadable {
+                domain_id,
+                interface,
+                path,
+                error,
+            } => write!(
+                f,
+                "{domain_id}/{interface}: contract '{path}' unreadable: {error}"
+            ),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ContractReport {
+    pub metrics: Vec<ContractMetrics>,
+    pub issues: Vec<ContractIssue>,
+}
+
+impl ContractReport {
+    pub fn is_valid(&self) -> bool {
+        self.issues.is_empty()
+    }
+}
+
+/// Counts operations in an OpenAPI (JSON or YAML), protobuf or GraphQL SDL document.
+pub fn count_operations(kind: ContractKind, text: &str) -> Result<usize, serde_json::Error> {
+    Ok(match kind {
+        ContractKind::Openapi if text.trim_start().starts_with('{') => {
+            let spec: serde_json::Value = serde_json::from_str(text)?;
+            spec["paths"]
+                .as_object()
+                .into_iter()
+                .flat_map(|paths| paths.values())
+                .filter_map(|item| item.as_object())
+                .flat_map(|item| item.keys())
+                .filter(|key| HTTP_METHODS.contains(&key.as_str()))
+                .count()
+        }
+        ContractKind::Openapi => count_yaml_operations(text),
+        ContractKind::Protobuf => text
+            .lines()
+            .filter(|line| line.trim_start().starts_with("rpc "))
+            .count(),
+        ContractKind::Graphql => count_graphql_root_fields(text),
+    })
+}
+
+/// Method keys under the top-level `paths:` mapping
+fn count_yaml_operations(text: &str) -> usize {
+    let mut in_paths = false;
+    let mut count = 0;
+    for line in text.lines() {
+        if line.trim().is_empty() || line.trim_start().starts_with('#') {
+            continue;
+        }
+        if !line.starts_with([' ', '\t']) {
+            in_paths = line.trim_end() == "paths:";
+            continue;
+        }
+        if in_paths
+            && let Some(key) = line.trim().strip_suffix(':')
+            && HTTP_METHODS.contains(&key)
+        {
+            count += 1;
+        }
+    }
+    count
+}
+
+/// Fields of `Query`, `Mutation` and `Subscription` (including `extend type`)
+fn count_graphql_root_fields(text: &str) -> usize {
+    let mut in_root = false;
+    let mut parens = 0i32;
+    let mut count = 0;
+    for line in text.lines() {
+        let line = line.split('#').next().unwrap_or_default().trim();
+        if line.is_empty() {
+            continue;
+        }
+        if !in_root {
+            let header = line
+                .trim_start_matches("extend ")
+                .trim_end_matches('{')
+                .trim();
+            in_root = line.ends_with('{')
+                && matches!(
+                    header.split_whitespace().collect::<Vec<_>>().as_slice(),
+                    ["type", "Query" | "Mutation" | "Subscription", ..]
+                );
+            continue;
+        }
+        if parens == 0 {
+            if line.starts_with('}') {
+                in_root = false;
+                continue;
+            }
+            if line.starts_with(|c: char| c.is_alphabetic() || c == '_') {
+                count += 1;
+            }
+        }
+        parens += line.matches('(').count() as i32 - line.matches(')').count() as i32;
+    }
+    count
+}
+
+impl ModuleMap {
+    /// Checks every interface contract exists under `root` and counts its operations.
+    pub fn validate_contracts(&self, root: &Path) -> ContractReport {
+        let mut report = ContractReport::default();
+        for domain in &self.domains {
+            for interface in &domain.interfaces {
+                let Some(contract) = &interface.contract else {
+                    continue;
+                };
+                let path = root.join(&contract.path);
+                if !path.is_file() {
+                    report.issues.push(ContractIssue::Missing {
+                        domain_id: domain.id.clone(),
+                        interface: interface.name.clone(),
+                        path: contract.path.clone(),
+                    });
+                    continue;
+                }
+                let counted = fs::read_to_string(&path)
+                    .map_err(|e| e.to_string())
+                    .and_then(|text| {
+                        count_operations(contract.kind, &text).map_err(|e| e.to_string())
+                    });
+                match counted {
+                    Ok(operations) => report.metrics.push(ContractMetrics {
+                        domain_id: domain.id.clone(),
+                        interface: interface.name.clone(),
+                        contract: contract.clone(),
+                        operations,
+                    }),
+                    Err(error) => report.issues.push(ContractIssue::Unreadable {
+                        domain_id: domain.id.clone(),
+                        interface: interface.name.clone(),
+                        path: contract.path.clone(),
+                        error,
+                    }),
+                }
+            }
+        }
+        report
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        Domain, DomainInterface, GeneratorInfo, InterfaceType, ProjectMetadata, TechStack,
+    };
+
+    #[test]
+    fn test_count_operations() {
+        let yaml = "\
+openapi: 3.0.0
+paths:
+  /users:
+    get:
+      summary: List
+    post:
+      summary: Create
+  /users/{id}:
+    delete:
+      summary: Remove
+components:
+  schemas:
+    get:
+      type: object
+";
+        assert_eq!(count_operations(ContractKind::Openapi, yaml).unwrap(), 3);
+
+        let json = r#"{"paths": {"/a": {"get": {}, "parameters": []}, "/b": {"put": {}}}}"#;
+        assert_eq!(count_operations(ContractKind::Openapi, json).unwrap(), 2);
+
+        let proto = "service Users {\n  rpc Get(GetRequest) returns (User);\n  rpc List(Empty) returns (stream User);\n}";
+        assert_eq!(count_operations(ContractKind::Protobuf, proto).unwrap(), 2);
+
+        let sdl = "\
+type User { id: ID! name: String }
+type Query {
+  user(id: ID!): User
+  users(
+    first: Int
+    after: String
+  ): [User!]!
+}
+extend type Mutation {
+  rename(id: ID!, name: String!): User
+}
+";
+        assert_eq!(count_operations(ContractKind::Graphql, sdl).unwrap(), 3);
+    }
+
+    #[test]
+    fn test_validate_contracts() {
+        let root = std::env::temp_dir().join(format!("modmap-contracts-{}", std::process::id()));
+        fs::create_dir_all(root.join("api")).unwrap();
+        fs::write(
+            root.join("api/users.proto"),
+            "service Users {\n  rpc Get(Req) returns (User);\n}\n",
+        )
+        .unwrap();
+
+        let map = ModuleMap::new(
+            GeneratorInfo::new("test", "1.0.0"),
+            ProjectMetadata::new("shop", TechStack::new("rust")),
+            vec![],
+            vec![],
+        )
+        .with_domains(vec![
+            Domain::new("identity", "Identity", vec![]).with_interfaces(vec![
+                DomainInterface::new("UsersRPC", InterfaceType::Api).with_contract(
+                    ContractRef::new(ContractKind::Protobuf, "api/users.proto").with_version("v1"),
+                ),
+                DomainInterface::new("UsersREST", InterfaceType::Api)
+                    .with_contract(ContractRef::new(ContractKind::Openapi, "api/openapi.yaml")),
+            ]),
+        ]);
+        let report = map.validate_contracts(&root);
+        fs::remove_dir_all(&root).unwrap();
+
+        assert_eq!(report.metrics.len(), 1);
+        assert_eq!(report.metrics[0].operations, 1);
+        assert!(!report.is_valid());
+        assert_eq!(
+            report.issues[0].to_string(),
+            "identity/UsersREST: contract 'api/openapi.yaml' not found"
+        );
+    }
+}
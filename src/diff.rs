@@ -0,0 +1,650 @@
+//! Changes between two module maps
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::{self, Write};
+
+use serde::{Deserialize, Serialize};
+
+use crate::module_map::{Module, ModuleMap};
+use crate::types::Score;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TextChange {
+    pub old: String,
+    pub new: String,
+}
+
+/// Score field of `ModuleMetrics` compared by `diff`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MetricKind {
+    CoverageRatio,
+    ValueScore,
+    RiskScore,
+}
+
+impl fmt::Display for MetricKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MetricKind::CoverageRatio => write!(f, "coverage_ratio"),
+            MetricKind::ValueScore => write!(f, "value_score"),
+            MetricKind::RiskScore => write!(f, "risk_score"),
+        }
+    }
+}
+
+/// A module metric whose value changed
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MetricDelta {
+    pub metric: MetricKind,
+    pub old: Score,
+    pub new: Score,
+}
+
+impl MetricDelta {
+    pub fn delta(&self) -> f64 {
+        self.new.get() - self.old.get()
+    }
+}
+
+/// Changes to a module present in both maps, possibly under a new id
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ModuleChange {
+    /// Id in the new map
+    pub module_id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub responsibility: Option<TextChange>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub added_dependencies: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub removed_dependencies: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub added_issues: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub removed_issues: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub metrics: Vec<MetricDelta>,
+}
+
+impl ModuleChange {
+    pub fn is_empty(&self) -> bool {
+        self.responsibility.is_none()
+            && self.metrics.is_empty()
+            && self.added_dependencies.is_empty()
+            && self.removed_dependencies.is_empty()
+            && self.added_issues.is_empty()
+            && self.removed_issues.is_empty()
+    }
+}
+
+/// Membership changes of a group present in both maps
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GroupChange {
+    pub group_id: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub added_modules: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub removed_modules: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct MapDiff {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub added_modules: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub removed_modules: Vec<String>,
+    /// Old and new id of modules whose new entry lists the old id in
+    /// `aliases`, or that kept exactly the same paths
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub renamed_modules: Vec<TextChange>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub changed_modules: Vec<ModuleChange>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub added_groups: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub removed_groups: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub changed_groups: Vec<GroupChange>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub added_domains: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub removed_domains: Vec<String>,
+}
+
+/// What `ModuleMap::diff` returns, under the name architecture-drift tooling expects
+pub type ModuleMapDiff = MapDiff;
+
+/// Items of `new` missing from `old` and items of `old` missing from `new`, both sorted.
+fn added_removed<'a>(
+    old: impl IntoIterator<Item = &'a str>,
+    new: impl IntoIterator<Item = &'a str>,
+) -> (Vec<String>, Vec<String>) {
+    let old: BTreeSet<&str> = old.into_iter().collect();
+    let new: BTreeSet<&str> = new.into_iter().collect();
+    (
+        new.difference(&old).map(|s| s.to_string()).collect(),
+        old.difference(&new).map(|s| s.to_string()).collect(),
+    )
+}
+
+/// Old id to new id of modules renamed between the maps.
+fn renames<'a>(
+    old: &'a ModuleMap,
+    new: &'a ModuleMap,
+    added: &[String],
+    removed: &[String],
+) -> BTreeMap<&'a str, &'a str> {
+    let same_paths = |a: &Module, b: &Module| {
+        !a.paths.is_empty()
+            && a.paths.iter().collect::<BTreeSet<_>>() == b.paths.iter().collect::<BTreeSet<_>>()
+    };
+    let mut renames = BTreeMap::new();
+    for module in new.modules.iter().filter(|m| added.contains(&m.id)) {
+        let candidates = || {
+            old.modules
+                .iter()
+                .filter(|m| removed.contains(&m.id) && !renames.contains_key(m.id.as_str()))
+        };
+        let before = candidates()
+            .find(|m| module.aliases.contains(&m.id))
+            .or_else(|| candidates().find(|m| same_paths(m, module)));
+        if let Some(before) = before {
+            renames.insert(before.id.as_str(), module.id.as_str());
+        }
+    }
+    renames
+}
+
+fn metric_deltas(old: &Module, new: &Module) -> Vec<MetricDelta> {
+    let (old, new) = (&old.metrics, &new.metrics);
+    [
+        (
+            MetricKind::CoverageRatio,
+            old.coverage_ratio,
+            new.coverage_ratio,
+        ),
+        (MetricKind::ValueScore, old.value_score, new.value_score),
+        (MetricKind::RiskScore, old.risk_score, new.risk_score),
+    ]
+    .into_iter()
+    .filter(|(_, old, new)| old != new)
+    .map(|(metric, old, new)| MetricDelta { metric, old, new })
+    .collect()
+}
+
+/// Changes from `old` to `new`; dependencies on renamed modules compare by their new id.
+fn module_change(old: &Module, new: &Module, renames: &BTreeMap<&str, &str>) -> ModuleChange {
+    let (added_dependencies, removed_dependencies) = added_removed(
+        old.dependencies.iter().map(|d| {
+            renames
+                .get(d.module_id.as_str())
+                .copied()
+                .unwrap_or(&d.module_id)
+        }),
+        new.dependencies.iter().map(|d| d.module_id.as_str()),
+    );
+    let (added_issues, removed_issues) = added_removed(
+        old.known_issues.iter().map(|i| i.id.as_str()),
+        new.known_issues.iter().map(|i| i.id.as_str()),
+    );
+    ModuleChange {
+        module_id: new.id.clone(),
+        responsibility: (old.responsibility != new.responsibility).then(|| TextChange {
+            old: old.responsibility.clone(),
+            new: new.responsibility.clone(),
+        }),
+        added_dependencies,
+        removed_dependencies,
+        added_issues,
+        removed_issues,
+        metrics: metric_deltas(old, new),
+    }
+}
+
+impl ModuleMap {
+    /// Changes from `old` to `new`. Renamed modules are reported once under
+    /// `renamed_modules`, and their other changes under the new id.
+    pub fn diff(old: &ModuleMap, new: &ModuleMap) -> ModuleMapDiff {
+        let (mut added_modules, mut removed_modules) = added_removed(
+            old.modules.iter().map(|m| m.id.as_str()),
+            new.modules.iter().map(|m| m.id.as_str()),
+        );
+        let renames = renames(old, new, &added_modules, &removed_modules);
+        added_modules.retain(|id| !renames.values().any(|new_id| new_id == id));
+        removed_modules.retain(|id| !renames.contains_key(id.as_str()));
+        let renamed_id =
+            |id: &'_ str| -> String { renames.get(id).copied().unwrap_or(id).to_string() };
+
+        let (added_groups, removed_groups) = added_removed(
+            old.groups.iter().map(|g| g.id.as_str()),
+            new.groups.iter().map(|g| g.id.as_str()),
+        );
+        let (added_domains, removed_domains) = added_removed(
+            old.domains.iter().map(|d| d.id.as_str()),
+            new.domains.iter().map(|d| d.id.as_str()),
+        );
+
+        let mut changed_modules: Vec<ModuleChange> = new
+            .modules
+            .iter()
+            .filter_map(|m| {
+                let old_id = renames
+                    .iter()
+                    .find(|(_, new_id)| **new_id == m.id)
+                    .map_or(m.id.as_str(), |(old_id, _)| old_id);
+                Some(module_change(old.find_module(old_id)?, m, &renames))
+            })
+            .filter(|c| !c.is_empty())
+            .collect();
+        changed_modules.sort_by(|a, b| a.module_id.cmp(&b.module_id));
+
+        let mut changed_groups: Vec<GroupChange> = new
+            .groups
+            .iter()
+            .filter_map(|g| {
+                let before = old.find_group(&g.id)?;
+                let before_ids: Vec<String> =
+                    before.module_ids.iter().map(|id| renamed_id(id)).collect();
+                let (added_modules, removed_modules) = added_removed(
+                    before_ids.iter().map(String::as_str),
+                    g.module_ids.iter().map(String::as_str),
+                );
+                (!added_modules.is_empty() || !removed_modules.is_empty()).then(|| GroupChange {
+                    group_id: g.id.clone(),
+                    added_modules,
+                    removed_modules,
+                })
+            })
+            .collect();
+        changed_groups.sort_by(|a, b| a.group_id.cmp(&b.group_id));
+
+        MapDiff {
+            added_modules,
+            removed_modules,
+            renamed_modules: renames
+                .into_iter()
+                .map(|(old, new)| TextChange {
+                    old: old.to_string(),
+                    new: new.to_string(),
+                })
+                .collect(),
+            changed_modules,
+            added_groups,
+            removed_groups,
+            changed_groups,
+            added_domains,
+            removed_domains,
+        }
+    }
+}
+
+fn list(out: &mut String, title: &str, items: &[String]) {
+    if items.is_empty() {
+        return;
+    }
+    let _ = writeln!(out, "\n### {title}\n");
+    for item in items {
+        let _ = writeln!(out, "- `{item}`");
+    }
+}
+
+impl MapDiff {
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    pub fn to_markdown(&self) -> String {
+        let mut out = String::from("## Module Map Changes\n");
+        if self.is_empty() {
+            out.push_str("\nNo changes.\n");
+            return out;
+        }
+
+        list(&mut out, "Added modules", &self.added_modules);
+        list(&mut out, "Removed modules", &self.removed_modules);
+        if !self.renamed_modules.is_empty() {
+            let _ = writeln!(out, "\n### Renamed modules\n");
+            for rename in &self.renamed_modules {
+                let _ = writeln!(out, "- `{}` → `{}`", rename.old, rename.new);
+            }
+        }
+        if !self.changed_modules.is_empty() {
+            let _ = writeln!(out, "\n### Changed modules\n");
+            for change in &self.changed_modules {
+                let _ = writeln!(out, "- `{}`", change.module_id);
+                if let Some(text) = &change.responsibility {
+                    let _ = writeln!(out, "  - responsibility: {} → {}", text.old, text.new);
+                }
+                for metric in &change.metrics {
+                    let _ = writeln!(
+                        out,
+                        "  - {}: {:.2} → {:.2} ({:+.2})",
+                        metric.metric,
+                        metric.old.get(),
+                        metric.new.get(),
+                        metric.delta()
+                    );
+                }
+                for (label, items) in [
+                    ("+ dependency", &change.added_dependencies),
+                    ("- dependency", &change.removed_dependencies),
+                    ("+ issue", &change.added_issues),
+                    ("- issue", &change.removed_issues),
+                ] {
+                    for item in items {
+                        let _ = writeln!(out, "  - {label} `{item}`");
+                    }
+                }
+            }
+        }
+        list(&mut out, "Added groups", &self.added_groups);
+        list(&mut out, "Removed groups", &self.removed_groups);
+        if !self.changed_groups.is_empty() {
+            let _ = writeln!(out, "\n### Changed groups\n");
+            for change in &self.changed_groups {
+                let _ = writeln!(out, "- `{}`", change.group_id);
+                for id in &change.added_modules {
+                    let _ = writeln!(out, "  - + module `{id}`");
+                }
+                for id in &change.removed_modules {
+                    let _ = writeln!(out, "  - - module `{id}`");
+                }
+            }
+        }
+        list(&mut out, "Added domains", &self.added_domains);
+        list(&mut out, "Removed domains", &self.removed_domains);
+        out
+    }
+}
+
+/// A diff split into per-owner slices
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct OwnerRouting {
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub owners: BTreeMap<String, MapDiff>,
+    /// Changes whose domain has no owner
+    #[serde(default)]
+    pub unowned: MapDiff,
+}
+
+impl OwnerRouting {
+    pub fn get(&self, owner: &str) -> Option<&MapDiff> {
+        self.owners.get(owner)
+    }
+
+    fn slice(&mut self, owner: Option<&str>) -> &mut MapDiff {
+        match owner {
+            Some(owner) => self.owners.entry(owner.to_string()).or_default(),
+            None => &mut self.unowned,
+        }
+    }
+
+    /// One section per owner, then unowned changes.
+    pub fn to_markdown(&self) -> String {
+        let mut out = String::new();
+        let sections = self
+            .owners
+            .iter()
+            .map(|(owner, diff)| (owner.as_str(), diff))
+            .chain((!self.unowned.is_empty()).then_some(("unowned", &self.unowned)));
+        for (owner, diff) in sections {
+            let _ = writeln!(out, "# {owner}\n");
+            out.push_str(&diff.to_markdown());
+            out.push('\n');
+        }
+        out
+    }
+}
+
+/// Resolves owners in the new map first, falling back to the old one for removals.
+struct Owners<'a> {
+    old: &'a ModuleMap,
+    new: &'a ModuleMap,
+}
+
+impl<'a> Owners<'a> {
+    fn module(&self, id: &str) -> Option<&'a str> {
+        self.new
+            .find_module_owner(id)
+            .or_else(|| self.old.find_module_owner(id))
+    }
+
+    fn group(&self, id: &str) -> Option<&'a str> {
+        self.new
+            .find_group_owner(id)
+            .or_else(|| self.old.find_group_owner(id))
+    }
+
+    fn domain(&self, id: &str) -> Option<&'a str> {
+        self.new
+            .find_domain(id)
+            .or_else(|| self.old.find_domain(id))
+            .and_then(|d| d.owner.as_deref())
+    }
+}
+
+impl MapDiff {
+    /// Splits this `old` → `new` diff by the owner of each change's domain.
+    pub fn route_by_owner(&self, old: &ModuleMap, new: &ModuleMap) -> OwnerRouting {
+        let owners = Owners { old, new };
+        let mut routing = OwnerRouting::default();
+
+        for id in &self.added_modules {
+            routing
+                .slice(owners.module(id))
+                .added_modules
+                .push(id.clone());
+        }
+        for id in &self.removed_modules {
+            routing
+                .slice(owners.module(id))
+                .removed_modules
+                .push(id.clone());
+        }
+        for rename in &self.renamed_modules {
+            routing
+                .slice(owners.module(&rename.new))
+                .renamed_modules
+                .push(rename.clone());
+        }
+        for change in &self.changed_modules {
+            routing
+                .slice(owners.module(&change.module_id))
+                .changed_modules
+                .push(change.clone());
+        }
+        for id in &self.added_groups {
+            routing
+                .slice(owners.group(id))
+                .added_groups
+                .push(id.clone());
+        }
+        for id in &self.removed_groups {
+            routing
+                .slice(owners.group(id))
+                .removed_groups
+                .push(id.clone());
+        }
+        for change in &self.changed_groups {
+            routing
+                .slice(owners.group(&change.group_id))
+                .changed_groups
+                .push(change.clone());
+        }
+        for id in &self.added_domains {
+            routing
+                .slice(owners.domain(id))
+                .added_domains
+                .push(id.clone());
+        }
+        for id in &self.removed_domains {
+            routing
+                .slice(owners.domain(id))
+                .removed_domains
+                .push(id.clone());
+        }
+        routing
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        Domain, GeneratorInfo, IssueCategory, IssueSeverity, KnownIssue, ModuleDependency,
+        ModuleGroup, ModuleMetrics, ProjectMetadata, TechStack,
+    };
+
+    fn module(id: &str, responsibility: &str) -> Module {
+        Module::new(id, id, vec![])
+            .with_responsibility(responsibility)
+            .with_primary_language("rust")
+    }
+
+    fn map(modules: Vec<Module>, groups: Vec<ModuleGroup>) -> ModuleMap {
+        ModuleMap::new(
+            GeneratorInfo::new("test", "1.0.0"),
+            ProjectMetadata::new("test", TechStack::new("rust")),
+            modules,
+            groups,
+        )
+    }
+
+    #[test]
+    fn test_diff() {
+        let old = map(
+            vec![module("auth", "Login"), module("legacy", "Old code")],
+            vec![ModuleGroup::new(
+                "core",
+                "Core",
+                vec!["auth".into(), "legacy".into()],
+            )],
+        );
+        let mut auth = module("auth", "Login and sessions");
+        auth.dependencies = vec![ModuleDependency::runtime("db")];
+        auth.known_issues = vec![KnownIssue::new(
+            "weak-hash",
+            "MD5 password hashing",
+            IssueSeverity::Critical,
+            IssueCategory::Security,
+        )];
+        let new = map(
+            vec![auth, module("db", "Storage")],
+            vec![ModuleGroup::new(
+                "core",
+                "Core",
+                vec!["auth".into(), "db".into()],
+            )],
+        );
+
+        let diff = ModuleMap::diff(&old, &new);
+        assert_eq!(diff.added_modules, vec!["db"]);
+        assert_eq!(diff.removed_modules, vec!["legacy"]);
+        assert_eq!(diff.changed_modules.len(), 1);
+        assert_eq!(diff.changed_modules[0].added_dependencies, vec!["db"]);
+        assert_eq!(diff.changed_modules[0].added_issues, vec!["weak-hash"]);
+        assert_eq!(diff.changed_groups[0].removed_modules, vec!["legacy"]);
+
+        let markdown = diff.to_markdown();
+        assert!(markdown.contains("### Added modules\n\n- `db`"));
+        assert!(markdown.contains("responsibility: Login → Login and sessions"));
+
+        assert!(ModuleMap::diff(&new, &new).is_empty());
+    }
+
+    #[test]
+    fn test_diff_renames_and_metrics() {
+        let mut api = module("api", "HTTP");
+        api.dependencies = vec![ModuleDependency::runtime("auth")];
+        let mut store = module("store", "Storage");
+        store.paths = vec!["src/store/".into()];
+        let old = map(
+            vec![module("auth", "Login"), api, store],
+            vec![ModuleGroup::new("core", "Core", vec!["auth".into()])],
+        );
+
+        let identity = Module {
+            aliases: vec!["auth".into()],
+            metrics: ModuleMetrics::new(0.0, 0.0, 0.5),
+            ..module("identity", "Login")
+        };
+        let mut api = module("api", "HTTP");
+        api.dependencies = vec![ModuleDependency::runtime("identity")];
+        let mut storage = module("storage", "Storage");
+        storage.paths = vec!["src/store/".into()];
+        let new = map(
+            vec![identity, api, storage],
+            vec![ModuleGroup::new("core", "Core", vec!["identity".into()])],
+        );
+
+        let diff = ModuleMap::diff(&old, &new);
+        assert!(diff.added_modules.is_empty() && diff.removed_modules.is_empty());
+        let renames: Vec<(&str, &str)> = diff
+            .renamed_modules
+            .iter()
+            .map(|r| (r.old.as_str(), r.new.as_str()))
+            .collect();
+        assert_eq!(renames, [("auth", "identity"), ("store", "storage")]);
+        assert!(diff.changed_groups.is_empty());
+        assert_eq!(
+            diff.changed_modules.len(),
+            1,
+            "api's edge follows the rename"
+        );
+        let metric = &diff.changed_modules[0].metrics[0];
+        assert_eq!(
+            (metric.metric, metric.delta()),
+            (MetricKind::RiskScore, 0.5)
+        );
+        assert_eq!(diff.summary(), "modules ~2");
+
+        let markdown = diff.to_markdown();
+        assert!(markdown.contains("- `auth` → `identity`"));
+        assert!(markdown.contains("  - risk_score: 0.00 → 0.50 (+0.50)"));
+    }
+
+    #[test]
+    fn test_route_by_owner() {
+        let groups = |ids: &[&str]| {
+            vec![
+                ModuleGroup::new("core", "Core", vec!["auth".into()]).with_domain("identity"),
+                ModuleGroup::new(
+                    "shop",
+                    "Shop",
+                    ids.iter().map(|id| id.to_string()).collect(),
+                )
+                .with_domain("commerce"),
+            ]
+        };
+        let domains = vec![
+            Domain::new("identity", "Identity", vec!["core".into()]).with_owner("team-id"),
+            Domain::new("commerce", "Commerce", vec!["shop".into()]),
+        ];
+        let old = map(
+            vec![module("auth", "Login"), module("cart", "Cart")],
+            groups(&["cart"]),
+        )
+        .with_domains(domains.clone());
+        let new = map(
+            vec![
+                module("auth", "Login and sessions"),
+                module("checkout", "Checkout"),
+            ],
+            groups(&["checkout"]),
+        )
+        .with_domains(domains);
+
+        let diff = ModuleMap::diff(&old, &new);
+        let routing = diff.route_by_owner(&old, &new);
+
+        let team = routing.get("team-id").unwrap();
+        assert_eq!(team.changed_modules[0].module_id, "auth");
+        assert!(team.added_modules.is_empty());
+        assert_eq!(routing.unowned.added_modules, vec!["checkout"]);
+        assert_eq!(routing.unowned.removed_modules, vec!["cart"]);
+
+        let markdown = routing.to_markdown();
+        assert!(markdown.starts_with("# team-id\n"));
+        assert!(markdown.contains("# unowned\n"));
+    }
+}
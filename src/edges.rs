@@ -0,0 +1,334 @@
+//! Consistency between module dependencies, dependents and graph edges
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+
+use crate::module_map::{DependencyEdge, DependencyGraph, ModuleMap};
+use crate::types::ModuleDependency;
+
+/// Which edge representation `sync_graph` treats as authoritative
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum EdgeSource {
+    /// Rebuild graph edges from `Module.dependencies`
+    Dependencies,
+    /// Rebuild `Module.dependencies` from graph edges
+    Graph,
+    /// Add what each side is missing; remove nothing
+    #[default]
+    Union,
+}
+
+/// What `sync_graph` changed
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SyncReport {
+    pub edges_added: usize,
+    pub edges_removed: usize,
+    pub dependencies_added: usize,
+    pub dependencies_removed: usize,
+}
+
+impl SyncReport {
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// Disagreement between `Module.dependencies`, `Module.dependents` and
+/// `DependencyGraph.edges`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EdgeInconsistency {
+    /// `dependent` depends on `module`, which does not list it
+    MissingDependent { module: String, dependent: String },
+    /// `module` lists `dependent`, which does not depend on it
+    StaleDependent { module: String, dependent: String },
+    /// Graph edge with no matching entry in `from`'s dependencies
+    EdgeWithoutDependency { from: String, to: String },
+    /// Dependency with no matching graph edge (checked only when the graph has edges)
+    DependencyWithoutEdge { from: String, to: String },
+}
+
+impl fmt::Display for EdgeInconsistency {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingDependent { module, dependent } => {
+                write!(f, "'{module}' does not list dependent '{dependent}'")
+            }
+            Self::StaleDependent { module, dependent } => write!(
+                f,
+                "'{module}' lists dependent '{dependent}', which does not depend on it"
+            ),
+            Self::EdgeWithoutDependency { from, to } => {
+                write!(
+                    f,
+                    "graph edge '{from}' -> '{to}' is not a declared dependency"
+                )
+            }
+            Self::DependencyWithoutEdge { from, to } => {
+                write!(f, "dependency '{from}' -> '{to}' is missing from the graph")
+            }
+        }
+    }
+}
+
+impl ModuleMap {
+    /// `(from, to)` pairs from per-module dependencies.
+    fn declared_pairs(&self) -> BTreeSet<(&str, &str)> {
+        self.modules
+            .iter()
+            .flat_map(|m| {
+                m.dependencies
+                    .iter()
+                    .map(move |d| (m.id.as_str(), d.module_id.as_str()))
+            })
+            .collect()
+    }
+
+    /// `(from, to)` pairs from the explicit dependency graph.
+    fn graph_pairs(&self) -> BTreeSet<(&str, &str)> {
+        self.dependency_graph
+            .iter()
+            .flat_map(|g| &g.edges)
+            .map(|e| (e.from.as_str(), e.to.as_str()))
+            .collect()
+    }
+
+    /// Dependents of each module derived from dependencies and graph edges.
+    fn derived_dependents(&self) -> BTreeMap<String, Vec<String>> {
+        let mut dependents: BTreeMap<String, Vec<String>> = BTreeMap::new();
+        let pairs: BTreeSet<_> = self
+            .declared_pairs()
+            .union(&self.graph_pairs())
+            .copied()
+            .collect();
+        for (from, to) in pairs {
+            if from != to {
+                dependents.entry(to.into()).or_default().push(from.into());
+            }
+        }
+        dependents
+    }
+
+    /// Rewrites every `Module.dependents` from dependencies and graph edges,
+    /// sorted. Returns the number of modules whose list changed.
+    pub fn recompute_dependents(&mut self) -> usize {
+        let mut derived = self.derived_dependents();
+        let mut changed = 0;
+        for module in &mut self.modules {
+            let dependents = derived.remove(&module.id).unwrap_or_default();
+            if module.dependents != dependents {
+                module.dependents = dependents;
+                changed += 1;
+            }
+        }
+        changed
+    }
+
+    /// Reconciles `Module.dependencies` with `DependencyGraph.edges`, then
+    /// recomputes dependents. Layers are kept; edges from ids that are not
+    /// modules are dropped when dependencies are the source and ignored otherwise.
+    pub fn sync_graph(&mut self, source: EdgeSource) -> SyncReport {
+        let mut report = SyncReport::default();
+        let graph = self
+            .dependency_graph
+            .get_or_insert_with(DependencyGraph::default);
+
+        if source != EdgeSource::Graph {
+            let declared: Vec<DependencyEdge> = self
+                .modules
+                .iter()
+                .flat_map(|m| {
+                    m.dependencies.iter().map(|d| DependencyEdge {
+                        from: m.id.clone(),
+                        to: d.module_id.clone(),
+                        edge_type: d.dependency_type.clone(),
+                    })
+                })
+                .collect();
+            if source == EdgeSource::Dependencies {
+                let before = graph.edges.len();
+                graph
+                    .edges
+                    .retain(|e| declared.iter().any(|d| d.from == e.from && d.to == e.to));
+                report.edges_removed = before - graph.edges.len();
+            }
+            for edge in declared {
+                if !graph
+                    .edges
+                    .iter()
+                    .any(|e| e.from == edge.from && e.to == edge.to)
+                {
+                    graph.edges.push(edge);
+                    report.edges_added += 1;
+                }
+            }
+        }
+
+        if source != EdgeSource::Dependencies {
+            for module in &mut self.modules {
+                let edges: Vec<&DependencyEdge> =
+                    graph.edges.iter().filter(|e| e.from == module.id).collect();
+                if source == EdgeSource::Graph {
+                    let before = module.dependencies.len();
+                    module
+                        .dependencies
+                        .retain(|d| edges.iter().any(|e| e.to == d.module_id));
+                    report.dependencies_removed += before - module.dependencies.len();
+                }
+                for edge in edges {
+                    if !module.dependencies.iter().any(|d| d.module_id == edge.to) {
+                        module.dependencies.push(ModuleDependency {
+                            module_id: edge.to.clone(),
+                            dependency_type: edge.edge_type.clone(),
+                        });
+                        report.dependencies_added += 1;
+                    }
+                }
+            }
+        }
+
+        if graph.edges.is_empty() && graph.layers.is_empty() {
+            self.dependency_graph = None;
+        }
+        self.recompute_dependents();
+        report
+    }
+
+    /// Where dependencies, dependents and graph edges disagree.
+    pub fn edge_inconsistencies(&self) -> Vec<EdgeInconsistency> {
+        let derived = self.derived_dependents();
+        let mut issues = Vec::new();
+        for module in &self.modules {
+            let expected = derived.get(&module.id).map(Vec::as_slice).unwrap_or(&[]);
+            for dependent in expected {
+                if !module.dependents.contains(dependent) {
+                    issues.push(EdgeInconsistency::MissingDependent {
+                        module: module.id.clone(),
+                        dependent: dependent.clone(),
+                    });
+                }
+            }
+            for dependent in &module.dependents {
+                if !expected.contains(dependent) {
+                    issues.push(EdgeInconsistency::StaleDependent {
+                        module: module.id.clone(),
+                        dependent: dependent.clone(),
+                    });
+                }
+            }
+        }
+
+        let declared = self.declared_pairs();
+        let graph = self.graph_pairs();
+        if !graph.is_empty() {
+            for &(from, to) in graph.difference(&declared) {
+                issues.push(EdgeInconsistency::EdgeWithoutDependency {
+                    from: from.into(),
+                    to: to.into(),
+                });
+            }
+            for &(from, to) in declared.difference(&graph) {
+                issues.push(EdgeInconsistency::DependencyWithoutEdge {
+                    from: from.into(),
+                    to: to.into(),
+                });
+            }
+        }
+        issues
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        DependencyEdge, DependencyGraph, DependencyType, GeneratorInfo, Module, ModuleDependency,
+        ProjectMetadata, TechStack,
+    };
+
+    fn module(id: &str, dependencies: &[&str], dependents: &[&str]) -> Module {
+        Module::new(id, id, vec![format!("src/{id}/")])
+            .with_dependencies(
+                dependencies
+                    .iter()
+                    .map(|d| ModuleDependency::runtime(*d))
+                    .collect(),
+            )
+            .with_dependents(dependents.iter().map(|d| d.to_string()).collect())
+            .with_primary_language("rust")
+    }
+
+    fn edge(from: &str, to: &str) -> DependencyEdge {
+        DependencyEdge {
+            from: from.into(),
+            to: to.into(),
+            edge_type: DependencyType::Runtime,
+        }
+    }
+
+    fn sample() -> ModuleMap {
+        let mut map = ModuleMap::new(
+            GeneratorInfo::new("test", "1.0.0"),
+            ProjectMetadata::new("shop", TechStack::new("rust")),
+            vec![
+                module("api", &["core"], &[]),
+                module("core", &[], &["worker"]),
+                module("db", &[], &[]),
+            ],
+            vec![],
+        );
+        map.dependency_graph = Some(DependencyGraph {
+            edges: vec![edge("api", "core"), edge("core", "db")],
+            layers: vec![],
+        });
+        map
+    }
+
+    #[test]
+    fn test_edge_inconsistencies() {
+        let issues: Vec<String> = sample()
+            .edge_inconsistencies()
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        assert_eq!(
+            issues,
+            vec![
+                "'core' does not list dependent 'api'",
+                "'core' lists dependent 'worker', which does not depend on it",
+                "'db' does not list dependent 'core'",
+                "graph edge 'core' -> 'db' is not a declared dependency",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_recompute_dependents() {
+        let mut map = sample();
+        assert_eq!(map.recompute_dependents(), 2);
+        assert_eq!(map.modules[1].dependents, vec!["api"]);
+        assert_eq!(map.modules[2].dependents, vec!["core"]);
+        assert_eq!(map.recompute_dependents(), 0);
+        assert_eq!(map.edge_inconsistencies().len(), 1);
+    }
+
+    #[test]
+    fn test_sync_graph() {
+        let mut from_deps = sample();
+        let report = from_deps.sync_graph(EdgeSource::Dependencies);
+        assert_eq!((report.edges_added, report.edges_removed), (0, 1));
+        assert!(from_deps.edge_inconsistencies().is_empty());
+
+        let mut from_graph = sample();
+        let report = from_graph.sync_graph(EdgeSource::Graph);
+        assert_eq!(report.dependencies_added, 1);
+        assert_eq!(from_graph.modules[1].dependencies[0].module_id, "db");
+        assert!(from_graph.edge_inconsistencies().is_empty());
+
+        let mut union = sample();
+        union.modules[2].dependencies = vec![ModuleDependency::runtime("api")];
+        let report = union.sync_graph(EdgeSource::Union);
+        assert_eq!((report.edges_added, report.dependencies_added), (1, 1));
+        assert!(union.edge_inconsistencies().is_empty());
+        assert!(union.sync_graph(EdgeSource::Union).is_empty());
+    }
+}
@@ -0,0 +1,191 @@
+//! Model-agnostic embedding hooks for semantic lookup
+
+use std::fmt;
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::module_map::ModuleMap;
+use crate::rule::Rule;
+
+/// Supplies vectors for text; implemented by callers for their model of choice
+pub trait EmbeddingProvider {
+    type Error;
+
+    /// Identifier stored alongside vectors so mismatched models are detectable
+    fn model_id(&self) -> &str;
+
+    fn embed(&self, text: &str) -> Result<Vec<f32>, Self::Error>;
+}
+
+/// Entity an embedding belongs to
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, JsonSchema)]
+#[serde(tag = "kind", content = "id", rename_all = "snake_case")]
+pub enum EmbeddingKey {
+    Module(String),
+    Rule(String),
+}
+
+impl fmt::Display for EmbeddingKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Module(id) => write!(f, "module:{id}"),
+            Self::Rule(name) => write!(f, "rule:{name}"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct EmbeddingEntry {
+    pub key: EmbeddingKey,
+    pub vector: Vec<f32>,
+}
+
+/// Inline vector storage with cosine nearest-neighbour lookup
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct EmbeddingStore {
+    pub model_id: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub entries: Vec<EmbeddingEntry>,
+}
+
+impl EmbeddingStore {
+    pub fn new(model_id: impl Into<String>) -> Self {
+        Self {
+            model_id: model_id.into(),
+            entries: Vec::new(),
+        }
+    }
+
+    /// Embeds every module (name + responsibility) and rule (content).
+    pub fn build<P: EmbeddingProvider>(
+        provider: &P,
+        map: &ModuleMap,
+        rules: &[Rule],
+    ) -> Result<Self, P::Error> {
+        let mut store = Self::new(provider.model_id());
+        for module in &map.modules {
+            let text = format!("{}\n{}", module.name, module.responsibility);
+            store.insert(
+                EmbeddingKey::Module(module.id.clone()),
+                provider.embed(&text)?,
+            );
+        }
+        for rule in rules {
+            let text = rule.content.join("\n");
+            store.insert(
+                EmbeddingKey::Rule(rule.name.clone()),
+                provider.embed(&text)?,
+            );
+        }
+        Ok(store)
+    }
+
+    pub fn insert(&mut self, key: EmbeddingKey, vector: Vec<f32>) {
+        match self.entries.iter_mut().find(|e| e.key == key) {
+            Some(entry) => entry.vector = vector,
+            None => self.entries.push(EmbeddingEntry { key, vector }),
+        }
+    }
+
+    pub fn get(&self, key: &EmbeddingKey) -> Option<&[f32]> {
+        self.entries
+            .iter()
+            .find(|e| &e.key == key)
+            .map(|e| e.vector.as_slice())
+    }
+
+    /// Top `k` entries by cosine similarity, most similar first.
+    pub fn nearest(&self, query: &[f32], k: usize) -> Vec<(&EmbeddingKey, f32)> {
+        let mut scored: Vec<_> = self
+            .entries
+            .iter()
+            .filter(|e| e.vector.len() == query.len())
+            .map(|e| (&e.key, cosine_similarity(query, &e.vector)))
+            .collect();
+        scored.sort_by(|a, b| b.1.total_cmp(&a.1));
+        scored.truncate(k);
+        scored
+    }
+}
+
+pub fn cosine_similarity(a: &[f32], b: &[f32]) -> f32 {
+    let dot: f32 = a.iter().zip(b).map(|(x, y)| x * y).sum();
+    let norm_a = a.iter().map(|x| x * x).sum::<f32>().sqrt();
+    let norm_b = b.iter().map(|x| x * x).sum::<f32>().sqrt();
+    if norm_a == 0.0 || norm_b == 0.0 {
+        0.0
+    } else {
+        dot / (norm_a * norm_b)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{GeneratorInfo, Module, ProjectMetadata, TechStack};
+
+    /// Counts a few keywords so tests don't need a model
+    struct KeywordProvider;
+
+    impl EmbeddingProvider for KeywordProvider {
+        type Error = std::convert::Infallible;
+
+        fn model_id(&self) -> &str {
+            "keywords-v1"
+        }
+
+        fn embed(&self, text: &str) -> Result<Vec<f32>, Self::Error> {
+            let text = text.to_lowercase();
+            Ok(["auth", "payment", "retry"]
+                .iter()
+                .map(|k| text.matches(k).count() as f32)
+                .collect())
+        }
+    }
+
+    fn module(id: &str, responsibility: &str) -> Module {
+        Module::new(id, id, vec![])
+            .with_responsibility(responsibility)
+            .with_primary_language("rust")
+    }
+
+    #[test]
+    fn test_nearest() {
+        let map = ModuleMap::new(
+            GeneratorInfo::new("test", "1.0.0"),
+            ProjectMetadata::new("test", TechStack::new("rust")),
+            vec![
+                module("login", "auth sessions and auth tokens"),
+                module("billing", "payment processing with retry"),
+            ],
+            vec![],
+        );
+        let rules = [Rule::tech(
+            "retries",
+            vec![],
+            vec!["Always retry idempotent calls".into()],
+        )];
+        let store = EmbeddingStore::build(&KeywordProvider, &map, &rules).unwrap();
+
+        assert_eq!(store.model_id, "keywords-v1");
+        assert_eq!(store.entries.len(), 3);
+
+        let query = KeywordProvider.embed("payment").unwrap();
+        let nearest = store.nearest(&query, 1);
+        assert_eq!(nearest[0].0, &EmbeddingKey::Module("billing".into()));
+
+        let query = KeywordProvider.embed("retry").unwrap();
+        assert_eq!(
+            store.nearest(&query, 1)[0].0,
+            &EmbeddingKey::Rule("retries".into())
+        );
+    }
+
+    #[test]
+    fn test_cosine_similarity() {
+        assert!((cosine_similarity(&[1.0, 0.0], &[2.0, 0.0]) - 1.0).abs() < f32::EPSILON);
+        assert_eq!(cosine_similarity(&[1.0, 0.0], &[0.0, 1.0]), 0.0);
+        assert_eq!(cosine_similarity(&[0.0, 0.0], &[1.0, 1.0]), 0.0);
+    }
+}
@@ -0,0 +1,296 @@
+//! Typed references to map facts: `module:auth`, `group:payments/checkout`,
+//! `domain:identity`, `rule:tech/rust`
+
+use std::borrow::Cow;
+use std::fmt;
+use std::str::FromStr;
+
+use schemars::{JsonSchema, Schema, SchemaGenerator, json_schema};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use thiserror::Error;
+
+use crate::manifest::ProjectManifest;
+use crate::module_map::ModuleMap;
+use crate::rule::Rule;
+
+/// A kinded id, written `kind:id`, so lookups never hit the wrong collection
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum EntityRef {
+    Module(String),
+    Group(String),
+    Domain(String),
+    /// Rule name as listed in the manifest
+    Rule(String),
+    /// `Convention::name` on any module
+    Convention(String),
+    /// `KnownIssue::id` on any module
+    Issue(String),
+    /// Architecture decision record kept outside the map, e.g. `ADR-0012`
+    Adr(String),
+    /// Database table of a domain interface, `schema.table` when the schema is set
+    Table(String),
+}
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum EntityRefError {
+    #[error("entity reference '{0}' has no kind, expected e.g. 'module:{0}'")]
+    MissingKind(String),
+    #[error("unknown entity kind '{kind}' in '{input}'")]
+    UnknownKind { kind: String, input: String },
+    #[error("entity reference '{0}' has an empty id")]
+    EmptyId(String),
+}
+
+impl EntityRef {
+    pub const KINDS: [&'static str; 8] = [
+        "module",
+        "group",
+        "domain",
+        "rule",
+        "convention",
+        "issue",
+        "adr",
+        "table",
+    ];
+
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::Module(_) => "module",
+            Self::Group(_) => "group",
+            Self::Domain(_) => "domain",
+            Self::Rule(_) => "rule",
+            Self::Convention(_) => "convention",
+            Self::Issue(_) => "issue",
+            Self::Adr(_) => "adr",
+            Self::Table(_) => "table",
+        }
+    }
+
+    pub fn id(&self) -> &str {
+        match self {
+            Self::Module(id)
+            | Self::Group(id)
+            | Self::Domain(id)
+            | Self::Rule(id)
+            | Self::Convention(id)
+            | Self::Issue(id)
+            | Self::Adr(id)
+            | Self::Table(id) => id,
+        }
+    }
+
+    fn from_parts(kind: &str, id: &str, input: &str) -> Result<Self, EntityRefError> {
+        if id.is_empty() {
+            return Err(EntityRefError::EmptyId(input.to_string()));
+        }
+        let id = id.to_string();
+        Ok(match kind {
+            "module" => Self::Module(id),
+            "group" => Self::Group(id),
+            "domain" => Self::Domain(id),
+            "rule" => Self::Rule(id),
+            "convention" => Self::Convention(id),
+            "issue" => Self::Issue(id),
+            "adr" => Self::Adr(id),
+            "table" => Self::Table(id),
+            _ => {
+                return Err(EntityRefError::UnknownKind {
+                    kind: kind.to_string(),
+                    input: input.to_string(),
+                });
+            }
+        })
+    }
+}
+
+impl fmt::Display for EntityRef {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.kind(), self.id())
+    }
+}
+
+impl FromStr for EntityRef {
+    type Err = EntityRefError;
+
+    /// Splits on the first `:`; ids may contain further colons and slashes.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (kind, id) = s
+            .split_once(':')
+            .ok_or_else(|| EntityRefError::MissingKind(s.to_string()))?;
+        Self::from_parts(kind, id, s)
+    }
+}
+
+impl Serialize for EntityRef {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+/// `"module:auth"`, or the earlier `{"kind": "module", "id": "auth"}` form
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum EntityRefRepr {
+    Text(String),
+    Tagged { kind: String, id: String },
+}
+
+impl<'de> Deserialize<'de> for EntityRef {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        match EntityRefRepr::deserialize(deserializer)? {
+            EntityRefRepr::Text(text) => text.parse(),
+            EntityRefRepr::Tagged { kind, id } => {
+                Self::from_parts(&kind, &id, &format!("{kind}:{id}"))
+            }
+        }
+        .map_err(serde::de::Error::custom)
+    }
+}
+
+impl JsonSchema for EntityRef {
+    fn schema_name() -> Cow<'static, str> {
+        "EntityRef".into()
+    }
+
+    fn json_schema(_: &mut SchemaGenerator) -> Schema {
+        json_schema!({
+            "description": "Kinded id, `kind:id`",
+            "type": "string",
+            "pattern": format!("^({}):.+$", Self::KINDS.join("|")),
+            "examples": ["module:auth", "group:payments/checkout", "rule:tech/rust"],
+        })
+    }
+}
+
+impl ModuleMap {
+    /// Whether `entity` names something in this map. Rules and ADRs live
+    /// outside the map and always resolve.
+    pub fn resolves(&self, entity: &EntityRef) -> bool {
+        match entity {
+            EntityRef::Module(id) => self.find_module(id).is_some(),
+            EntityRef::Group(id) => self.find_group(id).is_some(),
+            EntityRef::Domain(id) => self.find_domain(id).is_some(),
+            EntityRef::Convention(name) => self.conventions_iter().any(|(_, c)| c.name == *name),
+            EntityRef::Issue(id) => self.issues_iter().any(|r| r.issue.id == *id),
+            EntityRef::Table(name) => self
+                .domains
+                .iter()
+                .flat_map(|d| &d.interfaces)
+                .filter_map(|i| i.database.as_ref())
+                .any(|db| db.tables.iter().any(|t| db.qualified_table(t) == *name)),
+            EntityRef::Rule(_) | EntityRef::Adr(_) => true,
+        }
+    }
+
+    /// Sources of `rule` that no longer exist in this map.
+    pub fn dangling_provenance<'a>(&self, rule: &'a Rule) -> Vec<&'a EntityRef> {
+        rule.derived_from
+            .iter()
+            .filter(|entity| !self.resolves(entity))
+            .collect()
+    }
+}
+
+impl ProjectManifest {
+    /// Like `ModuleMap::resolves`, checking rules against the manifest's rule list.
+    pub fn resolves(&self, entity: &EntityRef) -> bool {
+        match entity {
+            EntityRef::Rule(name) => self.rules.contains(name),
+            _ => self.project.resolves(entity),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        Convention, GeneratorInfo, IssueCategory, IssueSeverity, KnownIssue, Module,
+        ProjectMetadata, TechStack,
+    };
+
+    fn module(id: &str) -> Module {
+        Module::new(id, id, vec![format!("src/{id}/")])
+            .with_primary_language("rust")
+            .with_conventions(vec![Convention::new("error-handling", "thiserror enums")])
+            .with_known_issues(vec![KnownIssue::new(
+                "token-leak",
+                "Tokens logged",
+                IssueSeverity::High,
+                IssueCategory::Security,
+            )])
+    }
+
+    #[test]
+    fn test_rule_provenance() {
+        let map = ModuleMap::new(
+            GeneratorInfo::new("test", "1.0.0"),
+            ProjectMetadata::new("shop", TechStack::new("rust")),
+            vec![module("auth")],
+            vec![],
+        );
+        let rule =
+            Rule::module("auth", vec!["src/auth/**".into()], vec![]).with_derived_from(vec![
+                EntityRef::Module("auth".into()),
+                EntityRef::Convention("error-handling".into()),
+                EntityRef::Issue("token-leak".into()),
+                EntityRef::Issue("sql-injection".into()),
+                EntityRef::Adr("ADR-0012".into()),
+            ]);
+
+        assert!(rule.is_derived_from(&EntityRef::Issue("token-leak".into())));
+        assert!(!rule.is_derived_from(&EntityRef::Module("billing".into())));
+        let dangling = map.dangling_provenance(&rule);
+        assert_eq!(dangling, [&EntityRef::Issue("sql-injection".into())]);
+        assert_eq!(dangling[0].to_string(), "issue:sql-injection");
+
+        let json = serde_json::to_value(&rule).unwrap();
+        assert_eq!(json["derived_from"][0], serde_json::json!("module:auth"));
+        let parsed: Rule = serde_json::from_value(json).unwrap();
+        assert_eq!(parsed.derived_from, rule.derived_from);
+    }
+
+    #[test]
+    fn test_entity_ref_parse() {
+        let group: EntityRef = "group:payments/checkout".parse().unwrap();
+        assert_eq!(group, EntityRef::Group("payments/checkout".into()));
+        assert_eq!(group.to_string(), "group:payments/checkout");
+        assert_eq!(
+            "rule:tech/rust".parse::<EntityRef>().unwrap().id(),
+            "tech/rust"
+        );
+        assert_eq!(
+            "auth".parse::<EntityRef>(),
+            Err(EntityRefError::MissingKind("auth".into()))
+        );
+        assert!(matches!(
+            "view:orders".parse::<EntityRef>(),
+            Err(EntityRefError::UnknownKind { .. })
+        ));
+        assert_eq!(
+            "table:billing.orders".parse::<EntityRef>().unwrap(),
+            EntityRef::Table("billing.orders".into())
+        );
+        assert_eq!(
+            "domain:".parse::<EntityRef>(),
+            Err(EntityRefError::EmptyId("domain:".into()))
+        );
+
+        let legacy: EntityRef =
+            serde_json::from_value(serde_json::json!({"kind": "issue", "id": "token-leak"}))
+                .unwrap();
+        assert_eq!(legacy, EntityRef::Issue("token-leak".into()));
+        assert!(serde_json::from_value::<EntityRef>(serde_json::json!("nope")).is_err());
+
+        let manifest = ProjectManifest::new(ModuleMap::new(
+            GeneratorInfo::new("test", "1.0.0"),
+            ProjectMetadata::new("shop", TechStack::new("rust")),
+            vec![module("auth")],
+            vec![],
+        ))
+        .with_rules(vec!["tech/rust".into()]);
+        assert!(manifest.resolves(&"rule:tech/rust".parse().unwrap()));
+        assert!(!manifest.resolves(&"rule:tech/go".parse().unwrap()));
+        assert!(!manifest.resolves(&"group:core".parse().unwrap()));
+    }
+}
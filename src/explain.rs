@@ -0,0 +1,71 @@
+//! Human-readable reasoning attached to analytical results in explain mode
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Why a result came out as it did, plus the data points behind it
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct Explanation {
+    pub reason: String,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub data: BTreeMap<String, Value>,
+}
+
+impl Explanation {
+    pub fn new(reason: impl Into<String>) -> Self {
+        Self {
+            reason: reason.into(),
+            data: BTreeMap::new(),
+        }
+    }
+
+    pub fn with_data(mut self, key: impl Into<String>, value: impl Into<Value>) -> Self {
+        self.data.insert(key.into(), value.into());
+        self
+    }
+}
+
+/// `reason (key=value, ...)`, one line, for prompt injection
+impl fmt::Display for Explanation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.reason)?;
+        let mut data = self.data.iter();
+        if let Some((key, value)) = data.next() {
+            write!(f, " ({key}={value}")?;
+            for (key, value) in data {
+                write!(f, ", {key}={value}")?;
+            }
+            write!(f, ")")?;
+        }
+        Ok(())
+    }
+}
+
+/// Bulleted list of explanations for an agent prompt.
+pub fn explanations_to_prompt(explanations: &[Explanation]) -> String {
+    explanations.iter().map(|e| format!("- {e}\n")).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_explanation_display() {
+        let explanation = Explanation::new("rule injected from group context")
+            .with_data("group", "core")
+            .with_data("depth", 2);
+        assert_eq!(
+            explanation.to_string(),
+            "rule injected from group context (depth=2, group=\"core\")"
+        );
+        assert_eq!(
+            explanations_to_prompt(&[Explanation::new("a"), Explanation::new("b")]),
+            "- a\n- b\n"
+        );
+    }
+}
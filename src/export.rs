@@ -0,0 +1,419 @@
+//! Flat tables of modules, issues and edges for analytics pipelines
+
+use std::io::{self, Write};
+
+use serde::Serialize;
+
+use crate::module_map::ModuleMap;
+use crate::query::IssueRef;
+use crate::types::{DependencyType, IssueCategory, IssueSeverity};
+
+/// Which table to export
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Table {
+    Modules,
+    Issues,
+    Edges,
+}
+
+/// One module with its group and domain denormalized in
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ModuleRow<'a> {
+    pub module_id: &'a str,
+    pub name: &'a str,
+    pub group_id: Option<&'a str>,
+    pub domain_id: Option<&'a str>,
+    pub primary_language: &'a str,
+    pub paths: &'a [String],
+    pub responsibility: &'a str,
+    pub coverage_ratio: f64,
+    pub value_score: f64,
+    pub risk_score: f64,
+    pub dependency_count: usize,
+    pub dependent_count: usize,
+    pub issue_count: usize,
+}
+
+/// One known issue with its owning module, group and domain
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct IssueRow<'a> {
+    pub issue_id: &'a str,
+    pub module_id: &'a str,
+    pub group_id: Option<&'a str>,
+    pub domain_id: Option<&'a str>,
+    pub severity: IssueSeverity,
+    pub category: &'a IssueCategory,
+    pub subcategory: Option<&'a str>,
+    pub description: &'a str,
+    pub tags: &'a [String],
+}
+
+/// One dependency edge with the groups on either side
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct EdgeRow<'a> {
+    pub from: &'a str,
+    pub to: &'a str,
+    pub edge_type: &'a DependencyType,
+    pub from_group_id: Option<&'a str>,
+    pub to_group_id: Option<&'a str>,
+}
+
+fn group_and_domain<'a>(map: &'a ModuleMap, module_id: &str) -> (Option<&'a str>, Option<&'a str>) {
+    (
+        map.find_group_containing(module_id).map(|g| g.id.as_str()),
+        map.find_domain_containing_module(module_id)
+            .map(|d| d.id.as_str()),
+    )
+}
+
+pub fn module_rows(map: &ModuleMap) -> Vec<ModuleRow<'_>> {
+    map.modules
+        .iter()
+        .map(|m| {
+            let (group_id, domain_id) = group_and_domain(map, &m.id);
+            ModuleRow {
+                module_id: &m.id,
+                name: &m.name,
+                group_id,
+                domain_id,
+                primary_language: &m.primary_language,
+                paths: &m.paths,
+                responsibility: &m.responsibility,
+                coverage_ratio: m.metrics.coverage_ratio.get(),
+                value_score: m.metrics.value_score.get(),
+                risk_score: m.metrics.risk_score.get(),
+                dependency_count: m.dependencies.len(),
+                dependent_count: m.dependents.len(),
+                issue_count: m.known_issues.len(),
+            }
+        })
+        .collect()
+}
+
+pub fn issue_rows(map: &ModuleMap) -> Vec<IssueRow<'_>> {
+    map.issues_iter()
+        .map(|IssueRef { module: m, issue }| {
+            let (group_id, domain_id) = group_and_domain(map, &m.id);
+            IssueRow {
+                issue_id: &issue.id,
+                module_id: &m.id,
+                group_id,
+                domain_id,
+                severity: issue.severity,
+                category: &issue.category,
+                subcategory: issue.subcategory.as_deref(),
+                description: &issue.description,
+                tags: &issue.tags,
+            }
+        })
+        .collect()
+}
+
+/// Rows for the explicit graph, or module dependencies when it has no edges.
+pub fn edge_rows(map: &ModuleMap) -> Vec<EdgeRow<'_>> {
+    let pairs: Vec<(&str, &str, &DependencyType)> = match &map.dependency_graph {
+        Some(graph) if !graph.edges.is_empty() => graph
+            .edges
+            .iter()
+            .map(|e| (e.from.as_str(), e.to.as_str(), &e.edge_type))
+            .collect(),
+        _ => map
+            .modules
+            .iter()
+            .flat_map(|m| {
+                m.dependencies
+                    .iter()
+                    .map(|d| (m.id.as_str(), d.module_id.as_str(), &d.dependency_type))
+            })
+            .collect(),
+    };
+    let group_of = |id: &str| map.find_group_containing(id).map(|g| g.id.as_str());
+    pairs
+        .into_iter()
+        .map(|(from, to, edge_type)| EdgeRow {
+            from,
+            to,
+            edge_type,
+            from_group_id: group_of(from),
+            to_group_id: group_of(to),
+        })
+        .collect()
+}
+
+fn write_lines<W: Write, T: Serialize>(mut writer: W, rows: &[T]) -> io::Result<usize> {
+    for row in rows {
+        serde_json::to_writer(&mut writer, row)?;
+        writer.write_all(b"\n")?;
+    }
+    writer.flush()?;
+    Ok(rows.len())
+}
+
+/// Writes `table` as newline-delimited JSON, one row per line. Returns the
+/// number of rows written.
+pub fn ndjson<W: Write>(map: &ModuleMap, table: Table, writer: W) -> io::Result<usize> {
+    match table {
+        Table::Modules => write_lines(writer, &module_rows(map)),
+        Table::Issues => write_lines(writer, &issue_rows(map)),
+        Table::Edges => write_lines(writer, &edge_rows(map)),
+    }
+}
+
+/// Arrow record batches and Parquet files (feature: arrow)
+#[cfg(feature = "arrow")]
+mod columnar {
+    use std::io::Write;
+    use std::sync::Arc;
+
+    use arrow::array::{
+        ArrayRef, Float64Array, ListBuilder, RecordBatch, StringArray, StringBuilder, UInt64Array,
+    };
+    use arrow::error::ArrowError;
+    use parquet::arrow::ArrowWriter;
+    use parquet::errors::ParquetError;
+
+    use super::{Table, edge_rows, issue_rows, module_rows};
+    use crate::module_map::ModuleMap;
+
+    fn text<'a>(values: impl IntoIterator<Item = &'a str>) -> ArrayRef {
+        Arc::new(StringArray::from_iter_values(values))
+    }
+
+    fn optional_text<'a>(values: impl IntoIterator<Item = Option<&'a str>>) -> ArrayRef {
+        Arc::new(values.into_iter().collect::<StringArray>())
+    }
+
+    fn owned_text(values: impl IntoIterator<Item = String>) -> ArrayRef {
+        Arc::new(StringArray::from_iter_values(values))
+    }
+
+    fn text_list<'a>(values: impl IntoIterator<Item = &'a [String]>) -> ArrayRef {
+        let mut builder = ListBuilder::new(StringBuilder::new());
+        for list in values {
+            builder.values().extend(list.iter().map(Some));
+            builder.append(true);
+        }
+        Arc::new(builder.finish())
+    }
+
+    fn float(values: impl IntoIterator<Item = f64>) -> ArrayRef {
+        Arc::new(values.into_iter().collect::<Float64Array>())
+    }
+
+    fn count(values: impl IntoIterator<Item = usize>) -> ArrayRef {
+        Arc::new(
+            values
+                .into_iter()
+                .map(|v| v as u64)
+                .collect::<UInt64Array>(),
+        )
+    }
+
+    /// `table` as one Arrow record batch, with the same columns as the NDJSON rows.
+    pub fn record_batch(map: &ModuleMap, table: Table) -> Result<RecordBatch, ArrowError> {
+        match table {
+            Table::Modules => {
+                let rows = module_rows(map);
+                RecordBatch::try_from_iter([
+                    ("module_id", text(rows.iter().map(|r| r.module_id))),
+                    ("name", text(rows.iter().map(|r| r.name))),
+                    ("group_id", optional_text(rows.iter().map(|r| r.group_id))),
+                    ("domain_id", optional_text(rows.iter().map(|r| r.domain_id))),
+                    (
+                        "primary_language",
+                        text(rows.iter().map(|r| r.primary_language)),
+                    ),
+                    ("paths", text_list(rows.iter().map(|r| r.paths))),
+                    (
+                        "responsibility",
+                        text(rows.iter().map(|r| r.responsibility)),
+                    ),
+                    (
+                        "coverage_ratio",
+                        float(rows.iter().map(|r| r.coverage_ratio)),
+                    ),
+                    ("value_score", float(rows.iter().map(|r| r.value_score))),
+                    ("risk_score", float(rows.iter().map(|r| r.risk_score))),
+                    (
+                        "dependency_count",
+                        count(rows.iter().map(|r| r.dependency_count)),
+                    ),
+                    (
+                        "dependent_count",
+                        count(rows.iter().map(|r| r.dependent_count)),
+                    ),
+                    ("issue_count", count(rows.iter().map(|r| r.issue_count))),
+                ])
+            }
+            Table::Issues => {
+                let rows = issue_rows(map);
+                RecordBatch::try_from_iter([
+                    ("issue_id", text(rows.iter().map(|r| r.issue_id))),
+                    ("module_id", text(rows.iter().map(|r| r.module_id))),
+                    ("group_id", optional_text(rows.iter().map(|r| r.group_id))),
+                    ("domain_id", optional_text(rows.iter().map(|r| r.domain_id))),
+                    (
+                        "severity",
+                        owned_text(rows.iter().map(|r| serde_name(&r.severity))),
+                    ),
+                    (
+                        "category",
+                        owned_text(rows.iter().map(|r| serde_name(r.category))),
+                    ),
+                    (
+                        "subcategory",
+                        optional_text(rows.iter().map(|r| r.subcategory)),
+                    ),
+                    ("description", text(rows.iter().map(|r| r.description))),
+                    ("tags", text_list(rows.iter().map(|r| r.tags))),
+                ])
+            }
+            Table::Edges => {
+                let rows = edge_rows(map);
+                RecordBatch::try_from_iter([
+                    ("from", text(rows.iter().map(|r| r.from))),
+                    ("to", text(rows.iter().map(|r| r.to))),
+                    (
+                        "edge_type",
+                        owned_text(rows.iter().map(|r| serde_name(r.edge_type))),
+                    ),
+                    (
+                        "from_group_id",
+                        optional_text(rows.iter().map(|r| r.from_group_id)),
+                    ),
+                    (
+                        "to_group_id",
+                        optional_text(rows.iter().map(|r| r.to_group_id)),
+                    ),
+                ])
+            }
+        }
+    }
+
+    /// Value as serialized in JSON, matching the NDJSON rows.
+    fn serde_name(value: &impl serde::Serialize) -> String {
+        serde_json::to_value(value)
+            .ok()
+            .and_then(|v| v.as_str().map(str::to_string))
+            .unwrap_or_default()
+    }
+
+    /// Writes `table` as a Parquet file. Returns the number of rows written.
+    pub fn parquet<W: Write + Send>(
+        map: &ModuleMap,
+        table: Table,
+        writer: W,
+    ) -> Result<usize, ParquetError> {
+        let batch = record_batch(map, table)?;
+        let mut writer = ArrowWriter::try_new(writer, batch.schema(), None)?;
+        writer.write(&batch)?;
+        writer.close()?;
+        Ok(batch.num_rows())
+    }
+}
+
+#[cfg(feature = "arrow")]
+pub use columnar::{parquet, record_batch};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        Domain, GeneratorInfo, KnownIssue, Module, ModuleDependency, ModuleGroup, ModuleMetrics,
+        ProjectMetadata, TechStack,
+    };
+
+    fn module(id: &str, dependencies: &[&str]) -> Module {
+        Module::new(id, id, vec![format!("src/{id}/")])
+            .with_dependencies(
+                dependencies
+                    .iter()
+                    .map(|d| ModuleDependency::runtime(*d))
+                    .collect(),
+            )
+            .with_primary_language("rust")
+            .with_metrics(ModuleMetrics::new(0.5, 0.25, 0.75))
+    }
+
+    fn lines(map: &ModuleMap, table: Table) -> Vec<serde_json::Value> {
+        let mut out = Vec::new();
+        let count = ndjson(map, table, &mut out).unwrap();
+        let rows: Vec<_> = String::from_utf8(out)
+            .unwrap()
+            .lines()
+            .map(|l| serde_json::from_str(l).unwrap())
+            .collect();
+        assert_eq!(rows.len(), count);
+        rows
+    }
+
+    fn sample_map() -> ModuleMap {
+        let mut api = module("api", &["core"]);
+        api.known_issues.push(KnownIssue::new(
+            "n-plus-one",
+            "Query per item",
+            IssueSeverity::High,
+            IssueCategory::Performance,
+        ));
+        ModuleMap::new(
+            GeneratorInfo::new("test", "1.0.0"),
+            ProjectMetadata::new("shop", TechStack::new("rust")),
+            vec![api, module("core", &[])],
+            vec![ModuleGroup::new("web", "Web", vec!["api".into()])],
+        )
+        .with_domains(vec![Domain::new(
+            "storefront",
+            "Storefront",
+            vec!["web".into()],
+        )])
+    }
+
+    #[test]
+    fn test_ndjson_tables() {
+        let map = sample_map();
+        let modules = lines(&map, Table::Modules);
+        assert_eq!(modules.len(), 2);
+        assert_eq!(modules[0]["domain_id"], "storefront");
+        assert_eq!(modules[0]["risk_score"], 0.75);
+        assert_eq!(modules[1]["group_id"], serde_json::Value::Null);
+
+        let issues = lines(&map, Table::Issues);
+        assert_eq!(issues[0]["issue_id"], "n-plus-one");
+        assert_eq!(issues[0]["module_id"], "api");
+        assert_eq!(issues[0]["group_id"], "web");
+        assert_eq!(issues[0]["severity"], "high");
+
+        let edges = lines(&map, Table::Edges);
+        assert_eq!(edges.len(), 1);
+        assert_eq!(edges[0]["from_group_id"], "web");
+        assert_eq!(edges[0]["edge_type"], "runtime");
+    }
+
+    #[cfg(feature = "arrow")]
+    #[test]
+    fn test_arrow_tables() {
+        use arrow::array::{Array, AsArray};
+
+        let map = sample_map();
+        let modules = record_batch(&map, Table::Modules).unwrap();
+        assert_eq!(modules.num_rows(), 2);
+        assert_eq!(modules.schema().field(0).name(), "module_id");
+        let domains = modules
+            .column_by_name("domain_id")
+            .unwrap()
+            .as_string::<i32>();
+        assert_eq!(domains.value(0), "storefront");
+        assert!(domains.is_null(1));
+
+        let issues = record_batch(&map, Table::Issues).unwrap();
+        let severity = issues
+            .column_by_name("severity")
+            .unwrap()
+            .as_string::<i32>();
+        assert_eq!(severity.value(0), "high");
+
+        let mut file = Vec::new();
+        assert_eq!(parquet(&map, Table::Edges, &mut file).unwrap(), 1);
+        assert_eq!(&file[..4], b"PAR1");
+    }
+}
@@ -0,0 +1,167 @@
+//! Cargo features compiled into this build, for graceful degradation at runtime
+
+use std::fmt;
+
+use serde::Serialize;
+use thiserror::Error;
+
+/// One cargo feature of the crate and what it enables
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct Feature {
+    pub name: &'static str,
+    pub enabled: bool,
+    pub description: &'static str,
+    /// Capability ids checked by `FeatureSet::require`
+    pub capabilities: &'static [&'static str],
+}
+
+const FEATURES: [Feature; 9] = [
+    Feature {
+        name: "search",
+        enabled: cfg!(feature = "search"),
+        description: "Full-text search over modules, conventions and issues",
+        capabilities: &["search"],
+    },
+    Feature {
+        name: "contracts",
+        enabled: cfg!(feature = "contracts"),
+        description: "API contract file checks",
+        capabilities: &["contract-validation"],
+    },
+    Feature {
+        name: "inline-embeddings",
+        enabled: cfg!(feature = "inline-embeddings"),
+        description: "Module and rule vectors stored inside the manifest",
+        capabilities: &["inline-embeddings"],
+    },
+    Feature {
+        name: "cli",
+        enabled: cfg!(feature = "cli"),
+        description: "The `modmap` binary and its terminal explorer",
+        capabilities: &["cli", "tui"],
+    },
+    Feature {
+        name: "server",
+        enabled: cfg!(feature = "server"),
+        description: "Read-only REST API over a manifest",
+        capabilities: &["http"],
+    },
+    Feature {
+        name: "graphql",
+        enabled: cfg!(feature = "graphql"),
+        description: "GraphQL schema, served at `/graphql` together with `server`",
+        capabilities: &["graphql"],
+    },
+    Feature {
+        name: "arrow",
+        enabled: cfg!(feature = "arrow"),
+        description: "Arrow and Parquet table export",
+        capabilities: &["arrow-export", "parquet-export"],
+    },
+    Feature {
+        name: "petgraph",
+        enabled: cfg!(feature = "petgraph"),
+        description: "Conversion of the dependency graph to and from petgraph",
+        capabilities: &["petgraph"],
+    },
+    Feature {
+        name: "policy-toml",
+        enabled: cfg!(feature = "policy-toml"),
+        description: "`modmap.policy.toml` files; JSON policies need no feature",
+        capabilities: &["toml-policy"],
+    },
+];
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum FeatureError {
+    #[error("{capability} is not available: recompile modmap with `--features {feature}`")]
+    Disabled {
+        capability: String,
+        feature: &'static str,
+    },
+    #[error("unknown capability '{0}'")]
+    UnknownCapability(String),
+}
+
+/// Every feature of this build, enabled or not
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(transparent)]
+pub struct FeatureSet(Vec<Feature>);
+
+/// Features of the running build.
+pub fn features() -> FeatureSet {
+    FeatureSet(FEATURES.to_vec())
+}
+
+impl FeatureSet {
+    pub fn all(&self) -> &[Feature] {
+        &self.0
+    }
+
+    pub fn enabled(&self) -> impl Iterator<Item = &Feature> {
+        self.0.iter().filter(|f| f.enabled)
+    }
+
+    pub fn is_enabled(&self, feature: &str) -> bool {
+        self.0.iter().any(|f| f.enabled && f.name == feature)
+    }
+
+    /// Feature providing `capability`.
+    pub fn provider(&self, capability: &str) -> Option<&Feature> {
+        self.0.iter().find(|f| f.capabilities.contains(&capability))
+    }
+
+    /// Ok when the feature providing `capability` is compiled in; the error
+    /// names the feature to recompile with.
+    pub fn require(&self, capability: &str) -> Result<(), FeatureError> {
+        match self.provider(capability) {
+            Some(feature) if feature.enabled => Ok(()),
+            Some(feature) => Err(FeatureError::Disabled {
+                capability: capability.to_string(),
+                feature: feature.name,
+            }),
+            None => Err(FeatureError::UnknownCapability(capability.to_string())),
+        }
+    }
+}
+
+/// Enabled feature names, comma separated, or `none`.
+impl fmt::Display for FeatureSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let names: Vec<&str> = self.enabled().map(|feature| feature.name).collect();
+        if names.is_empty() {
+            f.write_str("none")
+        } else {
+            f.write_str(&names.join(", "))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_features() {
+        let features = features();
+        assert_eq!(features.all().len(), 9);
+        assert_eq!(features.is_enabled("server"), cfg!(feature = "server"));
+        assert_eq!(features.provider("parquet-export").unwrap().name, "arrow");
+
+        if cfg!(feature = "graphql") {
+            assert_eq!(features.require("graphql"), Ok(()));
+        } else {
+            let error = features.require("graphql").unwrap_err();
+            assert_eq!(
+                error.to_string(),
+                "graphql is not available: recompile modmap with `--features graphql`"
+            );
+        }
+        assert_eq!(
+            features.require("wasm"),
+            Err(FeatureError::UnknownCapability("wasm".into()))
+        );
+        let json = serde_json::to_value(&features).unwrap();
+        assert_eq!(json[0]["name"], "search");
+    }
+}
@@ -0,0 +1,466 @@
+//! Architecture fitness functions: pluggable invariants over a map
+
+use std::collections::HashMap;
+use std::fmt;
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::entity::EntityRef;
+use crate::explain::Explanation;
+use crate::module_map::ModuleMap;
+use crate::policy::PolicyConfig;
+
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Default,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    Serialize,
+    Deserialize,
+    JsonSchema,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum DiagnosticSeverity {
+    #[default]
+    Error,
+    Warning,
+    Info,
+}
+
+impl fmt::Display for DiagnosticSeverity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Error => write!(f, "error"),
+            Self::Warning => write!(f, "warning"),
+            Self::Info => write!(f, "info"),
+        }
+    }
+}
+
+/// One finding of a fitness function
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct Diagnostic {
+    /// Module, group or domain the finding is about
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub target: Option<EntityRef>,
+    pub message: String,
+}
+
+impl Diagnostic {
+    pub fn new(message: impl Into<String>) -> Self {
+        Self {
+            target: None,
+            message: message.into(),
+        }
+    }
+
+    pub fn with_target(mut self, target: EntityRef) -> Self {
+        self.target = Some(target);
+        self
+    }
+}
+
+/// An architectural invariant checked against a whole map
+pub trait FitnessFunction {
+    /// Stable check id, e.g. `no-cycles`
+    fn name(&self) -> &str;
+
+    fn severity(&self) -> DiagnosticSeverity {
+        DiagnosticSeverity::Error
+    }
+
+    fn evaluate(&self, map: &ModuleMap) -> Vec<Diagnostic>;
+}
+
+/// Module dependencies must not form cycles; one diagnostic per strongly
+/// connected component
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NoCycles;
+
+/// Modules may depend only on their own or later (lower) layers of the
+/// dependency graph
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Layering;
+
+/// Every module belongs to a domain with an owner
+#[derive(Debug, Clone, Copy, Default)]
+pub struct OwnershipCoverage;
+
+/// Cross-domain dependencies and declared consumers agree (`verify_consumers`)
+#[derive(Debug, Clone, Copy, Default)]
+pub struct InterfaceUsage;
+
+impl FitnessFunction for NoCycles {
+    fn name(&self) -> &str {
+        "no-cycles"
+    }
+
+    fn evaluate(&self, map: &ModuleMap) -> Vec<Diagnostic> {
+        map.compile_graph()
+            .cycles()
+            .into_iter()
+            .map(|cycle| {
+                Diagnostic::new(format!("dependency cycle: {}", cycle.join(", ")))
+                    .with_target(EntityRef::Module(cycle[0].to_string()))
+            })
+            .collect()
+    }
+}
+
+impl FitnessFunction for Layering {
+    fn name(&self) -> &str {
+        "layering"
+    }
+
+    fn evaluate(&self, map: &ModuleMap) -> Vec<Diagnostic> {
+        let Some(graph) = &map.dependency_graph else {
+            return Vec::new();
+        };
+        let layer_of: HashMap<&str, usize> = graph
+            .layers
+            .iter()
+            .enumerate()
+            .flat_map(|(i, l)| l.modules.iter().map(move |m| (m.as_str(), i)))
+            .collect();
+        map.dependency_edges()
+            .into_iter()
+            .filter_map(|edge| {
+                let from = *layer_of.get(edge.from.as_str())?;
+                let to = *layer_of.get(edge.to.as_str())?;
+                (to < from).then(|| {
+                    Diagnostic::new(format!(
+                        "'{}' ({}) depends on '{}' in higher layer {}",
+                        edge.from, graph.layers[from].name, edge.to, graph.layers[to].name
+                    ))
+                    .with_target(EntityRef::Module(edge.from.clone()))
+                })
+            })
+            .collect()
+    }
+}
+
+impl FitnessFunction for OwnershipCoverage {
+    fn name(&self) -> &str {
+        "ownership-coverage"
+    }
+
+    fn severity(&self) -> DiagnosticSeverity {
+        DiagnosticSeverity::Warning
+    }
+
+    fn evaluate(&self, map: &ModuleMap) -> Vec<Diagnostic> {
+        map.modules
+            .iter()
+            .filter(|m| {
+                map.find_domain_containing_module(&m.id)
+                    .is_none_or(|d| d.owner.is_none())
+            })
+            .map(|m| {
+                Diagnostic::new(format!("module '{}' has no owner", m.id))
+                    .with_target(EntityRef::Module(m.id.clone()))
+            })
+            .collect()
+    }
+}
+
+impl FitnessFunction for InterfaceUsage {
+    fn name(&self) -> &str {
+        "interface-usage"
+    }
+
+    fn evaluate(&self, map: &ModuleMap) -> Vec<Diagnostic> {
+        map.verify_consumers()
+            .into_iter()
+            .map(|v| Diagnostic::new(v.to_string()))
+            .collect()
+    }
+}
+
+/// Diagnostics of one fitness function
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct FitnessResult {
+    pub check: String,
+    pub severity: DiagnosticSeverity,
+    pub diagnostics: Vec<Diagnostic>,
+    /// Findings covered by a waiver (see `ProjectManifest::run_fitness`)
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub waived: Vec<Diagnostic>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct FitnessReport {
+    pub results: Vec<FitnessResult>,
+    /// Per-check reasoning, filled by `FitnessRunner::with_explanations`
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub explanations: Vec<Explanation>,
+}
+
+impl FitnessReport {
+    /// Whether any error-severity check produced a diagnostic.
+    pub fn has_errors(&self) -> bool {
+        self.results
+            .iter()
+            .any(|r| r.severity == DiagnosticSeverity::Error && !r.diagnostics.is_empty())
+    }
+
+    /// `(check, severity, diagnostic)` for every finding, in run order.
+    pub fn diagnostics(&self) -> impl Iterator<Item = (&str, DiagnosticSeverity, &Diagnostic)> {
+        self.results.iter().flat_map(|r| {
+            r.diagnostics
+                .iter()
+                .map(|d| (r.check.as_str(), r.severity, d))
+        })
+    }
+}
+
+/// Runs a set of fitness functions over a map
+#[derive(Default)]
+pub struct FitnessRunner {
+    functions: Vec<Box<dyn FitnessFunction>>,
+    policy: Option<PolicyConfig>,
+    explain: bool,
+}
+
+impl FitnessRunner {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The built-in checks: no cycles, layering, ownership coverage, interface usage.
+    pub fn builtin() -> Self {
+        Self::new()
+            .with(NoCycles)
+            .with(Layering)
+            .with(OwnershipCoverage)
+            .with(InterfaceUsage)
+    }
+
+    pub fn with(mut self, function: impl FitnessFunction + 'static) -> Self {
+        self.functions.push(Box::new(function));
+        self
+    }
+
+    /// Skips disabled checks and applies severity overrides.
+    pub fn with_policy(mut self, policy: PolicyConfig) -> Self {
+        self.policy = Some(policy);
+        self
+    }
+
+    /// Records why each check ran or was skipped, and at which severity.
+    pub fn with_explanations(mut self) -> Self {
+        self.explain = true;
+        self
+    }
+
+    pub fn has_policy(&self) -> bool {
+        self.policy.is_some()
+    }
+
+    pub fn names(&self) -> Vec<&str> {
+        self.functions.iter().map(|f| f.name()).collect()
+    }
+
+    pub fn run(&self, map: &ModuleMap) -> FitnessReport {
+        self.run_with_policy(map, self.policy.as_ref())
+    }
+
+    pub(crate) fn run_with_policy(
+        &self,
+        map: &ModuleMap,
+        policy: Option<&PolicyConfig>,
+    ) -> FitnessReport {
+        let mut report = FitnessReport::default();
+        for function in &self.functions {
+            let name = function.name();
+            if policy.is_some_and(|p| !p.is_enabled(name)) {
+                if self.explain {
+                    report.explanations.push(
+                        Explanation::new(format!("'{name}' skipped: disabled by policy"))
+                            .with_data("check", name),
+                    );
+                }
+                continue;
+            }
+            let severity = policy.map_or(function.severity(), |p| {
+                p.severity_for(name, function.severity())
+            });
+            let diagnostics = function.evaluate(map);
+            if self.explain {
+                let source = if severity == function.severity() {
+                    "check"
+                } else {
+                    "policy"
+                };
+                report.explanations.push(
+                    Explanation::new(format!(
+                        "'{name}' reported {} finding(s) at {severity} severity",
+                        diagnostics.len()
+                    ))
+                    .with_data("check", name)
+                    .with_data("findings", diagnostics.len())
+                    .with_data("severity_source", source),
+                );
+            }
+            report.results.push(FitnessResult {
+                check: name.to_string(),
+                severity,
+                diagnostics,
+                waived: Vec::new(),
+            });
+        }
+        report
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        ArchitectureLayer, DependencyGraph, Domain, GeneratorInfo, Module, ModuleDependency,
+        ModuleGroup, ProjectMetadata, TechStack,
+    };
+
+    fn module(id: &str, dependencies: &[&str]) -> Module {
+        Module::new(id, id, vec![format!("src/{id}/")])
+            .with_dependencies(
+                dependencies
+                    .iter()
+                    .map(|d| ModuleDependency::runtime(*d))
+                    .collect(),
+            )
+            .with_primary_language("rust")
+    }
+
+    fn sample() -> ModuleMap {
+        ModuleMap::new(
+            GeneratorInfo::new("test", "1.0.0"),
+            ProjectMetadata::new("shop", TechStack::new("rust")),
+            vec![
+                module("api", &["core"]),
+                module("core", &["db", "api"]),
+                module("db", &[]),
+            ],
+            vec![ModuleGroup::new(
+                "backend",
+                "Backend",
+                vec!["api".into(), "core".into()],
+            )],
+        )
+        .with_domains(vec![
+            Domain::new("platform", "Platform", vec!["backend".into()]).with_owner("@acme/core"),
+        ])
+    }
+
+    struct NoDb;
+
+    impl FitnessFunction for NoDb {
+        fn name(&self) -> &str {
+            "no-db"
+        }
+
+        fn severity(&self) -> DiagnosticSeverity {
+            DiagnosticSeverity::Info
+        }
+
+        fn evaluate(&self, map: &ModuleMap) -> Vec<Diagnostic> {
+            map.find_module("db")
+                .map(|m| {
+                    Diagnostic::new("db module is deprecated")
+                        .with_target(EntityRef::Module(m.id.clone()))
+                })
+                .into_iter()
+                .collect()
+        }
+    }
+
+    #[test]
+    fn test_builtin_fitness_functions() {
+        let map = sample();
+        let report = FitnessRunner::builtin().run(&map);
+        let findings: Vec<_> = report
+            .diagnostics()
+            .map(|(check, _, d)| (check, d.message.as_str()))
+            .collect();
+        assert_eq!(
+            findings,
+            vec![
+                ("no-cycles", "dependency cycle: api, core"),
+                ("ownership-coverage", "module 'db' has no owner"),
+            ]
+        );
+        assert!(report.has_errors());
+
+        let mut layered = map.clone();
+        layered.modules[0].dependencies.clear();
+        layered.modules[1].dependencies.pop();
+        layered.dependency_graph = Some(DependencyGraph {
+            edges: vec![],
+            layers: vec![
+                ArchitectureLayer {
+                    name: "presentation".into(),
+                    modules: vec!["api".into()],
+                },
+                ArchitectureLayer {
+                    name: "domain".into(),
+                    modules: vec!["core".into(), "db".into()],
+                },
+            ],
+        });
+        layered.modules[2].dependencies = vec![ModuleDependency::runtime("api")];
+        let report = FitnessRunner::new()
+            .with(NoCycles)
+            .with(Layering)
+            .run(&layered);
+        let diagnostics: Vec<_> = report.diagnostics().collect();
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(
+            diagnostics[0].2.message,
+            "'db' (domain) depends on 'api' in higher layer presentation"
+        );
+    }
+
+    #[test]
+    fn test_fitness_explanations() {
+        let policy = PolicyConfig::default().with_check(
+            "layering",
+            crate::CheckPolicy {
+                enabled: false,
+                severity: None,
+            },
+        );
+        let report = FitnessRunner::new()
+            .with(NoCycles)
+            .with(Layering)
+            .with_policy(policy)
+            .with_explanations()
+            .run(&sample());
+        assert_eq!(report.explanations.len(), 2);
+        assert_eq!(
+            report.explanations[0].data["findings"],
+            report.results[0].diagnostics.len()
+        );
+        assert_eq!(
+            report.explanations[1].reason,
+            "'layering' skipped: disabled by policy"
+        );
+    }
+
+    #[test]
+    fn test_custom_fitness_function() {
+        let runner = FitnessRunner::new().with(NoDb);
+        assert_eq!(runner.names(), vec!["no-db"]);
+        let report = runner.run(&sample());
+        assert!(!report.has_errors());
+        assert_eq!(report.results[0].severity, DiagnosticSeverity::Info);
+        assert_eq!(
+            report.results[0].diagnostics[0].target,
+            Some(EntityRef::Module("db".into()))
+        );
+    }
+}
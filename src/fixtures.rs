@@ -0,0 +1,288 @@
+//! Seeded synthetic maps shaped like real repositories, for benchmarks and load tests
+//!
+//! Dependencies point from later modules to earlier ones with Zipf-distributed
+//! targets, so a few foundational modules collect most dependents, as in real
+//! codebases, and the graph stays acyclic. The same profile always yields the
+//! same map, `generated_at` included.
+
+use chrono::{DateTime, Utc};
+
+use crate::module_map::{Domain, Module, ModuleGroup, ModuleMap, ModuleMetrics, ProjectMetadata};
+use crate::types::{
+    GeneratorInfo, IssueCategory, IssueSeverity, KnownIssue, ModuleDependency, TechStack,
+};
+
+/// Shape of a generated map
+#[derive(Debug, Clone, PartialEq)]
+pub struct SyntheticProfile {
+    pub seed: u64,
+    pub modules: usize,
+    pub domains: usize,
+    /// Top-level groups per domain
+    pub groups_per_domain: usize,
+    /// Nested groups below each top-level group; 0 for a flat hierarchy
+    pub group_depth: u8,
+    /// Mean dependencies per module
+    pub edge_density: f64,
+    /// Zipf exponent of dependency targets; higher concentrates dependents
+    /// on fewer modules
+    pub dependent_skew: f64,
+    /// Mean known issues per module
+    pub issue_rate: f64,
+    /// Relative weights of critical, high, medium and low issues
+    pub severity_weights: [f64; 4],
+}
+
+impl Default for SyntheticProfile {
+    fn default() -> Self {
+        Self::medium()
+    }
+}
+
+impl SyntheticProfile {
+    /// A single service: 40 modules.
+    pub fn small() -> Self {
+        Self {
+            seed: 1,
+            modules: 40,
+            domains: 2,
+            groups_per_domain: 2,
+            group_depth: 0,
+            edge_density: 2.0,
+            dependent_skew: 1.1,
+            issue_rate: 0.3,
+            severity_weights: [0.05, 0.2, 0.35, 0.4],
+        }
+    }
+
+    /// A mid-sized monorepo: 400 modules.
+    pub fn medium() -> Self {
+        Self {
+            modules: 400,
+            domains: 8,
+            groups_per_domain: 4,
+            group_depth: 1,
+            edge_density: 3.5,
+            ..Self::small()
+        }
+    }
+
+    /// A large monorepo: 5000 modules.
+    pub fn large() -> Self {
+        Self {
+            modules: 5000,
+            domains: 24,
+            groups_per_domain: 6,
+            group_depth: 2,
+            edge_density: 5.0,
+            dependent_skew: 1.3,
+            ..Self::small()
+        }
+    }
+
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    pub fn with_modules(mut self, modules: usize) -> Self {
+        self.modules = modules;
+        self
+    }
+}
+
+/// SplitMix64: small, fast and stable across platforms and releases.
+struct Rng(u64);
+
+impl Rng {
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform in `[0, 1)`.
+    fn unit(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    fn below(&mut self, n: usize) -> usize {
+        (self.unit() * n as f64) as usize
+    }
+
+    /// Count with the given mean: a uniform draw in `[0, 2 * mean)`, rounded
+    /// up with the probability of its fraction.
+    fn count(&mut self, mean: f64) -> usize {
+        let spread = mean * 2.0 * self.unit();
+        spread as usize + usize::from(self.unit() < spread.fract())
+    }
+
+    /// Index picked by `weights`.
+    fn weighted(&mut self, weights: &[f64]) -> usize {
+        let total: f64 = weights.iter().sum();
+        let mut target = self.unit() * total;
+        for (i, weight) in weights.iter().enumerate() {
+            if target < *weight {
+                return i;
+            }
+            target -= weight;
+        }
+        weights.len() - 1
+    }
+}
+
+const CATEGORIES: [IssueCategory; 5] = [
+    IssueCategory::Correctness,
+    IssueCategory::Maintainability,
+    IssueCategory::Performance,
+    IssueCategory::Security,
+    IssueCategory::Concurrency,
+];
+
+const SEVERITIES: [IssueSeverity; 4] = [
+    IssueSeverity::Critical,
+    IssueSeverity::High,
+    IssueSeverity::Medium,
+    IssueSeverity::Low,
+];
+
+fn module(id: &str, domain: &str) -> Module {
+    Module::new(id, id, vec![format!("src/{domain}/{id}/")])
+        .with_responsibility(format!("Synthetic module {id}"))
+        .with_primary_language("rust")
+}
+
+/// Deterministic map of the given shape.
+pub fn synthetic(profile: &SyntheticProfile) -> ModuleMap {
+    let mut rng = Rng(profile.seed);
+    let domain_count = profile.domains.max(1);
+
+    let mut groups = Vec::new();
+    let mut domains = Vec::new();
+    for d in 0..domain_count {
+        let domain_id = format!("domain-{d}");
+        let mut top_level = Vec::new();
+        for g in 0..profile.groups_per_domain.max(1) {
+            let mut parent = format!("{domain_id}-group-{g}");
+            top_level.push(parent.clone());
+            groups.push(
+                ModuleGroup::new(&parent, format!("Group {d}.{g}"), Vec::new())
+                    .with_domain(&domain_id),
+            );
+            for depth in 1..=profile.group_depth {
+                let child = format!("{parent}-{depth}");
+                groups.push(
+                    ModuleGroup::new(&child, format!("Group {d}.{g}.{depth}"), Vec::new())
+                        .with_parent(&parent, depth)
+                        .with_domain(&domain_id),
+                );
+                parent = child;
+            }
+        }
+        domains.push(Domain::new(&domain_id, format!("Domain {d}"), top_level));
+    }
+
+    // Cumulative Zipf weights, so a target among the first `i` modules is a binary search
+    let zipf: Vec<f64> = (0..profile.modules)
+        .scan(0.0, |total, j| {
+            *total += 1.0 / ((j + 1) as f64).powf(profile.dependent_skew);
+            Some(*total)
+        })
+        .collect();
+    let mut modules: Vec<Module> = Vec::with_capacity(profile.modules);
+    for i in 0..profile.modules {
+        let group = rng.below(groups.len());
+        let domain = groups[group].domain_id.clone().unwrap_or_default();
+        let mut module = module(&format!("module-{i:05}"), &domain);
+        groups[group].module_ids.push(module.id.clone());
+
+        if i > 0 {
+            for _ in 0..rng.count(profile.edge_density).min(i) {
+                let draw = rng.unit() * zipf[i - 1];
+                let target = zipf[..i].partition_point(|&c| c <= draw);
+                let target_id = &modules[target].id;
+                if !module
+                    .dependencies
+                    .iter()
+                    .any(|d| d.module_id == *target_id)
+                {
+                    module
+                        .dependencies
+                        .push(ModuleDependency::runtime(target_id.clone()));
+                }
+            }
+        }
+        for n in 0..rng.count(profile.issue_rate) {
+            let severity = SEVERITIES[rng.weighted(&profile.severity_weights)];
+            let category = CATEGORIES[rng.below(CATEGORIES.len())].clone();
+            module.known_issues.push(KnownIssue::new(
+                format!("issue-{i}-{n}"),
+                "Synthetic issue",
+                severity,
+                category,
+            ));
+        }
+        module.metrics = ModuleMetrics::new(rng.unit(), rng.unit(), rng.unit());
+        modules.push(module);
+    }
+
+    let mut project = ProjectMetadata::new("synthetic", TechStack::new("rust"));
+    project.total_files = profile.modules * 12;
+    let mut map = ModuleMap::new(
+        GeneratorInfo::new("modmap-synthetic", env!("CARGO_PKG_VERSION"))
+            .with_run_id(format!("seed-{}", profile.seed)),
+        project,
+        modules,
+        groups,
+    )
+    .with_domains(domains);
+    map.generated_at = DateTime::<Utc>::UNIX_EPOCH;
+    map.recompute_dependents();
+    map
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_synthetic_is_reproducible_and_skewed() {
+        let profile = SyntheticProfile::medium();
+        let map = synthetic(&profile);
+        assert_eq!(
+            serde_json::to_string(&map).unwrap(),
+            serde_json::to_string(&synthetic(&profile)).unwrap()
+        );
+        assert_ne!(
+            serde_json::to_string(&map).unwrap(),
+            serde_json::to_string(&synthetic(&profile.clone().with_seed(2))).unwrap()
+        );
+
+        assert_eq!(map.modules.len(), 400);
+        assert_eq!(map.domains.len(), 8);
+        assert_eq!(map.groups.len(), 8 * 4 * 2);
+        assert!(map.compile_graph().cycles().is_empty());
+        let edges: usize = map.modules.iter().map(|m| m.dependencies.len()).sum();
+        assert!((2.5..=3.5).contains(&(edges as f64 / 400.0)), "{edges}");
+
+        // A handful of modules carry most dependents
+        let mut dependents: Vec<usize> = map.modules.iter().map(|m| m.dependents.len()).collect();
+        dependents.sort_unstable_by(|a, b| b.cmp(a));
+        let top: usize = dependents[..20].iter().sum();
+        assert!(top * 2 > edges, "top 5% hold {top} of {edges}");
+
+        assert!(
+            map.modules
+                .iter()
+                .all(|m| map.find_group_containing(&m.id).is_some())
+        );
+        assert_eq!(
+            synthetic(&SyntheticProfile::small().with_modules(0))
+                .modules
+                .len(),
+            0
+        );
+    }
+}
@@ -0,0 +1,135 @@
+//! Forgiving id lookup for imprecise, often LLM-produced, references
+
+use serde::Serialize;
+
+use crate::module_map::{Domain, Module, ModuleGroup, ModuleMap};
+
+/// Lowest score `find_*_fuzzy` returns
+pub const FUZZY_THRESHOLD: f64 = 0.6;
+
+/// A candidate and how well it matched, `1.0` being an exact id match
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct FuzzyMatch<'a, T> {
+    pub item: &'a T,
+    pub score: f64,
+}
+
+/// Lowercased with `-`, `_`, `.`, `/` and whitespace removed: `AuthCore` and
+/// `auth-core` both become `authcore`.
+fn normalize(id: &str) -> String {
+    id.chars()
+        .filter(|c| !matches!(c, '-' | '_' | '.' | '/') && !c.is_whitespace())
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+/// Edit distance over chars.
+pub fn levenshtein(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = diagonal + usize::from(ca != *cb);
+            diagonal = row[j + 1];
+            row[j + 1] = substitution.min(row[j] + 1).min(row[j + 1] + 1);
+        }
+    }
+    row[b.len()]
+}
+
+/// `1.0` for equal ids, `0.95` when equal after normalizing, else the
+/// normalized Levenshtein similarity.
+fn similarity(query: &str, candidate: &str) -> f64 {
+    if query == candidate {
+        return 1.0;
+    }
+    let (q, c) = (normalize(query), normalize(candidate));
+    if q.is_empty() || c.is_empty() {
+        return 0.0;
+    }
+    if q == c {
+        return 0.95;
+    }
+    let longest = q.chars().count().max(c.chars().count());
+    (1.0 - levenshtein(&q, &c) as f64 / longest as f64) * 0.9
+}
+
+/// Items scoring at least `FUZZY_THRESHOLD` on id or name, best first, map
+/// order among equal scores.
+fn rank<'a, T>(
+    query: &str,
+    items: &'a [T],
+    keys: impl Fn(&T) -> [&str; 2],
+) -> Vec<FuzzyMatch<'a, T>> {
+    let mut matches: Vec<FuzzyMatch<'a, T>> = items
+        .iter()
+        .map(|item| FuzzyMatch {
+            item,
+            score: keys(item)
+                .iter()
+                .map(|key| similarity(query, key))
+                .fold(0.0, f64::max),
+        })
+        .filter(|m| m.score >= FUZZY_THRESHOLD)
+        .collect();
+    matches.sort_by(|a, b| b.score.total_cmp(&a.score));
+    matches
+}
+
+impl ModuleMap {
+    /// Modules whose id or name resembles `query`, ignoring case and separators.
+    pub fn find_module_fuzzy(&self, query: &str) -> Vec<FuzzyMatch<'_, Module>> {
+        rank(query, &self.modules, |m| [&m.id, &m.name])
+    }
+
+    pub fn find_group_fuzzy(&self, query: &str) -> Vec<FuzzyMatch<'_, ModuleGroup>> {
+        rank(query, &self.groups, |g| [&g.id, &g.name])
+    }
+
+    pub fn find_domain_fuzzy(&self, query: &str) -> Vec<FuzzyMatch<'_, Domain>> {
+        rank(query, &self.domains, |d| [&d.id, &d.name])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{GeneratorInfo, ProjectMetadata, TechStack};
+
+    fn module(id: &str, name: &str) -> Module {
+        Module::new(id, name, vec![format!("src/{id}/")]).with_primary_language("rust")
+    }
+
+    #[test]
+    fn test_find_module_fuzzy() {
+        let map = ModuleMap::new(
+            GeneratorInfo::new("test", "1.0.0"),
+            ProjectMetadata::new("shop", TechStack::new("rust")),
+            vec![
+                module("auth-core", "Auth Core"),
+                module("auth-api", "Auth API"),
+                module("billing", "Invoicing"),
+            ],
+            vec![],
+        );
+        let ids = |query| -> Vec<(String, f64)> {
+            map.find_module_fuzzy(query)
+                .into_iter()
+                .map(|m| (m.item.id.clone(), m.score))
+                .collect()
+        };
+
+        assert_eq!(ids("auth-core")[0], ("auth-core".to_string(), 1.0));
+        assert_eq!(ids("AuthCore")[0], ("auth-core".to_string(), 0.95));
+        assert_eq!(ids("INVOICING")[0].0, "billing");
+        let typo = ids("auth-cor");
+        assert_eq!(typo[0].0, "auth-core");
+        assert!(typo[0].1 < 0.95);
+        assert!(ids("payments").is_empty());
+
+        assert_eq!(levenshtein("kitten", "sitting"), 3);
+        assert_eq!(levenshtein("", "abc"), 3);
+    }
+}
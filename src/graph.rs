@@ -0,0 +1,391 @@
+//! Compact index-based dependency graph for repeated queries on large maps
+
+use std::collections::HashMap;
+use std::mem::size_of;
+
+use serde::{Deserialize, Serialize};
+
+use crate::module_map::{DependencyEdge, DependencyGraph, Module, ModuleMap};
+
+/// Dependency graph with interned module ids and adjacency in flat
+/// offset/target vectors, so traversals never hash or clone strings.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CompiledGraph {
+    ids: Vec<String>,
+    index: HashMap<String, u32>,
+    out_offsets: Vec<u32>,
+    out_targets: Vec<u32>,
+    in_offsets: Vec<u32>,
+    in_targets: Vec<u32>,
+}
+
+impl CompiledGraph {
+    /// Nodes are `nodes` followed by any edge endpoint not among them.
+    /// Duplicate edges are kept once.
+    pub fn new<'a>(nodes: impl IntoIterator<Item = &'a str>, edges: &[DependencyEdge]) -> Self {
+        let mut graph = Self::default();
+        for node in nodes {
+            graph.intern(node);
+        }
+        let mut pairs: Vec<(u32, u32)> = edges
+            .iter()
+            .map(|e| (graph.intern(&e.from), graph.intern(&e.to)))
+            .collect();
+        pairs.sort_unstable();
+        pairs.dedup();
+
+        let n = graph.ids.len();
+        (graph.out_offsets, graph.out_targets) = adjacency(n, pairs.iter().copied());
+        (graph.in_offsets, graph.in_targets) =
+            adjacency(n, pairs.iter().map(|&(from, to)| (to, from)));
+        graph
+    }
+
+    fn intern(&mut self, id: &str) -> u32 {
+        if let Some(&i) = self.index.get(id) {
+            return i;
+        }
+        let i = self.ids.len() as u32;
+        self.ids.push(id.to_string());
+        self.index.insert(id.to_string(), i);
+        i
+    }
+
+    pub fn len(&self) -> usize {
+        self.ids.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ids.is_empty()
+    }
+
+    pub fn edge_count(&self) -> usize {
+        self.out_targets.len()
+    }
+
+    pub fn node(&self, id: &str) -> Option<usize> {
+        self.index.get(id).map(|&i| i as usize)
+    }
+
+    pub fn id(&self, node: usize) -> &str {
+        &self.ids[node]
+    }
+
+    /// Nodes `node` depends on.
+    pub fn dependencies(&self, node: usize) -> impl Iterator<Item = usize> + '_ {
+        neighbors(&self.out_offsets, &self.out_targets, node)
+    }
+
+    /// Nodes depending on `node`.
+    pub fn dependents(&self, node: usize) -> impl Iterator<Item = usize> + '_ {
+        neighbors(&self.in_offsets, &self.in_targets, node)
+    }
+
+    /// Ids reachable from `seeds` through dependencies, seeds first. Unknown seeds are ignored.
+    pub fn transitive_dependencies(&self, seeds: &[&str]) -> Vec<&str> {
+        self.reach(seeds, &self.out_offsets, &self.out_targets)
+    }
+
+    /// Ids reachable from `seeds` through dependents, seeds first: the impact set.
+    pub fn transitive_dependents(&self, seeds: &[&str]) -> Vec<&str> {
+        self.reach(seeds, &self.in_offsets, &self.in_targets)
+    }
+
+    fn reach(&self, seeds: &[&str], offsets: &[u32], targets: &[u32]) -> Vec<&str> {
+        let mut visited = vec![false; self.ids.len()];
+        let mut order: Vec<usize> = Vec::new();
+        for node in seeds.iter().filter_map(|s| self.node(s)) {
+            if !visited[node] {
+                visited[node] = true;
+                order.push(node);
+            }
+        }
+        let mut next = 0;
+        while next < order.len() {
+            for target in neighbors(offsets, targets, order[next]) {
+                if !visited[target] {
+                    visited[target] = true;
+                    order.push(target);
+                }
+            }
+            next += 1;
+        }
+        order.into_iter().map(|i| self.id(i)).collect()
+    }
+
+    /// Strongly connected components with more than one node, plus nodes
+    /// depending on themselves. Ids within a cycle and the cycles themselves
+    /// are sorted.
+    pub fn cycles(&self) -> Vec<Vec<&str>> {
+        // Iterative Tarjan: `stack` holds (node, next neighbor position).
+        let n = self.ids.len();
+        let mut index = vec![u32::MAX; n];
+        let mut low = vec![0u32; n];
+        let mut on_stack = vec![false; n];
+        let mut component: Vec<usize> = Vec::new();
+        let mut cycles: Vec<Vec<&str>> = Vec::new();
+        let mut counter = 0u32;
+
+        for root in 0..n {
+            if index[root] != u32::MAX {
+                continue;
+            }
+            let mut stack = vec![(root, self.out_offsets[root] as usize)];
+            index[root] = counter;
+            low[root] = counter;
+            counter += 1;
+            component.push(root);
+            on_stack[root] = true;
+
+            while let Some(&mut (node, ref mut next)) = stack.last_mut() {
+                if *next < self.out_offsets[node + 1] as usize {
+                    let target = self.out_targets[*next] as usize;
+                    *next += 1;
+                    if index[target] == u32::MAX {
+                        index[target] = counter;
+                        low[target] = counter;
+                        counter += 1;
+                        component.push(target);
+                        on_stack[target] = true;
+                        stack.push((target, self.out_offsets[target] as usize));
+                    } else if on_stack[target] {
+                        low[node] = low[node].min(index[target]);
+                    }
+                    continue;
+                }
+                stack.pop();
+                if let Some(&(parent, _)) = stack.last() {
+                    low[parent] = low[parent].min(low[node]);
+                }
+                if low[node] == index[node] {
+                    let mut members = Vec::new();
+                    while let Some(member) = component.pop() {
+                        on_stack[member] = false;
+                        members.push(member);
+                        if member == node {
+                            break;
+                        }
+                    }
+                    if members.len() > 1 || self.dependencies(node).any(|t| t == node) {
+                        let mut ids: Vec<&str> = members.into_iter().map(|i| self.id(i)).collect();
+                        ids.sort_unstable();
+                        cycles.push(ids);
+                    }
+                }
+            }
+        }
+        cycles.sort_unstable();
+        cycles
+    }
+
+    /// Approximate heap footprint, for memory budgeting.
+    pub fn memory_bytes(&self) -> usize {
+        let strings: usize = self.ids.iter().map(|id| id.capacity() * 2).sum();
+        let index = self.index.capacity() * (size_of::<String>() + size_of::<u32>());
+        let vectors = self.ids.capacity() * size_of::<String>()
+            + (self.out_offsets.capacity()
+                + self.out_targets.capacity()
+                + self.in_offsets.capacity()
+                + self.in_targets.capacity())
+                * size_of::<u32>();
+        strings + index + vectors
+    }
+}
+
+/// Offsets/targets layout of `pairs`, which must be sorted by source for `out`.
+fn adjacency(n: usize, pairs: impl Iterator<Item = (u32, u32)> + Clone) -> (Vec<u32>, Vec<u32>) {
+    let mut offsets = vec![0u32; n + 1];
+    for (from, _) in pairs.clone() {
+        offsets[from as usize + 1] += 1;
+    }
+    for i in 0..n {
+        offsets[i + 1] += offsets[i];
+    }
+    let mut cursor = offsets.clone();
+    let mut targets = vec![0u32; offsets[n] as usize];
+    for (from, to) in pairs {
+        let slot = &mut cursor[from as usize];
+        targets[*slot as usize] = to;
+        *slot += 1;
+    }
+    (offsets, targets)
+}
+
+fn neighbors<'a>(
+    offsets: &'a [u32],
+    targets: &'a [u32],
+    node: usize,
+) -> impl Iterator<Item = usize> + 'a {
+    let range = offsets[node] as usize..offsets[node + 1] as usize;
+    targets[range].iter().map(|&t| t as usize)
+}
+
+impl DependencyGraph {
+    /// Compiles the explicit edges; nodes are their endpoints.
+    pub fn compile(&self) -> CompiledGraph {
+        CompiledGraph::new([], &self.edges)
+    }
+
+    /// Modules on a dependency cycle, one sorted list per strongly connected component.
+    pub fn find_cycles(&self) -> Vec<Vec<String>> {
+        owned_cycles(&self.compile())
+    }
+}
+
+fn owned_cycles(graph: &CompiledGraph) -> Vec<Vec<String>> {
+    graph
+        .cycles()
+        .into_iter()
+        .map(|ids| ids.into_iter().map(String::from).collect())
+        .collect()
+}
+
+impl ModuleMap {
+    /// Compiles `dependency_edges()` over every module, including isolated ones.
+    pub fn compile_graph(&self) -> CompiledGraph {
+        CompiledGraph::new(
+            self.modules.iter().map(|m| m.id.as_str()),
+            &self.dependency_edges(),
+        )
+    }
+
+    /// `DependencyGraph::find_cycles` over the explicit graph, or over
+    /// `Module::dependencies` when the map has none.
+    pub fn find_cycles(&self) -> Vec<Vec<String>> {
+        owned_cycles(&self.compile_graph())
+    }
+
+    /// Modules that directly or indirectly depend on `module_id`, nearest
+    /// first: what a change to it affects.
+    pub fn transitive_dependents(&self, module_id: &str) -> Vec<&Module> {
+        self.reached(module_id, CompiledGraph::transitive_dependents)
+    }
+
+    /// Modules `module_id` directly or indirectly depends on, nearest first:
+    /// what it is affected by.
+    pub fn transitive_dependencies(&self, module_id: &str) -> Vec<&Module> {
+        self.reached(module_id, CompiledGraph::transitive_dependencies)
+    }
+
+    fn reached(
+        &self,
+        module_id: &str,
+        walk: impl for<'c> Fn(&'c CompiledGraph, &[&str]) -> Vec<&'c str>,
+    ) -> Vec<&Module> {
+        let module_id = self.find_module(module_id).map_or(module_id, |m| &m.id);
+        let graph = self.compile_graph();
+        walk(&graph, &[module_id])
+            .into_iter()
+            .filter(|id| *id != module_id)
+            .filter_map(|id| self.find_module(id))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{DependencyType, ModuleDependency};
+    use crate::{GeneratorInfo, ProjectMetadata, TechStack};
+
+    fn edge(from: &str, to: &str) -> DependencyEdge {
+        DependencyEdge {
+            from: from.into(),
+            to: to.into(),
+            edge_type: DependencyType::default(),
+        }
+    }
+
+    fn module(id: &str, deps: &[&str]) -> Module {
+        Module::new(id, id, vec![format!("src/{id}/")])
+            .with_dependencies(deps.iter().map(|d| ModuleDependency::new(*d)).collect())
+            .with_primary_language("rust")
+    }
+
+    #[test]
+    fn test_compiled_graph_traversal() {
+        let graph = DependencyGraph {
+            edges: vec![
+                edge("api", "core"),
+                edge("core", "db"),
+                edge("worker", "core"),
+                edge("api", "core"),
+            ],
+            layers: vec![],
+        }
+        .compile();
+        assert_eq!(graph.len(), 4);
+        assert_eq!(graph.edge_count(), 3);
+
+        let core = graph.node("core").unwrap();
+        let mut dependents: Vec<_> = graph.dependents(core).map(|n| graph.id(n)).collect();
+        dependents.sort();
+        assert_eq!(dependents, vec!["api", "worker"]);
+
+        assert_eq!(
+            graph.transitive_dependents(&["db", "missing"]),
+            vec!["db", "core", "api", "worker"]
+        );
+        assert_eq!(
+            graph.transitive_dependencies(&["api"]),
+            vec!["api", "core", "db"]
+        );
+        assert!(graph.memory_bytes() > 0);
+    }
+
+    #[test]
+    fn test_find_cycles() {
+        let graph = DependencyGraph {
+            edges: vec![
+                edge("api", "core"),
+                edge("core", "db"),
+                edge("db", "api"),
+                edge("worker", "worker"),
+                edge("worker", "core"),
+                edge("cli", "api"),
+            ],
+            layers: vec![],
+        };
+        assert_eq!(
+            graph.find_cycles(),
+            vec![vec!["api", "core", "db"], vec!["worker"]]
+        );
+        assert!(
+            DependencyGraph {
+                edges: vec![edge("api", "core")],
+                layers: vec![],
+            }
+            .find_cycles()
+            .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_transitive_impact() {
+        let map = ModuleMap::new(
+            GeneratorInfo::new("test", "1.0.0"),
+            ProjectMetadata::new("shop", TechStack::new("rust")),
+            vec![
+                module("auth", &["db", "serde"]),
+                module("api", &["auth"]),
+                module("web", &["api"]),
+                module("billing", &["db"]),
+                module("db", &[]),
+            ],
+            vec![],
+        );
+        let ids = |modules: Vec<&Module>| -> Vec<String> {
+            modules.into_iter().map(|m| m.id.clone()).collect()
+        };
+        assert_eq!(ids(map.transitive_dependents("auth")), ["api", "web"]);
+        assert_eq!(
+            ids(map.transitive_dependents("db")),
+            ["auth", "billing", "api", "web"]
+        );
+        assert_eq!(
+            ids(map.transitive_dependencies("web")),
+            ["api", "auth", "db"]
+        );
+        assert!(map.transitive_dependents("missing").is_empty());
+    }
+}
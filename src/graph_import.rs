@@ -0,0 +1,359 @@
+//! Adapters for build-system project graphs (Nx, Turborepo, Bazel)
+
+use std::collections::BTreeMap;
+
+use serde_json::Value;
+
+use crate::module_map::{
+    DependencyEdge, DependencyGraph, Module, ModuleMap, ProjectMetadata, WorkspaceInfo,
+};
+use crate::normalize::canonical_id;
+use crate::registry::SchemaError;
+use crate::types::{GeneratorInfo, ModuleDependency, WorkspaceType};
+
+/// A package or project as reported by a build tool
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PackageNode {
+    pub name: String,
+    /// Directory relative to the workspace root, with a trailing `/`
+    pub root: String,
+    pub dependencies: Vec<ModuleDependency>,
+}
+
+/// Package-level dependency graph read from a monorepo build tool
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PackageGraph {
+    pub tool: String,
+    pub packages: Vec<PackageNode>,
+}
+
+/// `@acme/ui` → `acme-ui`, `services/auth` → `services-auth`
+fn package_id(name: &str) -> String {
+    canonical_id(&name.trim_start_matches('@').replace(['/', ':'], "-"))
+}
+
+fn dir(root: &str) -> String {
+    let root = root.trim_matches('/');
+    if root.is_empty() {
+        String::new()
+    } else {
+        format!("{root}/")
+    }
+}
+
+fn push_dependency(deps: &mut Vec<ModuleDependency>, dependency: ModuleDependency) {
+    if !deps.iter().any(|d| d.module_id == dependency.module_id) {
+        deps.push(dependency);
+    }
+}
+
+fn collect(tool: &str, mut packages: BTreeMap<String, PackageNode>) -> PackageGraph {
+    for package in packages.values_mut() {
+        package
+            .dependencies
+            .sort_by(|a, b| a.module_id.cmp(&b.module_id));
+    }
+    PackageGraph {
+        tool: tool.to_string(),
+        packages: packages.into_values().collect(),
+    }
+}
+
+impl PackageGraph {
+    /// Reads `nx graph --file=graph.json` output.
+    ///
+    /// Implicit dependencies become build dependencies; npm nodes are skipped.
+    pub fn from_nx(json: &str) -> Result<Self, SchemaError> {
+        let value: Value = serde_json::from_str(json)?;
+        let graph = value.get("graph").unwrap_or(&value);
+
+        let mut packages = BTreeMap::new();
+        for (name, node) in graph["nodes"].as_object().into_iter().flatten() {
+            let root = node["data"]["root"].as_str().unwrap_or_default();
+            packages.insert(
+                name.clone(),
+                PackageNode {
+                    name: name.clone(),
+                    root: dir(root),
+                    dependencies: Vec::new(),
+                },
+            );
+        }
+
+        for (source, deps) in graph["dependencies"].as_object().into_iter().flatten() {
+            for dep in deps.as_array().into_iter().flatten() {
+                let Some(target) = dep["target"].as_str() else {
+                    continue;
+                };
+                if !packages.contains_key(target) {
+                    continue;
+                }
+                let dependency = if dep["type"] == "implicit" {
+                    ModuleDependency::build(package_id(target))
+                } else {
+                    ModuleDependency::runtime(package_id(target))
+                };
+                if let Some(package) = packages.get_mut(source) {
+                    push_dependency(&mut package.dependencies, dependency);
+                }
+            }
+        }
+        Ok(collect("nx", packages))
+    }
+
+    /// Reads `turbo run <task> --dry=json` output, collapsing `pkg#task`
+    /// edges into package dependencies.
+    pub fn from_turbo(json: &str) -> Result<Self, SchemaError> {
+        let value: Value = serde_json::from_str(json)?;
+        let tasks = value["tasks"]
+            .as_array()
+            .map(Vec::as_slice)
+            .unwrap_or_default();
+
+        let mut packages = BTreeMap::new();
+        for task in tasks {
+            let Some(name) = task["package"].as_str() else {
+                continue;
+            };
+            packages
+                .entry(name.to_string())
+                .or_insert_with(|| PackageNode {
+                    name: name.to_string(),
+                    root: dir(task["directory"].as_str().unwrap_or_default()),
+                    dependencies: Vec::new(),
+                });
+        }
+
+        for task in tasks {
+            let Some(name) = task["package"].as_str() else {
+                continue;
+            };
+            for dep in task["dependencies"].as_array().into_iter().flatten() {
+                let Some((target, _)) = dep.as_str().and_then(|d| d.split_once('#')) else {
+                    continue;
+                };
+                if target == name || target == "//" || !packages.contains_key(target) {
+                    continue;
+                }
+                if let Some(package) = packages.get_mut(name) {
+                    push_dependency(
+                        &mut package.dependencies,
+                        ModuleDependency::runtime(package_id(target)),
+                    );
+                }
+            }
+        }
+        Ok(collect("turborepo", packages))
+    }
+
+    /// Reads `bazel query --output=graph` (DOT) output, grouping targets by
+    /// Bazel package. External `@repo//` labels are skipped.
+    pub fn from_bazel(dot: &str) -> Self {
+        fn package(label: &str) -> Option<&str> {
+            let label = label.trim().trim_matches('"');
+            let label = label.strip_prefix("//")?;
+            Some(label.split(':').next().unwrap_or(label))
+        }
+
+        let mut packages = BTreeMap::new();
+        let node = |packages: &mut BTreeMap<String, PackageNode>, name: &str| {
+            packages
+                .entry(name.to_string())
+                .or_insert_with(|| PackageNode {
+                    name: name.to_string(),
+                    root: dir(name),
+                    dependencies: Vec::new(),
+                });
+        };
+
+        for line in dot.lines() {
+            let line = line.trim().trim_end_matches(';');
+            if let Some((from, to)) = line.split_once("->") {
+                // Collapsed nodes list several labels separated by `\n`
+                let targets: Vec<&str> = to
+                    .trim()
+                    .trim_matches('"')
+                    .split("\\n")
+                    .filter_map(package)
+                    .collect();
+                for source in from
+                    .trim()
+                    .trim_matches('"')
+                    .split("\\n")
+                    .filter_map(package)
+                {
+                    node(&mut packages, source);
+                    for &target in &targets {
+                        node(&mut packages, target);
+                        if target != source
+                            && let Some(package) = packages.get_mut(source)
+                        {
+                            push_dependency(
+                                &mut package.dependencies,
+                                ModuleDependency::runtime(package_id(target)),
+                            );
+                        }
+                    }
+                }
+            } else if line.starts_with('"') {
+                let label = line.split('[').next().unwrap_or(line);
+                for name in label
+                    .trim()
+                    .trim_matches('"')
+                    .split("\\n")
+                    .filter_map(package)
+                {
+                    node(&mut packages, name);
+                }
+            }
+        }
+        collect("bazel", packages)
+    }
+
+    /// Converts packages into modules with matching dependency edges and
+    /// `dependents`; languages and responsibilities are left for a later pass.
+    pub fn into_module_map(self, project: ProjectMetadata) -> ModuleMap {
+        let mut dependents: BTreeMap<String, Vec<String>> = BTreeMap::new();
+        let mut edges = Vec::new();
+        for package in &self.packages {
+            let from = package_id(&package.name);
+            for dep in &package.dependencies {
+                dependents
+                    .entry(dep.module_id.clone())
+                    .or_default()
+                    .push(from.clone());
+                edges.push(DependencyEdge {
+                    from: from.clone(),
+                    to: dep.module_id.clone(),
+                    edge_type: dep.dependency_type.clone(),
+                });
+            }
+        }
+
+        let modules = self
+            .packages
+            .into_iter()
+            .map(|package| {
+                let id = package_id(&package.name);
+                let dependents = dependents.remove(&id).unwrap_or_default();
+                Module::new(id, package.name, vec![package.root])
+                    .with_dependents(dependents)
+                    .with_dependencies(package.dependencies)
+                    .with_primary_language(project.tech_stack.primary_language.clone())
+            })
+            .collect();
+
+        let project = ProjectMetadata {
+            workspace: WorkspaceInfo {
+                workspace_type: WorkspaceType::Monorepo,
+                ..project.workspace
+            },
+            ..project
+        };
+        ModuleMap::new(
+            GeneratorInfo::new(format!("modmap-{}", self.tool), env!("CARGO_PKG_VERSION")),
+            project,
+            modules,
+            Vec::new(),
+        )
+        .with_dependency_graph(DependencyGraph {
+            edges,
+            layers: Vec::new(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TechStack;
+
+    const NX: &str = r#"{
+        "graph": {
+            "nodes": {
+                "web": { "name": "web", "type": "app", "data": { "root": "apps/web" } },
+                "ui": { "name": "ui", "type": "lib", "data": { "root": "libs/ui" } },
+                "config": { "name": "config", "type": "lib", "data": { "root": "libs/config" } }
+            },
+            "dependencies": {
+                "web": [
+                    { "source": "web", "target": "ui", "type": "static" },
+                    { "source": "web", "target": "config", "type": "implicit" },
+                    { "source": "web", "target": "npm:react", "type": "static" }
+                ],
+                "ui": [],
+                "config": []
+            }
+        }
+    }"#;
+
+    #[test]
+    fn test_from_nx() {
+        let graph = PackageGraph::from_nx(NX).unwrap();
+        let web = graph.packages.iter().find(|p| p.name == "web").unwrap();
+        assert_eq!(web.root, "apps/web/");
+        assert_eq!(web.dependencies.len(), 2);
+        assert_eq!(web.dependencies[0], ModuleDependency::build("config"));
+        assert_eq!(web.dependencies[1], ModuleDependency::runtime("ui"));
+
+        let map = graph.into_module_map(ProjectMetadata::new("shop", TechStack::new("typescript")));
+        assert_eq!(map.generator.name, "modmap-nx");
+        assert_eq!(
+            map.project.workspace.workspace_type,
+            WorkspaceType::Monorepo
+        );
+        assert_eq!(map.find_module("ui").unwrap().dependents, vec!["web"]);
+        assert_eq!(map.dependency_edges().len(), 2);
+        assert!(
+            map.find_module("web")
+                .unwrap()
+                .contains_file("apps/web/src/main.ts")
+        );
+    }
+
+    #[test]
+    fn test_from_turbo() {
+        let json = r#"{
+            "tasks": [
+                { "taskId": "web#build", "package": "web", "directory": "apps/web",
+                  "dependencies": ["@acme/ui#build", "web#lint"] },
+                { "taskId": "web#lint", "package": "web", "directory": "apps/web", "dependencies": [] },
+                { "taskId": "@acme/ui#build", "package": "@acme/ui", "directory": "packages/ui",
+                  "dependencies": ["//#codegen"] }
+            ]
+        }"#;
+        let graph = PackageGraph::from_turbo(json).unwrap();
+        let names: Vec<_> = graph.packages.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, vec!["@acme/ui", "web"]);
+        assert_eq!(
+            graph.packages[1].dependencies,
+            vec![ModuleDependency::runtime("acme-ui")]
+        );
+        assert!(graph.packages[0].dependencies.is_empty());
+    }
+
+    #[test]
+    fn test_from_bazel() {
+        let dot = r#"digraph mygraph {
+  node [shape=box];
+  "//services/api:server"
+  "//services/api:server" -> "//libs/auth:auth\n//libs/auth:tokens"
+  "//services/api:server" -> "//services/api:handlers"
+  "//libs/auth:auth" -> "@maven//:guava"
+}"#;
+        let graph = PackageGraph::from_bazel(dot);
+        let names: Vec<_> = graph.packages.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, vec!["libs/auth", "services/api"]);
+        assert_eq!(graph.packages[1].root, "services/api/");
+        assert_eq!(
+            graph.packages[1].dependencies,
+            vec![ModuleDependency::runtime("libs-auth")]
+        );
+
+        let map = graph.into_module_map(ProjectMetadata::new("mono", TechStack::new("java")));
+        assert_eq!(
+            map.find_module("libs-auth").unwrap().dependents,
+            vec!["services-api"]
+        );
+    }
+}
@@ -0,0 +1,410 @@
+//! GraphQL schema over a manifest (feature: graphql)
+//!
+//! Nodes hold the shared manifest plus an id, so nested
+//! domain → group → module → dependency queries resolve in one request.
+
+use std::sync::Arc;
+
+use async_graphql::{EmptyMutation, EmptySubscription, Object, Schema};
+
+use crate::context::ContextResolver;
+use crate::manifest::ProjectManifest;
+use crate::module_map::{Domain, Module, ModuleGroup, ModuleMap};
+use crate::types::KnownIssue;
+
+pub type ModmapSchema = Schema<QueryRoot, EmptyMutation, EmptySubscription>;
+
+pub fn schema(manifest: Arc<ProjectManifest>) -> ModmapSchema {
+    Schema::build(QueryRoot(manifest), EmptyMutation, EmptySubscription).finish()
+}
+
+pub struct QueryRoot(Arc<ProjectManifest>);
+
+pub struct DomainNode {
+    manifest: Arc<ProjectManifest>,
+    id: String,
+}
+
+pub struct GroupNode {
+    manifest: Arc<ProjectManifest>,
+    id: String,
+}
+
+pub struct ModuleNode {
+    manifest: Arc<ProjectManifest>,
+    id: String,
+}
+
+pub struct IssueNode {
+    severity: String,
+    issue: KnownIssue,
+}
+
+fn domain_node(manifest: &Arc<ProjectManifest>, domain: &Domain) -> DomainNode {
+    DomainNode {
+        manifest: Arc::clone(manifest),
+        id: domain.id.clone(),
+    }
+}
+
+fn group_node(manifest: &Arc<ProjectManifest>, group: &ModuleGroup) -> GroupNode {
+    GroupNode {
+        manifest: Arc::clone(manifest),
+        id: group.id.clone(),
+    }
+}
+
+fn module_nodes<'a>(
+    manifest: &Arc<ProjectManifest>,
+    modules: impl IntoIterator<Item = &'a Module>,
+) -> Vec<ModuleNode> {
+    modules
+        .into_iter()
+        .map(|m| ModuleNode {
+            manifest: Arc::clone(manifest),
+            id: m.id.clone(),
+        })
+        .collect()
+}
+
+#[Object]
+impl QueryRoot {
+    async fn project(&self) -> &str {
+        &self.0.project.project.name
+    }
+
+    async fn domains(&self) -> Vec<DomainNode> {
+        self.0
+            .project
+            .domains
+            .iter()
+            .map(|d| domain_node(&self.0, d))
+            .collect()
+    }
+
+    async fn domain(&self, id: String) -> Option<DomainNode> {
+        self.0
+            .project
+            .find_domain(&id)
+            .map(|d| domain_node(&self.0, d))
+    }
+
+    async fn groups(&self) -> Vec<GroupNode> {
+        self.0
+            .project
+            .groups
+            .iter()
+            .map(|g| group_node(&self.0, g))
+            .collect()
+    }
+
+    async fn group(&self, id: String) -> Option<GroupNode> {
+        self.0
+            .project
+            .find_group(&id)
+            .map(|g| group_node(&self.0, g))
+    }
+
+    async fn modules(&self) -> Vec<ModuleNode> {
+        module_nodes(&self.0, &self.0.project.modules)
+    }
+
+    async fn module(&self, id: String) -> Option<ModuleNode> {
+        self.0
+            .project
+            .find_module(&id)
+            .and_then(|m| module_nodes(&self.0, [m]).pop())
+    }
+
+    /// Modules whose paths contain `path`
+    async fn lookup(&self, path: String) -> Vec<ModuleNode> {
+        module_nodes(
+            &self.0,
+            self.0
+                .project
+                .modules
+                .iter()
+                .filter(|m| m.contains_file(&path)),
+        )
+    }
+
+    /// Modules owning `paths` and everything depending on them
+    async fn impact(&self, paths: Vec<String>) -> Vec<ModuleNode> {
+        let paths: Vec<&str> = paths.iter().map(String::as_str).collect();
+        module_nodes(&self.0, self.0.project.impacted_modules(&paths))
+    }
+}
+
+impl DomainNode {
+    fn get(&self) -> &Domain {
+        self.manifest
+            .project
+            .find_domain(&self.id)
+            .expect("node ids come from the map")
+    }
+}
+
+#[Object(name = "Domain")]
+impl DomainNode {
+    async fn id(&self) -> &str {
+        &self.id
+    }
+
+    async fn name(&self) -> &str {
+        &self.get().name
+    }
+
+    async fn responsibility(&self) -> &str {
+        &self.get().responsibility
+    }
+
+    async fn owner(&self) -> Option<&str> {
+        self.get().owner.as_deref()
+    }
+
+    async fn groups(&self) -> Vec<GroupNode> {
+        self.manifest
+            .project
+            .find_groups_in_domain(&self.id)
+            .into_iter()
+            .map(|g| group_node(&self.manifest, g))
+            .collect()
+    }
+}
+
+impl GroupNode {
+    fn get(&self) -> &ModuleGroup {
+        self.manifest
+            .project
+            .find_group(&self.id)
+            .expect("node ids come from the map")
+    }
+}
+
+#[Object(name = "Group")]
+impl GroupNode {
+    async fn id(&self) -> &str {
+        &self.id
+    }
+
+    async fn name(&self) -> &str {
+        &self.get().name
+    }
+
+    async fn responsibility(&self) -> &str {
+        &self.get().responsibility
+    }
+
+    async fn domain(&self) -> Option<DomainNode> {
+        let map = &self.manifest.project;
+        let domain = match &self.get().domain_id {
+            Some(id) => map.find_domain(id),
+            None => map.find_domain_containing_group(&self.id),
+        };
+        domain.map(|d| domain_node(&self.manifest, d))
+    }
+
+    async fn modules(&self) -> Vec<ModuleNode> {
+        module_nodes(
+            &self.manifest,
+            self.manifest.project.find_modules_in_group(&self.id),
+        )
+    }
+}
+
+impl ModuleNode {
+    fn map(&self) -> &ModuleMap {
+        &self.manifest.project
+    }
+
+    fn get(&self) -> &Module {
+        self.map()
+            .find_module(&self.id)
+            .expect("node ids come from the map")
+    }
+}
+
+#[Object(name = "Module")]
+impl ModuleNode {
+    async fn id(&self) -> &str {
+        &self.id
+    }
+
+    async fn name(&self) -> &str {
+        &self.get().name
+    }
+
+    async fn responsibility(&self) -> &str {
+        &self.get().responsibility
+    }
+
+    async fn paths(&self) -> &[String] {
+        &self.get().paths
+    }
+
+    async fn primary_language(&self) -> &str {
+        &self.get().primary_language
+    }
+
+    async fn coverage_ratio(&self) -> f64 {
+        self.get().metrics.coverage_ratio.get()
+    }
+
+    async fn value_score(&self) -> f64 {
+        self.get().metrics.value_score.get()
+    }
+
+    async fn risk_score(&self) -> f64 {
+        self.get().metrics.risk_score.get()
+    }
+
+    async fn group(&self) -> Option<GroupNode> {
+        self.map()
+            .find_group_containing(&self.id)
+            .map(|g| group_node(&self.manifest, g))
+    }
+
+    async fn domain(&self) -> Option<DomainNode> {
+        self.map()
+            .find_domain_containing_module(&self.id)
+            .map(|d| domain_node(&self.manifest, d))
+    }
+
+    /// Modules this module depends on (unknown ids are skipped)
+    async fn dependencies(&self) -> Vec<ModuleNode> {
+        let map = self.map();
+        module_nodes(
+            &self.manifest,
+            self.get()
+                .dependencies
+                .iter()
+                .filter_map(|d| map.find_module(&d.module_id)),
+        )
+    }
+
+    async fn dependents(&self) -> Vec<ModuleNode> {
+        let map = self.map();
+        module_nodes(
+            &self.manifest,
+            self.get()
+                .dependents
+                .iter()
+                .filter_map(|id| map.find_module(id)),
+        )
+    }
+
+    /// Issues with the domain severity policy applied
+    async fn issues(&self) -> Vec<IssueNode> {
+        let module = self.get();
+        module
+            .known_issues
+            .iter()
+            .map(|issue| IssueNode {
+                severity: self.map().effective_severity(issue, module).to_string(),
+                issue: issue.clone(),
+            })
+            .collect()
+    }
+
+    /// Rule names injected for this module's first path
+    async fn rules(&self) -> Vec<String> {
+        self.get()
+            .paths
+            .first()
+            .map(|p| ContextResolver::new(&self.manifest).resolve(p).rules)
+            .unwrap_or_default()
+    }
+}
+
+#[Object(name = "Issue")]
+impl IssueNode {
+    async fn id(&self) -> &str {
+        &self.issue.id
+    }
+
+    async fn description(&self) -> &str {
+        &self.issue.description
+    }
+
+    async fn severity(&self) -> &str {
+        &self.severity
+    }
+
+    async fn category(&self) -> String {
+        self.issue.category.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        GeneratorInfo, IssueCategory, IssueSeverity, ModuleDependency, ProjectMetadata, TechStack,
+    };
+
+    fn module(id: &str, dependencies: &[&str]) -> Module {
+        Module::new(id, id, vec![format!("src/{id}/")])
+            .with_dependencies(
+                dependencies
+                    .iter()
+                    .map(|d| ModuleDependency::runtime(*d))
+                    .collect(),
+            )
+            .with_primary_language("rust")
+    }
+
+    fn sample_schema() -> ModmapSchema {
+        let mut api = module("api", &["auth"]);
+        api.known_issues = vec![KnownIssue::new(
+            "no-rate-limit",
+            "Endpoints are not rate limited",
+            IssueSeverity::High,
+            IssueCategory::Security,
+        )];
+        let map = ModuleMap::new(
+            GeneratorInfo::new("test", "1.0.0"),
+            ProjectMetadata::new("shop", TechStack::new("rust")),
+            vec![module("auth", &[]), api],
+            vec![ModuleGroup::new(
+                "core",
+                "Core",
+                vec!["auth".into(), "api".into()],
+            )],
+        )
+        .with_domains(vec![Domain::new(
+            "identity",
+            "Identity",
+            vec!["core".into()],
+        )]);
+        schema(Arc::new(ProjectManifest::new(map)))
+    }
+
+    #[tokio::test]
+    async fn test_nested_query() {
+        let response = sample_schema()
+            .execute(
+                "{ domains { id groups { id modules { id dependencies { id } issues { severity category } } } } }",
+            )
+            .await;
+        assert!(response.errors.is_empty(), "{:?}", response.errors);
+
+        let data = response.data.into_json().unwrap();
+        let modules = &data["domains"][0]["groups"][0]["modules"];
+        assert_eq!(modules[0]["id"], "auth");
+        assert_eq!(modules[1]["dependencies"][0]["id"], "auth");
+        assert_eq!(modules[1]["issues"][0]["severity"], "HIGH");
+    }
+
+    #[tokio::test]
+    async fn test_lookup_and_impact() {
+        let response = sample_schema()
+            .execute(
+                r#"{ lookup(path: "src/api/main.rs") { id domain { id } } impact(paths: ["src/auth/x.rs"]) { id } }"#,
+            )
+            .await;
+        let data = response.data.into_json().unwrap();
+        assert_eq!(data["lookup"][0]["id"], "api");
+        assert_eq!(data["lookup"][0]["domain"]["id"], "identity");
+        assert_eq!(data["impact"].as_array().unwrap().len(), 2);
+    }
+}
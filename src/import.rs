@@ -0,0 +1,323 @@
+//! Importers for hand-written CLAUDE.md and Cursor rule files
+
+use std::fs;
+use std::path::Path;
+
+use crate::manifest::ProjectManifest;
+use crate::normalize::canonical_id;
+use crate::registry::SchemaError;
+use crate::rule::{Rule, RuleCategory};
+
+const LANGUAGES: &[&str] = &[
+    "rust",
+    "typescript",
+    "javascript",
+    "python",
+    "go",
+    "java",
+    "kotlin",
+    "ruby",
+    "swift",
+    "c++",
+    "c#",
+    "php",
+    "scala",
+    "sql",
+];
+
+const FRAMEWORKS: &[&str] = &[
+    "react", "next.js", "nextjs", "vue", "angular", "svelte", "django", "flask", "fastapi",
+    "rails", "spring", "express", "nestjs", "axum", "actix", "tokio", "gin",
+];
+
+/// Guesses a category from a section heading and its path globs.
+///
+/// Extension-only globs (`**/*.rs`) mean tech rules, other globs mean module
+/// rules; without globs, language and framework names in the heading decide.
+pub fn guess_category(heading: &str, paths: &[String]) -> RuleCategory {
+    if !paths.is_empty() {
+        let by_extension = paths.iter().all(|p| {
+            let name = p.rsplit('/').next().unwrap_or(p);
+            p.split('/').all(|seg| seg == "**" || seg == name) && name.starts_with("*.")
+        });
+        return if by_extension {
+            RuleCategory::Tech
+        } else {
+            RuleCategory::Module
+        };
+    }
+
+    let heading = heading.to_lowercase();
+    let words: Vec<&str> = heading
+        .split(|c: char| c.is_whitespace() || matches!(c, ',' | '/' | '(' | ')' | ':'))
+        .collect();
+    if FRAMEWORKS.iter().any(|f| words.contains(f)) {
+        RuleCategory::Framework
+    } else if LANGUAGES.iter().any(|l| words.contains(l)) {
+        RuleCategory::Tech
+    } else if words.contains(&"domain") {
+        RuleCategory::Domain
+    } else {
+        RuleCategory::Project
+    }
+}
+
+fn make_rule(name: &str, category: RuleCategory, paths: Vec<String>, lines: &[&str]) -> Rule {
+    let start = lines
+        .iter()
+        .position(|l| !l.trim().is_empty())
+        .unwrap_or(lines.len());
+    let end = lines
+        .iter()
+        .rposition(|l| !l.trim().is_empty())
+        .map_or(start, |i| i + 1);
+    let content = lines[start..end].iter().map(|l| l.to_string()).collect();
+
+    let mut rule = Rule::new(canonical_id(name), content).with_category(category.clone());
+    rule.always_inject = category == RuleCategory::Project;
+    if !paths.is_empty() {
+        rule = rule.with_paths(paths);
+    }
+    rule
+}
+
+/// Splits a CLAUDE.md (or legacy `.cursorrules`) file into one rule per `##` section.
+///
+/// Text before the first section becomes a project rule named after the `#`
+/// title, or `project` when there is none.
+pub fn parse_claude_md(text: &str) -> Vec<Rule> {
+    let mut title = "project".to_string();
+    let mut sections: Vec<(String, Vec<&str>)> = vec![(String::new(), Vec::new())];
+    let mut in_code = false;
+    for line in text.lines() {
+        if line.trim_start().starts_with("```") {
+            in_code = !in_code;
+        }
+        if !in_code {
+            if let Some(heading) = line.strip_prefix("## ") {
+                sections.push((heading.trim().to_string(), Vec::new()));
+                continue;
+            }
+            if let Some(heading) = line.strip_prefix("# ")
+                && sections.len() == 1
+            {
+                title = heading.trim().to_string();
+                continue;
+            }
+        }
+        if let Some((_, lines)) = sections.last_mut() {
+            lines.push(line);
+        }
+    }
+
+    sections
+        .into_iter()
+        .filter(|(_, lines)| lines.iter().any(|l| !l.trim().is_empty()))
+        .map(|(heading, lines)| {
+            if heading.is_empty() {
+                make_rule(&title, RuleCategory::Project, Vec::new(), &lines)
+            } else {
+                make_rule(&heading, guess_category(&heading, &[]), Vec::new(), &lines)
+            }
+        })
+        .collect()
+}
+
+/// Parses one `.cursor/rules/*.mdc` file with `description`, `globs` and
+/// `alwaysApply` frontmatter.
+pub fn parse_cursor_rule(name: &str, text: &str) -> Rule {
+    let mut description = String::new();
+    let mut globs = Vec::new();
+    let mut always_apply = false;
+    let mut body: Vec<&str> = text.lines().collect();
+
+    if body.first().is_some_and(|l| l.trim() == "---")
+        && let Some(end) = body.iter().skip(1).position(|l| l.trim() == "---")
+    {
+        for line in &body[1..=end] {
+            let Some((key, value)) = line.split_once(':') else {
+                continue;
+            };
+            let value = value.trim();
+            match key.trim() {
+                "description" => description = value.trim_matches('"').to_string(),
+                "globs" => {
+                    globs = value
+                        .trim_matches(['[', ']'])
+                        .split(',')
+                        .map(|g| g.trim().trim_matches(['"', '\'']).to_string())
+                        .filter(|g| !g.is_empty())
+                        .collect();
+                }
+                "alwaysApply" => always_apply = value == "true",
+                _ => {}
+            }
+        }
+        body.drain(..end + 2);
+    }
+
+    let category = if always_apply {
+        RuleCategory::Project
+    } else {
+        guess_category(&format!("{name} {description}"), &globs)
+    };
+    make_rule(name, category, globs, &body)
+}
+
+/// Reads every `.mdc`/`.md` file in a `.cursor/rules` directory, sorted by name.
+pub fn import_cursor_rules(dir: &Path) -> Result<Vec<Rule>, SchemaError> {
+    let mut files: Vec<_> = fs::read_dir(dir)?
+        .filter_map(|e| e.ok().map(|e| e.path()))
+        .filter(|p| p.extension().is_some_and(|e| e == "mdc" || e == "md"))
+        .collect();
+    files.sort();
+
+    files
+        .iter()
+        .map(|path| {
+            let name = path.file_stem().unwrap_or_default().to_string_lossy();
+            Ok(parse_cursor_rule(&name, &fs::read_to_string(path)?))
+        })
+        .collect()
+}
+
+/// Literal directory prefix of a glob (`src/auth/**/*.rs` → `src/auth/`).
+fn glob_prefix(glob: &str) -> &str {
+    let literal = glob.find(['*', '?', '[']).map_or(glob, |i| &glob[..i]);
+    literal.rfind('/').map_or("", |i| &literal[..=i])
+}
+
+impl ProjectManifest {
+    /// Lists imported rules by their output path and adds path-scoped rules
+    /// to the contexts of modules under their globs.
+    pub fn attach_rules(&mut self, rules: &[Rule]) {
+        for rule in rules {
+            let path = format!("rules/{}", rule.output_path());
+            if !self.rules.contains(&path) {
+                self.rules.push(path.clone());
+            }
+            if rule.category != RuleCategory::Module {
+                continue;
+            }
+            for module in &self.project.modules {
+                let covered = rule.paths.iter().any(|glob| {
+                    let prefix = glob_prefix(glob);
+                    !prefix.is_empty()
+                        && module
+                            .paths
+                            .iter()
+                            .any(|p| p.starts_with(prefix) || prefix.starts_with(p.as_str()))
+                });
+                if covered {
+                    let ctx = self.modules.entry(module.id.clone()).or_default();
+                    if !ctx.rules.contains(&path) {
+                        ctx.rules.push(path.clone());
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{GeneratorInfo, Module, ModuleMap, ProjectMetadata, TechStack};
+
+    const CLAUDE_MD: &str = "\
+# Acme Shop
+
+Always run `cargo fmt` before committing.
+
+## Rust Style
+
+- Prefer `?` over `unwrap`
+
+## Testing with React
+
+```bash
+## not a heading
+npm test
+```
+
+## Payments Domain
+
+Amounts are stored in minor units.
+";
+
+    #[test]
+    fn test_parse_claude_md() {
+        let rules = parse_claude_md(CLAUDE_MD);
+        let names: Vec<_> = rules.iter().map(|r| r.name.as_str()).collect();
+        assert_eq!(
+            names,
+            vec![
+                "acme-shop",
+                "rust-style",
+                "testing-with-react",
+                "payments-domain"
+            ]
+        );
+
+        assert_eq!(rules[0].category, RuleCategory::Project);
+        assert!(rules[0].always_inject);
+        assert_eq!(
+            rules[0].content,
+            vec!["Always run `cargo fmt` before committing."]
+        );
+        assert_eq!(rules[1].category, RuleCategory::Tech);
+        assert_eq!(rules[2].category, RuleCategory::Framework);
+        assert!(rules[2].content.contains(&"## not a heading".to_string()));
+        assert_eq!(rules[3].category, RuleCategory::Domain);
+    }
+
+    #[test]
+    fn test_parse_cursor_rule() {
+        let rule = parse_cursor_rule(
+            "auth",
+            "---\ndescription: Auth service rules\nglobs: src/auth/**/*.ts, src/session/*.ts\nalwaysApply: false\n---\n\nValidate tokens.\n",
+        );
+        assert_eq!(rule.category, RuleCategory::Module);
+        assert_eq!(rule.paths, vec!["src/auth/**/*.ts", "src/session/*.ts"]);
+        assert_eq!(rule.content, vec!["Validate tokens."]);
+
+        let rule = parse_cursor_rule("ts", "---\nglobs: [\"**/*.ts\"]\n---\nUse strict mode.");
+        assert_eq!(rule.category, RuleCategory::Tech);
+
+        let rule = parse_cursor_rule("general", "---\nalwaysApply: true\n---\nBe concise.");
+        assert_eq!(rule.category, RuleCategory::Project);
+        assert!(rule.always_inject);
+    }
+
+    #[test]
+    fn test_attach_rules() {
+        let module = Module::new("auth", "auth", vec!["src/auth/".into()])
+            .with_primary_language("typescript");
+        let mut manifest = ProjectManifest::new(ModuleMap::new(
+            GeneratorInfo::new("test", "1.0.0"),
+            ProjectMetadata::new("test", TechStack::new("typescript")),
+            vec![module],
+            vec![],
+        ));
+        let rules = vec![
+            parse_cursor_rule(
+                "auth",
+                "---\nglobs: src/auth/**/*.ts\n---\nValidate tokens.",
+            ),
+            parse_cursor_rule("general", "---\nalwaysApply: true\n---\nBe concise."),
+        ];
+
+        manifest.attach_rules(&rules);
+        manifest.attach_rules(&rules);
+
+        assert_eq!(
+            manifest.rules,
+            vec!["rules/modules/auth.md", "rules/general.md"]
+        );
+        assert_eq!(
+            manifest.modules["auth"].rules,
+            vec!["rules/modules/auth.md"]
+        );
+        assert!(manifest.validate_contexts(None).is_empty());
+    }
+}
@@ -0,0 +1,286 @@
+//! Hash and trie indexes over a map for constant-time lookups on large monorepos
+
+use std::collections::HashMap;
+use std::ops::Deref;
+
+use crate::module_map::{Domain, Module, ModuleGroup, ModuleMap, known_as};
+
+/// Byte trie of module path prefixes; a node lists the modules whose path ends there
+#[derive(Debug, Clone, Default)]
+struct PathTrie {
+    /// Child node indexes keyed by byte, sorted
+    children: Vec<Vec<(u8, u32)>>,
+    /// Module indexes per node, in map order
+    modules: Vec<Vec<u32>>,
+}
+
+impl PathTrie {
+    fn new() -> Self {
+        Self {
+            children: vec![Vec::new()],
+            modules: vec![Vec::new()],
+        }
+    }
+
+    fn insert(&mut self, prefix: &str, module: u32) {
+        let mut node = 0;
+        for byte in prefix.bytes() {
+            node = match self.children[node].binary_search_by_key(&byte, |(b, _)| *b) {
+                Ok(i) => self.children[node][i].1 as usize,
+                Err(i) => {
+                    let child = self.children.len();
+                    self.children.push(Vec::new());
+                    self.modules.push(Vec::new());
+                    self.children[node].insert(i, (byte, child as u32));
+                    child
+                }
+            };
+        }
+        if !self.modules[node].contains(&module) {
+            self.modules[node].push(module);
+        }
+    }
+
+    /// Modules with a prefix of `path`, longest prefix first, each once.
+    fn matches(&self, path: &str) -> Vec<u32> {
+        let mut found: Vec<&[u32]> = vec![&self.modules[0]];
+        let mut node = 0;
+        for byte in path.bytes() {
+            match self.children[node].binary_search_by_key(&byte, |(b, _)| *b) {
+                Ok(i) => node = self.children[node][i].1 as usize,
+                Err(_) => break,
+            }
+            found.push(&self.modules[node]);
+        }
+        let mut ordered: Vec<u32> = Vec::new();
+        for module in found.into_iter().rev().flatten() {
+            if !ordered.contains(module) {
+                ordered.push(*module);
+            }
+        }
+        ordered
+    }
+}
+
+/// A `ModuleMap` with id and path indexes, built once.
+///
+/// Lookups agree with the linear `ModuleMap` finders, including which entry
+/// wins on duplicate ids. Everything else is reachable through `Deref`.
+#[derive(Debug, Clone)]
+pub struct IndexedModuleMap<'a> {
+    map: &'a ModuleMap,
+    module_index: HashMap<&'a str, usize>,
+    group_index: HashMap<&'a str, usize>,
+    domain_index: HashMap<&'a str, usize>,
+    /// Module id to its first containing group
+    group_of_module: HashMap<&'a str, usize>,
+    /// Group id to its first containing domain
+    domain_of_group: HashMap<&'a str, usize>,
+    paths: PathTrie,
+}
+
+/// Id to the first item with it, then aliases not taken by an id, as `ModuleMap` resolves them.
+fn id_index<'a, T>(
+    items: &'a [T],
+    ids: impl Fn(&'a T) -> (&'a str, &'a [String]),
+) -> HashMap<&'a str, usize> {
+    let mut index = HashMap::new();
+    for (i, item) in items.iter().enumerate() {
+        index.entry(ids(item).0).or_insert(i);
+    }
+    for (i, item) in items.iter().enumerate() {
+        for alias in ids(item).1 {
+            index.entry(alias.as_str()).or_insert(i);
+        }
+    }
+    index
+}
+
+/// First container listing any of `names`.
+fn first_container(containers: &HashMap<&str, usize>, names: &[&str]) -> Option<usize> {
+    names
+        .iter()
+        .filter_map(|n| containers.get(n).copied())
+        .min()
+}
+
+impl<'a> IndexedModuleMap<'a> {
+    pub fn new(map: &'a ModuleMap) -> Self {
+        let mut group_of_module = HashMap::new();
+        for (i, group) in map.groups.iter().enumerate() {
+            for id in &group.module_ids {
+                group_of_module.entry(id.as_str()).or_insert(i);
+            }
+        }
+        let mut domain_of_group = HashMap::new();
+        for (i, domain) in map.domains.iter().enumerate() {
+            for id in &domain.group_ids {
+                domain_of_group.entry(id.as_str()).or_insert(i);
+            }
+        }
+        let mut paths = PathTrie::new();
+        for (i, module) in map.modules.iter().enumerate() {
+            for prefix in &module.paths {
+                paths.insert(prefix, i as u32);
+            }
+        }
+        Self {
+            map,
+            module_index: id_index(&map.modules, |m| (&m.id, &m.aliases)),
+            group_index: id_index(&map.groups, |g| (&g.id, &g.aliases)),
+            domain_index: id_index(&map.domains, |d| (&d.id, &d.aliases)),
+            group_of_module,
+            domain_of_group,
+            paths,
+        }
+    }
+
+    pub fn map(&self) -> &'a ModuleMap {
+        self.map
+    }
+
+    pub fn find_module(&self, module_id: &str) -> Option<&'a Module> {
+        self.module_index
+            .get(module_id)
+            .map(|&i| &self.map.modules[i])
+    }
+
+    pub fn find_group(&self, group_id: &str) -> Option<&'a ModuleGroup> {
+        self.group_index.get(group_id).map(|&i| &self.map.groups[i])
+    }
+
+    pub fn find_domain(&self, domain_id: &str) -> Option<&'a Domain> {
+        self.domain_index
+            .get(domain_id)
+            .map(|&i| &self.map.domains[i])
+    }
+
+    pub fn find_group_containing(&self, module_id: &str) -> Option<&'a ModuleGroup> {
+        let names = match self.find_module(module_id) {
+            Some(module) => known_as(&module.id, &module.aliases),
+            None => vec![module_id],
+        };
+        first_container(&self.group_of_module, &names).map(|i| &self.map.groups[i])
+    }
+
+    pub fn find_domain_containing_group(&self, group_id: &str) -> Option<&'a Domain> {
+        let names = match self.find_group(group_id) {
+            Some(group) => known_as(&group.id, &group.aliases),
+            None => vec![group_id],
+        };
+        first_container(&self.domain_of_group, &names).map(|i| &self.map.domains[i])
+    }
+
+    /// Domain of the module's group, via `ModuleGroup.domain_id` or domain membership.
+    pub fn find_domain_containing_module(&self, module_id: &str) -> Option<&'a Domain> {
+        let group = self.find_group_containing(module_id)?;
+        match &group.domain_id {
+            Some(domain_id) => self.find_domain(domain_id),
+            None => self.find_domain_containing_group(&group.id),
+        }
+    }
+
+    /// Same as `ModuleMap::modules_for_file`, in time linear in the path length.
+    pub fn modules_for_file(&self, path: &str) -> Vec<&'a Module> {
+        self.paths
+            .matches(path)
+            .into_iter()
+            .map(|i| &self.map.modules[i as usize])
+            .collect()
+    }
+
+    pub fn find_module_for_file(&self, path: &str) -> Option<&'a Module> {
+        self.modules_for_file(path).into_iter().next()
+    }
+}
+
+impl Deref for IndexedModuleMap<'_> {
+    type Target = ModuleMap;
+
+    fn deref(&self) -> &ModuleMap {
+        self.map
+    }
+}
+
+impl ModuleMap {
+    pub fn indexed(&self) -> IndexedModuleMap<'_> {
+        IndexedModuleMap::new(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{GeneratorInfo, ProjectMetadata, TechStack};
+
+    fn module(id: &str, paths: &[&str]) -> Module {
+        Module::new(id, id, paths.iter().map(|p| p.to_string()).collect())
+            .with_primary_language("rust")
+    }
+
+    #[test]
+    fn test_indexed_lookups_match_linear() {
+        let map = ModuleMap::new(
+            GeneratorInfo::new("test", "1.0.0"),
+            ProjectMetadata::new("shop", TechStack::new("rust")),
+            vec![
+                module("legacy", &["src/"]),
+                module("api", &["src/api/", "proto/api/"]),
+                module("api-admin", &["src/api/admin/"]),
+                Module {
+                    aliases: vec!["gateway".into(), "legacy".into()],
+                    ..module("apix", &["src/api"])
+                },
+            ],
+            vec![
+                ModuleGroup::new("edge", "Edge", vec!["api".into(), "api-admin".into()]),
+                ModuleGroup::new("core", "Core", vec!["legacy".into()]).with_domain("platform"),
+            ],
+        )
+        .with_domains(vec![
+            Domain::new("platform", "Platform", vec![]),
+            Domain::new("public", "Public", vec!["edge".into()]),
+        ]);
+        let indexed = map.indexed();
+
+        for id in ["legacy", "api", "api-admin", "apix", "gateway", "missing"] {
+            assert_eq!(
+                indexed.find_module(id).map(|m| &m.id),
+                map.find_module(id).map(|m| &m.id)
+            );
+            assert_eq!(
+                indexed.find_group_containing(id).map(|g| &g.id),
+                map.find_group_containing(id).map(|g| &g.id)
+            );
+            assert_eq!(
+                indexed.find_domain_containing_module(id).map(|d| &d.id),
+                map.find_domain_containing_module(id).map(|d| &d.id)
+            );
+        }
+        for path in [
+            "src/api/admin/users.rs",
+            "src/apiv2.rs",
+            "proto/api/x",
+            "docs/",
+        ] {
+            let ids = |modules: Vec<&Module>| -> Vec<String> {
+                modules.into_iter().map(|m| m.id.clone()).collect()
+            };
+            assert_eq!(
+                ids(indexed.modules_for_file(path)),
+                ids(map.modules_for_file(path)),
+                "{path}"
+            );
+        }
+        assert_eq!(
+            indexed
+                .find_module_for_file("src/api/admin/users.rs")
+                .unwrap()
+                .id,
+            "api-admin"
+        );
+        assert_eq!(indexed.find_group("core").unwrap().name, "Core");
+        assert_eq!(indexed.modules.len(), 4, "derefs to the map");
+        assert_eq!(indexed.map().groups.len(), 2);
+    }
+}
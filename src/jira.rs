@@ -0,0 +1,275 @@
+//! Tasks and refactor plans exported as CSV for Jira bulk import
+
+use std::fmt::Write;
+
+use serde::Serialize;
+
+use crate::manifest::ProjectManifest;
+use crate::refactor::RefactorPlan;
+use crate::render::csv_field;
+use crate::task::{Task, TaskStatus};
+
+/// One row of a Jira CSV import; `parent_id` refers to another row's `issue_id`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct JiraIssue {
+    pub issue_id: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parent_id: Option<usize>,
+    /// `Epic`, `Task`, `Story` or `Sub-task`
+    pub issue_type: &'static str,
+    pub summary: String,
+    pub description: String,
+    /// Module ids, imported as Jira components
+    pub components: Vec<String>,
+    pub labels: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<&'static str>,
+    /// Domain name on epic rows
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub epic_name: Option<String>,
+}
+
+/// Epics per domain, then tasks and refactor plans (with one sub-task per step)
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct JiraExport {
+    pub issues: Vec<JiraIssue>,
+}
+
+fn jira_status(status: TaskStatus) -> &'static str {
+    match status {
+        TaskStatus::Todo => "To Do",
+        TaskStatus::InProgress => "In Progress",
+        TaskStatus::Blocked => "Blocked",
+        TaskStatus::Done => "Done",
+    }
+}
+
+impl JiraExport {
+    fn push(&mut self, issue_type: &'static str, summary: impl Into<String>) -> &mut JiraIssue {
+        let issue_id = self.issues.len() + 1;
+        self.issues.push(JiraIssue {
+            issue_id,
+            parent_id: None,
+            issue_type,
+            summary: summary.into(),
+            description: String::new(),
+            components: Vec::new(),
+            labels: Vec::new(),
+            status: None,
+            epic_name: None,
+        });
+        self.issues.last_mut().expect("just pushed")
+    }
+
+    /// CSV for Jira's external system import. Multi-valued fields repeat
+    /// their column, as the importer expects.
+    pub fn to_csv(&self) -> String {
+        let width = |f: fn(&JiraIssue) -> usize| self.issues.iter().map(f).max().unwrap_or(0);
+        let component_columns = width(|i| i.components.len());
+        let label_columns = width(|i| i.labels.len());
+
+        let mut header = vec![
+            "Issue Id",
+            "Parent Id",
+            "Issue Type",
+            "Summary",
+            "Description",
+            "Status",
+            "Epic Name",
+        ];
+        header.extend(std::iter::repeat_n("Component/s", component_columns));
+        header.extend(std::iter::repeat_n("Labels", label_columns));
+        let mut out = header.join(",");
+        out.push('\n');
+
+        for issue in &self.issues {
+            let mut fields = vec![
+                issue.issue_id.to_string(),
+                issue.parent_id.map(|p| p.to_string()).unwrap_or_default(),
+                issue.issue_type.to_string(),
+                csv_field(&issue.summary),
+                csv_field(&issue.description),
+                issue.status.unwrap_or_default().to_string(),
+                csv_field(issue.epic_name.as_deref().unwrap_or_default()),
+            ];
+            for i in 0..component_columns {
+                fields.push(csv_field(
+                    issue.components.get(i).map_or("", String::as_str),
+                ));
+            }
+            for i in 0..label_columns {
+                fields.push(csv_field(issue.labels.get(i).map_or("", String::as_str)));
+            }
+            let _ = writeln!(out, "{}", fields.join(","));
+        }
+        out
+    }
+}
+
+impl ProjectManifest {
+    /// Domain of the first item that names one directly or through a module.
+    fn epic_domain<'a>(&'a self, domain_ids: &'a [String], module_ids: &[&str]) -> Option<&'a str> {
+        domain_ids.first().map(String::as_str).or_else(|| {
+            module_ids.iter().find_map(|id| {
+                self.project
+                    .find_domain_containing_module(id)
+                    .map(|d| d.id.as_str())
+            })
+        })
+    }
+
+    fn task_description(&self, task: &Task) -> String {
+        let mut description = String::new();
+        let issues = self.task_issues(task);
+        if !issues.is_empty() {
+            description.push_str("Known issues:\n");
+            for issue in issues {
+                let _ = writeln!(description, "- {issue}");
+            }
+        }
+        if let Some(skill) = &task.suggested_skill {
+            let _ = writeln!(description, "Suggested skill: {skill}");
+        }
+        if let Some(agent) = &task.suggested_agent {
+            let _ = writeln!(description, "Suggested agent: {agent}");
+        }
+        description
+    }
+
+    fn plan_description(plan: &RefactorPlan) -> String {
+        let mut description = format!("Estimated risk: {:.2}\n", plan.estimated_risk);
+        if !plan.checklist.is_empty() {
+            description.push_str("\nChecklist:\n");
+            for item in &plan.checklist {
+                let _ = writeln!(description, "- {item}");
+            }
+        }
+        description
+    }
+
+    /// Tasks and refactor plans as Jira issues: modules become components and
+    /// domains become epics that parent the work touching them.
+    pub fn jira_export(&self) -> JiraExport {
+        let mut export = JiraExport::default();
+        let mut epics: Vec<(&str, usize)> = Vec::new();
+        let mut epic_for = |export: &mut JiraExport, domain_id: Option<&str>| {
+            let domain = self.project.find_domain(domain_id?)?;
+            if let Some((_, id)) = epics.iter().find(|(d, _)| *d == domain.id) {
+                return Some(*id);
+            }
+            let epic = export.push("Epic", &domain.name);
+            epic.description = domain.responsibility.clone();
+            epic.epic_name = Some(domain.name.clone());
+            epic.labels.push(format!("domain:{}", domain.id));
+            epics.push((&domain.id, epic.issue_id));
+            Some(epic.issue_id)
+        };
+
+        for task in &self.tasks {
+            let module_ids: Vec<&str> = task.module_ids.iter().map(String::as_str).collect();
+            let parent_id = epic_for(&mut export, self.epic_domain(&task.domain_ids, &module_ids));
+            let issue = export.push("Task", &task.title);
+            issue.parent_id = parent_id;
+            issue.description = self.task_description(task);
+            issue.components = task.module_ids.clone();
+            issue.labels = vec!["modmap".to_string(), format!("modmap-task:{}", task.id)];
+            issue.status = Some(jira_status(task.status));
+        }
+
+        for plan in &self.refactor_plans {
+            let step_modules: Vec<&str> = plan.steps.iter().flat_map(|s| s.modules()).collect();
+            let parent_id = epic_for(&mut export, self.epic_domain(&[], &step_modules));
+            let story = export.push("Story", &plan.title);
+            story.parent_id = parent_id;
+            story.description = Self::plan_description(plan);
+            story.components = plan.affected_modules.clone();
+            story.labels = vec!["refactor".to_string(), format!("modmap-plan:{}", plan.id)];
+            let story_id = story.issue_id;
+
+            for step in &plan.steps {
+                let sub_task = export.push("Sub-task", step.to_string().replace('`', ""));
+                sub_task.parent_id = Some(story_id);
+                sub_task.components = step
+                    .modules()
+                    .into_iter()
+                    .filter(|id| self.project.find_module(id).is_some())
+                    .map(String::from)
+                    .collect();
+            }
+        }
+        export
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        Domain, GeneratorInfo, Module, ModuleGroup, ModuleMap, ProjectMetadata, Refactor, TechStack,
+    };
+
+    fn module(id: &str) -> Module {
+        Module::new(id, id, vec![format!("src/{id}/")]).with_primary_language("rust")
+    }
+
+    #[test]
+    fn test_jira_export() {
+        let map = ModuleMap::new(
+            GeneratorInfo::new("test", "1.0.0"),
+            ProjectMetadata::new("shop", TechStack::new("rust")),
+            vec![module("auth"), module("session"), module("billing")],
+            vec![ModuleGroup::new(
+                "identity-core",
+                "Identity",
+                vec!["auth".into(), "session".into()],
+            )],
+        )
+        .with_domains(vec![Domain::new(
+            "identity",
+            "Identity, Access",
+            vec!["identity-core".into()],
+        )]);
+        let plan = RefactorPlan::build(
+            &map,
+            "p1",
+            "Fold sessions into auth",
+            vec![Refactor::Merge {
+                a: "auth".into(),
+                b: "session".into(),
+            }],
+        )
+        .unwrap();
+        let manifest = ProjectManifest::new(map)
+            .with_tasks(vec![
+                Task::new("t1", "Rotate keys").with_modules(vec!["auth".into()]),
+                Task::new("t2", "Invoice export")
+                    .with_modules(vec!["billing".into()])
+                    .with_status(TaskStatus::InProgress),
+            ])
+            .with_refactor_plans(vec![plan]);
+
+        let export = manifest.jira_export();
+        let types: Vec<&str> = export.issues.iter().map(|i| i.issue_type).collect();
+        assert_eq!(types, ["Epic", "Task", "Task", "Story", "Sub-task"]);
+        assert_eq!(
+            export.issues[0].epic_name.as_deref(),
+            Some("Identity, Access")
+        );
+        assert_eq!(export.issues[1].parent_id, Some(1));
+        assert_eq!(export.issues[2].parent_id, None);
+        assert_eq!(export.issues[3].parent_id, Some(1));
+        assert_eq!(export.issues[4].parent_id, Some(4));
+        assert_eq!(export.issues[4].components, ["auth", "session"]);
+
+        let csv = export.to_csv();
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(
+            lines[0],
+            "Issue Id,Parent Id,Issue Type,Summary,Description,Status,Epic Name,Component/s,Component/s,Labels,Labels"
+        );
+        assert_eq!(
+            lines[3],
+            "3,,Task,Invoice export,,In Progress,,billing,,modmap,modmap-task:t2"
+        );
+    }
+}
@@ -0,0 +1,182 @@
+//! camelCase serialization profile for JavaScript consumers
+
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use serde_json::{Map, Value};
+
+use crate::manifest::ProjectManifest;
+use crate::module_map::ModuleMap;
+
+/// Object key convention for emitted JSON
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum KeyStyle {
+    /// Field names as declared (the on-disk format)
+    #[default]
+    SnakeCase,
+    CamelCase,
+}
+
+/// Fields whose object keys are ids or language tags, not field names.
+fn is_data_map(field: &str) -> bool {
+    matches!(field, "modules" | "groups" | "domains") || field.starts_with("localized_")
+}
+
+fn is_identifier(key: &str) -> bool {
+    key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+/// `schema_version` -> `schemaVersion`.
+pub fn to_camel_case(key: &str) -> String {
+    let mut out = String::with_capacity(key.len());
+    let mut upper = false;
+    for c in key.chars() {
+        if c == '_' && !out.is_empty() {
+            upper = true;
+        } else if upper {
+            out.push(c.to_ascii_uppercase());
+            upper = false;
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
+/// `schemaVersion` -> `schema_version`.
+pub fn to_snake_case(key: &str) -> String {
+    let mut out = String::with_capacity(key.len() + 4);
+    for c in key.chars() {
+        if c.is_ascii_uppercase() {
+            if !out.is_empty() {
+                out.push('_');
+            }
+            out.push(c.to_ascii_lowercase());
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
+/// Rewrites field-name keys to `style`, leaving keys of id-keyed maps and
+/// non-identifier keys (paths, kebab-case) untouched.
+pub fn restyle_keys(value: &mut Value, style: KeyStyle) {
+    match value {
+        Value::Object(object) => {
+            let fields = std::mem::take(object);
+            *object = fields
+                .into_iter()
+                .map(|(key, mut child)| {
+                    let field = to_snake_case(&key);
+                    match &mut child {
+                        Value::Object(entries) if is_data_map(&field) => {
+                            entries.values_mut().for_each(|v| restyle_keys(v, style));
+                        }
+                        _ => restyle_keys(&mut child, style),
+                    }
+                    let key = match style {
+                        _ if !is_identifier(&key) => key,
+                        KeyStyle::SnakeCase => field,
+                        KeyStyle::CamelCase => to_camel_case(&key),
+                    };
+                    (key, child)
+                })
+                .collect::<Map<_, _>>();
+        }
+        Value::Array(items) => items.iter_mut().for_each(|v| restyle_keys(v, style)),
+        _ => {}
+    }
+}
+
+/// Pretty JSON with keys in `style`.
+pub fn to_json_with<T: Serialize>(value: &T, style: KeyStyle) -> Result<String, serde_json::Error> {
+    let mut value = serde_json::to_value(value)?;
+    if style != KeyStyle::SnakeCase {
+        restyle_keys(&mut value, style);
+    }
+    serde_json::to_string_pretty(&value)
+}
+
+/// Parses JSON written in either key style.
+pub fn from_json_any<T: DeserializeOwned>(json: &str) -> Result<T, serde_json::Error> {
+    let mut value: Value = serde_json::from_str(json)?;
+    restyle_keys(&mut value, KeyStyle::SnakeCase);
+    serde_json::from_value(value)
+}
+
+impl ProjectManifest {
+    pub fn to_json_with(&self, style: KeyStyle) -> Result<String, serde_json::Error> {
+        to_json_with(self, style)
+    }
+}
+
+impl ModuleMap {
+    pub fn to_json_with(&self, style: KeyStyle) -> Result<String, serde_json::Error> {
+        to_json_with(self, style)
+    }
+
+    /// Parses a module map written in either key style.
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        from_json_any(json)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        GeneratorInfo, Module, ModuleContext, ModuleDependency, ProjectMetadata, TechStack,
+    };
+
+    fn sample_manifest() -> ProjectManifest {
+        let mut module = Module::new("user_store", "Users", vec!["src/users/".into()])
+            .with_dependencies(vec![ModuleDependency::build("core")])
+            .with_responsibility("Users")
+            .with_primary_language("rust");
+        module
+            .localized_responsibility
+            .insert("zh_Hans", "用户".to_string());
+        let map = ModuleMap::new(
+            GeneratorInfo::new("test", "1.0.0"),
+            ProjectMetadata::new("shop", TechStack::new("rust")),
+            vec![module],
+            vec![],
+        );
+        let mut manifest = ProjectManifest::new(map);
+        manifest
+            .modules
+            .insert("user_store".into(), ModuleContext::default());
+        manifest
+    }
+
+    #[test]
+    fn test_key_case_conversion() {
+        assert_eq!(to_camel_case("schema_version"), "schemaVersion");
+        assert_eq!(to_camel_case("id"), "id");
+        assert_eq!(
+            to_snake_case("localizedResponsibility"),
+            "localized_responsibility"
+        );
+        assert_eq!(
+            to_snake_case(&to_camel_case("coverage_ratio")),
+            "coverage_ratio"
+        );
+    }
+
+    #[test]
+    fn test_camel_case_round_trip() {
+        let manifest = sample_manifest();
+        let camel = manifest.to_json_with(KeyStyle::CamelCase).unwrap();
+        assert!(camel.contains(r#""schemaVersion""#));
+        assert!(camel.contains(r#""dependencyType": "build""#));
+        assert!(camel.contains(r#""localizedResponsibility": {"#));
+        assert!(camel.contains(r#""zh_Hans""#));
+        assert!(camel.contains(r#""user_store": {"#));
+        assert!(!camel.contains("created_at"));
+
+        let parsed = ProjectManifest::from_json(&camel).unwrap();
+        assert_eq!(parsed.to_json().unwrap(), manifest.to_json().unwrap());
+        let snake = ProjectManifest::from_json(&manifest.to_json().unwrap()).unwrap();
+        assert_eq!(snake.to_json().unwrap(), manifest.to_json().unwrap());
+    }
+}
@@ -1,15 +1,139 @@
 mod agent;
+mod annotate;
+mod bootstrap;
+mod boundaries;
+mod cache;
+mod commit_scope;
+mod compat;
+mod context;
+#[cfg(feature = "contracts")]
+mod contracts;
+mod diff;
+mod edges;
+mod embeddings;
+mod entity;
+mod explain;
+pub mod export;
+mod features;
+mod fitness;
+pub mod fixtures;
+mod fuzzy;
+mod graph;
+mod graph_import;
+#[cfg(feature = "graphql")]
+pub mod graphql;
+mod import;
+mod indexed;
+mod jira;
+mod key_style;
 mod manifest;
+mod merge;
 mod module_map;
+mod namespace;
+mod naming;
+mod normalize;
+mod oncall;
+mod ownership;
+mod paths;
+mod permissions;
+#[cfg(feature = "petgraph")]
+mod petgraph_interop;
+mod pointer;
+mod policy;
+mod profile;
+mod query;
+mod refactor;
+mod regenerate;
 mod registry;
+mod release_notes;
+mod render;
+mod risk;
 mod rule;
+mod scaffold;
+pub mod schema;
+#[cfg(feature = "search")]
+mod search;
+#[cfg(feature = "server")]
+pub mod server;
 mod skill;
+mod slice;
+mod staleness;
+mod store;
+mod summary;
+mod task;
+mod taxonomy;
+mod terraform;
+mod timestamp;
+mod tracker;
 mod types;
+mod validation;
+mod view;
+mod visibility;
+mod waiver;
+mod webhook;
+mod work_queue;
 
 pub use agent::*;
+pub use annotate::*;
+pub use bootstrap::*;
+pub use boundaries::*;
+pub use cache::*;
+pub use commit_scope::*;
+pub use compat::*;
+pub use context::*;
+#[cfg(feature = "contracts")]
+pub use contracts::*;
+pub use diff::*;
+pub use edges::*;
+pub use embeddings::*;
+pub use entity::*;
+pub use explain::*;
+pub use features::*;
+pub use fitness::*;
+pub use fuzzy::*;
+pub use graph::*;
+pub use graph_import::*;
+pub use import::*;
+pub use indexed::*;
+pub use jira::*;
+pub use key_style::*;
 pub use manifest::*;
+pub use merge::*;
 pub use module_map::*;
+pub use namespace::*;
+pub use naming::*;
+pub use normalize::*;
+pub use oncall::*;
+pub use ownership::*;
+pub use paths::*;
+pub use permissions::*;
+#[cfg(feature = "petgraph")]
+pub use petgraph_interop::*;
+pub use policy::*;
+pub use profile::*;
+pub use query::*;
+pub use refactor::*;
+pub use regenerate::*;
 pub use registry::*;
+pub use release_notes::*;
+pub use risk::*;
 pub use rule::*;
+pub use scaffold::*;
+#[cfg(feature = "search")]
+pub use search::*;
 pub use skill::*;
+pub use staleness::*;
+pub use store::*;
+pub use summary::*;
+pub use task::*;
+pub use taxonomy::*;
+pub use terraform::*;
+pub use timestamp::*;
+pub use tracker::*;
 pub use types::*;
+pub use validation::*;
+pub use view::*;
+pub use visibility::*;
+pub use waiver::*;
+pub use webhook::*;
+pub use work_queue::*;
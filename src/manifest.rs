@@ -1,23 +1,47 @@
 use std::collections::HashMap;
+use std::fs::{self, File, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
 
 use chrono::{DateTime, Utc};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-use crate::ModuleMap;
+use crate::module_map::known_as;
+use crate::{
+    Convention, GenerationProfile, KnownIssue, ModuleMap, PolicyConfig, RefactorPlan, Rule,
+    RuleCategory, SchemaError, Task, UnknownVariant, Waiver,
+};
+
+/// How `ProjectManifest::sync_contexts_from_map` treats existing contexts
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ContextSyncMode {
+    /// Rebuild every context from the map, dropping manual additions
+    Replace,
+    /// Refresh map-derived fields, keeping manually added rules and skills
+    #[default]
+    Merge,
+}
 
+/// Rules, skills and facts injected for files of one module
 #[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
 pub struct ModuleContext {
+    /// Rule names
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub rules: Vec<String>,
+    /// Skill names
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub skills: Vec<String>,
+    /// `"name: pattern"` entries
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub conventions: Vec<String>,
+    /// `"[SEVERITY] id: description"` entries
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub issues: Vec<String>,
+    /// Group the module belongs to
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub group_id: Option<String>,
+    /// Domain the module belongs to
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub domain_id: Option<String>,
 }
@@ -67,14 +91,203 @@ impl ModuleContext {
     }
 }
 
+/// Module context referencing map entities by id instead of display strings.
+///
+/// Conventions and issues are resolved through the `ModuleMap` on demand, so
+/// they cannot drift from the typed data. `to_legacy` renders the string form
+/// stored in `ProjectManifest::modules`.
+///
+/// Until the next schema major version the legacy `conventions` and `issues`
+/// strings are written alongside the ids, and a legacy `ModuleContext` reads
+/// back with its ids recovered from them. The strings are kept private; the
+/// ids are the source of truth.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(from = "ModuleContextRefsRepr")]
+pub struct ModuleContextRefs {
+    #[serde(default)]
+    pub module_id: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub rules: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub skills: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub convention_names: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub issue_ids: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub group_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub domain_id: Option<String>,
+    /// Legacy `"name: pattern"` strings, written for older readers
+    #[serde(default, rename = "conventions", skip_serializing_if = "Vec::is_empty")]
+    legacy_conventions: Vec<String>,
+    /// Legacy `"[SEVERITY] id: description"` strings, written for older readers
+    #[serde(default, rename = "issues", skip_serializing_if = "Vec::is_empty")]
+    legacy_issues: Vec<String>,
+}
+
+/// Wire form of `ModuleContextRefs`, which may carry only the legacy strings
+#[derive(Deserialize, JsonSchema)]
+#[schemars(rename = "ModuleContextRefs")]
+struct ModuleContextRefsRepr {
+    #[serde(default)]
+    module_id: String,
+    #[serde(default)]
+    rules: Vec<String>,
+    #[serde(default)]
+    skills: Vec<String>,
+    #[serde(default)]
+    convention_names: Vec<String>,
+    #[serde(default)]
+    issue_ids: Vec<String>,
+    #[serde(default)]
+    group_id: Option<String>,
+    #[serde(default)]
+    domain_id: Option<String>,
+    #[serde(default)]
+    conventions: Vec<String>,
+    #[serde(default)]
+    issues: Vec<String>,
+}
+
+impl From<ModuleContextRefsRepr> for ModuleContextRefs {
+    fn from(repr: ModuleContextRefsRepr) -> Self {
+        let convention_names = if repr.convention_names.is_empty() {
+            repr.conventions
+                .iter()
+                .map(|c| legacy_key(c).to_string())
+                .collect()
+        } else {
+            repr.convention_names
+        };
+        let issue_ids = if repr.issue_ids.is_empty() {
+            repr.issues
+                .iter()
+                .map(|i| issue_key(i).to_string())
+                .collect()
+        } else {
+            repr.issue_ids
+        };
+        Self {
+            module_id: repr.module_id,
+            rules: repr.rules,
+            skills: repr.skills,
+            convention_names,
+            issue_ids,
+            group_id: repr.group_id,
+            domain_id: repr.domain_id,
+            legacy_conventions: repr.conventions,
+            legacy_issues: repr.issues,
+        }
+    }
+}
+
+impl ModuleContextRefs {
+    pub fn new(module_id: impl Into<String>) -> Self {
+        Self {
+            module_id: module_id.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Recovers ids from legacy `"name: pattern"` / `"[SEVERITY] id: description"` strings.
+    pub fn from_legacy(module_id: impl Into<String>, ctx: &ModuleContext) -> Self {
+        Self {
+            module_id: module_id.into(),
+            rules: ctx.rules.clone(),
+            skills: ctx.skills.clone(),
+            convention_names: ctx
+                .conventions
+                .iter()
+                .map(|c| legacy_key(c).to_string())
+                .collect(),
+            issue_ids: ctx
+                .issues
+                .iter()
+                .map(|i| issue_key(i).to_string())
+                .collect(),
+            group_id: ctx.group_id.clone(),
+            domain_id: ctx.domain_id.clone(),
+            legacy_conventions: ctx.conventions.clone(),
+            legacy_issues: ctx.issues.clone(),
+        }
+    }
+
+    /// Re-renders the legacy strings from `map`, e.g. after changing the ids.
+    pub fn with_legacy_strings(mut self, map: &ModuleMap) -> Self {
+        let legacy = self.to_legacy(map);
+        self.legacy_conventions = legacy.conventions;
+        self.legacy_issues = legacy.issues;
+        self
+    }
+
+    pub fn conventions<'a>(&self, map: &'a ModuleMap) -> Vec<&'a Convention> {
+        map.find_module(&self.module_id)
+            .map(|m| {
+                self.convention_names
+                    .iter()
+                    .filter_map(|name| m.conventions.iter().find(|c| &c.name == name))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    pub fn issues<'a>(&self, map: &'a ModuleMap) -> Vec<&'a KnownIssue> {
+        map.find_module(&self.module_id)
+            .map(|m| {
+                self.issue_ids
+                    .iter()
+                    .filter_map(|id| m.known_issues.iter().find(|i| &i.id == id))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Renders the string-based context; references missing from the map are dropped.
+    pub fn to_legacy(&self, map: &ModuleMap) -> ModuleContext {
+        ModuleContext {
+            rules: self.rules.clone(),
+            skills: self.skills.clone(),
+            conventions: self
+                .conventions(map)
+                .iter()
+                .map(|c| c.to_string())
+                .collect(),
+            issues: self.issues(map).iter().map(|i| i.to_string()).collect(),
+            group_id: self.group_id.clone(),
+            domain_id: self.domain_id.clone(),
+        }
+    }
+}
+
+fn legacy_key(s: &str) -> &str {
+    s.split_once(": ").map_or(s, |(key, _)| key).trim()
+}
+
+fn strip_severity_tag(s: &str) -> &str {
+    s.strip_prefix('[')
+        .and_then(|rest| rest.split_once("] "))
+        .map_or(s, |(_, rest)| rest)
+}
+
+/// Issue id of a legacy `"[SEVERITY] id: description"` context entry.
+pub(crate) fn issue_key(s: &str) -> &str {
+    legacy_key(strip_severity_tag(s))
+}
+
+/// Rules and constraints shared by a group's modules
 #[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
 pub struct GroupContext {
+    /// Rule names
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub rules: Vec<String>,
+    /// Boundary constraints, in prose
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub constraints: Vec<String>,
+    /// Member module ids
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub member_modules: Vec<String>,
+    /// Domain the group belongs to
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub domain_id: Option<String>,
 }
@@ -112,14 +325,19 @@ impl GroupContext {
     }
 }
 
+/// Rules and constraints shared by a domain's groups
 #[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
 pub struct DomainContext {
+    /// Rule names
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub rules: Vec<String>,
+    /// Boundary constraints, in prose
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub constraints: Vec<String>,
+    /// Member group ids
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub member_groups: Vec<String>,
+    /// Exposed interface names
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub interfaces: Vec<String>,
 }
@@ -161,6 +379,12 @@ impl DomainContext {
 pub struct TrackedFile {
     pub path: String,
     pub hash: String,
+    /// Epoch seconds; RFC 3339, date-only and millis are accepted on input
+    #[serde(
+        alias = "modifiedAt",
+        alias = "modified_at",
+        deserialize_with = "crate::timestamp::deserialize_epoch_seconds"
+    )]
     pub modified: i64,
 }
 
@@ -174,26 +398,62 @@ impl TrackedFile {
     }
 }
 
+/// Everything generated for a project: the map plus injected artifacts
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct ProjectManifest {
+    /// Manifest format version
+    #[schemars(example = &"1.0.0")]
     pub version: String,
+    /// When the manifest was written (RFC 3339)
+    #[serde(
+        alias = "createdAt",
+        deserialize_with = "crate::timestamp::deserialize_timestamp"
+    )]
     pub created_at: DateTime<Utc>,
+    /// Tool that wrote the manifest
     pub generator: String,
+    /// Generation settings used
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub profile: Option<GenerationProfile>,
+    /// The module map
     pub project: ModuleMap,
+    /// Names of generated rules
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub rules: Vec<String>,
+    /// Names of generated skills
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub skills: Vec<String>,
+    /// Names of generated agents
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub agents: Vec<String>,
+    /// Context per module id
     #[serde(default, skip_serializing_if = "HashMap::is_empty")]
     pub modules: HashMap<String, ModuleContext>,
+    /// Context per group id
     #[serde(default, skip_serializing_if = "HashMap::is_empty")]
     pub groups: HashMap<String, GroupContext>,
+    /// Context per domain id
     #[serde(default, skip_serializing_if = "HashMap::is_empty")]
     pub domains: HashMap<String, DomainContext>,
+    /// Source files and hashes used for staleness checks
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub tracked: Vec<TrackedFile>,
+    /// Planned structural changes
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub refactor_plans: Vec<RefactorPlan>,
+    /// Work items tied to modules and domains
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tasks: Vec<Task>,
+    /// Approved exceptions to boundary and fitness checks
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub waivers: Vec<Waiver>,
+    /// Check policy loaded from `modmap.policy.{json,toml}`; not serialized
+    #[serde(skip)]
+    pub policy: Option<PolicyConfig>,
+    /// Inline module/rule vectors as a serialized `EmbeddingStore`. Kept as
+    /// raw JSON so builds without `inline-embeddings` still round-trip them.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub embeddings: Option<serde_json::Value>,
 }
 
 impl ProjectManifest {
@@ -202,6 +462,7 @@ impl ProjectManifest {
             version: "1.0.0".to_string(),
             created_at: Utc::now(),
             generator: "claudegen".to_string(),
+            profile: None,
             project,
             rules: Vec::new(),
             skills: Vec::new(),
@@ -210,6 +471,11 @@ impl ProjectManifest {
             groups: HashMap::new(),
             domains: HashMap::new(),
             tracked: Vec::new(),
+            refactor_plans: Vec::new(),
+            tasks: Vec::new(),
+            waivers: Vec::new(),
+            policy: None,
+            embeddings: None,
         }
     }
 
@@ -218,6 +484,11 @@ impl ProjectManifest {
         self
     }
 
+    pub fn with_profile(mut self, profile: GenerationProfile) -> Self {
+        self.profile = Some(profile);
+        self
+    }
+
     pub fn with_rules(mut self, rules: Vec<String>) -> Self {
         self.rules = rules;
         self
@@ -253,25 +524,192 @@ impl ProjectManifest {
         self
     }
 
+    pub fn with_refactor_plans(mut self, plans: Vec<RefactorPlan>) -> Self {
+        self.refactor_plans = plans;
+        self
+    }
+
+    pub fn with_tasks(mut self, tasks: Vec<Task>) -> Self {
+        self.tasks = tasks;
+        self
+    }
+
+    pub fn with_waivers(mut self, waivers: Vec<Waiver>) -> Self {
+        self.waivers = waivers;
+        self
+    }
+
+    pub fn with_policy(mut self, policy: PolicyConfig) -> Self {
+        self.policy = Some(policy);
+        self
+    }
+
+    #[cfg(feature = "inline-embeddings")]
+    pub fn with_embeddings(mut self, embeddings: crate::EmbeddingStore) -> Self {
+        self.embeddings =
+            Some(serde_json::to_value(embeddings).expect("EmbeddingStore serializes to JSON"));
+        self
+    }
+
+    /// The inline vectors, if any, decoded from `embeddings`.
+    #[cfg(feature = "inline-embeddings")]
+    pub fn embedding_store(&self) -> Result<Option<crate::EmbeddingStore>, serde_json::Error> {
+        self.embeddings
+            .clone()
+            .map(serde_json::from_value)
+            .transpose()
+    }
+
+    /// Enum values written by a newer generator, in the map and in `rules`.
+    ///
+    /// Rules live outside the manifest, which only lists their names, so the
+    /// caller passes the ones it loaded.
+    pub fn unknown_variants(&self, rules: &[Rule]) -> Vec<UnknownVariant> {
+        let mut found = self.project.unknown_variants();
+        for rule in rules {
+            if let RuleCategory::Unknown(value) = &rule.category {
+                found.push(UnknownVariant {
+                    kind: "RuleCategory",
+                    value: value.clone(),
+                    location: format!("rule '{}'", rule.name),
+                });
+            }
+        }
+        found
+    }
+
+    /// Context under `module_id`, else under the id or an alias of the module
+    /// it names, so contexts keyed by a former id keep applying.
     pub fn get_module_context(&self, module_id: &str) -> Option<&ModuleContext> {
-        self.modules.get(module_id)
+        self.modules.get(module_id).or_else(|| {
+            let module = self.project.find_module(module_id)?;
+            context_by_any(&self.modules, &module.id, &module.aliases)
+        })
     }
 
+    pub fn get_module_context_refs(&self, module_id: &str) -> Option<ModuleContextRefs> {
+        let id = self
+            .project
+            .find_module(module_id)
+            .map_or(module_id, |m| m.id.as_str());
+        self.get_module_context(module_id)
+            .map(|ctx| ModuleContextRefs::from_legacy(id, ctx))
+    }
+
+    /// Like `get_module_context`, for groups.
     pub fn get_group_context(&self, group_id: &str) -> Option<&GroupContext> {
-        self.groups.get(group_id)
+        self.groups.get(group_id).or_else(|| {
+            let group = self.project.find_group(group_id)?;
+            context_by_any(&self.groups, &group.id, &group.aliases)
+        })
     }
 
+    /// Like `get_module_context`, for domains.
     pub fn get_domain_context(&self, domain_id: &str) -> Option<&DomainContext> {
-        self.domains.get(domain_id)
+        self.domains.get(domain_id).or_else(|| {
+            let domain = self.project.find_domain(domain_id)?;
+            context_by_any(&self.domains, &domain.id, &domain.aliases)
+        })
+    }
+
+    /// Derives module, group and domain contexts from the embedded map.
+    pub fn sync_contexts_from_map(&mut self, mode: ContextSyncMode) {
+        let map = &self.project;
+        if mode == ContextSyncMode::Replace {
+            self.modules.clear();
+            self.groups.clear();
+            self.domains.clear();
+        }
+
+        for module in &map.modules {
+            let group = map.find_group_containing(&module.id);
+            let domain_id = map
+                .find_domain_containing_module(&module.id)
+                .map(|d| d.id.clone());
+            let ctx = self.modules.entry(module.id.clone()).or_default();
+            ctx.conventions = module.conventions.iter().map(|c| c.to_string()).collect();
+            ctx.issues = module.known_issues.iter().map(|i| i.to_string()).collect();
+            ctx.group_id = group.map(|g| g.id.clone());
+            ctx.domain_id = domain_id;
+        }
+
+        for group in &map.groups {
+            let domain_id = group.domain_id.clone().or_else(|| {
+                map.find_domain_containing_group(&group.id)
+                    .map(|d| d.id.clone())
+            });
+            let ctx = self.groups.entry(group.id.clone()).or_default();
+            ctx.constraints = group.boundary_rules.clone();
+            ctx.member_modules = group.module_ids.clone();
+            ctx.domain_id = domain_id;
+        }
+
+        for domain in &map.domains {
+            let ctx = self.domains.entry(domain.id.clone()).or_default();
+            ctx.constraints = domain.boundary_rules.clone();
+            ctx.member_groups = domain.group_ids.clone();
+            ctx.interfaces = domain.interfaces.iter().map(|i| i.name.clone()).collect();
+        }
     }
 
     pub fn to_json(&self) -> Result<String, serde_json::Error> {
         serde_json::to_string_pretty(self)
     }
 
+    /// Accepts snake_case or camelCase keys.
     pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
-        serde_json::from_str(json)
+        crate::key_style::from_json_any(json)
     }
+
+    /// Writes the manifest atomically, keeping the previous version as `<path>.bak`.
+    ///
+    /// Holds an advisory lock on `<path>.lock` for the duration of the write so
+    /// concurrent generators serialize instead of clobbering each other.
+    pub fn save_to(&self, path: impl AsRef<Path>) -> Result<(), SchemaError> {
+        let path = path.as_ref();
+        let json = self.to_json()?;
+
+        let lock = OpenOptions::new()
+            .create(true)
+            .truncate(false)
+            .write(true)
+            .open(sibling(path, "lock"))?;
+        lock.lock()?;
+
+        let tmp = sibling(path, &format!("tmp.{}", std::process::id()));
+        let result = (|| {
+            let mut file = File::create(&tmp)?;
+            file.write_all(json.as_bytes())?;
+            file.sync_all()?;
+            if path.exists() {
+                fs::copy(path, sibling(path, "bak"))?;
+            }
+            fs::rename(&tmp, path)
+        })();
+        if result.is_err() {
+            let _ = fs::remove_file(&tmp);
+        }
+        lock.unlock()?;
+        Ok(result?)
+    }
+}
+
+/// First context keyed by `id` or one of `aliases`.
+fn context_by_any<'a, T>(
+    contexts: &'a HashMap<String, T>,
+    id: &str,
+    aliases: &[String],
+) -> Option<&'a T> {
+    known_as(id, aliases)
+        .into_iter()
+        .find_map(|name| contexts.get(name))
+}
+
+fn sibling(path: &Path, suffix: &str) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(".");
+    name.push(suffix);
+    path.with_file_name(name)
 }
 
 #[cfg(test)]
@@ -297,6 +735,30 @@ mod tests {
         assert!(manifest.tracked.is_empty());
     }
 
+    #[test]
+    fn test_manifest_unknown_variants_cover_rules() {
+        let manifest = ProjectManifest::new(sample_module_map()).with_rules(vec!["infra".into()]);
+        let rule: Rule = serde_json::from_value(serde_json::json!({
+            "name": "infra", "category": "infrastructure", "content": []
+        }))
+        .unwrap();
+        assert_eq!(
+            rule.category,
+            RuleCategory::Unknown("infrastructure".into())
+        );
+
+        let unknown: Vec<String> = manifest
+            .unknown_variants(&[rule])
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        assert_eq!(
+            unknown,
+            vec!["unknown RuleCategory 'infrastructure' in rule 'infra'"]
+        );
+        assert!(manifest.unknown_variants(&[]).is_empty());
+    }
+
     #[test]
     fn test_manifest_serialization() {
         let manifest = ProjectManifest::new(sample_module_map());
@@ -308,6 +770,15 @@ mod tests {
         assert_eq!(parsed.project.project.name, "test-project");
     }
 
+    #[test]
+    fn test_manifest_with_profile() {
+        let manifest =
+            ProjectManifest::new(sample_module_map()).with_profile(GenerationProfile::quick());
+
+        let parsed = ProjectManifest::from_json(&manifest.to_json().unwrap()).unwrap();
+        assert_eq!(parsed.profile, Some(GenerationProfile::quick()));
+    }
+
     #[test]
     fn test_flat_resource_lists() {
         let manifest = ProjectManifest::new(sample_module_map())
@@ -426,6 +897,24 @@ mod tests {
         assert_eq!(file.modified, 1706529600);
     }
 
+    #[test]
+    fn test_tracked_file_modified_formats() {
+        for modified in [
+            r#"1706529600"#,
+            r#"1706529600000"#,
+            r#""2024-01-29T12:00:00Z""#,
+        ] {
+            let json = format!(r#"{{"path": "a.rs", "hash": "x", "modified": {modified}}}"#);
+            let file: TrackedFile = serde_json::from_str(&json).unwrap();
+            assert_eq!(file.modified, 1706529600);
+            assert!(
+                serde_json::to_string(&file)
+                    .unwrap()
+                    .contains(r#""modified":1706529600"#)
+            );
+        }
+    }
+
     #[test]
     fn test_manifest_with_tracked_files() {
         let tracked = vec![
@@ -439,6 +928,166 @@ mod tests {
         assert_eq!(manifest.tracked[0].path, "src/auth/mod.rs");
     }
 
+    #[test]
+    fn test_sync_contexts_from_map() {
+        use crate::{
+            Convention, Domain, IssueCategory, IssueSeverity, KnownIssue, Module, ModuleGroup,
+        };
+
+        let module = Module::new("auth", "auth", vec!["src/auth/".into()])
+            .with_primary_language("rust")
+            .with_conventions(vec![Convention::new("bcrypt", "Use cost factor 12")])
+            .with_known_issues(vec![KnownIssue::new(
+                "token-refresh",
+                "May fail under load",
+                IssueSeverity::Medium,
+                IssueCategory::Correctness,
+            )]);
+        let map = ModuleMap::new(
+            GeneratorInfo::new("claudegen", "1.0.0"),
+            ProjectMetadata::new("test-project", TechStack::new("rust")),
+            vec![module],
+            vec![ModuleGroup::new(
+                "authentication",
+                "Auth",
+                vec!["auth".into()],
+            )],
+        )
+        .with_domains(vec![Domain::new(
+            "identity",
+            "Identity",
+            vec!["authentication".into()],
+        )]);
+
+        let mut modules = HashMap::new();
+        modules.insert(
+            "auth".to_string(),
+            ModuleContext::new().with_rules(vec!["rules/modules/auth.md".into()]),
+        );
+        let mut manifest = ProjectManifest::new(map).with_modules(modules);
+
+        manifest.sync_contexts_from_map(ContextSyncMode::Merge);
+        let ctx = manifest.get_module_context("auth").unwrap();
+        assert_eq!(ctx.rules, vec!["rules/modules/auth.md"]);
+        assert_eq!(ctx.conventions, vec!["bcrypt: Use cost factor 12"]);
+        assert_eq!(
+            ctx.issues,
+            vec!["[MEDIUM] token-refresh: May fail under load"]
+        );
+        assert_eq!(ctx.group_id, Some("authentication".into()));
+        assert_eq!(ctx.domain_id, Some("identity".into()));
+        assert_eq!(
+            manifest
+                .get_group_context("authentication")
+                .unwrap()
+                .member_modules,
+            vec!["auth"]
+        );
+        assert_eq!(
+            manifest
+                .get_domain_context("identity")
+                .unwrap()
+                .member_groups,
+            vec!["authentication"]
+        );
+
+        manifest.sync_contexts_from_map(ContextSyncMode::Replace);
+        assert!(
+            manifest
+                .get_module_context("auth")
+                .unwrap()
+                .rules
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_module_context_refs_roundtrip() {
+        let ctx = ModuleContext::new()
+            .with_conventions(vec!["bcrypt: Use cost factor 12".into()])
+            .with_issues(vec!["[MEDIUM] token-refresh: May fail under load".into()])
+            .with_group("authentication");
+
+        let refs = ModuleContextRefs::from_legacy("auth", &ctx);
+        assert_eq!(refs.convention_names, vec!["bcrypt"]);
+        assert_eq!(refs.issue_ids, vec!["token-refresh"]);
+        assert_eq!(refs.group_id, Some("authentication".into()));
+
+        let mut map = sample_module_map();
+        map.modules.push(
+            crate::Module::new("auth", "auth", vec![])
+                .with_primary_language("rust")
+                .with_conventions(vec![Convention::new("bcrypt", "Use cost factor 14")]),
+        );
+
+        assert_eq!(refs.conventions(&map).len(), 1);
+        assert!(refs.issues(&map).is_empty());
+        let legacy = refs.to_legacy(&map);
+        assert_eq!(legacy.conventions, vec!["bcrypt: Use cost factor 14"]);
+        assert!(legacy.issues.is_empty());
+
+        let json = serde_json::to_value(&refs).unwrap();
+        assert_eq!(json["convention_names"][0], "bcrypt");
+        assert_eq!(json["conventions"][0], "bcrypt: Use cost factor 12");
+        let old_reader: ModuleContext = serde_json::from_value(json.clone()).unwrap();
+        assert_eq!(old_reader.issues, ctx.issues);
+        assert_eq!(
+            serde_json::from_value::<ModuleContextRefs>(json).unwrap(),
+            refs
+        );
+
+        let from_old: ModuleContextRefs =
+            serde_json::from_value(serde_json::to_value(&ctx).unwrap()).unwrap();
+        assert_eq!(from_old.issue_ids, vec!["token-refresh"]);
+        let json = serde_json::to_value(refs.with_legacy_strings(&map)).unwrap();
+        assert_eq!(json["conventions"][0], "bcrypt: Use cost factor 14");
+    }
+
+    #[test]
+    fn test_embeddings_survive_roundtrip() {
+        let mut manifest = ProjectManifest::new(sample_module_map());
+        manifest.embeddings = Some(serde_json::json!({
+            "model_id": "m1",
+            "entries": [{"key": {"kind": "module", "id": "auth"}, "vector": [0.5]}]
+        }));
+        let json = manifest.to_json().unwrap();
+        let loaded = ProjectManifest::from_json(&json).unwrap();
+        assert_eq!(loaded.embeddings, manifest.embeddings);
+
+        #[cfg(feature = "inline-embeddings")]
+        {
+            let store = loaded.embedding_store().unwrap().unwrap();
+            assert_eq!(store.model_id, "m1");
+            let rebuilt = ProjectManifest::new(sample_module_map()).with_embeddings(store);
+            assert_eq!(rebuilt.embeddings, manifest.embeddings);
+        }
+    }
+
+    #[test]
+    fn test_save_to_keeps_backup() {
+        let dir = std::env::temp_dir().join(format!("modmap-save-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("manifest.json");
+
+        ProjectManifest::new(sample_module_map())
+            .with_generator("first")
+            .save_to(&path)
+            .unwrap();
+        ProjectManifest::new(sample_module_map())
+            .with_generator("second")
+            .save_to(&path)
+            .unwrap();
+
+        let current = ProjectManifest::from_json(&fs::read_to_string(&path).unwrap()).unwrap();
+        let backup =
+            ProjectManifest::from_json(&fs::read_to_string(dir.join("manifest.json.bak")).unwrap())
+                .unwrap();
+        assert_eq!(current.generator, "second");
+        assert_eq!(backup.generator, "first");
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
     #[test]
     fn test_empty_fields_omitted_in_json() {
         let manifest = ProjectManifest::new(sample_module_map());
@@ -453,5 +1102,6 @@ mod tests {
         assert!(parsed.get("groups").is_none());
         assert!(parsed.get("domains").is_none());
         assert!(parsed.get("tracked").is_none());
+        assert!(parsed.get("profile").is_none());
     }
 }
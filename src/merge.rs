@@ -0,0 +1,338 @@
+//! Combining per-package maps into one project map
+
+use std::collections::BTreeMap;
+
+use thiserror::Error;
+
+use crate::module_map::{DependencyGraph, ModuleMap};
+use crate::namespace::Namespace;
+use crate::types::{DetectedLanguage, GeneratorInfo};
+
+/// What `ModuleMap::merge` does with a module id present in both maps
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MergeStrategy {
+    /// Renames the incoming module to `<namespace>::<id>`, along with every
+    /// reference to it in the incoming map
+    Namespace(Namespace),
+    /// Keeps the existing module; incoming references now point at it
+    KeepExisting,
+    /// Replaces the existing module with the incoming one
+    PreferIncoming,
+    /// Fails on the first collision
+    Reject,
+}
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum MergeError {
+    #[error("Module id exists in both maps: {0}")]
+    Collision(String),
+
+    #[error("Namespaced id already exists: {0}")]
+    NamespaceCollision(String),
+}
+
+/// Result of a merge, with the incoming ids that were renamed
+#[derive(Debug, Clone)]
+pub struct MergedMap {
+    pub map: ModuleMap,
+    /// Incoming id to its namespaced id
+    pub renamed: BTreeMap<String, String>,
+}
+
+fn push_unique<T: PartialEq>(items: &mut Vec<T>, item: T) {
+    if !items.contains(&item) {
+        items.push(item);
+    }
+}
+
+/// Renames module `old` to `new` and every reference to it within `map`.
+fn rename_module(map: &mut ModuleMap, old: &str, new: &str) {
+    let rename = |id: &mut String| {
+        if id == old {
+            *id = new.to_string();
+        }
+    };
+    for module in &mut map.modules {
+        rename(&mut module.id);
+        module
+            .dependencies
+            .iter_mut()
+            .for_each(|d| rename(&mut d.module_id));
+        module.dependents.iter_mut().for_each(rename);
+    }
+    for group in &mut map.groups {
+        group.module_ids.iter_mut().for_each(rename);
+        group.leader_module.iter_mut().for_each(rename);
+    }
+    for shared in &mut map.shared_paths {
+        shared.module_ids.iter_mut().for_each(rename);
+    }
+    if let Some(graph) = &mut map.dependency_graph {
+        for edge in &mut graph.edges {
+            rename(&mut edge.from);
+            rename(&mut edge.to);
+        }
+        for layer in &mut graph.layers {
+            layer.modules.iter_mut().for_each(rename);
+        }
+    }
+}
+
+/// Capabilities both generators populated; run-specific fields survive only when equal.
+fn reconcile_generator(ours: &mut GeneratorInfo, theirs: GeneratorInfo) {
+    ours.capabilities
+        .retain(|c| theirs.capabilities.contains(c));
+    if ours.config_digest != theirs.config_digest {
+        ours.config_digest = None;
+    }
+    if ours.run_id != theirs.run_id {
+        ours.run_id = None;
+    }
+}
+
+/// Percentages re-weighted by each side's file count.
+fn merge_languages(
+    ours: &mut Vec<DetectedLanguage>,
+    our_files: usize,
+    theirs: Vec<DetectedLanguage>,
+    their_files: usize,
+) {
+    let total = (our_files + their_files).max(1) as f64;
+    let (ours_weight, theirs_weight) = (our_files as f64 / total, their_files as f64 / total);
+    for language in ours.iter_mut() {
+        language.percentage *= ours_weight;
+    }
+    for theirs in theirs {
+        match ours.iter_mut().find(|l| l.name == theirs.name) {
+            Some(language) => {
+                language.percentage += theirs.percentage * theirs_weight;
+                for framework in theirs.frameworks {
+                    push_unique(&mut language.frameworks, framework);
+                }
+                for tool in theirs.build_tools {
+                    push_unique(&mut language.build_tools, tool);
+                }
+                for marker in theirs.marker_files {
+                    push_unique(&mut language.marker_files, marker);
+                }
+            }
+            None => ours.push(DetectedLanguage {
+                percentage: theirs.percentage * theirs_weight,
+                ..theirs
+            }),
+        }
+    }
+    ours.sort_by(|a, b| b.percentage.total_cmp(&a.percentage));
+}
+
+fn merge_graphs(ours: &mut Option<DependencyGraph>, theirs: Option<DependencyGraph>) {
+    let Some(theirs) = theirs else { return };
+    let graph = ours.get_or_insert_with(DependencyGraph::default);
+    for edge in theirs.edges {
+        if !graph
+            .edges
+            .iter()
+            .any(|e| e.from == edge.from && e.to == edge.to)
+        {
+            graph.edges.push(edge);
+        }
+    }
+    for layer in theirs.layers {
+        match graph.layers.iter_mut().find(|l| l.name == layer.name) {
+            Some(existing) => {
+                for module in layer.modules {
+                    push_unique(&mut existing.modules, module);
+                }
+            }
+            None => graph.layers.push(layer),
+        }
+    }
+}
+
+impl ModuleMap {
+    /// Combines `self` with `other`, e.g. maps generated per workspace
+    /// package. Groups and domains with the same id are unified, and project
+    /// metadata of `self` is kept, with file counts and languages combined.
+    pub fn merge(
+        mut self,
+        mut other: ModuleMap,
+        strategy: &MergeStrategy,
+    ) -> Result<MergedMap, MergeError> {
+        let mut renamed = BTreeMap::new();
+        let colliding: Vec<String> = other
+            .modules
+            .iter()
+            .filter(|m| self.find_module(&m.id).is_some())
+            .map(|m| m.id.clone())
+            .collect();
+        for id in colliding {
+            match strategy {
+                MergeStrategy::Reject => return Err(MergeError::Collision(id)),
+                MergeStrategy::Namespace(namespace) => {
+                    let new_id = namespace.qualify(&id);
+                    if self.find_module(&new_id).is_some() || other.find_module(&new_id).is_some() {
+                        return Err(MergeError::NamespaceCollision(new_id));
+                    }
+                    rename_module(&mut other, &id, &new_id);
+                    renamed.insert(id, new_id);
+                }
+                MergeStrategy::KeepExisting => other.modules.retain(|m| m.id != id),
+                MergeStrategy::PreferIncoming => self.modules.retain(|m| m.id != id),
+            }
+        }
+        self.modules.extend(other.modules);
+
+        for group in other.groups {
+            match self.find_group_mut(&group.id) {
+                Some(existing) => {
+                    for id in group.module_ids {
+                        push_unique(&mut existing.module_ids, id);
+                    }
+                    for rule in group.boundary_rules {
+                        push_unique(&mut existing.boundary_rules, rule);
+                    }
+                    existing.leader_module = existing.leader_module.take().or(group.leader_module);
+                    existing.domain_id = existing.domain_id.take().or(group.domain_id);
+                }
+                None => self.groups.push(group),
+            }
+        }
+        for domain in other.domains {
+            match self.find_domain_mut(&domain.id) {
+                Some(existing) => {
+                    for id in domain.group_ids {
+                        push_unique(&mut existing.group_ids, id);
+                    }
+                    for rule in domain.boundary_rules {
+                        push_unique(&mut existing.boundary_rules, rule);
+                    }
+                    existing.interfaces.extend(domain.interfaces);
+                    existing.owner = existing.owner.take().or(domain.owner);
+                    existing.oncall = existing.oncall.take().or(domain.oncall);
+                }
+                None => self.domains.push(domain),
+            }
+        }
+        for shared in other.shared_paths {
+            match self.shared_paths.iter_mut().find(|s| s.path == shared.path) {
+                Some(existing) if !existing.module_ids.is_empty() => {
+                    for id in shared.module_ids {
+                        push_unique(&mut existing.module_ids, id);
+                    }
+                }
+                Some(_) => {}
+                None => self.shared_paths.push(shared),
+            }
+        }
+        merge_graphs(&mut self.dependency_graph, other.dependency_graph);
+
+        reconcile_generator(&mut self.generator, other.generator);
+        let project = &mut self.project;
+        merge_languages(
+            &mut project.languages,
+            project.total_files,
+            other.project.languages,
+            other.project.total_files,
+        );
+        project.total_files += other.project.total_files;
+        for generated in other.project.generated_paths {
+            push_unique(&mut project.generated_paths, generated);
+        }
+        for vendored in other.project.vendored_paths {
+            push_unique(&mut project.vendored_paths, vendored);
+        }
+        self.generated_at = self.generated_at.max(other.generated_at);
+
+        Ok(MergedMap { map: self, renamed })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        Domain, GeneratorInfo, Module, ModuleDependency, ModuleGroup, ProjectMetadata, TechStack,
+    };
+
+    fn module(id: &str, deps: &[&str]) -> Module {
+        Module::new(id, id, vec![format!("src/{id}/")])
+            .with_dependencies(deps.iter().map(|d| ModuleDependency::new(*d)).collect())
+            .with_primary_language("rust")
+    }
+
+    fn package(name: &str, modules: Vec<Module>, group: ModuleGroup, files: usize) -> ModuleMap {
+        let mut metadata = ProjectMetadata::new(name, TechStack::new("rust"));
+        metadata.total_files = files;
+        metadata.languages = vec![DetectedLanguage::new("rust")];
+        metadata.languages[0].percentage = 100.0;
+        ModuleMap::new(
+            GeneratorInfo::new("modmap", "1.0.0")
+                .with_capabilities(vec!["dependency-edges".into(), "metrics".into()])
+                .with_run_id(name),
+            metadata,
+            modules,
+            vec![group],
+        )
+        .with_domains(vec![Domain::new(
+            "platform",
+            "Platform",
+            vec!["core".into()],
+        )])
+    }
+
+    #[test]
+    fn test_merge_namespaces_collisions() {
+        let api = package(
+            "api",
+            vec![module("config", &[]), module("http", &["config"])],
+            ModuleGroup::new("core", "Core", vec!["config".into(), "http".into()]),
+            30,
+        );
+        let mut worker = package(
+            "worker",
+            vec![module("config", &[]), module("jobs", &["config"])],
+            ModuleGroup::new("core", "Core", vec!["config".into(), "jobs".into()]),
+            10,
+        );
+        worker.generator.capabilities.pop();
+        worker.project.languages[0].name = "go".into();
+
+        let merged = api
+            .clone()
+            .merge(
+                worker.clone(),
+                &MergeStrategy::Namespace(Namespace::new("worker").unwrap()),
+            )
+            .unwrap();
+        assert_eq!(merged.renamed["config"], "worker::config");
+        let map = merged.map;
+        let ids: Vec<&str> = map.modules.iter().map(|m| m.id.as_str()).collect();
+        assert_eq!(ids, ["config", "http", "worker::config", "jobs"]);
+        assert_eq!(
+            map.find_module("jobs").unwrap().dependencies[0].module_id,
+            "worker::config"
+        );
+        assert_eq!(
+            map.groups[0].module_ids,
+            ["config", "http", "worker::config", "jobs"]
+        );
+        assert_eq!(map.domains.len(), 1);
+        assert_eq!(map.generator.capabilities, ["dependency-edges"]);
+        assert_eq!(map.generator.run_id, None);
+        assert_eq!(map.project.total_files, 40);
+        assert_eq!(map.project.languages[0].name, "rust");
+        assert_eq!(map.project.languages[0].percentage, 75.0);
+
+        let kept = api
+            .clone()
+            .merge(worker.clone(), &MergeStrategy::KeepExisting)
+            .unwrap()
+            .map;
+        assert_eq!(kept.modules.len(), 3);
+        assert_eq!(kept.groups[0].module_ids, ["config", "http", "jobs"]);
+        assert_eq!(
+            api.merge(worker, &MergeStrategy::Reject).unwrap_err(),
+            MergeError::Collision("config".into())
+        );
+    }
+}
@@ -1,43 +1,189 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
+use crate::naming::NamingRule;
+use crate::oncall::OncallSchedule;
+use crate::paths::glob_match;
+use crate::query::IssueRef;
+use crate::risk::RiskAssessment;
+use crate::scaffold::Scaffold;
 use crate::types::{
-    Convention, DetectedLanguage, EvidenceLocation, GeneratorInfo, KnownIssue, ModuleDependency,
-    ProjectType, TechStack, WorkspaceType,
+    ArtifactInfo, Convention, DependencyType, DetectedLanguage, EvidenceLocation, FlagRef,
+    FlagStatus, GeneratorInfo, IssueCategory, IssueSeverity, KnownIssue, Localized, LocalizedText,
+    ModuleDependency, ProjectType, Score, SeverityPolicy, TechStack, UnknownVariant, WorkspaceType,
 };
 
 pub const SCHEMA_VERSION: &str = "1.0.0";
 
+/// Architecture of one project: modules, their groups and domains
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct ModuleMap {
+    /// Version of this schema the map was written with
+    #[schemars(example = &"1.0.0")]
+    #[serde(alias = "schemaVersion")]
     pub schema_version: String,
+    /// Tool that produced the map
     pub generator: GeneratorInfo,
+    /// Project-wide metadata
     pub project: ProjectMetadata,
+    /// Units of code ownership; the leaves of the hierarchy
     pub modules: Vec<Module>,
+    /// Cohesive sets of modules
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub groups: Vec<ModuleGroup>,
+    /// Business areas, each made of groups
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub domains: Vec<Domain>,
-    #[serde(default, skip_serializing_if = "Option::is_none")]
+    /// Module dependency edges and layers, when computed
+    #[serde(
+        default,
+        alias = "dependencyGraph",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub dependency_graph: Option<DependencyGraph>,
+    /// Paths intentionally touched by many modules (e.g. `proto/`, root configs)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub shared_paths: Vec<SharedPath>,
+    /// When the map was generated (RFC 3339)
+    #[serde(
+        alias = "generatedAt",
+        deserialize_with = "crate::timestamp::deserialize_timestamp"
+    )]
     pub generated_at: chrono::DateTime<chrono::Utc>,
 }
 
+/// Communal path with the modules allowed to change it
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct SharedPath {
+    pub path: String,
+    /// Empty means any module may touch it
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub module_ids: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
+impl SharedPath {
+    pub fn new(path: impl Into<String>, module_ids: Vec<String>) -> Self {
+        Self {
+            path: path.into(),
+            module_ids,
+            description: None,
+        }
+    }
+
+    pub fn with_description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    pub fn contains_file(&self, path: &str) -> bool {
+        path.starts_with(&self.path)
+    }
+
+    pub fn allows(&self, module_id: &str) -> bool {
+        self.module_ids.is_empty() || self.module_ids.iter().any(|id| id == module_id)
+    }
+}
+
+/// Project identity, stack and layout
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct ProjectMetadata {
+    #[schemars(example = &"shop")]
     pub name: String,
+    /// Kind of project, e.g. `web_app`
     #[serde(default)]
     pub project_type: ProjectType,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
+    /// Repository URL
+    #[schemars(example = &"https://github.com/acme/shop")]
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub repository: Option<String>,
+    /// Single package or monorepo layout
     pub workspace: WorkspaceInfo,
     pub tech_stack: TechStack,
+    /// Languages by share of files
     pub languages: Vec<DetectedLanguage>,
+    /// Source files considered during analysis
     pub total_files: usize,
+    /// Build, test and lint commands
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub commands: Option<ProjectCommands>,
+    /// Generated code declared for the whole project
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub generated_paths: Vec<GeneratedCode>,
+    /// Third-party code checked into the tree (`vendor/`, `third_party/`)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub vendored_paths: Vec<VendoredPath>,
+}
+
+/// Generated files: excluded from metrics, rule injection and issue
+/// attribution, and never to be edited by hand
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct GeneratedCode {
+    /// Workspace-relative globs (see `glob_match`)
+    pub globs: Vec<String>,
+    /// Tool that produces the files, e.g. `protoc`, `sqlc`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub generator: Option<String>,
+}
+
+impl GeneratedCode {
+    pub fn new(globs: Vec<String>) -> Self {
+        Self {
+            globs,
+            generator: None,
+        }
+    }
+
+    pub fn with_generator(mut self, generator: impl Into<String>) -> Self {
+        self.generator = Some(generator.into());
+        self
+    }
+
+    pub fn matches(&self, path: &str) -> bool {
+        self.globs.iter().any(|g| glob_match(g, path))
+    }
+}
+
+/// How agents may treat a vendored directory
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum VendorPolicy {
+    /// Readable for reference, never edited
+    #[default]
+    ReadOnly,
+    /// Neither read nor edited
+    Hidden,
+}
+
+/// Third-party directory that belongs to no module
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct VendoredPath {
+    pub path: String,
+    #[serde(default)]
+    pub policy: VendorPolicy,
+}
+
+impl VendoredPath {
+    pub fn new(path: impl Into<String>) -> Self {
+        Self {
+            path: path.into(),
+            policy: VendorPolicy::default(),
+        }
+    }
+
+    pub fn with_policy(mut self, policy: VendorPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    pub fn contains_file(&self, path: &str) -> bool {
+        path.starts_with(&self.path)
+    }
 }
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
@@ -61,78 +207,268 @@ pub struct ProjectCommands {
 #[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
 #[serde(default)]
 pub struct ModuleMetrics {
-    pub coverage_ratio: f64,
-    pub value_score: f64,
-    pub risk_score: f64,
+    pub coverage_ratio: Score,
+    pub value_score: Score,
+    pub risk_score: Score,
+    /// Factors behind `risk_score` when computed by `ModuleMap::compute_risk`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub risk_assessment: Option<RiskAssessment>,
 }
 
 impl ModuleMetrics {
+    /// Values are clamped into `[0, 1]`.
     pub fn new(coverage_ratio: f64, value_score: f64, risk_score: f64) -> Self {
         Self {
-            coverage_ratio,
-            value_score,
-            risk_score,
+            coverage_ratio: Score::clamped(coverage_ratio),
+            value_score: Score::clamped(value_score),
+            risk_score: Score::clamped(risk_score),
+            risk_assessment: None,
         }
     }
 
     pub fn priority_score(&self) -> f64 {
-        self.value_score * 0.6 + self.risk_score * 0.4
+        self.value_score.get() * 0.6 + self.risk_score.get() * 0.4
+    }
+}
+
+/// Operational knowledge for running a module's tests
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct TestProfile {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub avg_duration_secs: Option<f64>,
+    /// Test ids known to fail intermittently
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub flaky_tests: Vec<String>,
+    /// Services to start first, e.g. "postgres", "redis"
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub required_services: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub fixtures: Vec<String>,
+}
+
+impl TestProfile {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_avg_duration_secs(mut self, secs: f64) -> Self {
+        self.avg_duration_secs = Some(secs);
+        self
+    }
+
+    pub fn with_flaky_tests(mut self, tests: Vec<String>) -> Self {
+        self.flaky_tests = tests;
+        self
+    }
+
+    pub fn with_required_services(mut self, services: Vec<String>) -> Self {
+        self.required_services = services;
+        self
     }
+
+    pub fn with_fixtures(mut self, fixtures: Vec<String>) -> Self {
+        self.fixtures = fixtures;
+        self
+    }
+}
+
+/// Combined test needs of several modules
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TestPlan {
+    /// Sum of known average durations
+    pub estimated_secs: f64,
+    /// Modules without a recorded duration
+    pub unknown_duration: Vec<String>,
+    pub required_services: Vec<String>,
+    pub fixtures: Vec<String>,
+    pub flaky_tests: Vec<String>,
 }
 
+/// A unit of code ownership: a set of paths with one responsibility
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct Module {
+    /// Unique identifier (kebab-case)
+    #[schemars(example = &"auth")]
     pub id: String,
+    /// Human-readable name
+    #[schemars(example = &"Authentication")]
     pub name: String,
+    /// Former ids, still accepted by lookups after a rename
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub aliases: Vec<String>,
+    /// Directories or files owned by the module; directories end with `/`
     pub paths: Vec<String>,
+    /// Entry points and files worth reading first
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub key_files: Vec<String>,
+    /// Modules this module depends on
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub dependencies: Vec<ModuleDependency>,
+    /// Ids of modules depending on this one
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub dependents: Vec<String>,
+    /// What the module is responsible for, in one or two sentences
     pub responsibility: String,
+    /// Translated `responsibility` keyed by language tag
+    #[serde(default, skip_serializing_if = "Localized::is_empty")]
+    pub localized_responsibility: LocalizedText,
+    #[schemars(example = &"rust")]
     pub primary_language: String,
+    /// Coverage, value and risk scores, flattened into the module
     #[serde(flatten)]
     pub metrics: ModuleMetrics,
+    /// Coding conventions observed in the module
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub conventions: Vec<Convention>,
+    /// Known problems to keep in mind when editing
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub known_issues: Vec<KnownIssue>,
+    /// Source locations supporting the analysis
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub evidence: Vec<EvidenceLocation>,
+    /// Event topics this module subscribes to
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub consumed_topics: Vec<String>,
+    /// How to run the module's tests
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub test_profile: Option<TestProfile>,
+    /// Build outputs: binaries, images, packages
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub artifacts: Vec<ArtifactInfo>,
+    /// Environments this module is deployed to, e.g. "prod-eu"
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub deploy_targets: Vec<String>,
+    /// Feature flags checked inside the module
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub feature_flags: Vec<FlagRef>,
+    /// Tie-breaker for overlapping paths under `OwnershipPolicy::Priority`; higher wins
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub path_priority: Option<i32>,
+    /// Generated code inside this module, on top of the project-level declarations
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub generated_paths: Vec<GeneratedCode>,
+    /// File and symbol naming rules for the module
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub naming_rules: Vec<NamingRule>,
+    /// Vector id in an external embedding store
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub embedding_ref: Option<String>,
 }
 
+/// Cohesive set of modules that change together
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct ModuleGroup {
+    /// Unique identifier (kebab-case)
+    #[schemars(example = &"payments-core")]
     pub id: String,
+    /// Human-readable name
     pub name: String,
+    /// Former ids, still accepted by lookups after a rename
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub aliases: Vec<String>,
+    /// Member module ids
     pub module_ids: Vec<String>,
+    /// What the group is responsible for
     pub responsibility: String,
+    /// Constraints on how members may be used from outside
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub boundary_rules: Vec<String>,
+    /// Member that fronts the group, if any
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub leader_module: Option<String>,
+    /// Enclosing group for nested groups
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub parent_group_id: Option<String>,
+    /// Domain the group belongs to
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub domain_id: Option<String>,
+    /// Nesting depth; 0 for top-level groups
     #[serde(default)]
     pub depth: u8,
+    /// Build outputs of the group as a whole
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub artifacts: Vec<ArtifactInfo>,
+    /// Deploy targets inherited by every member module
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub deploy_targets: Vec<String>,
+    /// Where new files of each kind belong
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub placement: Vec<PlacementRule>,
+    /// Descriptors for creating new member modules
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub scaffolds: Vec<Scaffold>,
+    /// Naming rules applied to every member module
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub naming_rules: Vec<NamingRule>,
 }
 
+/// Business area made of module groups
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct Domain {
+    /// Unique identifier (kebab-case)
+    #[schemars(example = &"identity")]
     pub id: String,
+    /// Human-readable name
     pub name: String,
+    /// Former ids, still accepted by lookups after a rename
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub aliases: Vec<String>,
+    /// Member group ids
     pub group_ids: Vec<String>,
+    /// What the domain is responsible for
     pub responsibility: String,
+    /// Constraints on access from other domains
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub boundary_rules: Vec<String>,
+    /// APIs, tables and events the domain exposes
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub interfaces: Vec<DomainInterface>,
+    /// Owning team or person
+    #[schemars(example = &"@identity-team")]
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub owner: Option<String>,
+    /// Rotation consulted by `ModuleMap::current_owner_at` before `owner`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub oncall: Option<OncallSchedule>,
+    /// Domain-specific severity overrides for known issues
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub severity_policy: Option<SeverityPolicy>,
+    /// Domain-wide placement rules; group rules take precedence
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub placement: Vec<PlacementRule>,
+}
+
+/// Where a new file of some kind goes, e.g. kind `http-handler` with path
+/// `src/api/handlers/<resource>.rs`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct PlacementRule {
+    pub kind: String,
+    /// Path template; `<name>` placeholders are filled by `PlacementRule::path_for`
+    pub path: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
+impl PlacementRule {
+    pub fn new(kind: impl Into<String>, path: impl Into<String>) -> Self {
+        Self {
+            kind: kind.into(),
+            path: path.into(),
+            description: None,
+        }
+    }
+
+    pub fn with_description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// Path with each `<key>` replaced; unknown placeholders are left as is.
+    pub fn path_for(&self, values: &[(&str, &str)]) -> String {
+        values.iter().fold(self.path.clone(), |path, (key, value)| {
+            path.replace(&format!("<{key}>"), value)
+        })
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
@@ -142,16 +478,116 @@ pub struct DomainInterface {
     pub interface_type: InterfaceType,
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub consumers: Vec<String>,
+    /// Ownership details for `InterfaceType::Database`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub database: Option<DatabaseInterface>,
+    /// Topic contract for `InterfaceType::Event`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub event: Option<EventInterface>,
+    /// Authoritative API definition for this interface
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub contract: Option<ContractRef>,
+    #[serde(default)]
+    pub stability: Stability,
+    /// Date after which a deprecated interface may be removed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sunset_date: Option<chrono::NaiveDate>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Stability {
+    Experimental,
+    #[default]
+    Stable,
+    Deprecated,
+}
+
+/// Pointer to an API definition file, relative to the project root
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct ContractRef {
+    pub kind: ContractKind,
+    pub path: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ContractKind {
+    Openapi,
+    Protobuf,
+    Graphql,
+}
+
+/// Tables a domain owns and who else may touch them
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+pub struct DatabaseInterface {
+    /// e.g. `postgres`, `mysql`, `dynamodb`
+    pub engine: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub schema: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tables: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub migration_paths: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub consumers: Vec<DatabaseConsumer>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct DatabaseConsumer {
+    /// Domain or module id
+    pub consumer: String,
+    #[serde(default)]
+    pub access: AccessMode,
+}
+
+/// A topic a domain publishes
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+pub struct EventInterface {
+    pub topic: String,
+    /// Schema registry subject or path to the payload schema
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub schema_ref: Option<String>,
+    /// Module ids publishing to the topic
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub producers: Vec<String>,
+    /// Module ids subscribed to the topic
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub consumers: Vec<String>,
+    #[serde(default)]
+    pub delivery: DeliveryGuarantee,
 }
 
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "snake_case")]
+pub enum DeliveryGuarantee {
+    AtMostOnce,
+    #[default]
+    AtLeastOnce,
+    ExactlyOnce,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum AccessMode {
+    #[default]
+    Read,
+    Write,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
 pub enum InterfaceType {
     #[default]
     Api,
     Event,
     SharedLibrary,
     Database,
+    /// Value written by a newer generator, kept verbatim
+    #[serde(untagged)]
+    Unknown(String),
 }
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
@@ -166,7 +602,7 @@ pub struct DependencyGraph {
 pub struct DependencyEdge {
     pub from: String,
     pub to: String,
-    #[serde(default)]
+    #[serde(default, alias = "edgeType", alias = "edge-type")]
     pub edge_type: crate::types::DependencyType,
 }
 
@@ -176,6 +612,26 @@ pub struct ArchitectureLayer {
     pub modules: Vec<String>,
 }
 
+/// Index of the item with `id`, else of the first one listing it as an alias.
+fn position_by_id<T>(
+    items: &[T],
+    id: &str,
+    ids: impl Fn(&T) -> (&String, &Vec<String>),
+) -> Option<usize> {
+    items.iter().position(|item| ids(item).0 == id).or_else(|| {
+        items
+            .iter()
+            .position(|item| ids(item).1.iter().any(|alias| alias == id))
+    })
+}
+
+/// An id followed by its aliases.
+pub(crate) fn known_as<'a>(id: &'a str, aliases: &'a [String]) -> Vec<&'a str> {
+    std::iter::once(id)
+        .chain(aliases.iter().map(String::as_str))
+        .collect()
+}
+
 impl ModuleMap {
     pub fn new(
         generator: GeneratorInfo,
@@ -191,6 +647,7 @@ impl ModuleMap {
             groups,
             domains: Vec::new(),
             dependency_graph: None,
+            shared_paths: Vec::new(),
             generated_at: chrono::Utc::now(),
         }
     }
@@ -200,33 +657,148 @@ impl ModuleMap {
         self
     }
 
+    pub fn with_shared_paths(mut self, shared_paths: Vec<SharedPath>) -> Self {
+        self.shared_paths = shared_paths;
+        self
+    }
+
     pub fn with_dependency_graph(mut self, graph: DependencyGraph) -> Self {
         self.dependency_graph = Some(graph);
         self
     }
 
+    /// Module with the id, else the first one listing it in `aliases`.
     pub fn find_module(&self, module_id: &str) -> Option<&Module> {
-        self.modules.iter().find(|m| m.id == module_id)
+        self.position_of(module_id).map(|i| &self.modules[i])
     }
 
     pub fn find_group(&self, group_id: &str) -> Option<&ModuleGroup> {
-        self.groups.iter().find(|g| g.id == group_id)
+        self.group_position_of(group_id).map(|i| &self.groups[i])
     }
 
     pub fn find_domain(&self, domain_id: &str) -> Option<&Domain> {
-        self.domains.iter().find(|d| d.id == domain_id)
+        self.domain_position_of(domain_id).map(|i| &self.domains[i])
+    }
+
+    pub fn find_module_mut(&mut self, module_id: &str) -> Option<&mut Module> {
+        self.position_of(module_id).map(|i| &mut self.modules[i])
+    }
+
+    pub fn find_group_mut(&mut self, group_id: &str) -> Option<&mut ModuleGroup> {
+        self.group_position_of(group_id)
+            .map(|i| &mut self.groups[i])
+    }
+
+    pub fn find_domain_mut(&mut self, domain_id: &str) -> Option<&mut Domain> {
+        self.domain_position_of(domain_id)
+            .map(|i| &mut self.domains[i])
+    }
+
+    /// Index of the module in `modules`, by id or alias.
+    pub fn position_of(&self, module_id: &str) -> Option<usize> {
+        position_by_id(&self.modules, module_id, |m| (&m.id, &m.aliases))
+    }
+
+    pub fn group_position_of(&self, group_id: &str) -> Option<usize> {
+        position_by_id(&self.groups, group_id, |g| (&g.id, &g.aliases))
+    }
+
+    pub fn domain_position_of(&self, domain_id: &str) -> Option<usize> {
+        position_by_id(&self.domains, domain_id, |d| (&d.id, &d.aliases))
+    }
+
+    /// Current id of a possibly renamed group; unknown ids are returned as is.
+    fn canonical_group_id<'a>(&'a self, group_id: &'a str) -> &'a str {
+        self.find_group(group_id).map_or(group_id, |g| &g.id)
+    }
+
+    /// Entry for in-place update or insertion of a module.
+    pub fn module_entry(&mut self, module_id: impl Into<String>) -> ModuleEntry<'_> {
+        let module_id = module_id.into();
+        let index = self.position_of(&module_id);
+        ModuleEntry {
+            modules: &mut self.modules,
+            module_id,
+            index,
+        }
+    }
+
+    /// Id and aliases of the module `module_id` names, or just `module_id`.
+    fn module_names<'a>(&'a self, module_id: &'a str) -> Vec<&'a str> {
+        match self.find_module(module_id) {
+            Some(module) => known_as(&module.id, &module.aliases),
+            None => vec![module_id],
+        }
+    }
+
+    fn group_names<'a>(&'a self, group_id: &'a str) -> Vec<&'a str> {
+        match self.find_group(group_id) {
+            Some(group) => known_as(&group.id, &group.aliases),
+            None => vec![group_id],
+        }
+    }
+
+    /// Whether `group` lists the module under its id or any of its aliases.
+    pub fn group_lists_module(&self, group: &ModuleGroup, module_id: &str) -> bool {
+        let names = self.module_names(module_id);
+        group
+            .module_ids
+            .iter()
+            .any(|id| names.contains(&id.as_str()))
+    }
+
+    /// Whether `domain` lists the group under its id or any of its aliases.
+    pub fn domain_lists_group(&self, domain: &Domain, group_id: &str) -> bool {
+        let names = self.group_names(group_id);
+        domain
+            .group_ids
+            .iter()
+            .any(|id| names.contains(&id.as_str()))
     }
 
+    /// First group listing the module under its id or any of its aliases.
     pub fn find_group_containing(&self, module_id: &str) -> Option<&ModuleGroup> {
         self.groups
             .iter()
-            .find(|g| g.module_ids.iter().any(|id| id == module_id))
+            .find(|g| self.group_lists_module(g, module_id))
     }
 
     pub fn find_domain_containing_group(&self, group_id: &str) -> Option<&Domain> {
         self.domains
             .iter()
-            .find(|d| d.group_ids.iter().any(|id| id == group_id))
+            .find(|d| self.domain_lists_group(d, group_id))
+    }
+
+    /// Domain of the module's group, via `ModuleGroup.domain_id` or domain membership.
+    pub fn find_domain_containing_module(&self, module_id: &str) -> Option<&Domain> {
+        let group = self.find_group_containing(module_id)?;
+        match &group.domain_id {
+            Some(domain_id) => self.find_domain(domain_id),
+            None => self.find_domain_containing_group(&group.id),
+        }
+    }
+
+    /// Owner of the domain containing the group.
+    pub fn find_group_owner(&self, group_id: &str) -> Option<&str> {
+        let domain = match self.find_group(group_id)?.domain_id.as_deref() {
+            Some(domain_id) => self.find_domain(domain_id),
+            None => self.find_domain_containing_group(group_id),
+        };
+        domain?.owner.as_deref()
+    }
+
+    /// Owner of the domain containing the module.
+    pub fn find_module_owner(&self, module_id: &str) -> Option<&str> {
+        self.find_domain_containing_module(module_id)?
+            .owner
+            .as_deref()
+    }
+
+    /// Issue severity after applying the module's domain severity policy.
+    pub fn effective_severity(&self, issue: &KnownIssue, module: &Module) -> IssueSeverity {
+        self.find_domain_containing_module(&module.id)
+            .and_then(|d| d.severity_policy.as_ref())
+            .map_or(issue.severity, |policy| policy.apply(issue))
     }
 
     pub fn find_modules_in_group(&self, group_id: &str) -> Vec<&Module> {
@@ -251,22 +823,364 @@ impl ModuleMap {
             .unwrap_or_default()
     }
 
+    /// Edges of the explicit dependency graph, or derived from module dependencies when absent.
+    /// Endpoints naming a module alias are rewritten to the module id.
+    pub fn dependency_edges(&self) -> Vec<DependencyEdge> {
+        let mut edges = match &self.dependency_graph {
+            Some(graph) if !graph.edges.is_empty() => graph.edges.clone(),
+            _ => self
+                .modules
+                .iter()
+                .flat_map(|m| {
+                    m.dependencies.iter().map(|d| DependencyEdge {
+                        from: m.id.clone(),
+                        to: d.module_id.clone(),
+                        edge_type: d.dependency_type.clone(),
+                    })
+                })
+                .collect(),
+        };
+        let aliases = self.module_aliases();
+        if !aliases.is_empty() {
+            for edge in &mut edges {
+                for endpoint in [&mut edge.from, &mut edge.to] {
+                    if let Some(id) = aliases.get(endpoint.as_str()) {
+                        *endpoint = id.to_string();
+                    }
+                }
+            }
+        }
+        edges
+    }
+
+    /// Alias to module id, for aliases that are not themselves module ids;
+    /// the first module listing an alias wins, as in `find_module`.
+    fn module_aliases(&self) -> HashMap<&str, &str> {
+        let ids: HashSet<&str> = self.modules.iter().map(|m| m.id.as_str()).collect();
+        let mut aliases = HashMap::new();
+        for module in &self.modules {
+            for alias in &module.aliases {
+                if !ids.contains(alias.as_str()) {
+                    aliases.entry(alias.as_str()).or_insert(module.id.as_str());
+                }
+            }
+        }
+        aliases
+    }
+
+    /// Enum values written by a newer generator and kept as `Unknown`.
+    pub fn unknown_variants(&self) -> Vec<UnknownVariant> {
+        let mut found = Vec::new();
+        let mut push = |kind, value: &str, location: String| {
+            found.push(UnknownVariant {
+                kind,
+                value: value.to_string(),
+                location,
+            })
+        };
+        if let ProjectType::Unknown(value) = &self.project.project_type {
+            push("ProjectType", value, "project".into());
+        }
+        for module in &self.modules {
+            for dep in &module.dependencies {
+                if let DependencyType::Unknown(value) = &dep.dependency_type {
+                    push("DependencyType", value, format!("module '{}'", module.id));
+                }
+            }
+            for issue in &module.known_issues {
+                if let IssueCategory::Unknown(value) = &issue.category {
+                    push("IssueCategory", value, format!("module '{}'", module.id));
+                }
+            }
+        }
+        for domain in &self.domains {
+            for interface in &domain.interfaces {
+                if let InterfaceType::Unknown(value) = &interface.interface_type {
+                    push("InterfaceType", value, format!("domain '{}'", domain.id));
+                }
+            }
+        }
+        for edge in self.dependency_graph.iter().flat_map(|g| &g.edges) {
+            if let DependencyType::Unknown(value) = &edge.edge_type {
+                push(
+                    "DependencyType",
+                    value,
+                    format!("edge '{}' -> '{}'", edge.from, edge.to),
+                );
+            }
+        }
+        found
+    }
+
+    /// Every known issue with the module that lists it.
+    pub fn issues_iter(&self) -> impl Iterator<Item = IssueRef<'_>> {
+        self.modules.iter().flat_map(|module| {
+            module
+                .known_issues
+                .iter()
+                .map(move |issue| IssueRef { module, issue })
+        })
+    }
+
+    /// Every convention with the module that declares it.
+    pub fn conventions_iter(&self) -> impl Iterator<Item = (&Module, &Convention)> {
+        self.modules
+            .iter()
+            .flat_map(|m| m.conventions.iter().map(move |c| (m, c)))
+    }
+
+    /// Every evidence location on modules, their conventions and their issues.
+    pub fn evidence_iter(&self) -> impl Iterator<Item = (&Module, &EvidenceLocation)> {
+        self.modules.iter().flat_map(|m| {
+            m.evidence
+                .iter()
+                .chain(m.conventions.iter().flat_map(|c| &c.evidence))
+                .chain(m.known_issues.iter().flat_map(|i| &i.evidence))
+                .map(move |e| (m, e))
+        })
+    }
+
+    /// Modules keyed by containing domain id; `None` collects modules outside any domain.
+    pub fn modules_by_domain(&self) -> BTreeMap<Option<&str>, Vec<&Module>> {
+        let mut by_domain: BTreeMap<Option<&str>, Vec<&Module>> = BTreeMap::new();
+        for module in &self.modules {
+            let domain = self
+                .find_domain_containing_module(&module.id)
+                .map(|d| d.id.as_str());
+            by_domain.entry(domain).or_default().push(module);
+        }
+        by_domain
+    }
+
+    /// Modules keyed by primary language.
+    pub fn modules_by_language(&self) -> BTreeMap<&str, Vec<&Module>> {
+        let mut by_language: BTreeMap<&str, Vec<&Module>> = BTreeMap::new();
+        for module in &self.modules {
+            by_language
+                .entry(module.primary_language.as_str())
+                .or_default()
+                .push(module);
+        }
+        by_language
+    }
+
     pub fn find_child_groups(&self, parent_group_id: &str) -> Vec<&ModuleGroup> {
+        let parent = self.canonical_group_id(parent_group_id);
         self.groups
             .iter()
-            .filter(|g| g.parent_group_id.as_deref() == Some(parent_group_id))
+            .filter(|g| {
+                g.parent_group_id
+                    .as_deref()
+                    .is_some_and(|id| self.canonical_group_id(id) == parent)
+            })
+            .collect()
+    }
+
+    /// Modules deployed to `target`, directly or through their group.
+    pub fn modules_shipping_to(&self, target: &str) -> Vec<&Module> {
+        self.modules
+            .iter()
+            .filter(|m| {
+                m.deploy_targets.iter().any(|t| t == target)
+                    || self
+                        .find_group_containing(&m.id)
+                        .is_some_and(|g| g.deploy_targets.iter().any(|t| t == target))
+            })
+            .collect()
+    }
+
+    /// Generated-code declaration covering `path`, project level first.
+    pub fn generated_code_for(&self, path: &str) -> Option<&GeneratedCode> {
+        self.project
+            .generated_paths
+            .iter()
+            .chain(
+                self.modules
+                    .iter()
+                    .filter(|m| m.contains_file(path))
+                    .flat_map(|m| &m.generated_paths),
+            )
+            .find(|g| g.matches(path))
+    }
+
+    pub fn is_generated(&self, path: &str) -> bool {
+        self.generated_code_for(path).is_some()
+    }
+
+    /// Where a new file of `kind` goes in `domain`: the first matching rule of
+    /// the domain's groups (in domain order), else the domain's own rule.
+    pub fn placement_for(&self, kind: &str, domain: &str) -> Option<&PlacementRule> {
+        let domain = self.find_domain(domain)?;
+        domain
+            .group_ids
+            .iter()
+            .filter_map(|id| self.find_group(id))
+            .flat_map(|g| &g.placement)
+            .chain(&domain.placement)
+            .find(|p| p.kind == kind)
+    }
+
+    /// Most specific vendored directory containing `path`.
+    pub fn vendored_path_for(&self, path: &str) -> Option<&VendoredPath> {
+        self.project
+            .vendored_paths
+            .iter()
+            .filter(|v| v.contains_file(path))
+            .max_by_key(|v| v.path.len())
+    }
+
+    pub fn is_vendored(&self, path: &str) -> bool {
+        self.vendored_path_for(path).is_some()
+    }
+
+    /// Module whose code evaluates the flag `key`.
+    pub fn module_for_flag(&self, key: &str) -> Option<&Module> {
+        self.modules
+            .iter()
+            .find(|m| m.feature_flags.iter().any(|f| f.key == key))
+    }
+
+    /// `(module, flag)` pairs for every flag with `status`.
+    pub fn flags_with_status(&self, status: FlagStatus) -> Vec<(&Module, &FlagRef)> {
+        self.modules
+            .iter()
+            .flat_map(|m| m.feature_flags.iter().map(move |f| (m, f)))
+            .filter(|(_, f)| f.status == status)
             .collect()
     }
 
+    /// Artifacts of a module followed by those of its group.
+    pub fn artifacts_for_module(&self, module_id: &str) -> Vec<&ArtifactInfo> {
+        let Some(module) = self.find_module(module_id) else {
+            return Vec::new();
+        };
+        module
+            .artifacts
+            .iter()
+            .chain(
+                self.find_group_containing(module_id)
+                    .into_iter()
+                    .flat_map(|g| &g.artifacts),
+            )
+            .collect()
+    }
+
+    /// Time budget and dependencies for testing `module_ids`; unknown ids are skipped.
+    pub fn test_plan(&self, module_ids: &[&str]) -> TestPlan {
+        let mut plan = TestPlan::default();
+        for module in module_ids.iter().filter_map(|id| self.find_module(id)) {
+            let profile = module.test_profile.as_ref();
+            match profile.and_then(|p| p.avg_duration_secs) {
+                Some(secs) => plan.estimated_secs += secs,
+                None => plan.unknown_duration.push(module.id.clone()),
+            }
+            let Some(profile) = profile else {
+                continue;
+            };
+            for (target, items) in [
+                (&mut plan.required_services, &profile.required_services),
+                (&mut plan.fixtures, &profile.fixtures),
+                (&mut plan.flaky_tests, &profile.flaky_tests),
+            ] {
+                for item in items {
+                    if !target.contains(item) {
+                        target.push(item.clone());
+                    }
+                }
+            }
+        }
+        plan
+    }
+
     pub fn to_json(&self) -> Result<String, serde_json::Error> {
         serde_json::to_string_pretty(self)
     }
 }
 
 impl Module {
+    pub fn new(id: impl Into<String>, name: impl Into<String>, paths: Vec<String>) -> Self {
+        Self {
+            id: id.into(),
+            name: name.into(),
+            aliases: Vec::new(),
+            paths,
+            key_files: Vec::new(),
+            dependencies: Vec::new(),
+            dependents: Vec::new(),
+            responsibility: String::new(),
+            localized_responsibility: LocalizedText::default(),
+            primary_language: String::new(),
+            metrics: ModuleMetrics::default(),
+            conventions: Vec::new(),
+            known_issues: Vec::new(),
+            evidence: Vec::new(),
+            consumed_topics: Vec::new(),
+            test_profile: None,
+            artifacts: Vec::new(),
+            deploy_targets: Vec::new(),
+            feature_flags: Vec::new(),
+            path_priority: None,
+            generated_paths: Vec::new(),
+            naming_rules: Vec::new(),
+            embedding_ref: None,
+        }
+    }
+
+    pub fn with_aliases(mut self, aliases: Vec<String>) -> Self {
+        self.aliases = aliases;
+        self
+    }
+
+    pub fn with_key_files(mut self, key_files: Vec<String>) -> Self {
+        self.key_files = key_files;
+        self
+    }
+
+    pub fn with_dependencies(mut self, dependencies: Vec<ModuleDependency>) -> Self {
+        self.dependencies = dependencies;
+        self
+    }
+
+    pub fn with_dependents(mut self, dependents: Vec<String>) -> Self {
+        self.dependents = dependents;
+        self
+    }
+
+    pub fn with_responsibility(mut self, responsibility: impl Into<String>) -> Self {
+        self.responsibility = responsibility.into();
+        self
+    }
+
+    pub fn with_primary_language(mut self, language: impl Into<String>) -> Self {
+        self.primary_language = language.into();
+        self
+    }
+
+    pub fn with_metrics(mut self, metrics: ModuleMetrics) -> Self {
+        self.metrics = metrics;
+        self
+    }
+
+    pub fn with_conventions(mut self, conventions: Vec<Convention>) -> Self {
+        self.conventions = conventions;
+        self
+    }
+
+    pub fn with_known_issues(mut self, issues: Vec<KnownIssue>) -> Self {
+        self.known_issues = issues;
+        self
+    }
+
     pub fn contains_file(&self, path: &str) -> bool {
         self.paths.iter().any(|p| path.starts_with(p))
     }
+
+    /// Responsibility in the first available preferred locale, else the default.
+    pub fn responsibility_for(&self, locales: &[&str]) -> &str {
+        self.localized_responsibility
+            .resolve(locales)
+            .map_or(&self.responsibility, |s| s)
+    }
 }
 
 impl ModuleGroup {
@@ -274,6 +1188,7 @@ impl ModuleGroup {
         Self {
             id: id.into(),
             name: name.into(),
+            aliases: Vec::new(),
             module_ids,
             responsibility: String::new(),
             boundary_rules: Vec::new(),
@@ -281,6 +1196,11 @@ impl ModuleGroup {
             parent_group_id: None,
             domain_id: None,
             depth: 0,
+            artifacts: Vec::new(),
+            deploy_targets: Vec::new(),
+            placement: Vec::new(),
+            scaffolds: Vec::new(),
+            naming_rules: Vec::new(),
         }
     }
 
@@ -289,6 +1209,11 @@ impl ModuleGroup {
         self
     }
 
+    pub fn with_aliases(mut self, aliases: Vec<String>) -> Self {
+        self.aliases = aliases;
+        self
+    }
+
     pub fn with_boundary_rules(mut self, rules: Vec<String>) -> Self {
         self.boundary_rules = rules;
         self
@@ -304,6 +1229,31 @@ impl ModuleGroup {
         self.depth = depth;
         self
     }
+
+    pub fn with_artifacts(mut self, artifacts: Vec<ArtifactInfo>) -> Self {
+        self.artifacts = artifacts;
+        self
+    }
+
+    pub fn with_deploy_targets(mut self, targets: Vec<String>) -> Self {
+        self.deploy_targets = targets;
+        self
+    }
+
+    pub fn with_placement(mut self, placement: Vec<PlacementRule>) -> Self {
+        self.placement = placement;
+        self
+    }
+
+    pub fn with_scaffolds(mut self, scaffolds: Vec<Scaffold>) -> Self {
+        self.scaffolds = scaffolds;
+        self
+    }
+
+    pub fn with_naming_rules(mut self, rules: Vec<NamingRule>) -> Self {
+        self.naming_rules = rules;
+        self
+    }
 }
 
 impl Domain {
@@ -311,11 +1261,15 @@ impl Domain {
         Self {
             id: id.into(),
             name: name.into(),
+            aliases: Vec::new(),
             group_ids,
             responsibility: String::new(),
             boundary_rules: Vec::new(),
             interfaces: Vec::new(),
             owner: None,
+            oncall: None,
+            severity_policy: None,
+            placement: Vec::new(),
         }
     }
 
@@ -324,6 +1278,11 @@ impl Domain {
         self
     }
 
+    pub fn with_aliases(mut self, aliases: Vec<String>) -> Self {
+        self.aliases = aliases;
+        self
+    }
+
     pub fn with_boundary_rules(mut self, rules: Vec<String>) -> Self {
         self.boundary_rules = rules;
         self
@@ -338,6 +1297,21 @@ impl Domain {
         self.owner = Some(owner.into());
         self
     }
+
+    pub fn with_oncall(mut self, schedule: OncallSchedule) -> Self {
+        self.oncall = Some(schedule);
+        self
+    }
+
+    pub fn with_severity_policy(mut self, policy: SeverityPolicy) -> Self {
+        self.severity_policy = Some(policy);
+        self
+    }
+
+    pub fn with_placement(mut self, placement: Vec<PlacementRule>) -> Self {
+        self.placement = placement;
+        self
+    }
 }
 
 impl DomainInterface {
@@ -346,13 +1320,143 @@ impl DomainInterface {
             name: name.into(),
             interface_type,
             consumers: Vec::new(),
+            database: None,
+            event: None,
+            contract: None,
+            stability: Stability::default(),
+            sunset_date: None,
+        }
+    }
+
+    pub fn with_consumers(mut self, consumers: Vec<String>) -> Self {
+        self.consumers = consumers;
+        self
+    }
+
+    pub fn with_database(mut self, database: DatabaseInterface) -> Self {
+        self.database = Some(database);
+        self
+    }
+
+    pub fn with_event(mut self, event: EventInterface) -> Self {
+        self.event = Some(event);
+        self
+    }
+
+    pub fn with_contract(mut self, contract: ContractRef) -> Self {
+        self.contract = Some(contract);
+        self
+    }
+
+    pub fn with_stability(mut self, stability: Stability) -> Self {
+        self.stability = stability;
+        self
+    }
+
+    /// Marks the interface deprecated, removable after `sunset_date`.
+    pub fn deprecated(mut self, sunset_date: Option<chrono::NaiveDate>) -> Self {
+        self.stability = Stability::Deprecated;
+        self.sunset_date = sunset_date;
+        self
+    }
+
+    /// Declared consumers plus event and database consumers, de-duplicated.
+    pub fn all_consumers(&self) -> Vec<&str> {
+        let mut consumers: Vec<&str> = self.consumers.iter().map(String::as_str).collect();
+        if let Some(event) = &self.event {
+            consumers.extend(event.consumers.iter().map(String::as_str));
+        }
+        if let Some(database) = &self.database {
+            consumers.extend(database.consumers.iter().map(|c| c.consumer.as_str()));
+        }
+        let mut seen = std::collections::HashSet::new();
+        consumers.retain(|c| seen.insert(*c));
+        consumers
+    }
+}
+
+impl ContractRef {
+    pub fn new(kind: ContractKind, path: impl Into<String>) -> Self {
+        Self {
+            kind,
+            path: path.into(),
+            version: None,
+        }
+    }
+
+    pub fn with_version(mut self, version: impl Into<String>) -> Self {
+        self.version = Some(version.into());
+        self
+    }
+}
+
+impl EventInterface {
+    pub fn new(topic: impl Into<String>) -> Self {
+        Self {
+            topic: topic.into(),
+            ..Default::default()
         }
     }
 
+    pub fn with_schema_ref(mut self, schema_ref: impl Into<String>) -> Self {
+        self.schema_ref = Some(schema_ref.into());
+        self
+    }
+
+    pub fn with_producers(mut self, producers: Vec<String>) -> Self {
+        self.producers = producers;
+        self
+    }
+
     pub fn with_consumers(mut self, consumers: Vec<String>) -> Self {
         self.consumers = consumers;
         self
     }
+
+    pub fn with_delivery(mut self, delivery: DeliveryGuarantee) -> Self {
+        self.delivery = delivery;
+        self
+    }
+}
+
+impl DatabaseInterface {
+    pub fn new(engine: impl Into<String>) -> Self {
+        Self {
+            engine: engine.into(),
+            ..Default::default()
+        }
+    }
+
+    pub fn with_schema(mut self, schema: impl Into<String>) -> Self {
+        self.schema = Some(schema.into());
+        self
+    }
+
+    pub fn with_tables(mut self, tables: Vec<String>) -> Self {
+        self.tables = tables;
+        self
+    }
+
+    pub fn with_migration_paths(mut self, paths: Vec<String>) -> Self {
+        self.migration_paths = paths;
+        self
+    }
+
+    pub fn with_consumer(mut self, consumer: impl Into<String>, access: AccessMode) -> Self {
+        self.consumers.push(DatabaseConsumer {
+            consumer: consumer.into(),
+            access,
+        });
+        self
+    }
+
+    /// `schema.table`, or just `table` without a schema
+    pub fn qualified_table(&self, table: &str) -> String {
+        match &self.schema {
+            Some(schema) if !table.contains('.') => format!("{schema}.{table}"),
+            _ => table.to_string(),
+        }
+    }
 }
 
 impl ProjectMetadata {
@@ -367,6 +1471,8 @@ impl ProjectMetadata {
             languages: Vec::new(),
             total_files: 0,
             commands: None,
+            generated_paths: Vec::new(),
+            vendored_paths: Vec::new(),
         }
     }
 
@@ -399,6 +1505,16 @@ impl ProjectMetadata {
         self.commands = Some(commands);
         self
     }
+
+    pub fn with_generated_paths(mut self, generated: Vec<GeneratedCode>) -> Self {
+        self.generated_paths = generated;
+        self
+    }
+
+    pub fn with_vendored_paths(mut self, vendored: Vec<VendoredPath>) -> Self {
+        self.vendored_paths = vendored;
+        self
+    }
 }
 
 impl ProjectCommands {
@@ -422,53 +1538,84 @@ impl ProjectCommands {
     }
 }
 
+/// Existing or missing module slot returned by [`ModuleMap::module_entry`]
+pub struct ModuleEntry<'a> {
+    modules: &'a mut Vec<Module>,
+    module_id: String,
+    index: Option<usize>,
+}
+
+impl<'a> ModuleEntry<'a> {
+    pub fn module_id(&self) -> &str {
+        &self.module_id
+    }
+
+    pub fn is_occupied(&self) -> bool {
+        self.index.is_some()
+    }
+
+    /// Applies `f` to the module if it exists.
+    pub fn and_modify(self, f: impl FnOnce(&mut Module)) -> Self {
+        if let Some(index) = self.index {
+            f(&mut self.modules[index]);
+        }
+        self
+    }
+
+    /// Existing module, or the one built by `f` from the id appended to the map.
+    pub fn or_insert_with(self, f: impl FnOnce(&str) -> Module) -> &'a mut Module {
+        let index = match self.index {
+            Some(index) => index,
+            None => {
+                let mut module = f(&self.module_id);
+                module.id = self.module_id;
+                self.modules.push(module);
+                self.modules.len() - 1
+            }
+        };
+        &mut self.modules[index]
+    }
+
+    pub fn get_mut(self) -> Option<&'a mut Module> {
+        self.index.map(|index| &mut self.modules[index])
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::types::{IssueCategory, IssueSeverity};
+    use crate::types::{ArtifactKind, IssueCategory, IssueSeverity};
 
     fn sample_module(id: &str) -> Module {
-        Module {
-            id: id.into(),
-            name: id.into(),
-            paths: vec![format!("src/{}/", id)],
-            key_files: vec![],
-            dependencies: vec![],
-            dependents: vec![],
-            responsibility: format!("{} module", id),
-            primary_language: "rust".into(),
-            metrics: ModuleMetrics::new(0.8, 0.7, 0.3),
-            conventions: vec![],
-            known_issues: vec![],
-            evidence: vec![],
-        }
+        Module::new(id, id, vec![format!("src/{}/", id)])
+            .with_responsibility(format!("{} module", id))
+            .with_primary_language("rust")
+            .with_metrics(ModuleMetrics::new(0.8, 0.7, 0.3))
     }
 
     fn sample_module_with_conventions(id: &str) -> Module {
         Module {
-            id: id.into(),
-            name: id.into(),
-            paths: vec![format!("src/{}/", id)],
-            key_files: vec![format!("src/{}/mod.rs", id)],
-            dependencies: vec![ModuleDependency::runtime("types")],
-            dependents: vec!["cli".into()],
-            responsibility: format!("{} module", id),
-            primary_language: "rust".into(),
-            metrics: ModuleMetrics::new(0.8, 0.7, 0.3),
-            conventions: vec![Convention::new(
-                "error-handling",
-                "Use ? operator for propagation",
-            )],
-            known_issues: vec![
-                KnownIssue::new(
-                    "memory-leak",
-                    "Unbounded cache growth",
-                    IssueSeverity::Medium,
-                    IssueCategory::Performance,
-                )
-                .with_prevention("Add TTL or max size limit"),
-            ],
             evidence: vec![EvidenceLocation::new("src/pipeline/mod.rs", 1)],
+            ..Module::new(id, id, vec![format!("src/{}/", id)])
+                .with_key_files(vec![format!("src/{}/mod.rs", id)])
+                .with_dependencies(vec![ModuleDependency::runtime("types")])
+                .with_dependents(vec!["cli".into()])
+                .with_responsibility(format!("{} module", id))
+                .with_primary_language("rust")
+                .with_metrics(ModuleMetrics::new(0.8, 0.7, 0.3))
+                .with_conventions(vec![Convention::new(
+                    "error-handling",
+                    "Use ? operator for propagation",
+                )])
+                .with_known_issues(vec![
+                    KnownIssue::new(
+                        "memory-leak",
+                        "Unbounded cache growth",
+                        IssueSeverity::Medium,
+                        IssueCategory::Performance,
+                    )
+                    .with_prevention("Add TTL or max size limit"),
+                ])
         }
     }
 
@@ -562,6 +1709,43 @@ mod tests {
         assert!(map.find_domain_containing_group("authentication").is_some());
     }
 
+    #[test]
+    fn test_effective_severity() {
+        use crate::types::SeverityRule;
+
+        let module = sample_module_with_conventions("billing");
+        let other = sample_module_with_conventions("search");
+        let groups = vec![ModuleGroup::new(
+            "payments-core",
+            "Payments",
+            vec!["billing".into()],
+        )];
+        let domains = vec![
+            Domain::new("payments", "Payments", vec!["payments-core".into()]).with_severity_policy(
+                SeverityPolicy::new(vec![SeverityRule::new(
+                    IssueSeverity::Medium,
+                    IssueSeverity::High,
+                )]),
+            ),
+        ];
+        let map = ModuleMap::new(
+            GeneratorInfo::new("test", "1.0.0"),
+            sample_project(),
+            vec![module.clone(), other.clone()],
+            groups,
+        )
+        .with_domains(domains);
+
+        let issue = &module.known_issues[0];
+        assert_eq!(map.effective_severity(issue, &module), IssueSeverity::High);
+        assert_eq!(map.effective_severity(issue, &other), IssueSeverity::Medium);
+        assert_eq!(
+            map.find_domain_containing_module("billing")
+                .map(|d| d.id.as_str()),
+            Some("payments")
+        );
+    }
+
     #[test]
     fn test_nested_groups() {
         let child_group =
@@ -584,6 +1768,110 @@ mod tests {
         assert!(module.known_issues[0].prevention.is_some());
     }
 
+    #[test]
+    fn test_flattening_iterators() {
+        let mut python = sample_module("scripts");
+        python.primary_language = "python".into();
+        let map = ModuleMap::new(
+            GeneratorInfo::new("test", "1.0.0"),
+            sample_project(),
+            vec![sample_module_with_conventions("pipeline"), python],
+            vec![ModuleGroup::new("core", "Core", vec!["pipeline".into()])],
+        )
+        .with_domains(vec![Domain::new("data", "Data", vec!["core".into()])]);
+
+        let issues: Vec<_> = map
+            .issues_iter()
+            .map(|r| (r.module.id.as_str(), r.issue.id.as_str()))
+            .collect();
+        assert_eq!(issues, vec![("pipeline", "memory-leak")]);
+        assert_eq!(map.conventions_iter().count(), 1);
+        assert_eq!(
+            map.evidence_iter().next().unwrap().1.file,
+            "src/pipeline/mod.rs"
+        );
+
+        let by_domain = map.modules_by_domain();
+        assert_eq!(by_domain[&Some("data")][0].id, "pipeline");
+        assert_eq!(by_domain[&None][0].id, "scripts");
+        let languages: Vec<_> = map.modules_by_language().into_keys().collect();
+        assert_eq!(languages, vec!["python", "rust"]);
+    }
+
+    #[test]
+    fn test_mutable_accessors() {
+        let mut map = ModuleMap::new(
+            GeneratorInfo::new("test", "1.0.0"),
+            sample_project(),
+            vec![sample_module("auth"), sample_module("api")],
+            vec![],
+        );
+        assert_eq!(map.position_of("api"), Some(1));
+        map.find_module_mut("api").unwrap().metrics.coverage_ratio = Score::ONE;
+        assert_eq!(map.modules[1].metrics.coverage_ratio, Score::ONE);
+
+        map.module_entry("auth")
+            .and_modify(|m| m.responsibility = "Login".into())
+            .or_insert_with(sample_module);
+        assert_eq!(map.modules[0].responsibility, "Login");
+
+        let entry = map.module_entry("billing");
+        assert!(!entry.is_occupied());
+        entry.or_insert_with(sample_module).primary_language = "go".into();
+        assert_eq!(map.position_of("billing"), Some(2));
+        assert_eq!(map.modules[2].primary_language, "go");
+        assert!(map.module_entry("missing").get_mut().is_none());
+    }
+
+    #[test]
+    fn test_lookup_by_alias() {
+        let mut identity = sample_module("identity");
+        identity.aliases = vec!["auth".into()];
+        let mut api = sample_module("api");
+        api.dependencies = vec![ModuleDependency::runtime("auth")];
+        let map = ModuleMap::new(
+            GeneratorInfo::new("test", "1.0.0"),
+            sample_project(),
+            vec![identity, api],
+            vec![
+                ModuleGroup::new("security", "Security", vec!["auth".into()])
+                    .with_aliases(vec!["core".into()]),
+                ModuleGroup::new("session", "Session", vec![]).with_parent("core", 1),
+            ],
+        )
+        .with_domains(vec![Domain::new(
+            "platform",
+            "Platform",
+            vec!["core".into()],
+        )]);
+
+        assert_eq!(map.find_module("auth").unwrap().id, "identity");
+        assert_eq!(map.find_group("core").unwrap().id, "security");
+        assert_eq!(
+            map.find_group_containing("identity").unwrap().id,
+            "security"
+        );
+        assert_eq!(
+            map.find_domain_containing_module("auth").unwrap().id,
+            "platform"
+        );
+        assert_eq!(map.find_child_groups("security")[0].id, "session");
+        assert_eq!(map.find_modules_in_group("core")[0].id, "identity");
+        assert!(map.find_module("missing").is_none());
+
+        // Edges written against the old id still reach the renamed module
+        assert_eq!(map.dependency_edges()[0].to, "identity");
+        let ids = |modules: Vec<&Module>| -> Vec<String> {
+            modules.into_iter().map(|m| m.id.clone()).collect()
+        };
+        assert_eq!(ids(map.transitive_dependents("identity")), ["api"]);
+        assert_eq!(ids(map.transitive_dependents("auth")), ["api"]);
+        assert_eq!(
+            ids(map.impacted_modules(&["src/identity/x.rs"])),
+            ["identity", "api"]
+        );
+    }
+
     #[test]
     fn test_module_contains_file() {
         let module = sample_module("auth");
@@ -591,6 +1879,20 @@ mod tests {
         assert!(!module.contains_file("src/api/routes.rs"));
     }
 
+    #[test]
+    fn test_localized_responsibility() {
+        let mut module = sample_module("auth");
+        module
+            .localized_responsibility
+            .insert("ko", "인증 모듈".into());
+
+        assert_eq!(module.responsibility_for(&["ko-KR", "en"]), "인증 모듈");
+        assert_eq!(module.responsibility_for(&["en"]), "auth module");
+
+        let json = serde_json::to_string(&module).unwrap();
+        assert!(json.contains("\"localized_responsibility\":{\"ko\":"));
+    }
+
     #[test]
     fn test_priority_score() {
         let metrics = ModuleMetrics::new(0.8, 0.8, 0.5);
@@ -598,6 +1900,161 @@ mod tests {
         assert!((metrics.priority_score() - expected).abs() < 0.001);
     }
 
+    #[test]
+    fn test_test_plan() {
+        let mut api = sample_module("api");
+        api.test_profile = Some(
+            TestProfile::new()
+                .with_avg_duration_secs(90.0)
+                .with_required_services(vec!["postgres".into(), "redis".into()])
+                .with_flaky_tests(vec!["api::tests::test_timeout".into()]),
+        );
+        let mut worker = sample_module("worker");
+        worker.test_profile = Some(
+            TestProfile::new()
+                .with_avg_duration_secs(30.0)
+                .with_required_services(vec!["redis".into()])
+                .with_fixtures(vec!["fixtures/jobs.json".into()]),
+        );
+        let map = ModuleMap::new(
+            GeneratorInfo::new("test", "1.0.0"),
+            ProjectMetadata::new("test", TechStack::new("rust")),
+            vec![api, worker, sample_module("cli")],
+            vec![],
+        );
+
+        let plan = map.test_plan(&["api", "worker", "cli", "ghost"]);
+        assert_eq!(plan.estimated_secs, 120.0);
+        assert_eq!(plan.unknown_duration, vec!["cli"]);
+        assert_eq!(plan.required_services, vec!["postgres", "redis"]);
+        assert_eq!(plan.fixtures, vec!["fixtures/jobs.json"]);
+        assert_eq!(plan.flaky_tests, vec!["api::tests::test_timeout"]);
+
+        let json = serde_json::to_value(&map.modules[1]).unwrap();
+        assert_eq!(json["test_profile"]["required_services"][0], "redis");
+        assert!(
+            serde_json::to_value(&map.modules[2])
+                .unwrap()
+                .get("test_profile")
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_modules_shipping_to() {
+        let mut api = sample_module("api");
+        api.deploy_targets = vec!["prod-us".into()];
+        api.artifacts =
+            vec![ArtifactInfo::new(ArtifactKind::DockerImage, "api").with_registry("ghcr.io/acme")];
+        let map = ModuleMap::new(
+            GeneratorInfo::new("test", "1.0.0"),
+            ProjectMetadata::new("test", TechStack::new("rust")),
+            vec![api, sample_module("worker"), sample_module("cli")],
+            vec![
+                ModuleGroup::new("backend", "Backend", vec!["api".into(), "worker".into()])
+                    .with_deploy_targets(vec!["prod-eu".into()])
+                    .with_artifacts(vec![ArtifactInfo::new(ArtifactKind::Binary, "backend")]),
+            ],
+        );
+
+        let ids = |target| {
+            map.modules_shipping_to(target)
+                .iter()
+                .map(|m| m.id.clone())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(ids("prod-eu"), vec!["api", "worker"]);
+        assert_eq!(ids("prod-us"), vec!["api"]);
+        assert!(ids("staging").is_empty());
+
+        let artifacts = map.artifacts_for_module("api");
+        assert_eq!(artifacts.len(), 2);
+        assert_eq!(artifacts[1].name, "backend");
+
+        let json = serde_json::to_value(&map.modules[0]).unwrap();
+        assert_eq!(json["artifacts"][0]["kind"], "docker_image");
+        assert_eq!(json["deploy_targets"][0], "prod-us");
+    }
+
+    #[test]
+    fn test_module_for_flag() {
+        let mut checkout = sample_module("checkout");
+        checkout.feature_flags = vec![
+            FlagRef::new("new-cart").with_provider("launchdarkly"),
+            FlagRef::new("legacy-tax").with_status(FlagStatus::Stale),
+        ];
+        let map = ModuleMap::new(
+            GeneratorInfo::new("test", "1.0.0"),
+            ProjectMetadata::new("test", TechStack::new("rust")),
+            vec![sample_module("api"), checkout],
+            vec![],
+        );
+
+        assert_eq!(map.module_for_flag("legacy-tax").unwrap().id, "checkout");
+        assert!(map.module_for_flag("unknown").is_none());
+        let stale = map.flags_with_status(FlagStatus::Stale);
+        assert_eq!(stale.len(), 1);
+        assert_eq!(stale[0].1.key, "legacy-tax");
+
+        let json = serde_json::to_value(&map.modules[1]).unwrap();
+        assert_eq!(json["feature_flags"][0]["status"], "active");
+        assert_eq!(json["feature_flags"][1]["status"], "stale");
+    }
+
+    #[test]
+    fn test_generated_code_for() {
+        let mut api = sample_module("api");
+        api.generated_paths = vec![GeneratedCode::new(vec!["src/api/gen/**".into()])];
+        let map = ModuleMap::new(
+            GeneratorInfo::new("test", "1.0.0"),
+            sample_project().with_generated_paths(vec![
+                GeneratedCode::new(vec!["**/*.pb.go".into()]).with_generator("protoc"),
+            ]),
+            vec![api, sample_module("cli")],
+            vec![],
+        );
+
+        let generated = map.generated_code_for("src/cli/users.pb.go").unwrap();
+        assert_eq!(generated.generator.as_deref(), Some("protoc"));
+        assert!(map.is_generated("src/api/gen/client.rs"));
+        assert!(!map.is_generated("src/cli/gen/client.rs"));
+        assert!(!map.is_generated("src/api/routes.rs"));
+    }
+
+    #[test]
+    fn test_placement_for() {
+        let map = ModuleMap::new(
+            GeneratorInfo::new("test", "1.0.0"),
+            sample_project(),
+            vec![sample_module("api"), sample_module("auth")],
+            vec![
+                ModuleGroup::new("http", "HTTP", vec!["api".into()]).with_placement(vec![
+                    PlacementRule::new("http-handler", "src/api/handlers/<resource>.rs"),
+                ]),
+            ],
+        )
+        .with_domains(vec![
+            Domain::new("platform", "Platform", vec!["http".into()]).with_placement(vec![
+                PlacementRule::new("http-handler", "src/handlers/<resource>.rs"),
+                PlacementRule::new("migration", "migrations/<timestamp>_<name>.sql")
+                    .with_description("One migration per schema change"),
+            ]),
+        ]);
+
+        let handler = map.placement_for("http-handler", "platform").unwrap();
+        assert_eq!(
+            handler.path_for(&[("resource", "users")]),
+            "src/api/handlers/users.rs"
+        );
+        let migration = map.placement_for("migration", "platform").unwrap();
+        assert_eq!(
+            migration.path_for(&[("name", "add_users")]),
+            "migrations/<timestamp>_add_users.sql"
+        );
+        assert!(map.placement_for("worker", "platform").is_none());
+        assert!(map.placement_for("http-handler", "billing").is_none());
+    }
+
     #[test]
     fn test_dependency_graph() {
         let project = sample_project();
@@ -676,4 +2133,56 @@ mod tests {
         assert_eq!(parsed.schema_version, "1.0.0");
         assert_eq!(parsed.modules[0].conventions.len(), 1);
     }
+
+    #[test]
+    fn test_unknown_enum_variants_round_trip() {
+        let mut module = sample_module_with_conventions("pipeline");
+        module.dependencies = vec![ModuleDependency::runtime("core")];
+        let map = ModuleMap::new(
+            GeneratorInfo::new("claudegen", "0.2.0"),
+            sample_project(),
+            vec![module, sample_module("core")],
+            vec![],
+        )
+        .with_domains(vec![Domain::new("data", "Data", vec![]).with_interfaces(
+            vec![DomainInterface::new("orders", InterfaceType::Database)],
+        )])
+        .with_dependency_graph(DependencyGraph {
+            edges: vec![DependencyEdge {
+                from: "pipeline".into(),
+                to: "core".into(),
+                edge_type: DependencyType::Build,
+            }],
+            layers: vec![],
+        });
+        let mut value = serde_json::to_value(&map).unwrap();
+        value["project"]["project_type"] = "daemon".into();
+        value["modules"][0]["dependencies"][0]["dependency_type"] = "peer".into();
+        value["modules"][0]["known_issues"][0]["category"] = "privacy".into();
+        value["domains"][0]["interfaces"][0]["interface_type"] = "grpc_stream".into();
+        value["dependency_graph"]["edges"][0]["edge_type"] = "dev".into();
+
+        let parsed: ModuleMap = serde_json::from_value(value).unwrap();
+        let dep = &parsed.modules[0].dependencies[0];
+        assert_eq!(dep.dependency_type, DependencyType::Unknown("peer".into()));
+        let unknown: Vec<String> = parsed
+            .unknown_variants()
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        assert_eq!(
+            unknown,
+            vec![
+                "unknown ProjectType 'daemon' in project",
+                "unknown DependencyType 'peer' in module 'pipeline'",
+                "unknown IssueCategory 'privacy' in module 'pipeline'",
+                "unknown InterfaceType 'grpc_stream' in domain 'data'",
+                "unknown DependencyType 'dev' in edge 'pipeline' -> 'core'",
+            ]
+        );
+
+        let json = parsed.to_json().unwrap();
+        assert!(json.contains("\"dependency_type\": \"peer\""));
+        assert!(map.unknown_variants().is_empty());
+    }
 }
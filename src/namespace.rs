@@ -0,0 +1,293 @@
+//! Namespaced ids for maps composed from several repositories: `payments::billing-core`
+//!
+//! Within a component `\` and `:` are escaped as `\\` and `\:`, so the first
+//! unescaped `::` always separates the namespace from the id.
+
+use std::fmt;
+use std::str::FromStr;
+
+use thiserror::Error;
+
+use crate::entity::EntityRef;
+use crate::module_map::{Domain, Module, ModuleGroup, ModuleMap};
+
+pub const NAMESPACE_SEPARATOR: &str = "::";
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum NamespaceError {
+    #[error("namespace must not be empty")]
+    Empty,
+    #[error("namespaced id '{0}' has an empty id")]
+    EmptyId(String),
+    #[error("dangling escape at the end of '{0}'")]
+    DanglingEscape(String),
+}
+
+fn escape(component: &str) -> String {
+    component.replace('\\', "\\\\").replace(':', "\\:")
+}
+
+/// Name of the map ids are qualified with, e.g. the repository
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Namespace(String);
+
+impl Namespace {
+    pub fn new(name: impl Into<String>) -> Result<Self, NamespaceError> {
+        let name = name.into();
+        if name.is_empty() {
+            return Err(NamespaceError::Empty);
+        }
+        Ok(Self(name))
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// `id` qualified with this namespace, escaped.
+    pub fn qualify(&self, id: &str) -> String {
+        NamespacedId::new(Some(self.clone()), id).to_string()
+    }
+}
+
+impl fmt::Display for Namespace {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// An id, optionally qualified by the namespace of the map it comes from
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct NamespacedId {
+    pub namespace: Option<Namespace>,
+    /// Unescaped id within the namespace
+    pub id: String,
+}
+
+impl NamespacedId {
+    pub fn new(namespace: Option<Namespace>, id: impl Into<String>) -> Self {
+        Self {
+            namespace,
+            id: id.into(),
+        }
+    }
+}
+
+impl fmt::Display for NamespacedId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.namespace {
+            Some(namespace) => write!(
+                f,
+                "{}{NAMESPACE_SEPARATOR}{}",
+                escape(namespace.as_str()),
+                escape(&self.id)
+            ),
+            None => f.write_str(&self.id),
+        }
+    }
+}
+
+impl FromStr for NamespacedId {
+    type Err = NamespaceError;
+
+    /// Splits on the first unescaped `::`; plain ids parse as unqualified.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut namespace = None;
+        let mut current = String::new();
+        let mut chars = s.chars().peekable();
+        while let Some(c) = chars.next() {
+            match c {
+                '\\' => current.push(
+                    chars
+                        .next()
+                        .ok_or_else(|| NamespaceError::DanglingEscape(s.to_string()))?,
+                ),
+                ':' if namespace.is_none() && chars.peek() == Some(&':') => {
+                    chars.next();
+                    namespace = Some(Namespace::new(std::mem::take(&mut current))?);
+                }
+                c => current.push(c),
+            }
+        }
+        if current.is_empty() {
+            return Err(NamespaceError::EmptyId(s.to_string()));
+        }
+        Ok(Self::new(namespace, current))
+    }
+}
+
+/// Resolution shared by modules, groups and domains: the literal id (or
+/// alias) first, then a bare id matching exactly one namespaced entry.
+fn resolve<'a, T>(
+    items: &'a [T],
+    id: &str,
+    find: impl Fn(&str) -> Option<&'a T>,
+    id_of: impl Fn(&T) -> &str,
+) -> Option<&'a T> {
+    if let Some(found) = find(id) {
+        return Some(found);
+    }
+    let wanted: NamespacedId = id.parse().ok()?;
+    if wanted.namespace.is_some() {
+        return find(&wanted.id);
+    }
+    let mut matches = items.iter().filter(|item| {
+        id_of(item)
+            .parse::<NamespacedId>()
+            .is_ok_and(|n| n.namespace.is_some() && n.id == wanted.id)
+    });
+    let found = matches.next()?;
+    matches.next().is_none().then_some(found)
+}
+
+impl ModuleMap {
+    /// Copy with every module, group and domain id qualified by `namespace`,
+    /// references included.
+    pub fn namespaced(&self, namespace: &Namespace) -> ModuleMap {
+        let mut map = self.clone();
+        let qualify = |id: &mut String| *id = namespace.qualify(id);
+        for module in &mut map.modules {
+            qualify(&mut module.id);
+            module.aliases.iter_mut().for_each(qualify);
+            module
+                .dependencies
+                .iter_mut()
+                .for_each(|d| qualify(&mut d.module_id));
+            module.dependents.iter_mut().for_each(qualify);
+        }
+        for group in &mut map.groups {
+            qualify(&mut group.id);
+            group.aliases.iter_mut().for_each(qualify);
+            group.module_ids.iter_mut().for_each(qualify);
+            group.leader_module.iter_mut().for_each(qualify);
+            group.parent_group_id.iter_mut().for_each(qualify);
+            group.domain_id.iter_mut().for_each(qualify);
+        }
+        for domain in &mut map.domains {
+            qualify(&mut domain.id);
+            domain.aliases.iter_mut().for_each(qualify);
+            domain.group_ids.iter_mut().for_each(qualify);
+        }
+        for shared in &mut map.shared_paths {
+            shared.module_ids.iter_mut().for_each(qualify);
+        }
+        if let Some(graph) = &mut map.dependency_graph {
+            for edge in &mut graph.edges {
+                qualify(&mut edge.from);
+                qualify(&mut edge.to);
+            }
+            for layer in &mut graph.layers {
+                layer.modules.iter_mut().for_each(qualify);
+            }
+        }
+        map
+    }
+
+    /// Like `find_module`, also resolving `ns::id` in a map that was not
+    /// namespaced, and a bare id owned by exactly one namespace.
+    pub fn resolve_module(&self, id: &str) -> Option<&Module> {
+        resolve(&self.modules, id, |id| self.find_module(id), |m| &m.id)
+    }
+
+    pub fn resolve_group(&self, id: &str) -> Option<&ModuleGroup> {
+        resolve(&self.groups, id, |id| self.find_group(id), |g| &g.id)
+    }
+
+    pub fn resolve_domain(&self, id: &str) -> Option<&Domain> {
+        resolve(&self.domains, id, |id| self.find_domain(id), |d| &d.id)
+    }
+
+    /// Modules, groups and domains whose id appears in both maps, i.e. what
+    /// needs a namespace before the two can be composed.
+    pub fn id_collisions(&self, other: &ModuleMap) -> Vec<EntityRef> {
+        let modules = self
+            .modules
+            .iter()
+            .filter(|m| other.find_module(&m.id).is_some())
+            .map(|m| EntityRef::Module(m.id.clone()));
+        let groups = self
+            .groups
+            .iter()
+            .filter(|g| other.find_group(&g.id).is_some())
+            .map(|g| EntityRef::Group(g.id.clone()));
+        let domains = self
+            .domains
+            .iter()
+            .filter(|d| other.find_domain(&d.id).is_some())
+            .map(|d| EntityRef::Domain(d.id.clone()));
+        modules.chain(groups).chain(domains).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{GeneratorInfo, ModuleDependency, ProjectMetadata, TechStack};
+
+    fn module(id: &str, deps: &[&str]) -> Module {
+        Module::new(id, id, vec![format!("src/{id}/")])
+            .with_dependencies(deps.iter().map(|d| ModuleDependency::new(*d)).collect())
+            .with_primary_language("rust")
+    }
+
+    fn repo(name: &str, modules: Vec<Module>) -> ModuleMap {
+        ModuleMap::new(
+            GeneratorInfo::new("test", "1.0.0"),
+            ProjectMetadata::new(name, TechStack::new("rust")),
+            modules,
+            vec![ModuleGroup::new("core", "Core", vec!["utils".into()])],
+        )
+    }
+
+    #[test]
+    fn test_namespaced_ids() {
+        let payments = Namespace::new("payments").unwrap();
+        assert_eq!(payments.qualify("billing-core"), "payments::billing-core");
+        assert_eq!(payments.qualify("a::b"), "payments::a\\:\\:b");
+        let parsed: NamespacedId = "payments::a\\:\\:b".parse().unwrap();
+        assert_eq!(parsed.namespace, Some(payments.clone()));
+        assert_eq!(parsed.id, "a::b");
+        let bare: NamespacedId = "utils".parse().unwrap();
+        assert_eq!((bare.namespace, bare.id.as_str()), (None, "utils"));
+        assert_eq!(
+            "payments::".parse::<NamespacedId>(),
+            Err(NamespaceError::EmptyId("payments::".into()))
+        );
+        assert_eq!("::x".parse::<NamespacedId>(), Err(NamespaceError::Empty));
+
+        let a = repo(
+            "payments",
+            vec![module("utils", &[]), module("billing", &["utils"])],
+        );
+        let b = repo("shipping", vec![module("utils", &[])]);
+        assert_eq!(
+            a.id_collisions(&b),
+            [
+                EntityRef::Module("utils".into()),
+                EntityRef::Group("core".into())
+            ]
+        );
+
+        let qualified = a.namespaced(&payments);
+        let billing = qualified.find_module("payments::billing").unwrap();
+        assert_eq!(billing.dependencies[0].module_id, "payments::utils");
+        assert_eq!(qualified.groups[0].module_ids, ["payments::utils"]);
+        assert!(qualified.id_collisions(&b).is_empty());
+
+        assert_eq!(
+            qualified.resolve_module("billing").unwrap().id,
+            "payments::billing"
+        );
+        assert_eq!(a.resolve_module("payments::billing").unwrap().id, "billing");
+        assert_eq!(
+            qualified.resolve_group("core").unwrap().id,
+            "payments::core"
+        );
+
+        let mut both = qualified.clone();
+        both.modules
+            .extend(b.namespaced(&Namespace::new("shipping").unwrap()).modules);
+        assert!(both.resolve_module("utils").is_none(), "ambiguous");
+        assert!(both.resolve_module("shipping::utils").is_some());
+    }
+}
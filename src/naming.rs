@@ -0,0 +1,246 @@
+//! Machine-checkable naming conventions
+
+use std::fmt;
+
+use regex::Regex;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::module_map::{Module, ModuleMap};
+
+/// What kind of name a rule constrains
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum NamingTarget {
+    /// File name without its directory
+    File,
+    Type,
+    Function,
+    /// Module id
+    Module,
+}
+
+impl fmt::Display for NamingTarget {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::File => write!(f, "file"),
+            Self::Type => write!(f, "type"),
+            Self::Function => write!(f, "function"),
+            Self::Module => write!(f, "module"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct NamingRule {
+    pub target: NamingTarget,
+    /// Regex the whole name must match
+    pub pattern: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub example: Option<String>,
+}
+
+impl NamingRule {
+    pub fn new(target: NamingTarget, pattern: impl Into<String>) -> Self {
+        Self {
+            target,
+            pattern: pattern.into(),
+            example: None,
+        }
+    }
+
+    pub fn with_example(mut self, example: impl Into<String>) -> Self {
+        self.example = Some(example.into());
+        self
+    }
+
+    /// Anchored so the pattern must cover the whole name.
+    fn compile(&self) -> Result<Regex, NamingError> {
+        Regex::new(&format!("^(?:{})$", self.pattern)).map_err(|source| {
+            NamingError::InvalidPattern {
+                pattern: self.pattern.clone(),
+                source,
+            }
+        })
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum NamingError {
+    #[error("Invalid naming pattern {pattern}: {source}")]
+    InvalidPattern {
+        pattern: String,
+        source: regex::Error,
+    },
+}
+
+/// A name to check. `path` is the file declaring it (the file itself for
+/// `File`, unused for `Module`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NamedItem<'a> {
+    pub target: NamingTarget,
+    pub name: &'a str,
+    pub path: &'a str,
+}
+
+impl<'a> NamedItem<'a> {
+    pub fn file(path: &'a str) -> Self {
+        Self {
+            target: NamingTarget::File,
+            name: path.rsplit('/').next().unwrap_or(path),
+            path,
+        }
+    }
+
+    pub fn symbol(target: NamingTarget, name: &'a str, path: &'a str) -> Self {
+        Self { target, name, path }
+    }
+
+    pub fn module(id: &'a str) -> Self {
+        Self {
+            target: NamingTarget::Module,
+            name: id,
+            path: "",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NamingViolation {
+    pub module_id: String,
+    pub target: NamingTarget,
+    pub name: String,
+    pub path: String,
+    pub rule: NamingRule,
+}
+
+impl fmt::Display for NamingViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} '{}' in module '{}' does not match /{}/",
+            self.target, self.name, self.module_id, self.rule.pattern
+        )?;
+        if let Some(example) = &self.rule.example {
+            write!(f, " (e.g. {example})")?;
+        }
+        Ok(())
+    }
+}
+
+impl ModuleMap {
+    /// Naming rules in force for `module`: its own, then its group's.
+    pub fn naming_rules_for<'a>(&'a self, module: &'a Module) -> Vec<&'a NamingRule> {
+        module
+            .naming_rules
+            .iter()
+            .chain(
+                self.find_group_containing(&module.id)
+                    .into_iter()
+                    .flat_map(|g| &g.naming_rules),
+            )
+            .collect()
+    }
+
+    /// Checks names against the rules of the module owning each one. Names
+    /// outside any module, and targets without rules, pass.
+    pub fn check_names(&self, items: &[NamedItem]) -> Result<Vec<NamingViolation>, NamingError> {
+        let mut violations = Vec::new();
+        for item in items {
+            let module = match item.target {
+                NamingTarget::Module => self.find_module(item.name),
+                _ => self.modules.iter().find(|m| m.contains_file(item.path)),
+            };
+            let Some(module) = module else {
+                continue;
+            };
+            for rule in self.naming_rules_for(module) {
+                if rule.target == item.target && !rule.compile()?.is_match(item.name) {
+                    violations.push(NamingViolation {
+                        module_id: module.id.clone(),
+                        target: item.target,
+                        name: item.name.to_string(),
+                        path: item.path.to_string(),
+                        rule: rule.clone(),
+                    });
+                }
+            }
+        }
+        Ok(violations)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{GeneratorInfo, ModuleGroup, ProjectMetadata, TechStack};
+
+    fn module(id: &str) -> Module {
+        Module::new(id, id, vec![format!("src/{id}/")]).with_primary_language("rust")
+    }
+
+    #[test]
+    fn test_check_names() {
+        let mut api = module("api");
+        api.naming_rules = vec![
+            NamingRule::new(NamingTarget::Type, "[A-Z][A-Za-z0-9]*(Request|Response)")
+                .with_example("CreateUserRequest"),
+        ];
+        let map = ModuleMap::new(
+            GeneratorInfo::new("test", "1.0.0"),
+            ProjectMetadata::new("shop", TechStack::new("rust")),
+            vec![api, module("Billing")],
+            vec![
+                ModuleGroup::new("core", "Core", vec!["api".into(), "Billing".into()])
+                    .with_naming_rules(vec![
+                        NamingRule::new(NamingTarget::Module, "[a-z][a-z0-9-]*"),
+                        NamingRule::new(NamingTarget::File, r"[a-z0-9_]+\.rs"),
+                    ]),
+            ],
+        );
+
+        let violations = map
+            .check_names(&[
+                NamedItem::file("src/api/user_routes.rs"),
+                NamedItem::file("src/api/UserRoutes.rs"),
+                NamedItem::symbol(NamingTarget::Type, "CreateUserRequest", "src/api/dto.rs"),
+                NamedItem::symbol(NamingTarget::Type, "UserDto", "src/api/dto.rs"),
+                NamedItem::symbol(NamingTarget::Type, "Invoice", "src/Billing/lib.rs"),
+                NamedItem::module("api"),
+                NamedItem::module("Billing"),
+                NamedItem::file("README.MD"),
+            ])
+            .unwrap();
+
+        let names: Vec<_> = violations.iter().map(|v| v.name.as_str()).collect();
+        assert_eq!(names, vec!["UserRoutes.rs", "UserDto", "Billing"]);
+        assert_eq!(
+            violations[1].to_string(),
+            "type 'UserDto' in module 'api' does not match /[A-Z][A-Za-z0-9]*(Request|Response)/ (e.g. CreateUserRequest)"
+        );
+    }
+
+    #[test]
+    fn test_invalid_pattern() {
+        let mut api = module("api");
+        api.naming_rules = vec![NamingRule::new(NamingTarget::Function, "(unclosed")];
+        let map = ModuleMap::new(
+            GeneratorInfo::new("test", "1.0.0"),
+            ProjectMetadata::new("shop", TechStack::new("rust")),
+            vec![api],
+            vec![],
+        );
+        let err = map
+            .check_names(&[NamedItem::symbol(
+                NamingTarget::Function,
+                "run",
+                "src/api/a.rs",
+            )])
+            .unwrap_err();
+        assert!(
+            err.to_string()
+                .starts_with("Invalid naming pattern (unclosed")
+        );
+    }
+}
@@ -0,0 +1,745 @@
+//! Canonical form for manifests before committing them
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fmt;
+
+use serde_json::Value;
+
+use crate::entity::EntityRef;
+use crate::manifest::ProjectManifest;
+use crate::module_map::ModuleMap;
+use crate::refactor::Refactor;
+
+/// Summary of what `ProjectManifest::normalize` changed
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct NormalizationReport {
+    /// Collections whose order changed
+    pub sorted: usize,
+    /// Duplicate entries removed
+    pub deduplicated: usize,
+    /// Strings with surrounding whitespace trimmed
+    pub trimmed: usize,
+    /// Empty optional values removed
+    pub stripped: usize,
+    /// Ids rewritten to canonical form (original -> canonical)
+    pub renamed_ids: BTreeMap<String, String>,
+    /// Entries sharing a canonical id; all of them keep their original id
+    pub collisions: Vec<Collision>,
+}
+
+/// Entries of `collection` that share `key` after normalization and need a
+/// manual merge or rename
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Collision {
+    /// e.g. `modules`, `modules/auth/conventions`, `module_contexts`
+    pub collection: String,
+    pub key: String,
+}
+
+impl NormalizationReport {
+    /// Whether nothing was changed. Collisions are findings, not changes.
+    pub fn is_empty(&self) -> bool {
+        self.sorted == 0
+            && self.deduplicated == 0
+            && self.trimmed == 0
+            && self.stripped == 0
+            && self.renamed_ids.is_empty()
+    }
+
+    pub fn has_collisions(&self) -> bool {
+        !self.collisions.is_empty()
+    }
+
+    fn collision(&mut self, collection: &str, key: &str) {
+        let collision = Collision {
+            collection: collection.to_string(),
+            key: key.to_string(),
+        };
+        if !self.collisions.contains(&collision) {
+            self.collisions.push(collision);
+        }
+    }
+
+    fn id(&mut self, id: &mut String) {
+        let canonical = canonical_id(id);
+        if canonical != *id {
+            self.renamed_ids.insert(id.clone(), canonical.clone());
+            *id = canonical;
+        }
+    }
+
+    fn ids(&mut self, ids: &mut Vec<String>) {
+        ids.iter_mut().for_each(|id| self.id(id));
+        self.list(ids);
+    }
+
+    /// Like `id`, but leaves ids whose canonical form is in `colliding`.
+    fn id_in(&mut self, id: &mut String, colliding: &BTreeSet<String>) {
+        if !colliding.contains(&canonical_id(id)) {
+            self.id(id);
+        }
+    }
+
+    fn opt_id_in(&mut self, id: &mut Option<String>, colliding: &BTreeSet<String>) {
+        if let Some(inner) = id {
+            self.id_in(inner, colliding);
+        }
+        self.strip_empty(id);
+    }
+
+    fn ids_in(&mut self, ids: &mut Vec<String>, colliding: &BTreeSet<String>) {
+        ids.iter_mut().for_each(|id| self.id_in(id, colliding));
+        self.list(ids);
+    }
+
+    fn text(&mut self, text: &mut String) {
+        let trimmed = text.trim();
+        if trimmed.len() != text.len() {
+            *text = trimmed.to_string();
+            self.trimmed += 1;
+        }
+    }
+
+    fn opt_text(&mut self, text: &mut Option<String>) {
+        if let Some(inner) = text {
+            self.text(inner);
+        }
+        self.strip_empty(text);
+    }
+
+    fn texts(&mut self, texts: &mut Vec<String>) {
+        texts.iter_mut().for_each(|t| self.text(t));
+        let before = texts.len();
+        texts.retain(|t| !t.is_empty());
+        self.stripped += before - texts.len();
+        self.list(texts);
+    }
+
+    fn strip_empty(&mut self, value: &mut Option<String>) {
+        if value.as_deref().is_some_and(str::is_empty) {
+            *value = None;
+            self.stripped += 1;
+        }
+    }
+
+    fn list<T: Ord>(&mut self, items: &mut Vec<T>) {
+        if !items.is_sorted() {
+            items.sort();
+            self.sorted += 1;
+        }
+        let before = items.len();
+        items.dedup();
+        self.deduplicated += before - items.len();
+    }
+
+    /// Sorts by key and drops entries whose key repeats; the key must cover
+    /// the whole value.
+    fn list_by<T, K: Ord>(&mut self, items: &mut Vec<T>, key: impl Fn(&T) -> K) {
+        self.sort_by(items, &key);
+        let before = items.len();
+        items.dedup_by(|a, b| key(a) == key(b));
+        self.deduplicated += before - items.len();
+    }
+
+    /// Sorts by key, keeping every entry and recording ids that repeat.
+    fn entries_by<T, K: Ord>(
+        &mut self,
+        items: &mut [T],
+        collection: &str,
+        key: impl Fn(&T) -> K,
+        id: impl Fn(&T) -> &str,
+    ) {
+        self.sort_by(items, key);
+        let mut seen = BTreeSet::new();
+        for item in items.iter() {
+            if !seen.insert(id(item)) {
+                self.collision(collection, id(item));
+            }
+        }
+    }
+
+    /// Canonicalizes the new module ids of a split, keeping any id whose
+    /// canonical form another part already uses.
+    fn rekey_split(
+        &mut self,
+        parts: &mut BTreeMap<String, Vec<String>>,
+        colliding: &BTreeSet<String>,
+    ) {
+        let canonical: BTreeSet<String> = parts.keys().map(|id| canonical_id(id)).collect();
+        if canonical.len() < parts.len() {
+            return;
+        }
+        *parts = std::mem::take(parts)
+            .into_iter()
+            .map(|(mut id, paths)| {
+                self.id_in(&mut id, colliding);
+                (id, paths)
+            })
+            .collect();
+    }
+
+    fn sort_by<T, K: Ord>(&mut self, items: &mut [T], key: impl Fn(&T) -> K) {
+        if !items.is_sorted_by_key(&key) {
+            items.sort_by_key(&key);
+            self.sorted += 1;
+        }
+    }
+
+    /// Canonicalizes keys. A key whose canonical form is taken, or belongs
+    /// to `colliding` entries, stays as it was; the former is recorded as a
+    /// collision.
+    fn rekey<V>(
+        &mut self,
+        map: &mut HashMap<String, V>,
+        collection: &str,
+        colliding: &BTreeSet<String>,
+    ) {
+        let mut entries: Vec<_> = map.drain().collect();
+        // Keys already canonical first, so they keep their slot
+        entries.sort_by_cached_key(|(key, _)| (canonical_id(key) != *key, key.clone()));
+        for (mut key, value) in entries {
+            let canonical = canonical_id(&key);
+            if map.contains_key(&canonical) {
+                self.collision(collection, &canonical);
+            } else if !colliding.contains(&canonical) {
+                self.id(&mut key);
+            }
+            map.insert(key, value);
+        }
+    }
+}
+
+/// Canonical module, group and domain ids shared by more than one entry.
+/// Those entries and every reference to them keep their original ids, so
+/// normalizing never merges two entries into one id.
+#[derive(Debug, Default)]
+struct Colliding {
+    modules: BTreeSet<String>,
+    groups: BTreeSet<String>,
+    domains: BTreeSet<String>,
+}
+
+impl Colliding {
+    fn find(map: &ModuleMap, report: &mut NormalizationReport) -> Self {
+        Self {
+            modules: shared_ids(map.modules.iter().map(|m| &m.id), "modules", report),
+            groups: shared_ids(map.groups.iter().map(|g| &g.id), "groups", report),
+            domains: shared_ids(map.domains.iter().map(|d| &d.id), "domains", report),
+        }
+    }
+}
+
+fn shared_ids<'a>(
+    ids: impl Iterator<Item = &'a String>,
+    collection: &str,
+    report: &mut NormalizationReport,
+) -> BTreeSet<String> {
+    let mut seen = BTreeSet::new();
+    let mut shared = BTreeSet::new();
+    for id in ids {
+        let canonical = canonical_id(id);
+        if !seen.insert(canonical.clone()) {
+            report.collision(collection, &canonical);
+            shared.insert(canonical);
+        }
+    }
+    shared
+}
+
+/// Trimmed, lowercase id with inner whitespace replaced by `-`.
+pub fn canonical_id(id: &str) -> String {
+    id.split_whitespace()
+        .collect::<Vec<_>>()
+        .join("-")
+        .to_lowercase()
+}
+
+/// Decimal places kept for floats by `to_canonical_json`
+pub const DEFAULT_FLOAT_PRECISION: u32 = 3;
+
+/// Module score outside `[0, 1]` in raw manifest JSON
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScoreViolation {
+    pub module_id: String,
+    pub field: &'static str,
+    pub value: f64,
+    /// JSON pointer to the score, e.g. `/project/modules/0/value_score`
+    pub pointer: String,
+}
+
+impl fmt::Display for ScoreViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "module '{}' has {} = {} outside [0, 1]",
+            self.module_id, self.field, self.value
+        )
+    }
+}
+
+/// Module scores in a raw manifest that `Score` would reject.
+///
+/// A deserialized map cannot hold them, so this checks the JSON before
+/// loading; `SchemaRegistry::load_lenient` clamps what it finds here.
+pub fn score_violations(manifest: &Value) -> Vec<ScoreViolation> {
+    let Some(Value::Array(modules)) = manifest.pointer("/project/modules") else {
+        return Vec::new();
+    };
+    let mut violations = Vec::new();
+    for (index, module) in modules.iter().enumerate() {
+        for field in ["coverage_ratio", "value_score", "risk_score"] {
+            let Some(value) = module.get(field).and_then(Value::as_f64) else {
+                continue;
+            };
+            if !(0.0..=1.0).contains(&value) {
+                violations.push(ScoreViolation {
+                    module_id: module
+                        .get("id")
+                        .and_then(Value::as_str)
+                        .unwrap_or_default()
+                        .to_string(),
+                    field,
+                    value,
+                    pointer: format!("/project/modules/{index}/{field}"),
+                });
+            }
+        }
+    }
+    violations
+}
+
+/// Rounds every non-integer number in `value` to multiples of `1 / factor`.
+fn round_floats(value: &mut Value, factor: f64) {
+    match value {
+        Value::Number(n) if n.is_f64() => {
+            if let Some(rounded) = n
+                .as_f64()
+                .map(|x| (x * factor).round() / factor)
+                .and_then(serde_json::Number::from_f64)
+            {
+                *n = rounded;
+            }
+        }
+        Value::Array(items) => items.iter_mut().for_each(|v| round_floats(v, factor)),
+        Value::Object(entries) => entries.values_mut().for_each(|v| round_floats(v, factor)),
+        _ => {}
+    }
+}
+
+impl ModuleMap {
+    fn normalize_into(&mut self, report: &mut NormalizationReport, colliding: &Colliding) {
+        let project = &mut self.project;
+        report.text(&mut project.name);
+        report.opt_text(&mut project.description);
+        report.opt_text(&mut project.repository);
+        report.opt_text(&mut project.workspace.root);
+
+        for module in &mut self.modules {
+            report.id_in(&mut module.id, &colliding.modules);
+            report.ids_in(&mut module.aliases, &colliding.modules);
+            report.text(&mut module.name);
+            report.text(&mut module.responsibility);
+            report.texts(&mut module.paths);
+            report.texts(&mut module.key_files);
+            for dep in &mut module.dependencies {
+                report.id_in(&mut dep.module_id, &colliding.modules);
+            }
+            report.list_by(&mut module.dependencies, |d| {
+                (d.module_id.clone(), d.dependency_type.clone())
+            });
+            report.ids_in(&mut module.dependents, &colliding.modules);
+            for convention in &mut module.conventions {
+                report.text(&mut convention.name);
+                report.text(&mut convention.pattern);
+                report.opt_text(&mut convention.rationale);
+            }
+            report.entries_by(
+                &mut module.conventions,
+                &format!("modules/{}/conventions", module.id),
+                |c| c.name.clone(),
+                |c| &c.name,
+            );
+            for issue in &mut module.known_issues {
+                report.id(&mut issue.id);
+                report.text(&mut issue.description);
+                report.opt_text(&mut issue.prevention);
+            }
+            report.entries_by(
+                &mut module.known_issues,
+                &format!("modules/{}/known_issues", module.id),
+                |i| (i.severity, i.id.clone()),
+                |i| &i.id,
+            );
+        }
+        report.entries_by(&mut self.modules, "modules", |m| m.id.clone(), |m| &m.id);
+
+        for group in &mut self.groups {
+            report.id_in(&mut group.id, &colliding.groups);
+            report.ids_in(&mut group.aliases, &colliding.groups);
+            report.text(&mut group.responsibility);
+            report.ids_in(&mut group.module_ids, &colliding.modules);
+            report.texts(&mut group.boundary_rules);
+            report.opt_id_in(&mut group.leader_module, &colliding.modules);
+            report.opt_id_in(&mut group.parent_group_id, &colliding.groups);
+            report.opt_id_in(&mut group.domain_id, &colliding.domains);
+        }
+        report.entries_by(&mut self.groups, "groups", |g| g.id.clone(), |g| &g.id);
+
+        for domain in &mut self.domains {
+            report.id_in(&mut domain.id, &colliding.domains);
+            report.ids_in(&mut domain.aliases, &colliding.domains);
+            report.text(&mut domain.responsibility);
+            report.ids_in(&mut domain.group_ids, &colliding.groups);
+            report.texts(&mut domain.boundary_rules);
+            report.opt_text(&mut domain.owner);
+            // Consumers name a module or a domain
+            let consumers: BTreeSet<String> = colliding
+                .modules
+                .union(&colliding.domains)
+                .cloned()
+                .collect();
+            for interface in &mut domain.interfaces {
+                report.ids_in(&mut interface.consumers, &consumers);
+            }
+            report.entries_by(
+                &mut domain.interfaces,
+                &format!("domains/{}/interfaces", domain.id),
+                |i| i.name.clone(),
+                |i| &i.name,
+            );
+        }
+        report.entries_by(&mut self.domains, "domains", |d| d.id.clone(), |d| &d.id);
+
+        for shared in &mut self.shared_paths {
+            report.text(&mut shared.path);
+            report.ids_in(&mut shared.module_ids, &colliding.modules);
+        }
+
+        if let Some(graph) = &mut self.dependency_graph {
+            for edge in &mut graph.edges {
+                report.id_in(&mut edge.from, &colliding.modules);
+                report.id_in(&mut edge.to, &colliding.modules);
+            }
+            report.list_by(&mut graph.edges, |e| {
+                (e.from.clone(), e.to.clone(), e.edge_type.clone())
+            });
+            for layer in &mut graph.layers {
+                report.ids_in(&mut layer.modules, &colliding.modules);
+            }
+            if graph.edges.is_empty() && graph.layers.is_empty() {
+                self.dependency_graph = None;
+                report.stripped += 1;
+            }
+        }
+    }
+}
+
+impl ProjectManifest {
+    /// Sorts, deduplicates, trims and canonicalizes ids in place.
+    ///
+    /// Only exact duplicates are dropped. Modules, groups, domains and
+    /// context entries whose ids share a canonical form keep their original
+    /// ids, as do references to them, and are listed in
+    /// `NormalizationReport::collisions`.
+    ///
+    /// Ids are rewritten consistently across the map, the context maps,
+    /// tasks, waivers and refactor plans.
+    /// Architecture layer order is preserved; empty context entries are kept
+    /// since they mark modules as explicitly context-free.
+    pub fn normalize(&mut self) -> NormalizationReport {
+        let mut report = NormalizationReport::default();
+        let colliding = Colliding::find(&self.project, &mut report);
+        self.project.normalize_into(&mut report, &colliding);
+
+        report.texts(&mut self.rules);
+        report.texts(&mut self.skills);
+        report.texts(&mut self.agents);
+
+        report.rekey(&mut self.modules, "module_contexts", &colliding.modules);
+        for ctx in self.modules.values_mut() {
+            report.texts(&mut ctx.rules);
+            report.texts(&mut ctx.skills);
+            report.texts(&mut ctx.conventions);
+            report.texts(&mut ctx.issues);
+            report.opt_id_in(&mut ctx.group_id, &colliding.groups);
+            report.opt_id_in(&mut ctx.domain_id, &colliding.domains);
+        }
+
+        report.rekey(&mut self.groups, "group_contexts", &colliding.groups);
+        for ctx in self.groups.values_mut() {
+            report.texts(&mut ctx.rules);
+            report.texts(&mut ctx.constraints);
+            report.ids_in(&mut ctx.member_modules, &colliding.modules);
+            report.opt_id_in(&mut ctx.domain_id, &colliding.domains);
+        }
+
+        report.rekey(&mut self.domains, "domain_contexts", &colliding.domains);
+        for ctx in self.domains.values_mut() {
+            report.texts(&mut ctx.rules);
+            report.texts(&mut ctx.constraints);
+            report.ids_in(&mut ctx.member_groups, &colliding.groups);
+            report.texts(&mut ctx.interfaces);
+        }
+
+        for task in &mut self.tasks {
+            report.ids_in(&mut task.module_ids, &colliding.modules);
+            report.ids_in(&mut task.domain_ids, &colliding.domains);
+            report.ids(&mut task.issue_ids);
+        }
+        for waiver in &mut self.waivers {
+            match &mut waiver.target {
+                Some(EntityRef::Module(id)) => report.id_in(id, &colliding.modules),
+                Some(EntityRef::Group(id)) => report.id_in(id, &colliding.groups),
+                Some(EntityRef::Domain(id)) => report.id_in(id, &colliding.domains),
+                Some(EntityRef::Issue(id)) => report.id(id),
+                _ => {}
+            }
+        }
+        for plan in &mut self.refactor_plans {
+            for step in &mut plan.steps {
+                match step {
+                    Refactor::Merge { a, b } => {
+                        report.id_in(a, &colliding.modules);
+                        report.id_in(b, &colliding.modules);
+                    }
+                    Refactor::Split { module, by_paths } => {
+                        report.id_in(module, &colliding.modules);
+                        report.rekey_split(by_paths, &colliding.modules);
+                    }
+                    Refactor::MoveToGroup { module, group } => {
+                        report.id_in(module, &colliding.modules);
+                        report.id_in(group, &colliding.groups);
+                    }
+                }
+            }
+            report.ids_in(&mut plan.affected_modules, &colliding.modules);
+        }
+
+        for file in &mut self.tracked {
+            report.text(&mut file.path);
+        }
+        report.entries_by(
+            &mut self.tracked,
+            "tracked",
+            |t| t.path.clone(),
+            |t| &t.path,
+        );
+
+        report
+    }
+
+    /// Pretty JSON with object keys sorted and floats rounded to `decimals`
+    /// places, so goldens and diffs do not churn on float noise.
+    pub fn to_canonical_json(&self, decimals: u32) -> Result<String, serde_json::Error> {
+        let mut value = serde_json::to_value(self)?;
+        round_floats(&mut value, 10f64.powi(decimals as i32));
+        serde_json::to_string_pretty(&value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        GeneratorInfo, Module, ModuleContext, ModuleGroup, ProjectMetadata, RefactorPlan,
+        SharedPath, Task, TechStack, TrackedFile, Waiver,
+    };
+
+    fn sample_manifest() -> ProjectManifest {
+        let map = ModuleMap::new(
+            GeneratorInfo::new("test", "1.0.0"),
+            ProjectMetadata::new("test", TechStack::new("rust")).with_description("  "),
+            vec![],
+            vec![
+                ModuleGroup::new(
+                    "Core",
+                    "Core",
+                    vec!["Auth".into(), "api".into(), "api".into()],
+                ),
+                ModuleGroup::new("api-group", "API", vec![]),
+            ],
+        );
+
+        let mut modules = HashMap::new();
+        modules.insert(
+            "Auth".to_string(),
+            ModuleContext::new()
+                .with_rules(vec!["rules/b.md".into(), " rules/a.md ".into()])
+                .with_group("Core"),
+        );
+
+        ProjectManifest::new(map)
+            .with_modules(modules)
+            .with_skills(vec!["skills/x/SKILL.md".into(), "skills/x/SKILL.md".into()])
+            .with_tracked(vec![
+                TrackedFile::new("src/b.rs", "1", 0),
+                TrackedFile::new("src/a.rs", "2", 0),
+            ])
+    }
+
+    #[test]
+    fn test_canonical_id() {
+        assert_eq!(canonical_id("  Auth Core "), "auth-core");
+        assert_eq!(canonical_id("api_v2"), "api_v2");
+    }
+
+    #[test]
+    fn test_normalize() {
+        let mut manifest = sample_manifest();
+        let report = manifest.normalize();
+
+        assert_eq!(report.renamed_ids.get("Auth"), Some(&"auth".to_string()));
+        assert_eq!(report.renamed_ids.get("Core"), Some(&"core".to_string()));
+        assert!(report.stripped >= 1);
+        assert!(report.deduplicated >= 2);
+
+        let map = &manifest.project;
+        assert!(map.project.description.is_none());
+        assert_eq!(map.groups[0].id, "api-group");
+        assert_eq!(map.groups[1].module_ids, vec!["api", "auth"]);
+
+        let ctx = manifest.get_module_context("auth").unwrap();
+        assert_eq!(ctx.rules, vec!["rules/a.md", "rules/b.md"]);
+        assert_eq!(ctx.group_id, Some("core".into()));
+        assert_eq!(manifest.skills.len(), 1);
+        assert_eq!(manifest.tracked[0].path, "src/a.rs");
+    }
+
+    #[test]
+    fn test_normalize_keeps_colliding_entries() {
+        let mut manifest = sample_manifest();
+        manifest
+            .project
+            .groups
+            .push(ModuleGroup::new("core", "Core (new)", vec![]));
+        manifest.modules.insert(
+            "auth".into(),
+            ModuleContext::new().with_rules(vec!["rules/c.md".into()]),
+        );
+        let report = manifest.normalize();
+
+        let groups: Vec<(&str, &str)> = manifest
+            .project
+            .groups
+            .iter()
+            .map(|g| (g.id.as_str(), g.name.as_str()))
+            .collect();
+        assert_eq!(
+            groups,
+            [
+                ("Core", "Core"),
+                ("api-group", "API"),
+                ("core", "Core (new)")
+            ]
+        );
+        assert!(!report.renamed_ids.contains_key("Core"));
+        assert_eq!(manifest.modules["Auth"].group_id.as_deref(), Some("Core"));
+        assert_eq!(manifest.modules.len(), 2);
+        assert_eq!(
+            manifest.get_module_context("auth").unwrap().rules,
+            ["rules/c.md"]
+        );
+        assert_eq!(manifest.modules["Auth"].rules.len(), 2);
+        assert_eq!(
+            report.collisions,
+            [
+                Collision {
+                    collection: "groups".into(),
+                    key: "core".into(),
+                },
+                Collision {
+                    collection: "module_contexts".into(),
+                    key: "auth".into(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_normalize_rewrites_references() {
+        let mut manifest = sample_manifest();
+        let map = &mut manifest.project;
+        map.modules = vec![Module::new(
+            "Billing",
+            "Billing",
+            vec!["src/billing/".into()],
+        )];
+        map.modules[0].aliases = vec!["Payments".into()];
+        map.shared_paths = vec![SharedPath::new("Cargo.toml", vec!["Billing".into()])];
+        manifest.tasks = vec![Task::new("t1", "Fix").with_modules(vec!["Billing".into()])];
+        manifest.waivers = vec![Waiver::new(
+            "layering",
+            EntityRef::Group("Core".into()),
+            "legacy",
+        )];
+        manifest.refactor_plans = vec![RefactorPlan {
+            id: "p1".into(),
+            title: "Move".into(),
+            steps: vec![Refactor::MoveToGroup {
+                module: "Billing".into(),
+                group: "Core".into(),
+            }],
+            affected_modules: vec!["Billing".into()],
+            estimated_risk: 0.0,
+            checklist: vec![],
+        }];
+        manifest.normalize();
+
+        let map = &manifest.project;
+        assert_eq!(map.modules[0].aliases, ["payments"]);
+        assert_eq!(map.shared_paths[0].module_ids, ["billing"]);
+        assert_eq!(manifest.tasks[0].module_ids, ["billing"]);
+        assert_eq!(
+            manifest.waivers[0].target,
+            Some(EntityRef::Group("core".into()))
+        );
+        let plan = &manifest.refactor_plans[0];
+        assert_eq!(
+            plan.steps[0],
+            Refactor::MoveToGroup {
+                module: "billing".into(),
+                group: "core".into(),
+            }
+        );
+        assert_eq!(plan.affected_modules, ["billing"]);
+    }
+
+    #[test]
+    fn test_normalize_is_idempotent() {
+        let mut manifest = sample_manifest();
+        manifest.normalize();
+        assert!(manifest.normalize().is_empty());
+    }
+
+    #[test]
+    fn test_canonical_json_rounds_floats() {
+        let module = |value_score: f64| {
+            serde_json::from_value::<crate::Module>(serde_json::json!({
+                "id": "auth", "name": "Auth", "paths": ["src/auth/"],
+                "responsibility": "", "primary_language": "rust",
+                "coverage_ratio": 0.1 + 0.2, "value_score": value_score, "risk_score": 0.0
+            }))
+        };
+        let mut manifest = sample_manifest();
+        manifest.project.modules = vec![module(0.5).unwrap()];
+
+        let json = manifest.to_canonical_json(DEFAULT_FLOAT_PRECISION).unwrap();
+        assert!(json.contains("\"coverage_ratio\": 0.3,"));
+        assert!(!json.contains("0.30000000000000004"));
+        assert!(json.contains("\"risk_score\": 0.0"));
+
+        let err = module(1.5).unwrap_err();
+        assert!(err.to_string().contains("score 1.5 is outside [0, 1]"));
+
+        let raw = serde_json::json!({"project": {"modules": [
+            {"id": "auth", "coverage_ratio": 0.3, "value_score": 1.5, "risk_score": 0.0}
+        ]}});
+        let violations = score_violations(&raw);
+        assert_eq!(violations.len(), 1);
+        assert_eq!(
+            violations[0].to_string(),
+            "module 'auth' has value_score = 1.5 outside [0, 1]"
+        );
+        assert_eq!(violations[0].pointer, "/project/modules/0/value_score");
+    }
+}
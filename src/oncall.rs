@@ -0,0 +1,187 @@
+//! On-call schedules: who owns a domain right now
+
+use std::convert::Infallible;
+
+use chrono::{DateTime, Utc};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::module_map::ModuleMap;
+
+/// One on-call shift, `start` inclusive and `end` exclusive
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct OncallShift {
+    pub owner: String,
+    #[serde(deserialize_with = "crate::timestamp::deserialize_timestamp")]
+    pub start: DateTime<Utc>,
+    #[serde(deserialize_with = "crate::timestamp::deserialize_timestamp")]
+    pub end: DateTime<Utc>,
+}
+
+impl OncallShift {
+    pub fn new(owner: impl Into<String>, start: DateTime<Utc>, end: DateTime<Utc>) -> Self {
+        Self {
+            owner: owner.into(),
+            start,
+            end,
+        }
+    }
+
+    pub fn covers(&self, at: DateTime<Utc>) -> bool {
+        self.start <= at && at < self.end
+    }
+}
+
+/// Reference to an external on-call schedule, with an optional static rotation
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct OncallSchedule {
+    /// Provider-specific schedule id, e.g. `pagerduty:P1ABC23`
+    pub schedule_ref: String,
+    /// Shifts used by `StaticOncall`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub rotation: Vec<OncallShift>,
+}
+
+impl OncallSchedule {
+    pub fn new(schedule_ref: impl Into<String>) -> Self {
+        Self {
+            schedule_ref: schedule_ref.into(),
+            rotation: Vec::new(),
+        }
+    }
+
+    pub fn with_rotation(mut self, rotation: Vec<OncallShift>) -> Self {
+        self.rotation = rotation;
+        self
+    }
+}
+
+/// Resolves a schedule to the person on call; implemented by callers for
+/// PagerDuty, Opsgenie or an internal calendar
+pub trait OncallProvider {
+    type Error;
+
+    /// `None` when nobody is on call at `at`
+    fn on_call(
+        &self,
+        schedule: &OncallSchedule,
+        at: DateTime<Utc>,
+    ) -> Result<Option<String>, Self::Error>;
+}
+
+/// Reads the schedule's own `rotation`; the first covering shift wins
+#[derive(Debug, Clone, Copy, Default)]
+pub struct StaticOncall;
+
+impl OncallProvider for StaticOncall {
+    type Error = Infallible;
+
+    fn on_call(
+        &self,
+        schedule: &OncallSchedule,
+        at: DateTime<Utc>,
+    ) -> Result<Option<String>, Self::Error> {
+        Ok(schedule
+            .rotation
+            .iter()
+            .find(|shift| shift.covers(at))
+            .map(|shift| shift.owner.clone()))
+    }
+}
+
+impl ModuleMap {
+    /// On-call owner of the module's domain at `at` from its static rotation,
+    /// falling back to the domain `owner`.
+    pub fn current_owner_at(&self, module_id: &str, at: DateTime<Utc>) -> Option<String> {
+        match self.current_owner_at_with(&StaticOncall, module_id, at) {
+            Ok(owner) => owner,
+            Err(never) => match never {},
+        }
+    }
+
+    /// Like `current_owner_at`, resolving the domain's schedule through `provider`.
+    pub fn current_owner_at_with<P: OncallProvider>(
+        &self,
+        provider: &P,
+        module_id: &str,
+        at: DateTime<Utc>,
+    ) -> Result<Option<String>, P::Error> {
+        let Some(domain) = self.find_domain_containing_module(module_id) else {
+            return Ok(None);
+        };
+        let on_call = match &domain.oncall {
+            Some(schedule) => provider.on_call(schedule, at)?,
+            None => None,
+        };
+        Ok(on_call.or_else(|| domain.owner.clone()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+    use crate::{Domain, GeneratorInfo, ModuleGroup, ProjectMetadata, TechStack};
+
+    fn at(day: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 3, day, 9, 0, 0).unwrap()
+    }
+
+    struct Pager;
+
+    impl OncallProvider for Pager {
+        type Error = String;
+
+        fn on_call(
+            &self,
+            schedule: &OncallSchedule,
+            _at: DateTime<Utc>,
+        ) -> Result<Option<String>, String> {
+            Err(format!("{} unreachable", schedule.schedule_ref))
+        }
+    }
+
+    #[test]
+    fn test_current_owner_at() {
+        let schedule = OncallSchedule::new("pagerduty:P1").with_rotation(vec![
+            OncallShift::new("@alice", at(1), at(8)),
+            OncallShift::new("@bob", at(8), at(15)),
+        ]);
+        let mut domain = Domain::new("identity", "Identity", vec!["core".into()])
+            .with_owner("identity-team")
+            .with_oncall(schedule);
+        let map = ModuleMap::new(
+            GeneratorInfo::new("test", "1.0.0"),
+            ProjectMetadata::new("shop", TechStack::new("rust")),
+            vec![],
+            vec![ModuleGroup::new("core", "Core", vec!["auth".into()])],
+        )
+        .with_domains(vec![domain.clone()]);
+
+        assert_eq!(
+            map.current_owner_at("auth", at(3)).as_deref(),
+            Some("@alice")
+        );
+        assert_eq!(map.current_owner_at("auth", at(8)).as_deref(), Some("@bob"));
+        assert_eq!(
+            map.current_owner_at("auth", at(20)).as_deref(),
+            Some("identity-team")
+        );
+        assert_eq!(map.current_owner_at("billing", at(3)), None);
+        assert_eq!(
+            map.current_owner_at_with(&Pager, "auth", at(3)),
+            Err("pagerduty:P1 unreachable".to_string())
+        );
+
+        let json = serde_json::to_value(&map).unwrap();
+        assert_eq!(json["domains"][0]["oncall"]["rotation"][1]["owner"], "@bob");
+        domain.oncall = None;
+        assert!(
+            serde_json::to_value(&domain)
+                .unwrap()
+                .get("oncall")
+                .is_none()
+        );
+    }
+}
@@ -0,0 +1,385 @@
+//! Path ownership lookup: shared paths, vendored code and overlap resolution
+
+use thiserror::Error;
+
+use crate::module_map::{Module, ModuleMap, SharedPath};
+
+/// How `ModuleIndex` picks one module when several paths contain a file
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum OwnershipPolicy {
+    /// Most specific path wins; equal prefixes fall back to map order
+    #[default]
+    LongestPrefix,
+    /// Highest `Module::path_priority` wins, then longest prefix
+    Priority,
+    /// More than one candidate module is an error
+    ErrorOnAmbiguity,
+}
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum OwnershipError {
+    #[error("Path {path} is claimed by several modules: {}", modules.join(", "))]
+    Ambiguous { path: String, modules: Vec<String> },
+}
+
+/// A module path containing the looked-up file
+#[derive(Debug, Clone, Copy)]
+pub struct OwnershipCandidate<'a> {
+    pub module: &'a Module,
+    /// The matching entry of `module.paths`
+    pub prefix: &'a str,
+}
+
+/// Chosen owner plus every module path that matched
+#[derive(Debug, Clone)]
+pub struct Resolution<'a> {
+    pub owner: PathOwner<'a>,
+    /// Longest prefix first
+    pub candidates: Vec<OwnershipCandidate<'a>>,
+}
+
+/// Path-prefix index over a map's modules with a configurable tie-break policy.
+///
+/// Shared paths always take precedence. `ModuleMap::owner_of` and
+/// `ModuleMap::modules_for_file` resolve through it with the default policy.
+#[derive(Debug, Clone)]
+pub struct ModuleIndex<'a> {
+    map: &'a ModuleMap,
+    /// Longest prefix first, map order among equal lengths
+    entries: Vec<OwnershipCandidate<'a>>,
+    policy: OwnershipPolicy,
+}
+
+impl<'a> ModuleIndex<'a> {
+    pub fn new(map: &'a ModuleMap) -> Self {
+        let mut entries: Vec<OwnershipCandidate> = map
+            .modules
+            .iter()
+            .flat_map(|module| {
+                module
+                    .paths
+                    .iter()
+                    .map(move |prefix| OwnershipCandidate { module, prefix })
+            })
+            .collect();
+        entries.sort_by_key(|c| std::cmp::Reverse(c.prefix.len()));
+        Self {
+            map,
+            entries,
+            policy: OwnershipPolicy::default(),
+        }
+    }
+
+    pub fn with_policy(mut self, policy: OwnershipPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// Every module path containing `path`, longest prefix first.
+    pub fn candidates(&self, path: &str) -> Vec<OwnershipCandidate<'a>> {
+        self.entries
+            .iter()
+            .filter(|c| path.starts_with(c.prefix))
+            .copied()
+            .collect()
+    }
+
+    pub fn resolve(&self, path: &str) -> Result<Resolution<'a>, OwnershipError> {
+        let candidates = self.candidates(path);
+        if let Some(owner) = self.map.shared_owner(path) {
+            return Ok(Resolution { owner, candidates });
+        }
+
+        let chosen = match self.policy {
+            OwnershipPolicy::LongestPrefix => candidates.first(),
+            OwnershipPolicy::Priority => candidates
+                .iter()
+                .enumerate()
+                .max_by_key(|(i, c)| (c.module.path_priority.unwrap_or(0), std::cmp::Reverse(*i)))
+                .map(|(_, c)| c),
+            OwnershipPolicy::ErrorOnAmbiguity => {
+                let mut modules: Vec<&str> = Vec::new();
+                for c in &candidates {
+                    if !modules.contains(&c.module.id.as_str()) {
+                        modules.push(&c.module.id);
+                    }
+                }
+                if modules.len() > 1 {
+                    return Err(OwnershipError::Ambiguous {
+                        path: path.to_string(),
+                        modules: modules.into_iter().map(String::from).collect(),
+                    });
+                }
+                candidates.first()
+            }
+        };
+        Ok(Resolution {
+            owner: chosen.map_or(PathOwner::Unowned, |c| PathOwner::Module(c.module)),
+            candidates,
+        })
+    }
+
+    /// Chosen owning module, `None` for shared, unowned or ambiguous paths.
+    pub fn find_module(&self, path: &str) -> Option<&'a Module> {
+        self.resolve(path).ok()?.owner.module()
+    }
+}
+
+/// Who owns a file path
+#[derive(Debug, Clone)]
+pub enum PathOwner<'a> {
+    Module(&'a Module),
+    /// Communal path; `modules` are the ones allowed to touch it (all when unrestricted)
+    Shared {
+        shared: &'a SharedPath,
+        modules: Vec<&'a Module>,
+    },
+    Unowned,
+}
+
+impl<'a> PathOwner<'a> {
+    pub fn module(&self) -> Option<&'a Module> {
+        match self {
+            Self::Module(module) => Some(module),
+            _ => None,
+        }
+    }
+
+    pub fn is_shared(&self) -> bool {
+        matches!(self, Self::Shared { .. })
+    }
+}
+
+impl ModuleMap {
+    /// Most specific shared path containing `path`.
+    pub fn shared_path_for(&self, path: &str) -> Option<&SharedPath> {
+        self.shared_paths
+            .iter()
+            .filter(|s| s.contains_file(path))
+            .max_by_key(|s| s.path.len())
+    }
+
+    fn shared_owner(&self, path: &str) -> Option<PathOwner<'_>> {
+        let shared = self.shared_path_for(path)?;
+        Some(PathOwner::Shared {
+            shared,
+            modules: self
+                .modules
+                .iter()
+                .filter(|m| shared.allows(&m.id))
+                .collect(),
+        })
+    }
+
+    /// Owner of `path`: the module with the longest containing path. Shared
+    /// paths take precedence over module paths so communal files never
+    /// resolve to whichever module happens to match.
+    pub fn owner_of(&self, path: &str) -> PathOwner<'_> {
+        ModuleIndex::new(self)
+            .resolve(path)
+            .map_or(PathOwner::Unowned, |r| r.owner)
+    }
+
+    /// Module whose longest path contains `path`; equal prefixes fall back to map
+    /// order. Unlike `owner_of`, shared paths are not consulted.
+    pub fn find_module_for_file(&self, path: &str) -> Option<&Module> {
+        ModuleIndex::new(self)
+            .candidates(path)
+            .first()
+            .map(|c| c.module)
+    }
+
+    /// Every module with a path containing `path`, longest matching prefix first.
+    pub fn modules_for_file(&self, path: &str) -> Vec<&Module> {
+        let mut modules: Vec<&Module> = Vec::new();
+        for candidate in ModuleIndex::new(self).candidates(path) {
+            if !modules.iter().any(|m| std::ptr::eq(*m, candidate.module)) {
+                modules.push(candidate.module);
+            }
+        }
+        modules
+    }
+
+    /// Whether `module_id` may change `path`: it owns it, or the path is shared with it.
+    pub fn may_touch(&self, module_id: &str, path: &str) -> bool {
+        match self.shared_path_for(path) {
+            Some(shared) => shared.allows(module_id),
+            None => self
+                .find_module(module_id)
+                .is_some_and(|m| m.contains_file(path)),
+        }
+    }
+
+    /// Files that belong to no module and are neither shared nor vendored,
+    /// i.e. code the map has yet to cluster.
+    pub fn unclustered_paths<'f>(&self, files: &[&'f str]) -> Vec<&'f str> {
+        files
+            .iter()
+            .copied()
+            .filter(|f| {
+                !self.is_vendored(f)
+                    && self.shared_path_for(f).is_none()
+                    && !self.modules.iter().any(|m| m.contains_file(f))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{BoundaryViolation, GeneratorInfo, ProjectMetadata, TechStack, VendoredPath};
+
+    fn module(id: &str, paths: &[&str]) -> Module {
+        Module::new(id, id, paths.iter().map(|p| p.to_string()).collect())
+            .with_primary_language("rust")
+    }
+
+    fn sample() -> ModuleMap {
+        ModuleMap::new(
+            GeneratorInfo::new("test", "1.0.0"),
+            ProjectMetadata::new("shop", TechStack::new("rust")),
+            vec![
+                module("api", &["src/api/", "proto/api/"]),
+                module("worker", &["src/worker/"]),
+                module("web", &["web/"]),
+            ],
+            vec![],
+        )
+        .with_shared_paths(vec![
+            SharedPath::new("proto/", vec!["api".into(), "worker".into()])
+                .with_description("gRPC contracts"),
+            SharedPath::new("docs/", vec![]),
+        ])
+    }
+
+    #[test]
+    fn test_owner_of() {
+        let map = sample();
+        assert_eq!(map.owner_of("src/api/lib.rs").module().unwrap().id, "api");
+        assert!(matches!(map.owner_of("README.md"), PathOwner::Unowned));
+
+        let mut nested = map.clone();
+        nested.modules.insert(0, module("legacy", &["src/"]));
+        assert_eq!(
+            nested.owner_of("src/api/lib.rs").module().unwrap().id,
+            "api",
+            "longest prefix, not map order"
+        );
+
+        let PathOwner::Shared { shared, modules } = map.owner_of("proto/api/users.proto") else {
+            panic!("expected shared");
+        };
+        assert_eq!(shared.path, "proto/");
+        let ids: Vec<_> = modules.iter().map(|m| m.id.as_str()).collect();
+        assert_eq!(ids, vec!["api", "worker"]);
+        assert!(map.owner_of("docs/intro.md").is_shared());
+    }
+
+    #[test]
+    fn test_may_touch_and_boundaries() {
+        let map = sample();
+        assert!(map.may_touch("worker", "proto/jobs.proto"));
+        assert!(!map.may_touch("web", "proto/jobs.proto"));
+        assert!(map.may_touch("web", "docs/web.md"));
+        assert!(!map.may_touch("web", "src/api/lib.rs"));
+
+        assert_eq!(
+            map.check_boundaries(),
+            vec![BoundaryViolation::ClaimedSharedPath {
+                module_id: "api".into(),
+                path: "proto/api/".into(),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_unclustered_paths_skip_vendored() {
+        let mut map = sample();
+        map.project.vendored_paths = vec![VendoredPath::new("vendor/")];
+        assert_eq!(
+            map.unclustered_paths(&[
+                "src/api/lib.rs",
+                "vendor/github.com/x/y.go",
+                "docs/intro.md",
+                "scripts/release.sh",
+            ]),
+            vec!["scripts/release.sh"]
+        );
+
+        map.modules.push(module("vendor", &["vendor/github.com/"]));
+        assert!(
+            map.check_boundaries()
+                .contains(&BoundaryViolation::ClaimedVendoredPath {
+                    module_id: "vendor".into(),
+                    path: "vendor/github.com/".into(),
+                })
+        );
+    }
+
+    #[test]
+    fn test_module_index_policies() {
+        let mut map = sample();
+        map.modules.push(module("api-admin", &["src/api/admin/"]));
+        map.modules.push(module("legacy", &["src/"]));
+        map.modules[3].path_priority = Some(-1);
+        map.modules[4].path_priority = Some(5);
+
+        let index = ModuleIndex::new(&map);
+        let resolution = index.resolve("src/api/admin/users.rs").unwrap();
+        assert_eq!(resolution.owner.module().unwrap().id, "api-admin");
+        let prefixes: Vec<_> = resolution.candidates.iter().map(|c| c.prefix).collect();
+        assert_eq!(prefixes, vec!["src/api/admin/", "src/api/", "src/"]);
+
+        let index = ModuleIndex::new(&map).with_policy(OwnershipPolicy::Priority);
+        assert_eq!(
+            index.find_module("src/api/admin/users.rs").unwrap().id,
+            "legacy"
+        );
+
+        let index = ModuleIndex::new(&map).with_policy(OwnershipPolicy::ErrorOnAmbiguity);
+        assert_eq!(
+            index.resolve("src/worker/job.rs").unwrap_err(),
+            OwnershipError::Ambiguous {
+                path: "src/worker/job.rs".into(),
+                modules: vec!["worker".into(), "legacy".into()],
+            }
+        );
+        assert_eq!(index.find_module("web/app.ts").unwrap().id, "web");
+
+        let shared = index.resolve("proto/api/users.proto").unwrap();
+        assert!(shared.owner.is_shared());
+        assert_eq!(shared.candidates[0].module.id, "api");
+        assert!(matches!(
+            index.resolve("README.md").unwrap().owner,
+            PathOwner::Unowned
+        ));
+    }
+
+    #[test]
+    fn test_find_module_for_file() {
+        let mut map = sample();
+        map.modules.insert(0, module("legacy", &["src/"]));
+        map.modules.push(module("api-admin", &["src/api/admin/"]));
+
+        assert_eq!(
+            map.find_module_for_file("src/api/admin/users.rs")
+                .unwrap()
+                .id,
+            "api-admin"
+        );
+        let ids: Vec<&str> = map
+            .modules_for_file("src/api/admin/users.rs")
+            .iter()
+            .map(|m| m.id.as_str())
+            .collect();
+        assert_eq!(ids, ["api-admin", "api", "legacy"]);
+        assert_eq!(
+            map.find_module_for_file("proto/api/users.proto")
+                .unwrap()
+                .id,
+            "api"
+        );
+        assert!(map.find_module_for_file("README.md").is_none());
+    }
+}
@@ -0,0 +1,226 @@
+//! Workspace-relative path resolution
+
+use std::path::{Component, Path, PathBuf};
+
+use thiserror::Error;
+
+use crate::manifest::{ProjectManifest, TrackedFile};
+use crate::module_map::{Module, WorkspaceInfo};
+use crate::rule::Rule;
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum PathError {
+    #[error("Absolute path not allowed: {0}")]
+    Absolute(String),
+
+    #[error("Path escapes base directory: {0}")]
+    EscapesBase(String),
+}
+
+/// Resolves manifest-relative paths to absolute locations.
+///
+/// Module paths and tracked files are relative to the workspace root
+/// (`WorkspaceInfo.root`, itself relative to the manifest directory).
+/// Resource paths (rules, skills, agents) are relative to the manifest
+/// directory. Resolved paths never escape their base.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WorkspacePaths {
+    manifest_dir: PathBuf,
+    root: PathBuf,
+}
+
+impl WorkspacePaths {
+    pub fn new(manifest_path: &Path, workspace: &WorkspaceInfo) -> Self {
+        let manifest_dir = manifest_path
+            .parent()
+            .map(Path::to_path_buf)
+            .unwrap_or_default();
+        let root = match workspace.root.as_deref() {
+            Some(root) => normalize(&manifest_dir.join(root)),
+            None => manifest_dir.clone(),
+        };
+        Self { manifest_dir, root }
+    }
+
+    pub fn from_manifest(manifest_path: &Path, manifest: &ProjectManifest) -> Self {
+        Self::new(manifest_path, &manifest.project.project.workspace)
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    pub fn manifest_dir(&self) -> &Path {
+        &self.manifest_dir
+    }
+
+    /// Resolves a workspace-relative path (module paths, tracked files).
+    pub fn resolve(&self, relative: &str) -> Result<PathBuf, PathError> {
+        Self::join_within(&self.root, relative)
+    }
+
+    /// Resolves a manifest-relative resource path (rule, skill, agent files).
+    pub fn resolve_resource(&self, relative: &str) -> Result<PathBuf, PathError> {
+        Self::join_within(&self.manifest_dir, relative)
+    }
+
+    pub fn module_paths(&self, module: &Module) -> Result<Vec<PathBuf>, PathError> {
+        module.paths.iter().map(|p| self.resolve(p)).collect()
+    }
+
+    pub fn rule_output_path(&self, rule: &Rule) -> Result<PathBuf, PathError> {
+        self.resolve_resource(&format!("rules/{}", rule.output_path()))
+    }
+
+    pub fn tracked_file(&self, file: &TrackedFile) -> Result<PathBuf, PathError> {
+        self.resolve(&file.path)
+    }
+
+    /// Converts an absolute path back to a workspace-relative string.
+    pub fn relativize(&self, path: &Path) -> Option<String> {
+        normalize(path)
+            .strip_prefix(&self.root)
+            .ok()
+            .map(|p| p.to_string_lossy().replace('\\', "/"))
+    }
+
+    /// Joins `relative` onto `base`, rejecting absolute paths and any `..`
+    /// that would climb above `base`. The check is lexical; symlinks are
+    /// not resolved.
+    pub fn join_within(base: &Path, relative: &str) -> Result<PathBuf, PathError> {
+        let mut resolved = base.to_path_buf();
+        let mut depth = 0usize;
+        for component in Path::new(relative).components() {
+            match component {
+                Component::Normal(part) => {
+                    resolved.push(part);
+                    depth += 1;
+                }
+                Component::CurDir => {}
+                Component::ParentDir => {
+                    if depth == 0 {
+                        return Err(PathError::EscapesBase(relative.to_string()));
+                    }
+                    resolved.pop();
+                    depth -= 1;
+                }
+                Component::RootDir | Component::Prefix(_) => {
+                    return Err(PathError::Absolute(relative.to_string()));
+                }
+            }
+        }
+        Ok(resolved)
+    }
+}
+
+/// Matches a workspace-relative path against a glob.
+///
+/// `*` and `?` stay within one path segment, `**` spans any number of
+/// segments, and a pattern ending in `/` matches everything below it.
+pub fn glob_match(pattern: &str, path: &str) -> bool {
+    if pattern.ends_with('/') {
+        return path.starts_with(pattern);
+    }
+    glob_match_bytes(pattern.as_bytes(), path.as_bytes())
+}
+
+fn glob_match_bytes(pattern: &[u8], path: &[u8]) -> bool {
+    match pattern {
+        [] => path.is_empty(),
+        [b'*', b'*', b'/', rest @ ..] => (0..=path.len())
+            .filter(|&i| i == 0 || path[i - 1] == b'/')
+            .any(|i| glob_match_bytes(rest, &path[i..])),
+        [b'*', b'*', rest @ ..] => (0..=path.len()).any(|i| glob_match_bytes(rest, &path[i..])),
+        [b'*', rest @ ..] => (0..=path.len())
+            .take_while(|&i| i == 0 || path[i - 1] != b'/')
+            .any(|i| glob_match_bytes(rest, &path[i..])),
+        [b'?', rest @ ..] => {
+            matches!(path.first(), Some(c) if *c != b'/') && glob_match_bytes(rest, &path[1..])
+        }
+        [c, rest @ ..] => path.first() == Some(c) && glob_match_bytes(rest, &path[1..]),
+    }
+}
+
+fn normalize(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                if !normalized.pop() {
+                    normalized.push("..");
+                }
+            }
+            other => normalized.push(other),
+        }
+    }
+    normalized
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn workspace(root: Option<&str>) -> WorkspaceInfo {
+        WorkspaceInfo {
+            root: root.map(String::from),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_resolve_relative_to_root() {
+        let paths = WorkspacePaths::new(
+            Path::new("/repo/.claude/manifest.json"),
+            &workspace(Some("..")),
+        );
+
+        assert_eq!(paths.root(), Path::new("/repo"));
+        assert_eq!(paths.manifest_dir(), Path::new("/repo/.claude"));
+        assert_eq!(
+            paths.resolve("src/auth/./mod.rs").unwrap(),
+            PathBuf::from("/repo/src/auth/mod.rs")
+        );
+        assert_eq!(
+            paths
+                .rule_output_path(&Rule::tech("rust", vec![], vec![]))
+                .unwrap(),
+            PathBuf::from("/repo/.claude/rules/tech/rust.md")
+        );
+        assert_eq!(
+            paths.relativize(Path::new("/repo/src/lib.rs")),
+            Some("src/lib.rs".into())
+        );
+    }
+
+    #[test]
+    fn test_traversal_rejected() {
+        let paths = WorkspacePaths::new(Path::new("/repo/manifest.json"), &workspace(None));
+
+        assert_eq!(
+            paths.resolve("src/../../etc/passwd"),
+            Err(PathError::EscapesBase("src/../../etc/passwd".into()))
+        );
+        assert_eq!(
+            paths.resolve("/etc/passwd"),
+            Err(PathError::Absolute("/etc/passwd".into()))
+        );
+        assert!(paths.resolve("src/../lib.rs").is_ok());
+    }
+
+    #[test]
+    fn test_glob_match() {
+        assert!(glob_match("**/*.pb.go", "api/v1/users.pb.go"));
+        assert!(glob_match("**/*.pb.go", "users.pb.go"));
+        assert!(!glob_match("**/*.pb.go", "api/users.go"));
+        assert!(glob_match("src/gen/*.rs", "src/gen/types.rs"));
+        assert!(!glob_match("src/gen/*.rs", "src/gen/nested/types.rs"));
+        assert!(glob_match(
+            "src/**/generated/**",
+            "src/a/b/generated/x/y.ts"
+        ));
+        assert!(glob_match("vendor/", "vendor/github.com/x/y.go"));
+        assert!(glob_match("file?.txt", "file1.txt"));
+        assert!(!glob_match("file?.txt", "file/.txt"));
+    }
+}
@@ -0,0 +1,108 @@
+//! Claude Code permission settings derived from vendored and generated paths
+
+use serde::{Deserialize, Serialize};
+
+use crate::manifest::ProjectManifest;
+use crate::module_map::VendorPolicy;
+
+/// `permissions` block of a Claude Code `settings.json`
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PermissionRules {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub allow: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub deny: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PermissionSettings {
+    pub permissions: PermissionRules,
+}
+
+impl PermissionSettings {
+    fn deny(&mut self, tool: &str, pattern: &str) {
+        let rule = format!("{tool}({pattern})");
+        if !self.permissions.deny.contains(&rule) {
+            self.permissions.deny.push(rule);
+        }
+    }
+}
+
+/// `vendor/` → `vendor/**`; globs are kept as written
+fn pattern_for(path: &str) -> String {
+    if path.ends_with('/') {
+        format!("{path}**")
+    } else {
+        path.to_string()
+    }
+}
+
+impl ProjectManifest {
+    /// Settings that keep agents out of vendored code and hand edits out of
+    /// generated files.
+    ///
+    /// Vendored paths deny `Edit` and `Write`, plus `Read` when hidden;
+    /// generated globs deny `Edit` and `Write`.
+    pub fn permission_settings(&self) -> PermissionSettings {
+        let mut settings = PermissionSettings::default();
+        let project = &self.project.project;
+        for vendored in &project.vendored_paths {
+            let pattern = pattern_for(&vendored.path);
+            if vendored.policy == VendorPolicy::Hidden {
+                settings.deny("Read", &pattern);
+            }
+            settings.deny("Edit", &pattern);
+            settings.deny("Write", &pattern);
+        }
+        let generated = project
+            .generated_paths
+            .iter()
+            .chain(self.project.modules.iter().flat_map(|m| &m.generated_paths));
+        for glob in generated.flat_map(|g| &g.globs) {
+            settings.deny("Edit", glob);
+            settings.deny("Write", glob);
+        }
+        settings
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        GeneratedCode, GeneratorInfo, ModuleMap, ProjectMetadata, TechStack, VendoredPath,
+    };
+
+    #[test]
+    fn test_permission_settings() {
+        let project = ProjectMetadata::new("shop", TechStack::new("go"))
+            .with_vendored_paths(vec![
+                VendoredPath::new("vendor/"),
+                VendoredPath::new("third_party/secrets/").with_policy(VendorPolicy::Hidden),
+            ])
+            .with_generated_paths(vec![GeneratedCode::new(vec!["**/*.pb.go".into()])]);
+        let manifest = ProjectManifest::new(ModuleMap::new(
+            GeneratorInfo::new("test", "1.0.0"),
+            project,
+            vec![],
+            vec![],
+        ));
+
+        let settings = manifest.permission_settings();
+        assert_eq!(
+            settings.permissions.deny,
+            vec![
+                "Edit(vendor/**)",
+                "Write(vendor/**)",
+                "Read(third_party/secrets/**)",
+                "Edit(third_party/secrets/**)",
+                "Write(third_party/secrets/**)",
+                "Edit(**/*.pb.go)",
+                "Write(**/*.pb.go)",
+            ]
+        );
+        let json = serde_json::to_value(&settings).unwrap();
+        assert_eq!(json["permissions"]["deny"][0], "Edit(vendor/**)");
+        assert!(json["permissions"].get("allow").is_none());
+    }
+}
@@ -0,0 +1,146 @@
+//! Conversion to and from `petgraph` for algorithms this crate does not ship (feature: petgraph)
+
+use std::collections::HashMap;
+
+use petgraph::graph::{DiGraph, NodeIndex};
+
+use crate::module_map::{DependencyEdge, DependencyGraph, Module, ModuleMap, ModuleMetrics};
+use crate::types::DependencyType;
+
+/// Node weight: a module id, with metrics when the module is known
+#[derive(Debug, Clone)]
+pub struct ModuleNode {
+    pub id: String,
+    pub metrics: Option<ModuleMetrics>,
+}
+
+/// Dependency graph as a petgraph `DiGraph`; edges point from dependent to dependency
+pub type ModuleGraph = DiGraph<ModuleNode, DependencyType>;
+
+fn build<'a>(
+    nodes: impl IntoIterator<Item = &'a Module>,
+    edges: &'a [DependencyEdge],
+    modules: &'a [Module],
+) -> ModuleGraph {
+    let mut metrics: HashMap<&str, &ModuleMetrics> = HashMap::new();
+    for module in modules {
+        metrics.entry(module.id.as_str()).or_insert(&module.metrics);
+    }
+    let mut graph = ModuleGraph::new();
+    let mut index: HashMap<&str, NodeIndex> = HashMap::new();
+    let mut intern = |graph: &mut ModuleGraph, id: &'a str| {
+        *index.entry(id).or_insert_with(|| {
+            graph.add_node(ModuleNode {
+                id: id.to_string(),
+                metrics: metrics.get(id).map(|m| (*m).clone()),
+            })
+        })
+    };
+    for module in nodes {
+        intern(&mut graph, &module.id);
+    }
+    for edge in edges {
+        let from = intern(&mut graph, &edge.from);
+        let to = intern(&mut graph, &edge.to);
+        if graph.find_edge(from, to).is_none() {
+            graph.add_edge(from, to, edge.edge_type.clone());
+        }
+    }
+    graph
+}
+
+impl DependencyGraph {
+    /// Edge endpoints become nodes in first-appearance order, with metrics
+    /// taken from `modules` when listed there. Duplicate edges are kept once.
+    pub fn to_petgraph(&self, modules: &[Module]) -> ModuleGraph {
+        build([], &self.edges, modules)
+    }
+
+    /// Edges of `graph`; layers are not represented in petgraph and stay empty.
+    pub fn from_petgraph(graph: &ModuleGraph) -> Self {
+        Self {
+            edges: graph
+                .edge_indices()
+                .filter_map(|e| {
+                    let (from, to) = graph.edge_endpoints(e)?;
+                    Some(DependencyEdge {
+                        from: graph[from].id.clone(),
+                        to: graph[to].id.clone(),
+                        edge_type: graph[e].clone(),
+                    })
+                })
+                .collect(),
+            layers: Vec::new(),
+        }
+    }
+}
+
+impl ModuleMap {
+    /// Every module as a node, in map order, plus `dependency_edges()`.
+    pub fn to_petgraph(&self) -> ModuleGraph {
+        build(&self.modules, &self.dependency_edges(), &self.modules)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{GeneratorInfo, ModuleDependency, ProjectMetadata, TechStack};
+    use petgraph::algo::toposort;
+
+    fn module(id: &str, deps: &[&str], risk: f64) -> Module {
+        Module::new(id, id, vec![format!("src/{id}/")])
+            .with_dependencies(deps.iter().map(|d| ModuleDependency::new(*d)).collect())
+            .with_primary_language("rust")
+            .with_metrics(ModuleMetrics::new(0.5, 0.5, risk))
+    }
+
+    #[test]
+    fn test_petgraph_round_trip() {
+        let map = ModuleMap::new(
+            GeneratorInfo::new("test", "1.0.0"),
+            ProjectMetadata::new("shop", TechStack::new("rust")),
+            vec![
+                module("api", &["auth", "db"], 0.2),
+                module("auth", &["db"], 0.9),
+                module("db", &[], 0.1),
+                module("docs", &[], 0.0),
+            ],
+            vec![],
+        );
+
+        let graph = map.to_petgraph();
+        assert_eq!((graph.node_count(), graph.edge_count()), (4, 3));
+        let auth = &graph[NodeIndex::new(1)];
+        assert_eq!(auth.id, "auth");
+        assert_eq!(auth.metrics.as_ref().unwrap().risk_score.get(), 0.9);
+        let order: Vec<&str> = toposort(&graph, None)
+            .unwrap()
+            .into_iter()
+            .map(|n| graph[n].id.as_str())
+            .collect();
+        assert!(
+            order.iter().position(|id| *id == "auth") < order.iter().position(|id| *id == "db")
+        );
+
+        let back = DependencyGraph::from_petgraph(&graph);
+        let edges: Vec<(&str, &str)> = back
+            .edges
+            .iter()
+            .map(|e| (e.from.as_str(), e.to.as_str()))
+            .collect();
+        assert_eq!(edges, [("api", "auth"), ("api", "db"), ("auth", "db")]);
+
+        let external = DependencyGraph {
+            edges: vec![DependencyEdge {
+                from: "api".into(),
+                to: "billing".into(),
+                edge_type: DependencyType::default(),
+            }],
+            layers: vec![],
+        }
+        .to_petgraph(&map.modules);
+        assert_eq!(external.node_count(), 2);
+        assert!(external[NodeIndex::new(1)].metrics.is_none());
+    }
+}
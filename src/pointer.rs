@@ -0,0 +1,199 @@
+//! Id-based JSON pointers into a map, stable across reordering
+//!
+//! `/modules/auth/known_issues/token-leak` addresses the issue by id where an
+//! RFC 6901 pointer would say `/modules/3/known_issues/0`. Array elements that
+//! are objects are addressed by `id`, `name` or `module_id`; scalar arrays
+//! such as `paths` keep numeric indexes.
+
+use serde_json::Value;
+
+use crate::entity::EntityRef;
+use crate::module_map::ModuleMap;
+
+const KEY_FIELDS: [&str; 3] = ["id", "name", "module_id"];
+
+fn escape(segment: &str) -> String {
+    segment.replace('~', "~0").replace('/', "~1")
+}
+
+fn unescape(segment: &str) -> String {
+    segment.replace("~1", "/").replace("~0", "~")
+}
+
+fn key_of(element: &Value) -> Option<&str> {
+    KEY_FIELDS
+        .iter()
+        .find_map(|field| element.get(field)?.as_str())
+}
+
+/// Position of `segment` in `array`: by key for objects, by index otherwise.
+fn position(array: &[Value], segment: &str) -> Option<usize> {
+    if array.first().is_some_and(Value::is_object) {
+        array.iter().position(|e| key_of(e) == Some(segment))
+    } else {
+        segment.parse().ok().filter(|i| *i < array.len())
+    }
+}
+
+/// Walks `segments` from `value`, collecting the RFC 6901 segment of each step.
+fn walk<'v>(
+    mut value: &'v Value,
+    segments: &[String],
+    index_path: &mut Vec<String>,
+) -> Option<&'v Value> {
+    for segment in segments {
+        value = match value {
+            Value::Object(fields) => {
+                index_path.push(escape(segment));
+                fields.get(segment)?
+            }
+            Value::Array(items) => {
+                let i = position(items, segment)?;
+                index_path.push(i.to_string());
+                &items[i]
+            }
+            _ => return None,
+        };
+    }
+    Some(value)
+}
+
+fn split(pointer: &str) -> Option<Vec<String>> {
+    if pointer.is_empty() {
+        return Some(Vec::new());
+    }
+    Some(
+        pointer
+            .strip_prefix('/')?
+            .split('/')
+            .map(unescape)
+            .collect(),
+    )
+}
+
+impl ModuleMap {
+    /// Value at an id-based pointer, e.g. `/modules/auth/conventions/error-handling/pattern`.
+    pub fn resolve_pointer(&self, pointer: &str) -> Option<Value> {
+        self.locate(pointer).map(|(value, _)| value)
+    }
+
+    /// RFC 6901 pointer for an id-based one, for JSON Patch against the current order.
+    pub fn index_pointer(&self, pointer: &str) -> Option<String> {
+        self.locate(pointer)
+            .map(|(_, path)| path.iter().map(|s| format!("/{s}")).collect())
+    }
+
+    fn locate(&self, pointer: &str) -> Option<(Value, Vec<String>)> {
+        let segments = split(pointer)?;
+        let root = serde_json::to_value(self).ok()?;
+        let mut index_path = Vec::new();
+        let value = walk(&root, &segments, &mut index_path)?.clone();
+        Some((value, index_path))
+    }
+
+    /// Id-based pointer of `entity`; conventions and issues resolve to the first
+    /// module declaring them. Rules and ADRs live outside the map and, like
+    /// tables, have none.
+    pub fn pointer_of(&self, entity: &EntityRef) -> Option<String> {
+        let (module, field, key) = match entity {
+            EntityRef::Module(id) => {
+                let module = self.find_module(id)?;
+                return Some(format!("/modules/{}", escape(&module.id)));
+            }
+            EntityRef::Convention(name) => {
+                let (module, _) = self.conventions_iter().find(|(_, c)| c.name == *name)?;
+                (module, "conventions", name)
+            }
+            EntityRef::Issue(id) => {
+                let found = self.issues_iter().find(|r| r.issue.id == *id)?;
+                (found.module, "known_issues", id)
+            }
+            EntityRef::Group(id) => {
+                let group = self.find_group(id)?;
+                return Some(format!("/groups/{}", escape(&group.id)));
+            }
+            EntityRef::Domain(id) => {
+                let domain = self.find_domain(id)?;
+                return Some(format!("/domains/{}", escape(&domain.id)));
+            }
+            EntityRef::Rule(_) | EntityRef::Adr(_) | EntityRef::Table(_) => return None,
+        };
+        Some(format!(
+            "/modules/{}/{field}/{}",
+            escape(&module.id),
+            escape(key)
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        Convention, GeneratorInfo, IssueCategory, IssueSeverity, KnownIssue, Module, ModuleGroup,
+        ProjectMetadata, TechStack,
+    };
+
+    fn module(id: &str, issues: &[&str]) -> Module {
+        Module::new(id, id, vec![format!("src/{id}/"), format!("lib/{id}/")])
+            .with_primary_language("rust")
+            .with_conventions(vec![Convention::new("error-handling", "thiserror enums")])
+            .with_known_issues(
+                issues
+                    .iter()
+                    .map(|i| KnownIssue::new(*i, "", IssueSeverity::High, IssueCategory::Security))
+                    .collect(),
+            )
+    }
+
+    #[test]
+    fn test_id_based_pointers() {
+        let map = ModuleMap::new(
+            GeneratorInfo::new("test", "1.0.0"),
+            ProjectMetadata::new("shop", TechStack::new("rust")),
+            vec![
+                module("billing", &[]),
+                module("auth", &["slow-login", "token-leak"]),
+            ],
+            vec![ModuleGroup::new(
+                "payments/checkout",
+                "Checkout",
+                vec!["billing".into()],
+            )],
+        );
+
+        let pointer = map
+            .pointer_of(&EntityRef::Issue("token-leak".into()))
+            .unwrap();
+        assert_eq!(pointer, "/modules/auth/known_issues/token-leak");
+        assert_eq!(
+            map.resolve_pointer(&format!("{pointer}/id")),
+            Some(Value::from("token-leak"))
+        );
+        assert_eq!(
+            map.index_pointer(&pointer).as_deref(),
+            Some("/modules/1/known_issues/1")
+        );
+        assert_eq!(
+            map.resolve_pointer("/modules/auth/paths/1"),
+            Some(Value::from("lib/auth/"))
+        );
+        assert_eq!(
+            map.resolve_pointer("/groups/payments~1checkout/name"),
+            Some(Value::from("Checkout"))
+        );
+        assert_eq!(
+            map.pointer_of(&EntityRef::Convention("error-handling".into()))
+                .as_deref(),
+            Some("/modules/billing/conventions/error-handling")
+        );
+        assert!(map.resolve_pointer("/modules/0").is_none());
+        assert!(map.resolve_pointer("modules").is_none());
+        assert_eq!(
+            map.pointer_of(&EntityRef::Group("payments/checkout".into()))
+                .as_deref(),
+            Some("/groups/payments~1checkout")
+        );
+        assert!(map.pointer_of(&EntityRef::Adr("ADR-1".into())).is_none());
+    }
+}
@@ -0,0 +1,291 @@
+//! Policy-as-data: check configuration loaded from `modmap.policy.json`, or
+//! `modmap.policy.toml` with the `policy-toml` feature
+
+use std::collections::BTreeMap;
+use std::fs;
+#[cfg(not(feature = "policy-toml"))]
+use std::io;
+use std::path::Path;
+
+use chrono::NaiveDate;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[cfg(not(feature = "policy-toml"))]
+use crate::features::features;
+use crate::fitness::{DiagnosticSeverity, FitnessReport};
+use crate::registry::SchemaError;
+use crate::waiver::Waiver;
+
+/// File names searched by `PolicyConfig::discover`, in order
+#[cfg(feature = "policy-toml")]
+pub const POLICY_FILES: &[&str] = &["modmap.policy.toml", "modmap.policy.json"];
+/// File names searched by `PolicyConfig::discover`; TOML needs the `policy-toml` feature
+#[cfg(not(feature = "policy-toml"))]
+pub const POLICY_FILES: &[&str] = &["modmap.policy.json"];
+
+/// Per-check settings
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct CheckPolicy {
+    #[serde(default = "enabled")]
+    pub enabled: bool,
+    /// Overrides the check's own severity
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub severity: Option<DiagnosticSeverity>,
+}
+
+fn enabled() -> bool {
+    true
+}
+
+impl Default for CheckPolicy {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            severity: None,
+        }
+    }
+}
+
+/// What a waiver must carry to be honored
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct WaiverRequirements {
+    #[serde(default)]
+    pub require_approver: bool,
+    #[serde(default)]
+    pub require_expiry: bool,
+    /// Longest allowed time from `today` to expiry
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_days: Option<i64>,
+}
+
+/// Why a waiver is not honored under a policy
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WaiverProblem {
+    Expired,
+    MissingApprover,
+    MissingExpiry,
+    TooLong,
+}
+
+/// Which checks run, at what severity, with which thresholds and waiver rules
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct PolicyConfig {
+    /// Keyed by check id; unlisted checks run with their own severity
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub checks: BTreeMap<String, CheckPolicy>,
+    /// Named numeric limits for checks that take one
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub thresholds: BTreeMap<String, f64>,
+    /// Least severe finding that fails a gate
+    #[serde(default)]
+    pub fail_on: DiagnosticSeverity,
+    #[serde(default)]
+    pub waivers: WaiverRequirements,
+}
+
+impl PolicyConfig {
+    pub fn from_json(text: &str) -> Result<Self, SchemaError> {
+        Ok(serde_json::from_str(text)?)
+    }
+
+    #[cfg(feature = "policy-toml")]
+    pub fn from_toml(text: &str) -> Result<Self, SchemaError> {
+        Ok(toml::from_str(text)?)
+    }
+
+    /// Loads a `.toml` file, or JSON for any other extension. Without the
+    /// `policy-toml` feature a `.toml` path fails as unsupported.
+    pub fn load(path: &Path) -> Result<Self, SchemaError> {
+        let text = fs::read_to_string(path)?;
+        if path.extension().is_some_and(|e| e == "toml") {
+            #[cfg(feature = "policy-toml")]
+            return Self::from_toml(&text);
+            #[cfg(not(feature = "policy-toml"))]
+            return Err(io::Error::new(
+                io::ErrorKind::Unsupported,
+                features().require("toml-policy").unwrap_err(),
+            )
+            .into());
+        }
+        Self::from_json(&text)
+    }
+
+    /// First of `POLICY_FILES` present in `root`, if any.
+    pub fn discover(root: &Path) -> Result<Option<Self>, SchemaError> {
+        POLICY_FILES
+            .iter()
+            .map(|name| root.join(name))
+            .find(|path| path.is_file())
+            .map(|path| Self::load(&path))
+            .transpose()
+    }
+
+    pub fn with_check(mut self, check: impl Into<String>, policy: CheckPolicy) -> Self {
+        self.checks.insert(check.into(), policy);
+        self
+    }
+
+    pub fn is_enabled(&self, check: &str) -> bool {
+        self.checks.get(check).is_none_or(|c| c.enabled)
+    }
+
+    pub fn severity_for(&self, check: &str, default: DiagnosticSeverity) -> DiagnosticSeverity {
+        self.checks
+            .get(check)
+            .and_then(|c| c.severity)
+            .unwrap_or(default)
+    }
+
+    pub fn threshold(&self, name: &str) -> Option<f64> {
+        self.thresholds.get(name).copied()
+    }
+
+    /// Whether findings of `severity` fail a gate under this policy.
+    pub fn fails(&self, severity: DiagnosticSeverity) -> bool {
+        severity <= self.fail_on
+    }
+
+    pub fn waiver_problems(&self, waiver: &Waiver, today: NaiveDate) -> Vec<WaiverProblem> {
+        let rules = &self.waivers;
+        let mut problems = Vec::new();
+        if waiver.is_expired(today) {
+            problems.push(WaiverProblem::Expired);
+        }
+        if rules.require_approver && waiver.approver.is_none() {
+            problems.push(WaiverProblem::MissingApprover);
+        }
+        match waiver.expires_on {
+            None if rules.require_expiry => problems.push(WaiverProblem::MissingExpiry),
+            Some(date)
+                if rules
+                    .max_days
+                    .is_some_and(|max| (date - today).num_days() > max) =>
+            {
+                problems.push(WaiverProblem::TooLong)
+            }
+            _ => {}
+        }
+        problems
+    }
+
+    pub fn accepts_waiver(&self, waiver: &Waiver, today: NaiveDate) -> bool {
+        self.waiver_problems(waiver, today).is_empty()
+    }
+}
+
+impl FitnessReport {
+    /// Whether no remaining finding fails a gate under `policy`.
+    pub fn passes(&self, policy: &PolicyConfig) -> bool {
+        self.diagnostics()
+            .all(|(_, severity, _)| !policy.fails(severity))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entity::EntityRef;
+
+    const POLICY: &str = r#"{
+        "fail_on": "warning",
+        "checks": {
+            "layering": {"enabled": false},
+            "ownership-coverage": {"severity": "info"}
+        },
+        "thresholds": {"max_risk": 0.7},
+        "waivers": {"require_approver": true, "max_days": 90}
+    }"#;
+
+    #[cfg(feature = "policy-toml")]
+    const POLICY_TOML: &str = r#"
+fail_on = "warning"
+
+[checks.layering]
+enabled = false
+
+[checks.ownership-coverage]
+severity = "info"
+
+[thresholds]
+max_risk = 0.7
+
+[waivers]
+require_approver = true
+max_days = 90
+"#;
+
+    fn date(s: &str) -> NaiveDate {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn test_policy_from_json() {
+        let policy = PolicyConfig::from_json(POLICY).unwrap();
+        assert!(!policy.is_enabled("layering"));
+        assert!(policy.is_enabled("no-cycles"));
+        assert_eq!(
+            policy.severity_for("ownership-coverage", DiagnosticSeverity::Warning),
+            DiagnosticSeverity::Info
+        );
+        assert_eq!(policy.threshold("max_risk"), Some(0.7));
+        assert!(policy.fails(DiagnosticSeverity::Warning));
+        assert!(!policy.fails(DiagnosticSeverity::Info));
+
+        let json = serde_json::to_string(&policy).unwrap();
+        assert_eq!(PolicyConfig::from_json(&json).unwrap(), policy);
+
+        let root = std::env::temp_dir().join(format!("modmap-policy-{}", std::process::id()));
+        fs::create_dir_all(&root).unwrap();
+        assert_eq!(PolicyConfig::discover(&root).unwrap(), None);
+        fs::write(root.join("modmap.policy.json"), POLICY).unwrap();
+        let discovered = PolicyConfig::discover(&root).unwrap();
+        #[cfg(not(feature = "policy-toml"))]
+        {
+            fs::write(root.join("custom.toml"), "").unwrap();
+            let error = PolicyConfig::load(&root.join("custom.toml")).unwrap_err();
+            assert!(error.to_string().contains("--features policy-toml"));
+        }
+        fs::remove_dir_all(&root).unwrap();
+        assert_eq!(discovered, Some(policy));
+    }
+
+    #[cfg(feature = "policy-toml")]
+    #[test]
+    fn test_policy_from_toml() {
+        let policy = PolicyConfig::from_toml(POLICY_TOML).unwrap();
+        assert_eq!(policy, PolicyConfig::from_json(POLICY).unwrap());
+
+        let root = std::env::temp_dir().join(format!("modmap-policy-toml-{}", std::process::id()));
+        fs::create_dir_all(&root).unwrap();
+        fs::write(root.join("modmap.policy.json"), "{}").unwrap();
+        fs::write(root.join("modmap.policy.toml"), POLICY_TOML).unwrap();
+        let discovered = PolicyConfig::discover(&root).unwrap();
+        fs::remove_dir_all(&root).unwrap();
+        assert_eq!(discovered, Some(policy), "TOML is searched first");
+    }
+
+    #[test]
+    fn test_waiver_requirements() {
+        let policy = PolicyConfig::from_json(POLICY).unwrap();
+        let today = date("2026-06-01");
+        let waiver = Waiver::new("layering", EntityRef::Module("api".into()), "Migration");
+        assert_eq!(
+            policy.waiver_problems(&waiver, today),
+            vec![WaiverProblem::MissingApprover]
+        );
+        let waiver = waiver
+            .with_approver("@lead")
+            .with_expiry(date("2027-01-01"));
+        assert_eq!(
+            policy.waiver_problems(&waiver, today),
+            vec![WaiverProblem::TooLong]
+        );
+        let waiver = waiver.with_expiry(date("2026-07-01"));
+        assert!(policy.accepts_waiver(&waiver, today));
+        assert_eq!(
+            policy.waiver_problems(&waiver, date("2026-08-01")),
+            vec![WaiverProblem::Expired]
+        );
+    }
+}
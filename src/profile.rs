@@ -0,0 +1,138 @@
+//! Generation profile describing how a manifest was produced
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// How thoroughly the generator analyzed the codebase
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum AnalysisDepth {
+    /// Structure only, from file layout and manifests
+    Quick,
+    #[default]
+    Standard,
+    /// Full analysis including LLM-derived conventions and issues
+    Deep,
+}
+
+impl std::fmt::Display for AnalysisDepth {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Quick => write!(f, "quick"),
+            Self::Standard => write!(f, "standard"),
+            Self::Deep => write!(f, "deep"),
+        }
+    }
+}
+
+/// Optional section of the map or manifest a generator may emit
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum MapSection {
+    Groups,
+    Domains,
+    DependencyGraph,
+    Conventions,
+    KnownIssues,
+    Evidence,
+    Rules,
+    Skills,
+    Agents,
+}
+
+/// Generation preset persisted in the manifest so tools know what to expect
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct GenerationProfile {
+    pub name: String,
+    #[serde(default)]
+    pub depth: AnalysisDepth,
+    /// Emitted sections (empty = all sections)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub sections: Vec<MapSection>,
+    /// Token budget for the whole run
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub total_token_budget: Option<u64>,
+    /// Token budget per analyzed module
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub module_token_budget: Option<u64>,
+    /// Exporters run after generation (e.g. "rules", "mermaid")
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub exporters: Vec<String>,
+}
+
+impl GenerationProfile {
+    pub fn new(name: impl Into<String>, depth: AnalysisDepth) -> Self {
+        Self {
+            name: name.into(),
+            depth,
+            ..Default::default()
+        }
+    }
+
+    pub fn quick() -> Self {
+        Self::new("quick", AnalysisDepth::Quick)
+            .with_sections(vec![MapSection::Groups, MapSection::DependencyGraph])
+    }
+
+    pub fn standard() -> Self {
+        Self::new("standard", AnalysisDepth::Standard)
+    }
+
+    pub fn deep() -> Self {
+        Self::new("deep", AnalysisDepth::Deep)
+    }
+
+    pub fn with_sections(mut self, sections: Vec<MapSection>) -> Self {
+        self.sections = sections;
+        self
+    }
+
+    pub fn with_total_token_budget(mut self, budget: u64) -> Self {
+        self.total_token_budget = Some(budget);
+        self
+    }
+
+    pub fn with_module_token_budget(mut self, budget: u64) -> Self {
+        self.module_token_budget = Some(budget);
+        self
+    }
+
+    pub fn with_exporters(mut self, exporters: Vec<String>) -> Self {
+        self.exporters = exporters;
+        self
+    }
+
+    pub fn emits(&self, section: MapSection) -> bool {
+        self.sections.is_empty() || self.sections.contains(&section)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_presets() {
+        let quick = GenerationProfile::quick();
+        assert_eq!(quick.depth, AnalysisDepth::Quick);
+        assert!(quick.emits(MapSection::Groups));
+        assert!(!quick.emits(MapSection::KnownIssues));
+
+        let deep = GenerationProfile::deep();
+        assert!(deep.emits(MapSection::KnownIssues));
+    }
+
+    #[test]
+    fn test_profile_serialization() {
+        let profile = GenerationProfile::quick()
+            .with_total_token_budget(200_000)
+            .with_exporters(vec!["rules".into()]);
+
+        let json = serde_json::to_string(&profile).unwrap();
+        assert!(json.contains(r#""depth":"quick""#));
+        assert!(json.contains(r#""sections":["groups","dependency_graph"]"#));
+
+        let parsed: GenerationProfile = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, profile);
+    }
+}
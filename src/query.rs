@@ -0,0 +1,234 @@
+//! Paginated queries with stable, id-based cursors
+
+use serde::{Deserialize, Serialize};
+
+use crate::module_map::{Module, ModuleMap};
+use crate::types::KnownIssue;
+
+pub const DEFAULT_PAGE_LIMIT: usize = 100;
+
+/// Page request; `cursor` is the `next_cursor` of the previous page
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PageRequest {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cursor: Option<String>,
+    #[serde(default = "default_limit")]
+    pub limit: usize,
+}
+
+fn default_limit() -> usize {
+    DEFAULT_PAGE_LIMIT
+}
+
+impl Default for PageRequest {
+    fn default() -> Self {
+        Self {
+            cursor: None,
+            limit: DEFAULT_PAGE_LIMIT,
+        }
+    }
+}
+
+impl PageRequest {
+    pub fn first(limit: usize) -> Self {
+        Self {
+            cursor: None,
+            limit,
+        }
+    }
+
+    pub fn after(cursor: impl Into<String>, limit: usize) -> Self {
+        Self {
+            cursor: Some(cursor.into()),
+            limit,
+        }
+    }
+}
+
+/// One page of results ordered by a stable key
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    /// Cursor for the next page, `None` on the last page
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
+    /// Total matching items across all pages
+    pub total: usize,
+}
+
+/// Cursor after `keys[i]`: the bare key when it is unique and has no `#`,
+/// else `key#n` for the `n`th item with that key.
+fn cursor_at(keys: &[String], i: usize) -> String {
+    let key = &keys[i];
+    let run_start = keys.partition_point(|k| k < key);
+    let run_end = keys.partition_point(|k| k <= key);
+    if run_end - run_start > 1 || key.contains('#') {
+        format!("{key}#{}", i - run_start + 1)
+    } else {
+        key.clone()
+    }
+}
+
+/// Index of the first item after `cursor` in sorted `keys`.
+fn position_after(keys: &[String], cursor: &str) -> usize {
+    if let Some((key, n)) = cursor.rsplit_once('#')
+        && let Ok(n) = n.parse::<usize>()
+    {
+        let run_start = keys.partition_point(|k| k.as_str() < key);
+        let run_end = keys.partition_point(|k| k.as_str() <= key);
+        return (run_start + n).min(run_end);
+    }
+    keys.partition_point(|k| k.as_str() <= cursor)
+}
+
+impl<T> Page<T> {
+    /// Sorts `items` by `key` and returns the page following the request cursor.
+    ///
+    /// Cursors are the sort key of the last returned item, so pages stay
+    /// stable when items before the cursor are added or removed. Items sharing
+    /// a key keep their input order and get `key#n` cursors, as do keys
+    /// containing `#`, so none is skipped or repeated.
+    pub fn from_sorted_by<K: Fn(&T) -> String>(
+        mut items: Vec<T>,
+        key: K,
+        request: &PageRequest,
+    ) -> Self {
+        items.sort_by_key(|item| key(item));
+        let keys: Vec<String> = items.iter().map(&key).collect();
+        let total = items.len();
+        let start = request
+            .cursor
+            .as_deref()
+            .map_or(0, |cursor| position_after(&keys, cursor));
+        let limit = request.limit.max(1);
+        let mut items: Vec<T> = items.into_iter().skip(start).collect();
+        let has_more = items.len() > limit;
+        items.truncate(limit);
+        let next_cursor = has_more.then(|| cursor_at(&keys, start + limit - 1));
+        Self {
+            items,
+            next_cursor,
+            total,
+        }
+    }
+
+    pub fn map<U>(self, f: impl FnMut(T) -> U) -> Page<U> {
+        Page {
+            items: self.items.into_iter().map(f).collect(),
+            next_cursor: self.next_cursor,
+            total: self.total,
+        }
+    }
+}
+
+/// Known issue together with the module that owns it
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct IssueRef<'a> {
+    #[serde(rename = "module_id", serialize_with = "serialize_module_id")]
+    pub module: &'a Module,
+    pub issue: &'a KnownIssue,
+}
+
+fn serialize_module_id<S: serde::Serializer>(module: &&Module, s: S) -> Result<S::Ok, S::Error> {
+    s.serialize_str(&module.id)
+}
+
+impl IssueRef<'_> {
+    /// Stable `module_id/issue_id` key
+    pub fn key(&self) -> String {
+        format!("{}/{}", self.module.id, self.issue.id)
+    }
+}
+
+impl ModuleMap {
+    pub fn modules_page(&self, request: &PageRequest) -> Page<&Module> {
+        Page::from_sorted_by(self.modules.iter().collect(), |m| m.id.clone(), request)
+    }
+
+    pub fn issues(&self) -> Vec<IssueRef<'_>> {
+        self.issues_iter().collect()
+    }
+
+    pub fn issues_page(&self, request: &PageRequest) -> Page<IssueRef<'_>> {
+        Page::from_sorted_by(self.issues(), IssueRef::key, request)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{GeneratorInfo, IssueCategory, IssueSeverity, ProjectMetadata, TechStack};
+
+    fn sample_map(count: usize) -> ModuleMap {
+        let modules = (0..count)
+            .map(|i| {
+                Module::new(format!("m{i:03}"), format!("m{i:03}"), vec![])
+                    .with_primary_language("rust")
+                    .with_known_issues(vec![KnownIssue::new(
+                        "issue",
+                        "desc",
+                        IssueSeverity::Low,
+                        IssueCategory::Maintainability,
+                    )])
+            })
+            .rev()
+            .collect();
+        ModuleMap::new(
+            GeneratorInfo::new("test", "1.0.0"),
+            ProjectMetadata::new("test", TechStack::new("rust")),
+            modules,
+            vec![],
+        )
+    }
+
+    #[test]
+    fn test_modules_pagination() {
+        let map = sample_map(25);
+
+        let first = map.modules_page(&PageRequest::first(10));
+        assert_eq!(first.total, 25);
+        assert_eq!(first.items[0].id, "m000");
+        assert_eq!(first.next_cursor.as_deref(), Some("m009"));
+
+        let second = map.modules_page(&PageRequest::after("m009", 10));
+        assert_eq!(second.items[0].id, "m010");
+
+        let last = map.modules_page(&PageRequest::after("m019", 10));
+        assert_eq!(last.items.len(), 5);
+        assert!(last.next_cursor.is_none());
+    }
+
+    #[test]
+    fn test_issues_pagination() {
+        let map = sample_map(3);
+        let page = map.issues_page(&PageRequest::first(2));
+
+        assert_eq!(page.total, 3);
+        assert_eq!(page.next_cursor.as_deref(), Some("m001/issue"));
+
+        let json = serde_json::to_value(&page).unwrap();
+        assert_eq!(json["items"][0]["module_id"], "m000");
+    }
+
+    #[test]
+    fn test_pagination_with_duplicate_keys() {
+        let items = vec![(3, "c"), (0, "a"), (1, "b"), (2, "b"), (4, "b#1"), (5, "d")];
+        let key = |item: &(i32, &str)| item.1.to_string();
+        let mut seen = Vec::new();
+        let mut cursors = Vec::new();
+        let mut request = PageRequest::first(1);
+        loop {
+            let page = Page::from_sorted_by(items.clone(), key, &request);
+            seen.extend(page.items.iter().map(|item| item.0));
+            match page.next_cursor {
+                Some(cursor) => {
+                    cursors.push(cursor.clone());
+                    request = PageRequest::after(cursor, 1);
+                }
+                None => break,
+            }
+        }
+        assert_eq!(seen, vec![0, 1, 2, 4, 3, 5]);
+        assert_eq!(cursors, vec!["a", "b#1", "b#2", "b#1#1", "c"]);
+    }
+}
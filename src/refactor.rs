@@ -0,0 +1,634 @@
+//! "What if" simulation of module moves, merges and splits
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::{self, Write};
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::boundaries::BoundaryViolation;
+use crate::diff::MapDiff;
+use crate::module_map::{Module, ModuleMap, known_as};
+use crate::types::ModuleDependency;
+
+/// A structural change to a module map
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Refactor {
+    /// Folds `b` into `a`; references to `b` are redirected to `a`
+    Merge { a: String, b: String },
+    /// Moves the listed paths of `module` into new modules, keyed by new id
+    Split {
+        module: String,
+        by_paths: BTreeMap<String, Vec<String>>,
+    },
+    /// Moves `module` out of its current group into `group`
+    MoveToGroup { module: String, group: String },
+}
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum RefactorError {
+    #[error("Unknown module: {0}")]
+    UnknownModule(String),
+
+    #[error("Unknown group: {0}")]
+    UnknownGroup(String),
+
+    #[error("Module id already exists: {0}")]
+    DuplicateModule(String),
+
+    #[error("Path {path} is not owned by module {module}")]
+    PathNotOwned { module: String, path: String },
+}
+
+impl fmt::Display for Refactor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Merge { a, b } => write!(f, "Merge `{b}` into `{a}`"),
+            Self::Split { module, by_paths } => {
+                let parts: Vec<String> = by_paths
+                    .iter()
+                    .map(|(id, paths)| format!("`{id}` ({})", paths.join(", ")))
+                    .collect();
+                write!(f, "Split `{module}` into {}", parts.join(", "))
+            }
+            Self::MoveToGroup { module, group } => {
+                write!(f, "Move `{module}` to group `{group}`")
+            }
+        }
+    }
+}
+
+/// Ordered refactor steps with their blast radius and a review checklist
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct RefactorPlan {
+    pub id: String,
+    pub title: String,
+    pub steps: Vec<Refactor>,
+    /// Modules touched by a step or depending on one that is
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub affected_modules: Vec<String>,
+    /// 0.0–1.0, from affected module risk scores and introduced violations
+    #[serde(default)]
+    pub estimated_risk: f64,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub checklist: Vec<String>,
+}
+
+impl RefactorPlan {
+    /// Simulates `steps` in order against `map` and derives affected
+    /// modules, risk and checklist. Fails on the first invalid step.
+    pub fn build(
+        map: &ModuleMap,
+        id: impl Into<String>,
+        title: impl Into<String>,
+        steps: Vec<Refactor>,
+    ) -> Result<Self, RefactorError> {
+        let mut current = map.clone();
+        let mut affected = BTreeSet::new();
+        let mut checklist = Vec::new();
+        let mut introduced = Vec::new();
+
+        for step in &steps {
+            let touched = step.modules();
+            let dependents: BTreeSet<&str> = current
+                .modules
+                .iter()
+                .filter(|m| !touched.contains(&m.id.as_str()))
+                .filter(|m| {
+                    m.dependencies
+                        .iter()
+                        .any(|d| touched.contains(&d.module_id.as_str()))
+                })
+                .map(|m| m.id.as_str())
+                .collect();
+
+            let simulation = current.simulate(step)?;
+            checklist.push(step.to_string());
+            if !dependents.is_empty() && !matches!(step, Refactor::MoveToGroup { .. }) {
+                let list: Vec<&str> = dependents.iter().copied().collect();
+                checklist.push(format!("Update references in {}", list.join(", ")));
+            }
+            for violation in &simulation.introduced {
+                checklist.push(format!("Resolve boundary violation: {violation}"));
+            }
+
+            affected.extend(touched.iter().map(|s| s.to_string()));
+            affected.extend(dependents.into_iter().map(String::from));
+            introduced.extend(simulation.introduced);
+            current = simulation.map;
+        }
+
+        let max_risk = affected
+            .iter()
+            .filter_map(|id| map.find_module(id))
+            .map(|m| m.metrics.risk_score.get())
+            .fold(0.0, f64::max);
+        let estimated_risk = (max_risk + 0.1 * introduced.len() as f64).min(1.0);
+
+        let affected_modules: Vec<String> = affected.into_iter().collect();
+        checklist.push(format!(
+            "Run tests for {}",
+            affected_modules
+                .iter()
+                .map(|id| format!("`{id}`"))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ));
+        checklist.push("Regenerate the module map".to_string());
+
+        Ok(Self {
+            id: id.into(),
+            title: title.into(),
+            steps,
+            affected_modules,
+            estimated_risk,
+            checklist,
+        })
+    }
+
+    pub fn to_markdown(&self) -> String {
+        let mut out = format!("# {}\n\n", self.title);
+        let _ = writeln!(out, "Estimated risk: {:.2}\n", self.estimated_risk);
+        out.push_str("## Steps\n\n");
+        for (i, step) in self.steps.iter().enumerate() {
+            let _ = writeln!(out, "{}. {step}", i + 1);
+        }
+        if !self.affected_modules.is_empty() {
+            out.push_str("\n## Affected Modules\n\n");
+            for module in &self.affected_modules {
+                let _ = writeln!(out, "- `{module}`");
+            }
+        }
+        if !self.checklist.is_empty() {
+            out.push_str("\n## Checklist\n\n");
+            for item in &self.checklist {
+                let _ = writeln!(out, "- [ ] {item}");
+            }
+        }
+        out
+    }
+}
+
+/// Outcome of a refactor applied to a copy of the map
+#[derive(Debug, Clone)]
+pub struct Simulation {
+    pub map: ModuleMap,
+    pub diff: MapDiff,
+    /// Boundary violations present after but not before
+    pub introduced: Vec<BoundaryViolation>,
+    /// Boundary violations present before but not after
+    pub resolved: Vec<BoundaryViolation>,
+}
+
+fn boundary_violations(map: &ModuleMap) -> Vec<BoundaryViolation> {
+    let mut violations = map.check_boundaries();
+    violations.extend(map.verify_consumers());
+    violations
+}
+
+fn push_unique<T: PartialEq>(items: &mut Vec<T>, item: T) {
+    if !items.contains(&item) {
+        items.push(item);
+    }
+}
+
+impl Refactor {
+    /// Modules the refactor touches directly, including newly created ones.
+    pub fn modules(&self) -> Vec<&str> {
+        match self {
+            Self::Merge { a, b } => vec![a, b],
+            Self::Split { module, by_paths } => std::iter::once(module.as_str())
+                .chain(by_paths.keys().map(String::as_str))
+                .collect(),
+            Self::MoveToGroup { module, .. } => vec![module],
+        }
+    }
+
+    /// Applies the refactor in place. The map is left unchanged on error.
+    pub fn apply(&self, map: &mut ModuleMap) -> Result<(), RefactorError> {
+        match self {
+            Self::Merge { a, b } => merge(map, a, b),
+            Self::Split { module, by_paths } => split(map, module, by_paths),
+            Self::MoveToGroup { module, group } => move_to_group(map, module, group),
+        }
+    }
+}
+
+/// Index of the module with id or alias `id`.
+fn require_module(map: &ModuleMap, id: &str) -> Result<usize, RefactorError> {
+    map.position_of(id)
+        .ok_or_else(|| RefactorError::UnknownModule(id.to_string()))
+}
+
+fn merge(map: &mut ModuleMap, a: &str, b: &str) -> Result<(), RefactorError> {
+    let a = map.modules[require_module(map, a)?].id.clone();
+    let b = map.modules[require_module(map, b)?].id.clone();
+    let (a, b) = (a.as_str(), b.as_str());
+    if a == b {
+        return Ok(());
+    }
+    let removed = map.modules.remove(require_module(map, b)?);
+    let target = require_module(map, a)?;
+
+    let into = &mut map.modules[target];
+    for path in removed.paths {
+        push_unique(&mut into.paths, path);
+    }
+    for file in removed.key_files {
+        push_unique(&mut into.key_files, file);
+    }
+    for dep in removed.dependencies {
+        if dep.module_id != a
+            && !into
+                .dependencies
+                .iter()
+                .any(|d| d.module_id == dep.module_id)
+        {
+            into.dependencies.push(dep);
+        }
+    }
+    into.dependencies.retain(|d| d.module_id != b);
+    for dependent in removed.dependents {
+        if dependent != a {
+            push_unique(&mut into.dependents, dependent);
+        }
+    }
+    into.dependents.retain(|d| d != b);
+    into.conventions.extend(removed.conventions);
+    into.known_issues.extend(removed.known_issues);
+    into.evidence.extend(removed.evidence);
+    for topic in removed.consumed_topics {
+        push_unique(&mut into.consumed_topics, topic);
+    }
+    into.metrics.risk_score = into.metrics.risk_score.max(removed.metrics.risk_score);
+    into.metrics.value_score = into.metrics.value_score.max(removed.metrics.value_score);
+
+    for module in &mut map.modules {
+        if module.id == a {
+            continue;
+        }
+        if module.dependencies.iter().any(|d| d.module_id == b) {
+            let redirected: Vec<ModuleDependency> = module
+                .dependencies
+                .drain(..)
+                .map(|mut d| {
+                    if d.module_id == b {
+                        d.module_id = a.to_string();
+                    }
+                    d
+                })
+                .collect();
+            for dep in redirected {
+                if !module
+                    .dependencies
+                    .iter()
+                    .any(|d| d.module_id == dep.module_id)
+                {
+                    module.dependencies.push(dep);
+                }
+            }
+        }
+        if let Some(pos) = module.dependents.iter().position(|d| d == b) {
+            module.dependents.remove(pos);
+            push_unique(&mut module.dependents, a.to_string());
+        }
+    }
+    for group in &mut map.groups {
+        group.module_ids.retain(|id| id != b);
+        if group.leader_module.as_deref() == Some(b) {
+            group.leader_module = Some(a.to_string());
+        }
+    }
+    if let Some(graph) = &mut map.dependency_graph {
+        for edge in &mut graph.edges {
+            if edge.from == b {
+                edge.from = a.to_string();
+            }
+            if edge.to == b {
+                edge.to = a.to_string();
+            }
+        }
+        graph.edges.retain(|e| e.from != e.to);
+        let mut seen = Vec::new();
+        graph.edges.retain(|e| {
+            let key = (e.from.clone(), e.to.clone());
+            let fresh = !seen.contains(&key);
+            seen.push(key);
+            fresh
+        });
+        for layer in &mut graph.layers {
+            layer.modules.retain(|id| id != b);
+        }
+    }
+    Ok(())
+}
+
+fn split(
+    map: &mut ModuleMap,
+    module_id: &str,
+    by_paths: &BTreeMap<String, Vec<String>>,
+) -> Result<(), RefactorError> {
+    let source = require_module(map, module_id)?;
+    for (new_id, paths) in by_paths {
+        if map.find_module(new_id).is_some() {
+            return Err(RefactorError::DuplicateModule(new_id.clone()));
+        }
+        for path in paths {
+            if !map.modules[source].contains_file(path) {
+                return Err(RefactorError::PathNotOwned {
+                    module: map.modules[source].id.clone(),
+                    path: path.clone(),
+                });
+            }
+        }
+    }
+
+    let original = &mut map.modules[source];
+    let moved: Vec<&String> = by_paths.values().flatten().collect();
+    original.paths.retain(|p| !moved.contains(&p));
+    original
+        .key_files
+        .retain(|f| !moved.iter().any(|p| f.starts_with(p.as_str())));
+    let template = original.clone();
+
+    let group = map.find_group_containing(module_id).map(|g| g.id.clone());
+    for (new_id, paths) in by_paths {
+        map.modules.push(
+            Module::new(new_id.clone(), new_id.clone(), paths.clone())
+                .with_dependencies(template.dependencies.clone())
+                .with_primary_language(template.primary_language.clone())
+                .with_metrics(template.metrics.clone())
+                .with_conventions(template.conventions.clone()),
+        );
+        if let Some(group) = group
+            .as_deref()
+            .and_then(|id| map.groups.iter_mut().find(|g| g.id == id))
+        {
+            group.module_ids.push(new_id.clone());
+        }
+    }
+    Ok(())
+}
+
+/// Moves `module` into `group`; either may be named by an alias.
+fn move_to_group(map: &mut ModuleMap, module: &str, group: &str) -> Result<(), RefactorError> {
+    let moved = &map.modules[require_module(map, module)?];
+    let names: Vec<String> = known_as(&moved.id, &moved.aliases)
+        .into_iter()
+        .map(String::from)
+        .collect();
+    let group = map
+        .find_group(group)
+        .ok_or_else(|| RefactorError::UnknownGroup(group.to_string()))?
+        .id
+        .clone();
+    for g in &mut map.groups {
+        if g.id == group {
+            if !g.module_ids.iter().any(|id| names.contains(id)) {
+                g.module_ids.push(names[0].clone());
+            }
+        } else {
+            g.module_ids.retain(|id| !names.contains(id));
+            if g.leader_module.as_ref().is_some_and(|l| names.contains(l)) {
+                g.leader_module = None;
+            }
+        }
+    }
+    Ok(())
+}
+
+impl ModuleMap {
+    /// Applies `refactor` to a copy of the map and reports the resulting
+    /// diff and boundary changes. `self` is not modified.
+    pub fn simulate(&self, refactor: &Refactor) -> Result<Simulation, RefactorError> {
+        let mut map = self.clone();
+        refactor.apply(&mut map)?;
+
+        let before = boundary_violations(self);
+        let after = boundary_violations(&map);
+        Ok(Simulation {
+            diff: ModuleMap::diff(self, &map),
+            introduced: after
+                .iter()
+                .filter(|v| !before.contains(v))
+                .cloned()
+                .collect(),
+            resolved: before.into_iter().filter(|v| !after.contains(v)).collect(),
+            map,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        Domain, DomainInterface, GeneratorInfo, InterfaceType, ModuleGroup, ProjectMetadata, Score,
+        TechStack,
+    };
+
+    fn module(id: &str, dependencies: &[&str]) -> Module {
+        Module::new(id, id, vec![format!("src/{id}/")])
+            .with_dependencies(
+                dependencies
+                    .iter()
+                    .map(|d| ModuleDependency::runtime(*d))
+                    .collect(),
+            )
+            .with_primary_language("rust")
+    }
+
+    fn sample() -> ModuleMap {
+        ModuleMap::new(
+            GeneratorInfo::new("test", "1.0.0"),
+            ProjectMetadata::new("shop", TechStack::new("rust")),
+            vec![
+                module("auth", &[]),
+                module("session", &["auth"]),
+                module("api", &["session", "auth"]),
+                module("billing", &[]),
+            ],
+            vec![
+                ModuleGroup::new(
+                    "identity-core",
+                    "Identity",
+                    vec!["auth".into(), "session".into(), "api".into()],
+                ),
+                ModuleGroup::new("billing-core", "Billing", vec!["billing".into()]),
+            ],
+        )
+        .with_domains(vec![
+            Domain::new("identity", "Identity", vec!["identity-core".into()]),
+            Domain::new("billing", "Billing", vec!["billing-core".into()]).with_interfaces(vec![
+                DomainInterface::new("BillingAPI", InterfaceType::Api)
+                    .with_consumers(vec!["identity".into()]),
+            ]),
+        ])
+    }
+
+    #[test]
+    fn test_simulate_merge() {
+        let map = sample();
+        let sim = map
+            .simulate(&Refactor::Merge {
+                a: "auth".into(),
+                b: "session".into(),
+            })
+            .unwrap();
+
+        assert!(map.find_module("session").is_some());
+        assert!(sim.map.find_module("session").is_none());
+        let auth = sim.map.find_module("auth").unwrap();
+        assert_eq!(auth.paths, vec!["src/auth/", "src/session/"]);
+        assert!(auth.dependencies.is_empty());
+        assert_eq!(
+            sim.map.find_module("api").unwrap().dependencies,
+            vec![ModuleDependency::runtime("auth")]
+        );
+        assert_eq!(sim.diff.removed_modules, vec!["session"]);
+    }
+
+    #[test]
+    fn test_simulate_split() {
+        let mut map = sample();
+        map.modules[0].paths.push("src/oauth/".into());
+        let sim = map
+            .simulate(&Refactor::Split {
+                module: "auth".into(),
+                by_paths: [("oauth".to_string(), vec!["src/oauth/".to_string()])].into(),
+            })
+            .unwrap();
+
+        assert_eq!(
+            sim.map.find_module("auth").unwrap().paths,
+            vec!["src/auth/"]
+        );
+        assert_eq!(
+            sim.map.find_module("oauth").unwrap().paths,
+            vec!["src/oauth/"]
+        );
+        assert_eq!(
+            sim.map.find_group_containing("oauth").unwrap().id,
+            "identity-core"
+        );
+        assert_eq!(sim.diff.added_modules, vec!["oauth"]);
+
+        let err = map.simulate(&Refactor::Split {
+            module: "auth".into(),
+            by_paths: [("x".to_string(), vec!["lib/x/".to_string()])].into(),
+        });
+        assert!(matches!(err, Err(RefactorError::PathNotOwned { .. })));
+    }
+
+    #[test]
+    fn test_simulate_move_reports_boundary_changes() {
+        let map = sample();
+        let sim = map
+            .simulate(&Refactor::MoveToGroup {
+                module: "session".into(),
+                group: "billing-core".into(),
+            })
+            .unwrap();
+
+        assert_eq!(
+            sim.map.find_group("billing-core").unwrap().module_ids,
+            vec!["billing", "session"]
+        );
+        assert_eq!(
+            sim.introduced,
+            vec![BoundaryViolation::UndeclaredDependency {
+                from: "session".into(),
+                to: "auth".into(),
+                from_domain: "billing".into(),
+                to_domain: "identity".into(),
+            }]
+        );
+        assert!(matches!(
+            &sim.resolved[..],
+            [BoundaryViolation::UnbackedConsumer { consumer, .. }] if consumer.id() == "identity"
+        ));
+        assert_eq!(
+            map.simulate(&Refactor::MoveToGroup {
+                module: "auth".into(),
+                group: "ghost".into(),
+            })
+            .unwrap_err(),
+            RefactorError::UnknownGroup("ghost".into())
+        );
+
+        let mut renamed = map.clone();
+        renamed.groups[1].id = "payments".into();
+        renamed.groups[1].aliases = vec!["billing-core".into()];
+        renamed.modules[1].aliases = vec!["login".into()];
+        let sim = renamed
+            .simulate(&Refactor::MoveToGroup {
+                module: "login".into(),
+                group: "billing-core".into(),
+            })
+            .unwrap();
+        assert_eq!(
+            sim.map.find_group("payments").unwrap().module_ids,
+            vec!["billing", "session"]
+        );
+        assert_eq!(
+            sim.map.find_group_containing("session").unwrap().id,
+            "payments"
+        );
+    }
+
+    #[test]
+    fn test_refactor_plan() {
+        let mut map = sample();
+        map.modules[1].metrics.risk_score = Score::new(0.4).unwrap();
+        let plan = RefactorPlan::build(
+            &map,
+            "fold-session",
+            "Fold session into auth",
+            vec![
+                Refactor::Merge {
+                    a: "auth".into(),
+                    b: "session".into(),
+                },
+                Refactor::MoveToGroup {
+                    module: "auth".into(),
+                    group: "billing-core".into(),
+                },
+            ],
+        )
+        .unwrap();
+
+        assert_eq!(plan.affected_modules, vec!["api", "auth", "session"]);
+        assert!((plan.estimated_risk - 0.4).abs() < 1e-9);
+        assert_eq!(plan.checklist[0], "Merge `session` into `auth`");
+        assert_eq!(plan.checklist[1], "Update references in api");
+        assert!(
+            plan.checklist
+                .contains(&"Regenerate the module map".to_string())
+        );
+
+        let markdown = plan.to_markdown();
+        assert!(markdown.starts_with("# Fold session into auth\n"));
+        assert!(markdown.contains("2. Move `auth` to group `billing-core`"));
+        assert!(markdown.contains("- [ ] Run tests for `api`, `auth`, `session`"));
+
+        let json = serde_json::to_value(&plan).unwrap();
+        assert_eq!(json["steps"][0]["kind"], "merge");
+        let restored: RefactorPlan = serde_json::from_value(json).unwrap();
+        assert_eq!(restored, plan);
+
+        assert!(
+            RefactorPlan::build(
+                &map,
+                "bad",
+                "Bad",
+                vec![Refactor::MoveToGroup {
+                    module: "ghost".into(),
+                    group: "billing-core".into(),
+                }],
+            )
+            .is_err()
+        );
+    }
+}
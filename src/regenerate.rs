@@ -0,0 +1,348 @@
+//! Minimal regeneration planning from map changes, file changes and rule provenance
+
+use std::collections::BTreeSet;
+
+use serde::Serialize;
+
+use crate::entity::EntityRef;
+use crate::manifest::ProjectManifest;
+use crate::module_map::ModuleMap;
+use crate::ownership::ModuleIndex;
+use crate::rule::Rule;
+
+/// Names of generated artifacts, each list sorted
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct ArtifactSet {
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub rules: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub skills: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub agents: Vec<String>,
+    /// Module ids whose `ModuleContext` is affected
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub module_contexts: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub group_contexts: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub domain_contexts: Vec<String>,
+}
+
+impl ArtifactSet {
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+            && self.skills.is_empty()
+            && self.agents.is_empty()
+            && self.module_contexts.is_empty()
+            && self.group_contexts.is_empty()
+            && self.domain_contexts.is_empty()
+    }
+}
+
+/// What to regenerate, what to keep and which contexts to delete after a map update
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct RegenerationPlan {
+    /// Map facts that changed, the reasons behind `regenerate`
+    pub dirty: Vec<EntityRef>,
+    pub regenerate: ArtifactSet,
+    pub keep: ArtifactSet,
+    /// Contexts of modules, groups and domains no longer in the map
+    pub remove: ArtifactSet,
+}
+
+fn split(all: BTreeSet<String>, dirty: impl Fn(&str) -> bool) -> (Vec<String>, Vec<String>) {
+    all.into_iter().partition(|name| dirty(name))
+}
+
+impl RegenerationPlan {
+    /// Compares `old` with `new` and the files changed since `old` was
+    /// generated. Rules with `derived_from` are regenerated exactly when a
+    /// source changed; rules without provenance, and skills, follow the
+    /// module, group and domain contexts listing them. Agents are only
+    /// regenerated when modules are added or removed.
+    pub fn compute(
+        old: &ProjectManifest,
+        new: &ModuleMap,
+        changed_files: &[&str],
+        rules: &[Rule],
+    ) -> Self {
+        let prev = &old.project;
+        let diff = ModuleMap::diff(prev, new);
+        let mut dirty: BTreeSet<EntityRef> = BTreeSet::new();
+
+        let index = ModuleIndex::new(new);
+        for path in changed_files {
+            if let Some(module) = index.find_module(path) {
+                dirty.insert(EntityRef::Module(module.id.clone()));
+            }
+        }
+        for id in diff.added_modules.iter().chain(&diff.removed_modules) {
+            dirty.insert(EntityRef::Module(id.clone()));
+        }
+        for change in &diff.changed_modules {
+            dirty.insert(EntityRef::Module(change.module_id.clone()));
+        }
+        // Modules whose context lists a changed convention or issue
+        let mut fact_changed: BTreeSet<&str> = BTreeSet::new();
+        for module in &new.modules {
+            let before = prev.find_module(&module.id);
+            for convention in &module.conventions {
+                if !before.is_some_and(|b| b.conventions.contains(convention)) {
+                    dirty.insert(EntityRef::Convention(convention.name.clone()));
+                    fact_changed.insert(&module.id);
+                }
+            }
+            for issue in &module.known_issues {
+                if !before.is_some_and(|b| b.known_issues.contains(issue)) {
+                    dirty.insert(EntityRef::Issue(issue.id.clone()));
+                    fact_changed.insert(&module.id);
+                }
+            }
+        }
+        for module in &prev.modules {
+            let Some(after) = new.find_module(&module.id) else {
+                dirty.extend(
+                    module
+                        .conventions
+                        .iter()
+                        .map(|c| EntityRef::Convention(c.name.clone())),
+                );
+                dirty.extend(
+                    module
+                        .known_issues
+                        .iter()
+                        .map(|i| EntityRef::Issue(i.id.clone())),
+                );
+                continue;
+            };
+            for convention in &module.conventions {
+                if !after.conventions.iter().any(|c| c.name == convention.name) {
+                    dirty.insert(EntityRef::Convention(convention.name.clone()));
+                    fact_changed.insert(&after.id);
+                }
+            }
+            for issue in &module.known_issues {
+                if !after.known_issues.iter().any(|i| i.id == issue.id) {
+                    dirty.insert(EntityRef::Issue(issue.id.clone()));
+                    fact_changed.insert(&after.id);
+                }
+            }
+        }
+        let context_dirty = |id: &str| {
+            fact_changed.contains(id) || dirty.contains(&EntityRef::Module(id.to_string()))
+        };
+
+        let dirty_groups: BTreeSet<&str> = new
+            .groups
+            .iter()
+            .filter(|g| {
+                let before = prev.find_group(&g.id);
+                before.is_none_or(|b| {
+                    b.module_ids != g.module_ids
+                        || b.boundary_rules != g.boundary_rules
+                        || b.domain_id != g.domain_id
+                })
+            })
+            .map(|g| g.id.as_str())
+            .collect();
+        let dirty_domains: BTreeSet<&str> = new
+            .domains
+            .iter()
+            .filter(|d| {
+                let before = prev.find_domain(&d.id);
+                before.is_none_or(|b| {
+                    b.group_ids != d.group_ids
+                        || b.boundary_rules != d.boundary_rules
+                        || b.interfaces.len() != d.interfaces.len()
+                        || b.interfaces
+                            .iter()
+                            .zip(&d.interfaces)
+                            .any(|(x, y)| x.name != y.name)
+                })
+            })
+            .map(|d| d.id.as_str())
+            .collect();
+
+        let mut plan = Self {
+            dirty: dirty.iter().cloned().collect(),
+            ..Default::default()
+        };
+
+        let mut regenerated_rules: BTreeSet<String> = BTreeSet::new();
+        let mut regenerated_skills: BTreeSet<String> = BTreeSet::new();
+        let mut all_rules: BTreeSet<String> = old.rules.iter().cloned().collect();
+        let mut all_skills: BTreeSet<String> = old.skills.iter().cloned().collect();
+
+        let mut module_ids: BTreeSet<String> = old.modules.keys().cloned().collect();
+        module_ids.extend(
+            prev.modules
+                .iter()
+                .chain(&new.modules)
+                .map(|m| m.id.clone()),
+        );
+        for id in module_ids {
+            let ctx = old.modules.get(&id);
+            all_rules.extend(ctx.into_iter().flat_map(|c| c.rules.iter().cloned()));
+            all_skills.extend(ctx.into_iter().flat_map(|c| c.skills.iter().cloned()));
+            if new.find_module(&id).is_none() {
+                plan.remove.module_contexts.push(id);
+            } else if context_dirty(&id) {
+                regenerated_rules.extend(ctx.into_iter().flat_map(|c| c.rules.iter().cloned()));
+                regenerated_skills.extend(ctx.into_iter().flat_map(|c| c.skills.iter().cloned()));
+                plan.regenerate.module_contexts.push(id);
+            } else {
+                plan.keep.module_contexts.push(id);
+            }
+        }
+
+        let mut group_ids: BTreeSet<String> = old.groups.keys().cloned().collect();
+        group_ids.extend(prev.groups.iter().chain(&new.groups).map(|g| g.id.clone()));
+        for id in group_ids {
+            let ctx_rules = old
+                .groups
+                .get(&id)
+                .map(|c| c.rules.as_slice())
+                .unwrap_or(&[]);
+            all_rules.extend(ctx_rules.iter().cloned());
+            if new.find_group(&id).is_none() {
+                plan.remove.group_contexts.push(id);
+            } else if dirty_groups.contains(id.as_str()) {
+                regenerated_rules.extend(ctx_rules.iter().cloned());
+                plan.regenerate.group_contexts.push(id);
+            } else {
+                plan.keep.group_contexts.push(id);
+            }
+        }
+
+        let mut domain_ids: BTreeSet<String> = old.domains.keys().cloned().collect();
+        domain_ids.extend(
+            prev.domains
+                .iter()
+                .chain(&new.domains)
+                .map(|d| d.id.clone()),
+        );
+        for id in domain_ids {
+            let ctx_rules = old
+                .domains
+                .get(&id)
+                .map(|c| c.rules.as_slice())
+                .unwrap_or(&[]);
+            all_rules.extend(ctx_rules.iter().cloned());
+            if new.find_domain(&id).is_none() {
+                plan.remove.domain_contexts.push(id);
+            } else if dirty_domains.contains(id.as_str()) {
+                regenerated_rules.extend(ctx_rules.iter().cloned());
+                plan.regenerate.domain_contexts.push(id);
+            } else {
+                plan.keep.domain_contexts.push(id);
+            }
+        }
+
+        all_rules.extend(rules.iter().map(|r| r.name.clone()));
+        let rule_dirty = |name: &str| match rules.iter().find(|r| r.name == name) {
+            Some(rule) if !rule.derived_from.is_empty() => {
+                rule.derived_from.iter().any(|e| dirty.contains(e))
+            }
+            _ => regenerated_rules.contains(name),
+        };
+        (plan.regenerate.rules, plan.keep.rules) = split(all_rules, rule_dirty);
+        (plan.regenerate.skills, plan.keep.skills) =
+            split(all_skills, |name| regenerated_skills.contains(name));
+
+        let structure_changed = !diff.added_modules.is_empty() || !diff.removed_modules.is_empty();
+        let all_agents: BTreeSet<String> = old.agents.iter().cloned().collect();
+        (plan.regenerate.agents, plan.keep.agents) = split(all_agents, |_| structure_changed);
+
+        plan
+    }
+
+    /// Whether nothing needs regenerating or removing.
+    pub fn is_noop(&self) -> bool {
+        self.regenerate.is_empty() && self.remove.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        Convention, GeneratorInfo, Module, ModuleContext, ModuleGroup, ProjectMetadata, TechStack,
+    };
+
+    fn module(id: &str) -> Module {
+        Module::new(id, id, vec![format!("src/{id}/")]).with_primary_language("rust")
+    }
+
+    fn map(modules: Vec<Module>) -> ModuleMap {
+        let ids = modules.iter().map(|m| m.id.clone()).collect();
+        ModuleMap::new(
+            GeneratorInfo::new("test", "1.0.0"),
+            ProjectMetadata::new("shop", TechStack::new("rust")),
+            modules,
+            vec![ModuleGroup::new("core", "Core", ids)],
+        )
+    }
+
+    #[test]
+    fn test_regeneration_plan() {
+        let mut auth = module("auth");
+        auth.conventions
+            .push(Convention::new("errors", "thiserror enums"));
+        let old =
+            ProjectManifest::new(map(vec![auth.clone(), module("billing"), module("search")]))
+                .with_rules(vec!["project".into(), "auth-errors".into()])
+                .with_skills(vec!["billing-debug".into()])
+                .with_agents(vec!["reviewer".into()])
+                .with_modules(
+                    [
+                        (
+                            "auth".to_string(),
+                            ModuleContext::new().with_rules(vec!["auth".into()]),
+                        ),
+                        (
+                            "billing".to_string(),
+                            ModuleContext::new()
+                                .with_rules(vec!["billing".into()])
+                                .with_skills(vec!["billing-debug".into()]),
+                        ),
+                    ]
+                    .into(),
+                );
+
+        auth.conventions[0].pattern = "anyhow at the edges".into();
+        let new = map(vec![auth, module("billing"), module("search")]);
+        let rules = [
+            Rule::module("auth", vec![], vec![])
+                .with_derived_from(vec![EntityRef::Module("auth".into())]),
+            Rule::project("auth-errors", vec![])
+                .with_derived_from(vec![EntityRef::Convention("errors".into())]),
+            Rule::project("project", vec![])
+                .with_derived_from(vec![EntityRef::Adr("ADR-1".into())]),
+        ];
+
+        let plan = RegenerationPlan::compute(&old, &new, &["src/billing/invoice.rs"], &rules);
+        assert_eq!(
+            plan.dirty,
+            [
+                EntityRef::Module("billing".into()),
+                EntityRef::Convention("errors".into()),
+            ]
+        );
+        assert_eq!(plan.regenerate.rules, ["auth-errors", "billing"]);
+        assert_eq!(plan.keep.rules, ["auth", "project"]);
+        assert_eq!(plan.regenerate.skills, ["billing-debug"]);
+        assert_eq!(plan.regenerate.module_contexts, ["auth", "billing"]);
+        assert_eq!(plan.keep.module_contexts, ["search"]);
+        assert_eq!(plan.keep.group_contexts, ["core"]);
+        assert_eq!(plan.keep.agents, ["reviewer"]);
+        assert!(plan.remove.is_empty());
+
+        let shrunk = map(vec![module("auth"), module("billing")]);
+        let plan = RegenerationPlan::compute(&old, &shrunk, &[], &rules);
+        assert_eq!(plan.remove.module_contexts, ["search"]);
+        assert_eq!(plan.regenerate.group_contexts, ["core"]);
+        assert_eq!(plan.regenerate.agents, ["reviewer"]);
+
+        assert!(RegenerationPlan::compute(&old, &old.project, &[], &rules).is_noop());
+    }
+}
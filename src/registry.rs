@@ -1,8 +1,15 @@
+use std::fmt;
+
 use semver::Version;
+use serde::de::DeserializeOwned;
+use serde_json::{Map, Value};
 use thiserror::Error;
 
-use crate::manifest::ProjectManifest;
-use crate::module_map::SCHEMA_VERSION;
+use crate::key_style::{KeyStyle, restyle_keys};
+use crate::manifest::{DomainContext, GroupContext, ModuleContext, ProjectManifest};
+use crate::module_map::{Domain, Module, ModuleGroup, SCHEMA_VERSION};
+use crate::normalize::{ScoreViolation, score_violations};
+use crate::types::Score;
 
 #[derive(Debug, Error)]
 pub enum SchemaError {
@@ -12,10 +19,56 @@ pub enum SchemaError {
     #[error("JSON parse error: {0}")]
     JsonParse(#[from] serde_json::Error),
 
+    #[cfg(feature = "policy-toml")]
+    #[error("TOML parse error: {0}")]
+    TomlParse(#[from] toml::de::Error),
+
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+
     #[error("Incompatible schema version: found {found}, required major version {required_major}")]
     IncompatibleVersion { found: String, required_major: u64 },
 }
 
+/// Something `load_lenient` dropped or patched, at a JSON pointer
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LoadDiagnostic {
+    pub pointer: String,
+    pub message: String,
+}
+
+impl fmt::Display for LoadDiagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.pointer, self.message)
+    }
+}
+
+/// A manifest recovered by `load_lenient`, with what was lost on the way
+#[derive(Debug)]
+pub struct LenientLoad {
+    pub manifest: ProjectManifest,
+    pub diagnostics: Vec<LoadDiagnostic>,
+    /// Scores that were clamped, also reported in `diagnostics`
+    pub score_violations: Vec<ScoreViolation>,
+}
+
+/// Legacy or camelCase keys accepted in place of the canonical snake_case
+/// name, as `(alias, canonical)`. Each is also a `#[serde(alias)]` on its
+/// field, so `load` accepts them silently; `load_lenient` reports them.
+pub const LEGACY_KEY_ALIASES: &[(&str, &str)] = &[
+    ("schemaVersion", "schema_version"),
+    ("generatedAt", "generated_at"),
+    ("createdAt", "created_at"),
+    ("modifiedAt", "modified"),
+    ("modified_at", "modified"),
+    ("moduleId", "module_id"),
+    ("dependencyType", "dependency_type"),
+    ("dependency-type", "dependency_type"),
+    ("edgeType", "edge_type"),
+    ("edge-type", "edge_type"),
+    ("dependencyGraph", "dependency_graph"),
+];
+
 pub struct SchemaRegistry {
     current_version: Version,
 }
@@ -29,11 +82,58 @@ impl SchemaRegistry {
     }
 
     pub fn load(&self, data: &str) -> Result<ProjectManifest, SchemaError> {
-        let manifest: ProjectManifest = serde_json::from_str(data)?;
+        let manifest = ProjectManifest::from_json(data)?;
         self.validate_project_version(&manifest)?;
         Ok(manifest)
     }
 
+    /// Loads as much of a damaged manifest as possible.
+    ///
+    /// Legacy and camelCase keys from [`LEGACY_KEY_ALIASES`] are renamed.
+    /// Truncated input is cut back to the last complete object or array and
+    /// closed. Module scores outside `[0, 1]` are clamped and listed in
+    /// `score_violations`. Modules, groups, domains and context entries that
+    /// fail to deserialize are dropped, and missing timestamps are filled in;
+    /// each repair is reported as a diagnostic. Fails only when the remaining
+    /// skeleton is unusable or the schema version is incompatible.
+    pub fn load_lenient(&self, data: &str) -> Result<LenientLoad, SchemaError> {
+        let mut diagnostics = Vec::new();
+        let mut value: Value = match serde_json::from_str(data) {
+            Ok(value) => value,
+            Err(err) => {
+                let repaired = close_truncated(data).ok_or(err)?;
+                diagnostics.push(LoadDiagnostic {
+                    pointer: String::new(),
+                    message: format!(
+                        "input truncated; kept {} of {} bytes",
+                        repaired.len(),
+                        data.len()
+                    ),
+                });
+                serde_json::from_str(&repaired)?
+            }
+        };
+
+        rename_legacy_keys(&mut value, "", &mut diagnostics);
+        restyle_keys(&mut value, KeyStyle::SnakeCase);
+        let score_violations = clamp_scores(&mut value, &mut diagnostics);
+        salvage_array::<Module>(&mut value, "/project/modules", &mut diagnostics);
+        salvage_array::<ModuleGroup>(&mut value, "/project/groups", &mut diagnostics);
+        salvage_array::<Domain>(&mut value, "/project/domains", &mut diagnostics);
+        salvage_object::<ModuleContext>(&mut value, "/modules", &mut diagnostics);
+        salvage_object::<GroupContext>(&mut value, "/groups", &mut diagnostics);
+        salvage_object::<DomainContext>(&mut value, "/domains", &mut diagnostics);
+        fill_timestamps(&mut value, &mut diagnostics);
+
+        let manifest: ProjectManifest = serde_json::from_value(value)?;
+        self.validate_project_version(&manifest)?;
+        Ok(LenientLoad {
+            manifest,
+            diagnostics,
+            score_violations,
+        })
+    }
+
     fn validate_project_version(&self, manifest: &ProjectManifest) -> Result<(), SchemaError> {
         let version = Version::parse(&manifest.project.schema_version)?;
         if version.major != self.current_version.major {
@@ -50,6 +150,175 @@ impl SchemaRegistry {
     }
 }
 
+/// Cuts truncated JSON back to the last closed object or array and closes
+/// the brackets still open there. `None` when nothing was ever closed.
+fn close_truncated(data: &str) -> Option<String> {
+    let mut stack = Vec::new();
+    let mut in_string = false;
+    let mut escaped = false;
+    let mut last_complete: Option<(usize, Vec<u8>)> = None;
+    for (i, byte) in data.bytes().enumerate() {
+        if in_string {
+            match byte {
+                _ if escaped => escaped = false,
+                b'\\' => escaped = true,
+                b'"' => in_string = false,
+                _ => {}
+            }
+            continue;
+        }
+        match byte {
+            b'"' => in_string = true,
+            b'{' => stack.push(b'}'),
+            b'[' => stack.push(b']'),
+            b'}' | b']' => {
+                stack.pop();
+                last_complete = Some((i + 1, stack.clone()));
+            }
+            _ => {}
+        }
+    }
+    let (end, open) = last_complete.filter(|(_, open)| !open.is_empty())?;
+    let mut repaired = data[..end].to_string();
+    repaired.extend(open.iter().rev().map(|&b| b as char));
+    Some(repaired)
+}
+
+/// Clamps module scores into `[0, 1]` so one bad number does not cost a module.
+fn clamp_scores(value: &mut Value, diagnostics: &mut Vec<LoadDiagnostic>) -> Vec<ScoreViolation> {
+    let violations = score_violations(value);
+    for violation in &violations {
+        let clamped = Score::clamped(violation.value).get();
+        if let Some(score) = value.pointer_mut(&violation.pointer) {
+            *score = Value::from(clamped);
+        }
+        diagnostics.push(LoadDiagnostic {
+            pointer: violation.pointer.clone(),
+            message: format!("clamped {} to {clamped}", violation.value),
+        });
+    }
+    violations
+}
+
+fn salvage_array<T: DeserializeOwned>(
+    value: &mut Value,
+    pointer: &str,
+    diagnostics: &mut Vec<LoadDiagnostic>,
+) {
+    let Some(Value::Array(items)) = value.pointer_mut(pointer) else {
+        return;
+    };
+    let mut index = 0;
+    items.retain(|item| {
+        let kept = match serde_json::from_value::<T>(item.clone()) {
+            Ok(_) => true,
+            Err(err) => {
+                diagnostics.push(LoadDiagnostic {
+                    pointer: format!("{pointer}/{index}"),
+                    message: format!("dropped: {err}"),
+                });
+                false
+            }
+        };
+        index += 1;
+        kept
+    });
+}
+
+fn salvage_object<T: DeserializeOwned>(
+    value: &mut Value,
+    pointer: &str,
+    diagnostics: &mut Vec<LoadDiagnostic>,
+) {
+    let Some(Value::Object(entries)) = value.pointer_mut(pointer) else {
+        return;
+    };
+    entries.retain(
+        |key, entry| match serde_json::from_value::<T>(entry.clone()) {
+            Ok(_) => true,
+            Err(err) => {
+                diagnostics.push(LoadDiagnostic {
+                    pointer: format!("{pointer}/{key}"),
+                    message: format!("dropped: {err}"),
+                });
+                false
+            }
+        },
+    );
+}
+
+/// Renames aliased keys to their canonical name unless both are present.
+fn rename_legacy_keys(value: &mut Value, pointer: &str, diagnostics: &mut Vec<LoadDiagnostic>) {
+    match value {
+        Value::Object(object) => {
+            for &(alias, canonical) in LEGACY_KEY_ALIASES {
+                if object.contains_key(canonical) {
+                    continue;
+                }
+                if let Some(moved) = object.remove(alias) {
+                    diagnostics.push(LoadDiagnostic {
+                        pointer: format!("{pointer}/{alias}"),
+                        message: format!("legacy key; read as '{canonical}'"),
+                    });
+                    object.insert(canonical.into(), moved);
+                }
+            }
+            for (key, child) in object.iter_mut() {
+                let key = key.replace('~', "~0").replace('/', "~1");
+                rename_legacy_keys(child, &format!("{pointer}/{key}"), diagnostics);
+            }
+        }
+        Value::Array(items) => {
+            for (index, child) in items.iter_mut().enumerate() {
+                rename_legacy_keys(child, &format!("{pointer}/{index}"), diagnostics);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Fills a missing `created_at` or `project.generated_at` from the other,
+/// or the Unix epoch, and a missing module list with an empty one.
+fn fill_timestamps(value: &mut Value, diagnostics: &mut Vec<LoadDiagnostic>) {
+    let Some(root) = value.as_object_mut() else {
+        return;
+    };
+    let created_at = root.get("created_at").cloned();
+    let Some(Value::Object(project)) = root.get_mut("project") else {
+        return;
+    };
+    let generated_at = project.get("generated_at").cloned();
+    let fallback = || Value::String("1970-01-01T00:00:00Z".into());
+
+    let mut fill = |object: &mut Map<String, Value>, key: &str, pointer: &str, with: Value| {
+        if !object.contains_key(key) {
+            diagnostics.push(LoadDiagnostic {
+                pointer: pointer.into(),
+                message: format!("missing; set to {with}"),
+            });
+            object.insert(key.into(), with);
+        }
+    };
+    fill(
+        project,
+        "generated_at",
+        "/project/generated_at",
+        created_at.clone().unwrap_or_else(fallback),
+    );
+    fill(
+        project,
+        "modules",
+        "/project/modules",
+        Value::Array(Vec::new()),
+    );
+    fill(
+        root,
+        "created_at",
+        "/created_at",
+        generated_at.or(created_at).unwrap_or_else(fallback),
+    );
+}
+
 impl Default for SchemaRegistry {
     fn default() -> Self {
         Self::new()
@@ -59,6 +328,7 @@ impl Default for SchemaRegistry {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::types::DependencyType;
 
     fn sample_manifest_json(schema_version: &str) -> String {
         format!(
@@ -116,6 +386,119 @@ mod tests {
         ));
     }
 
+    fn module_json(id: &str) -> String {
+        format!(
+            r#"{{"id": "{id}", "name": "{id}", "paths": ["src/{id}/"], "responsibility": "",
+                "primary_language": "rust", "coverage_ratio": 0.5, "value_score": 0.5,
+                "risk_score": 0.5}}"#
+        )
+    }
+
+    fn manifest_with_modules(modules: &[String]) -> String {
+        sample_manifest_json("1.0.0").replace(
+            r#""modules": []"#,
+            &format!(r#""modules": [{}]"#, modules.join(",")),
+        )
+    }
+
+    #[test]
+    fn test_load_lenient_drops_bad_modules() {
+        let registry = SchemaRegistry::new();
+        let json = manifest_with_modules(&[
+            module_json("auth"),
+            r#"{"id": "broken"}"#.into(),
+            module_json("billing").replace(r#""risk_score": 0.5"#, r#""risk_score": 1.4"#),
+        ]);
+        assert!(registry.load(&json).is_err());
+
+        let loaded = registry.load_lenient(&json).unwrap();
+        let ids: Vec<_> = loaded
+            .manifest
+            .project
+            .modules
+            .iter()
+            .map(|m| &m.id)
+            .collect();
+        assert_eq!(ids, vec!["auth", "billing"]);
+        let pointers: Vec<_> = loaded
+            .diagnostics
+            .iter()
+            .map(|d| d.pointer.as_str())
+            .collect();
+        assert_eq!(
+            pointers,
+            vec!["/project/modules/2/risk_score", "/project/modules/1"]
+        );
+        assert_eq!(
+            loaded.manifest.project.modules[1].metrics.risk_score,
+            Score::ONE
+        );
+        assert_eq!(
+            loaded.score_violations[0].to_string(),
+            "module 'billing' has risk_score = 1.4 outside [0, 1]"
+        );
+    }
+
+    #[test]
+    fn test_load_lenient_truncated() {
+        let registry = SchemaRegistry::new();
+        let json = manifest_with_modules(&[module_json("auth"), module_json("billing")]);
+        let cut = json.find("billing").unwrap() + 20;
+
+        let loaded = registry.load_lenient(&json[..cut]).unwrap();
+        let ids: Vec<_> = loaded
+            .manifest
+            .project
+            .modules
+            .iter()
+            .map(|m| &m.id)
+            .collect();
+        assert_eq!(ids, vec!["auth"]);
+        assert_eq!(
+            loaded.manifest.project.generated_at,
+            loaded.manifest.created_at
+        );
+        let pointers: Vec<_> = loaded
+            .diagnostics
+            .iter()
+            .map(|d| d.pointer.as_str())
+            .collect();
+        assert_eq!(pointers, vec!["", "/project/generated_at"]);
+
+        assert!(registry.load_lenient("{\"version\": ").is_err());
+    }
+
+    #[test]
+    fn test_legacy_key_aliases() {
+        let registry = SchemaRegistry::new();
+        let json = sample_manifest_json("1.0.0")
+            .replace(r#""schema_version""#, r#""schemaVersion""#)
+            .replace(r#""created_at""#, r#""createdAt""#)
+            .replace(
+                r#""modules": []"#,
+                r#""modules": [], "dependency_graph": {"edges": [
+                    {"from": "api", "to": "core", "edge-type": "build"}]}"#,
+            );
+        let strict = registry.load(&json).unwrap();
+        let edge = &strict.project.dependency_graph.unwrap().edges[0];
+        assert_eq!(edge.edge_type, DependencyType::Build);
+
+        let loaded = registry.load_lenient(&json).unwrap();
+        let notes: Vec<_> = loaded.diagnostics.iter().map(ToString::to_string).collect();
+        assert_eq!(
+            notes,
+            vec![
+                "/createdAt: legacy key; read as 'created_at'",
+                "/project/schemaVersion: legacy key; read as 'schema_version'",
+                "/project/dependency_graph/edges/0/edge-type: legacy key; read as 'edge_type'",
+            ]
+        );
+
+        let file: crate::TrackedFile =
+            serde_json::from_str(r#"{"path": "a.rs", "hash": "x", "modifiedAt": 5}"#).unwrap();
+        assert_eq!(file.modified, 5);
+    }
+
     #[test]
     fn test_schema_version_constant() {
         Version::parse(SCHEMA_VERSION).expect("SCHEMA_VERSION must be valid semver");
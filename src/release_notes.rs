@@ -0,0 +1,194 @@
+//! Release-note outlines sectioned by domain and group
+
+use std::fmt::Write;
+
+use serde::Serialize;
+
+use crate::module_map::ModuleMap;
+
+/// Commits touching one group (or ungrouped modules when `group_id` is `None`)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ReleaseGroup {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub group_id: Option<String>,
+    pub title: String,
+    pub commits: Vec<String>,
+}
+
+/// One domain's section; `domain_id` is `None` for groups outside any domain
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ReleaseSection {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub domain_id: Option<String>,
+    pub title: String,
+    pub groups: Vec<ReleaseGroup>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct ReleaseNotesOutline {
+    pub sections: Vec<ReleaseSection>,
+    /// Commits whose files belong to no module
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub uncategorized: Vec<String>,
+}
+
+impl ReleaseNotesOutline {
+    pub fn to_markdown(&self) -> String {
+        let mut out = String::new();
+        for section in &self.sections {
+            let _ = writeln!(out, "## {}\n", section.title);
+            for group in &section.groups {
+                if section.groups.len() > 1 || group.group_id.is_some() {
+                    let _ = writeln!(out, "### {}\n", group.title);
+                }
+                for commit in &group.commits {
+                    let _ = writeln!(out, "- {commit}");
+                }
+                out.push('\n');
+            }
+        }
+        if !self.uncategorized.is_empty() {
+            out.push_str("## Miscellaneous\n\n");
+            for commit in &self.uncategorized {
+                let _ = writeln!(out, "- {commit}");
+            }
+            out.push('\n');
+        }
+        out.truncate(out.trim_end().len());
+        out.push('\n');
+        out
+    }
+}
+
+/// (domain index, group index) in map order
+type BucketKey = (Option<usize>, Option<usize>);
+
+impl ModuleMap {
+    /// Buckets `(commit, changed files)` pairs by domain and group, in map order.
+    /// A commit spanning several groups is listed under each of them.
+    pub fn release_notes_outline(&self, commits: &[(&str, &[&str])]) -> ReleaseNotesOutline {
+        let mut buckets: Vec<(BucketKey, Vec<String>)> = Vec::new();
+        let mut uncategorized = Vec::new();
+
+        for (commit, files) in commits {
+            let mut keys: Vec<BucketKey> = Vec::new();
+            for file in *files {
+                let Some(module) = self.modules.iter().find(|m| m.contains_file(file)) else {
+                    continue;
+                };
+                let group = self
+                    .groups
+                    .iter()
+                    .position(|g| g.module_ids.contains(&module.id));
+                let domain = self
+                    .find_domain_containing_module(&module.id)
+                    .and_then(|d| self.domains.iter().position(|x| x.id == d.id));
+                if !keys.contains(&(domain, group)) {
+                    keys.push((domain, group));
+                }
+            }
+            if keys.is_empty() {
+                uncategorized.push(commit.to_string());
+            }
+            for key in keys {
+                match buckets.iter_mut().find(|(k, _)| *k == key) {
+                    Some((_, list)) => list.push(commit.to_string()),
+                    None => buckets.push((key, vec![commit.to_string()])),
+                }
+            }
+        }
+
+        // Map order, with domain-less and group-less buckets last
+        buckets.sort_by_key(|((domain, group), _)| {
+            (domain.unwrap_or(usize::MAX), group.unwrap_or(usize::MAX))
+        });
+
+        let mut sections: Vec<ReleaseSection> = Vec::new();
+        for ((domain, group), commits) in buckets {
+            let group = ReleaseGroup {
+                group_id: group.map(|i| self.groups[i].id.clone()),
+                title: group.map_or_else(|| "Other".to_string(), |i| self.groups[i].name.clone()),
+                commits,
+            };
+            let domain_id = domain.map(|i| self.domains[i].id.clone());
+            match sections.last_mut().filter(|s| s.domain_id == domain_id) {
+                Some(section) => section.groups.push(group),
+                None => sections.push(ReleaseSection {
+                    title: domain
+                        .map_or_else(|| "Other".to_string(), |i| self.domains[i].name.clone()),
+                    domain_id,
+                    groups: vec![group],
+                }),
+            }
+        }
+
+        ReleaseNotesOutline {
+            sections,
+            uncategorized,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Domain, GeneratorInfo, Module, ModuleGroup, ProjectMetadata, TechStack};
+
+    fn module(id: &str) -> Module {
+        Module::new(id, id, vec![format!("src/{id}/")]).with_primary_language("rust")
+    }
+
+    #[test]
+    fn test_release_notes_outline() {
+        let map = ModuleMap::new(
+            GeneratorInfo::new("test", "1.0.0"),
+            ProjectMetadata::new("shop", TechStack::new("rust")),
+            vec![
+                module("auth"),
+                module("session"),
+                module("billing"),
+                module("tools"),
+            ],
+            vec![
+                ModuleGroup::new("identity-core", "Accounts", vec!["auth".into()]),
+                ModuleGroup::new("sessions", "Sessions", vec!["session".into()]),
+                ModuleGroup::new("billing-core", "Payments", vec!["billing".into()]),
+                ModuleGroup::new("dev", "Dev tooling", vec!["tools".into()]),
+            ],
+        )
+        .with_domains(vec![
+            Domain::new("billing", "Billing", vec!["billing-core".into()]),
+            Domain::new(
+                "identity",
+                "Identity",
+                vec!["identity-core".into(), "sessions".into()],
+            ),
+        ]);
+
+        let outline = map.release_notes_outline(&[
+            ("feat: login", &["src/auth/login.rs"]),
+            ("fix: refresh", &["src/session/a.rs", "src/auth/b.rs"]),
+            ("feat: invoices", &["src/billing/invoice.rs"]),
+            ("chore: lint", &["src/tools/lint.rs"]),
+            ("docs: readme", &["README.md"]),
+        ]);
+
+        let titles: Vec<_> = outline.sections.iter().map(|s| s.title.as_str()).collect();
+        assert_eq!(titles, vec!["Billing", "Identity", "Other"]);
+        let identity = &outline.sections[1];
+        assert_eq!(
+            identity.groups[0].commits,
+            vec!["feat: login", "fix: refresh"]
+        );
+        assert_eq!(identity.groups[1].commits, vec!["fix: refresh"]);
+        assert_eq!(outline.sections[2].domain_id, None);
+        assert_eq!(outline.uncategorized, vec!["docs: readme"]);
+
+        let markdown = outline.to_markdown();
+        assert!(markdown.starts_with("## Billing\n\n### Payments\n\n- feat: invoices\n"));
+        assert!(
+            markdown.contains("## Identity\n\n### Accounts\n\n- feat: login\n- fix: refresh\n")
+        );
+        assert!(markdown.ends_with("## Miscellaneous\n\n- docs: readme\n"));
+    }
+}
@@ -0,0 +1,361 @@
+//! Text renderings of the module map (Mermaid, Graphviz DOT, CSV, Prometheus)
+
+use std::collections::HashMap;
+use std::fmt::Write;
+
+use crate::module_map::{Module, ModuleMap};
+use crate::query::IssueRef;
+use crate::types::IssueSeverity;
+
+/// DOT quoted string
+fn quote(text: &str) -> String {
+    format!("\"{}\"", text.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// Mermaid quoted label; Mermaid has no backslash escapes, only entity codes
+fn mermaid_label(text: &str) -> String {
+    format!("\"{}\"", text.replace('"', "#quot;"))
+}
+
+pub(crate) fn csv_field(text: &str) -> String {
+    if text.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", text.replace('"', "\"\""))
+    } else {
+        text.to_string()
+    }
+}
+
+fn label_value(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+fn metric_header(out: &mut String, name: &str, help: &str) {
+    let _ = writeln!(out, "# HELP {name} {help}");
+    let _ = writeln!(out, "# TYPE {name} gauge");
+}
+
+impl ModuleMap {
+    /// Mermaid `graph TD` of modules and dependency edges.
+    pub fn to_mermaid(&self) -> String {
+        self.mermaid(false)
+    }
+
+    /// Like `to_mermaid`, with one subgraph per domain; modules outside any
+    /// domain stay at the top level.
+    pub fn to_mermaid_by_domain(&self) -> String {
+        self.mermaid(true)
+    }
+
+    /// Nodes are `m{index}` and subgraphs `d{index}`, so ids never collide
+    /// with each other or with Mermaid keywords; names only appear as labels.
+    fn mermaid(&self, by_domain: bool) -> String {
+        let edges = self.dependency_edges();
+        let mut node_ids: HashMap<&str, String> = HashMap::new();
+        for module in &self.modules {
+            let next = format!("m{}", node_ids.len());
+            node_ids.entry(&module.id).or_insert(next);
+        }
+        let mut external = Vec::new();
+        for edge in &edges {
+            for id in [&edge.from, &edge.to] {
+                if !node_ids.contains_key(id.as_str()) {
+                    node_ids.insert(id, format!("m{}", node_ids.len()));
+                    external.push(id.as_str());
+                }
+            }
+        }
+
+        let mut out = String::from("graph TD\n");
+        let node = |out: &mut String, module: &Module, indent: &str| {
+            let _ = writeln!(
+                out,
+                "{indent}{}[{}]",
+                node_ids[module.id.as_str()],
+                mermaid_label(&module.name)
+            );
+        };
+        let domain_of = |module: &Module| {
+            by_domain
+                .then(|| self.find_domain_containing_module(&module.id))
+                .flatten()
+        };
+        if by_domain {
+            for (index, domain) in self.domains.iter().enumerate() {
+                let members: Vec<&Module> = self
+                    .modules
+                    .iter()
+                    .filter(|m| domain_of(m).is_some_and(|d| d.id == domain.id))
+                    .collect();
+                if members.is_empty() {
+                    continue;
+                }
+                let _ = writeln!(
+                    out,
+                    "    subgraph d{index}[{}]",
+                    mermaid_label(&domain.name)
+                );
+                for module in members {
+                    node(&mut out, module, "        ");
+                }
+                out.push_str("    end\n");
+            }
+        }
+        for module in self.modules.iter().filter(|m| domain_of(m).is_none()) {
+            node(&mut out, module, "    ");
+        }
+        for id in external {
+            let _ = writeln!(out, "    {}[{}]", node_ids[id], mermaid_label(id));
+        }
+        for edge in &edges {
+            let _ = writeln!(
+                out,
+                "    {} --> {}",
+                node_ids[edge.from.as_str()],
+                node_ids[edge.to.as_str()]
+            );
+        }
+        out
+    }
+
+    /// Graphviz digraph of modules and dependency edges.
+    pub fn to_dot(&self) -> String {
+        let mut out = format!("digraph {} {{\n", quote(&self.project.name));
+        for module in &self.modules {
+            let _ = writeln!(
+                out,
+                "    {} [label={}];",
+                quote(&module.id),
+                quote(&module.name)
+            );
+        }
+        for edge in self.dependency_edges() {
+            let _ = writeln!(out, "    {} -> {};", quote(&edge.from), quote(&edge.to));
+        }
+        out.push_str("}\n");
+        out
+    }
+
+    /// One CSV row per module with its group, domain and metrics.
+    pub fn to_csv(&self) -> String {
+        let mut out = String::from(
+            "id,name,group,domain,primary_language,coverage_ratio,value_score,risk_score,issues,paths\n",
+        );
+        for module in &self.modules {
+            let group = self
+                .find_group_containing(&module.id)
+                .map_or("", |g| g.id.as_str());
+            let domain = self
+                .find_domain_containing_module(&module.id)
+                .map_or("", |d| d.id.as_str());
+            let fields = [
+                csv_field(&module.id),
+                csv_field(&module.name),
+                csv_field(group),
+                csv_field(domain),
+                csv_field(&module.primary_language),
+                module.metrics.coverage_ratio.to_string(),
+                module.metrics.value_score.to_string(),
+                module.metrics.risk_score.to_string(),
+                module.known_issues.len().to_string(),
+                csv_field(&module.paths.join(";")),
+            ];
+            out.push_str(&fields.join(","));
+            out.push('\n');
+        }
+        out
+    }
+
+    /// Prometheus text exposition of map health gauges: module count, known
+    /// issues per effective severity, mean risk per domain and dependency cycles
+    /// (strongly connected components, so the count stays cheap on dense graphs).
+    pub fn to_prometheus(&self) -> String {
+        let mut out = String::new();
+
+        metric_header(&mut out, "modmap_modules_total", "Modules in the map.");
+        let _ = writeln!(out, "modmap_modules_total {}", self.modules.len());
+
+        metric_header(
+            &mut out,
+            "modmap_open_issues",
+            "Known issues by effective severity.",
+        );
+        let mut counts = [0usize; 4];
+        for IssueRef { module, issue } in self.issues_iter() {
+            counts[self.effective_severity(issue, module) as usize] += 1;
+        }
+        let severities = [
+            IssueSeverity::Critical,
+            IssueSeverity::High,
+            IssueSeverity::Medium,
+            IssueSeverity::Low,
+        ];
+        for severity in severities {
+            let _ = writeln!(
+                out,
+                "modmap_open_issues{{severity=\"{}\"}} {}",
+                severity.to_string().to_lowercase(),
+                counts[severity as usize]
+            );
+        }
+
+        metric_header(
+            &mut out,
+            "modmap_avg_risk",
+            "Mean module risk score per domain.",
+        );
+        for (domain, modules) in self.modules_by_domain() {
+            let Some(domain) = domain else { continue };
+            let total: f64 = modules.iter().map(|m| m.metrics.risk_score.get()).sum();
+            let _ = writeln!(
+                out,
+                "modmap_avg_risk{{domain=\"{}\"}} {}",
+                label_value(domain),
+                total / modules.len() as f64
+            );
+        }
+
+        metric_header(
+            &mut out,
+            "modmap_cycles_total",
+            "Strongly connected components of modules with a dependency cycle.",
+        );
+        let _ = writeln!(
+            out,
+            "modmap_cycles_total {}",
+            self.compile_graph().cycles().len()
+        );
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        Domain, GeneratorInfo, IssueCategory, KnownIssue, Module, ModuleDependency, ModuleGroup,
+        ModuleMetrics, ProjectMetadata, TechStack,
+    };
+
+    fn module(id: &str, name: &str, dependencies: &[&str]) -> Module {
+        Module::new(id, name, vec![format!("src/{id}/")])
+            .with_dependencies(
+                dependencies
+                    .iter()
+                    .map(|d| ModuleDependency::runtime(*d))
+                    .collect(),
+            )
+            .with_primary_language("rust")
+            .with_metrics(ModuleMetrics::new(0.5, 0.25, 0.75))
+    }
+
+    fn sample_map() -> ModuleMap {
+        ModuleMap::new(
+            GeneratorInfo::new("test", "1.0.0"),
+            ProjectMetadata::new("shop", TechStack::new("rust")),
+            vec![
+                module("auth-core", "Auth, core", &[]),
+                module("api", "API", &["auth-core"]),
+            ],
+            vec![ModuleGroup::new("core", "Core", vec!["auth-core".into()])],
+        )
+    }
+
+    #[test]
+    fn test_to_mermaid() {
+        let mermaid = sample_map().to_mermaid();
+        assert!(mermaid.starts_with("graph TD\n"));
+        assert!(mermaid.contains("    m0[\"Auth, core\"]\n"));
+        assert!(mermaid.contains("    m1 --> m0\n"));
+    }
+
+    #[test]
+    fn test_to_mermaid_ids_do_not_collide() {
+        let map = ModuleMap::new(
+            GeneratorInfo::new("test", "1.0.0"),
+            ProjectMetadata::new("shop", TechStack::new("rust")),
+            vec![
+                module("user-api", "User \"API\"", &["user_api", "end"]),
+                module("user_api", "user_api", &[]),
+                module("end", "end", &["vendor/x"]),
+            ],
+            vec![],
+        );
+        assert_eq!(
+            map.to_mermaid(),
+            "graph TD\n    m0[\"User #quot;API#quot;\"]\n    m1[\"user_api\"]\n    m2[\"end\"]\n    m3[\"vendor/x\"]\n    m0 --> m1\n    m0 --> m2\n    m2 --> m3\n"
+        );
+    }
+
+    #[test]
+    fn test_to_mermaid_by_domain() {
+        let mut map = sample_map().with_domains(vec![Domain::new(
+            "identity",
+            "Identity",
+            vec!["core".into()],
+        )]);
+        assert_eq!(
+            map.to_mermaid_by_domain(),
+            "graph TD\n    subgraph d0[\"Identity\"]\n        m0[\"Auth, core\"]\n    end\n    m1[\"API\"]\n    m1 --> m0\n"
+        );
+        map.domains.clear();
+        assert_eq!(map.to_mermaid_by_domain(), map.to_mermaid());
+    }
+
+    #[test]
+    fn test_to_dot() {
+        let dot = sample_map().to_dot();
+        assert!(dot.starts_with("digraph \"shop\" {\n"));
+        assert!(dot.contains("    \"api\" -> \"auth-core\";\n"));
+        assert!(dot.ends_with("}\n"));
+    }
+
+    #[test]
+    fn test_to_csv() {
+        let csv = sample_map().to_csv();
+        let lines: Vec<_> = csv.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert_eq!(
+            lines[1],
+            "auth-core,\"Auth, core\",core,,rust,0.5,0.25,0.75,0,src/auth-core/"
+        );
+    }
+
+    #[test]
+    fn test_to_prometheus() {
+        let mut map = sample_map().with_domains(vec![Domain::new(
+            "platform",
+            "Platform",
+            vec!["core".into()],
+        )]);
+        map.modules[0]
+            .dependencies
+            .push(ModuleDependency::runtime("api"));
+        map.modules[1].known_issues.push(KnownIssue::new(
+            "leak",
+            "Session leak",
+            IssueSeverity::High,
+            IssueCategory::Security,
+        ));
+
+        let text = map.to_prometheus();
+        assert!(text.contains("# TYPE modmap_modules_total gauge\nmodmap_modules_total 2\n"));
+        assert!(text.contains("modmap_open_issues{severity=\"high\"} 1\n"));
+        assert!(text.contains("modmap_open_issues{severity=\"critical\"} 0\n"));
+        assert!(text.contains("modmap_avg_risk{domain=\"platform\"} 0.75\n"));
+        assert!(text.contains("modmap_cycles_total 1\n"));
+
+        // Every pair depends on each other: one component, however many simple cycles
+        let ids: Vec<String> = (0..12).map(|i| format!("m{i}")).collect();
+        for i in 0..2 {
+            map.modules[i].dependencies = ids.iter().map(ModuleDependency::runtime).collect();
+        }
+        for id in &ids {
+            let mut module = map.modules[0].clone();
+            module.id = id.clone();
+            map.modules.push(module);
+        }
+        assert!(map.to_prometheus().contains("modmap_cycles_total 1\n"));
+    }
+}
@@ -0,0 +1,371 @@
+//! Explainable module risk from weighted factors
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fmt;
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::explain::Explanation;
+use crate::module_map::{Module, ModuleMap};
+use crate::types::{IssueCategory, IssueSeverity, Score};
+
+#[derive(
+    Debug, Clone, Copy, Serialize, Deserialize, JsonSchema, PartialEq, Eq, PartialOrd, Ord, Hash,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum RiskFactorKind {
+    /// Recent change rate, supplied by the caller (e.g. from git history)
+    Churn,
+    /// Share of other modules depending on this one
+    Centrality,
+    CriticalIssues,
+    /// `1 - coverage_ratio`
+    CoverageGap,
+    SecurityClassification,
+}
+
+impl fmt::Display for RiskFactorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RiskFactorKind::Churn => write!(f, "churn"),
+            RiskFactorKind::Centrality => write!(f, "centrality"),
+            RiskFactorKind::CriticalIssues => write!(f, "critical issues"),
+            RiskFactorKind::CoverageGap => write!(f, "coverage gap"),
+            RiskFactorKind::SecurityClassification => write!(f, "security classification"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, JsonSchema, PartialEq)]
+pub struct RiskFactor {
+    pub kind: RiskFactorKind,
+    /// 0.0–1.0
+    pub value: f64,
+    pub weight: f64,
+}
+
+impl RiskFactor {
+    pub fn contribution(&self) -> f64 {
+        self.value * self.weight
+    }
+}
+
+/// Factors behind a module's `risk_score`
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema, PartialEq)]
+pub struct RiskAssessment {
+    pub factors: Vec<RiskFactor>,
+    /// Raw inputs behind each factor, when assessed in explain mode
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub explanations: Vec<Explanation>,
+}
+
+impl RiskAssessment {
+    /// Weighted mean of the factor values, 0.0 when all weights are zero.
+    pub fn score(&self) -> f64 {
+        let total: f64 = self.factors.iter().map(|f| f.weight).sum();
+        if total <= 0.0 {
+            return 0.0;
+        }
+        self.factors
+            .iter()
+            .map(RiskFactor::contribution)
+            .sum::<f64>()
+            / total
+    }
+
+    /// Factors ordered by contribution, largest first.
+    pub fn ranked(&self) -> Vec<&RiskFactor> {
+        let mut factors: Vec<&RiskFactor> = self.factors.iter().collect();
+        factors.sort_by(|a, b| b.contribution().total_cmp(&a.contribution()));
+        factors
+    }
+
+    /// One line per non-zero factor, e.g. `coverage gap 0.60 × 0.20`.
+    pub fn explain(&self) -> Vec<String> {
+        self.ranked()
+            .into_iter()
+            .filter(|f| f.contribution() > 0.0)
+            .map(|f| format!("{} {:.2} × {:.2}", f.kind, f.value, f.weight))
+            .collect()
+    }
+}
+
+/// Factor weights plus the inputs the map does not record itself
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq)]
+pub struct RiskPolicy {
+    pub weights: BTreeMap<RiskFactorKind, f64>,
+    /// Critical issue count at which that factor saturates
+    #[serde(default = "default_critical_issue_cap")]
+    pub critical_issue_cap: usize,
+    /// Per-module churn, 0.0–1.0
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub churn: HashMap<String, f64>,
+    /// Modules handling sensitive data; others score by open security issues
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub sensitive_modules: Vec<String>,
+    /// Attach `RiskAssessment::explanations`; set with `with_explanations`
+    #[serde(skip)]
+    explain: bool,
+}
+
+fn default_critical_issue_cap() -> usize {
+    3
+}
+
+impl Default for RiskPolicy {
+    fn default() -> Self {
+        Self {
+            weights: BTreeMap::from([
+                (RiskFactorKind::Churn, 0.25),
+                (RiskFactorKind::Centrality, 0.2),
+                (RiskFactorKind::CriticalIssues, 0.25),
+                (RiskFactorKind::CoverageGap, 0.2),
+                (RiskFactorKind::SecurityClassification, 0.1),
+            ]),
+            critical_issue_cap: default_critical_issue_cap(),
+            churn: HashMap::new(),
+            sensitive_modules: Vec::new(),
+            explain: false,
+        }
+    }
+}
+
+impl RiskPolicy {
+    pub fn with_weight(mut self, kind: RiskFactorKind, weight: f64) -> Self {
+        self.weights.insert(kind, weight);
+        self
+    }
+
+    pub fn with_churn(mut self, module_id: impl Into<String>, churn: f64) -> Self {
+        self.churn.insert(module_id.into(), churn.clamp(0.0, 1.0));
+        self
+    }
+
+    pub fn with_sensitive_modules(mut self, module_ids: Vec<String>) -> Self {
+        self.sensitive_modules = module_ids;
+        self
+    }
+
+    /// Attaches the reasoning behind each factor to assessments.
+    ///
+    /// Centrality is the only graph metric the crate derives, and only as a
+    /// risk factor, so its explanation lives here rather than on the graph.
+    pub fn with_explanations(mut self) -> Self {
+        self.explain = true;
+        self
+    }
+
+    fn weight(&self, kind: RiskFactorKind) -> f64 {
+        self.weights.get(&kind).copied().unwrap_or(0.0)
+    }
+}
+
+impl ModuleMap {
+    /// Assessment of one module under `policy`, without storing it.
+    pub fn assess_risk(&self, module: &Module, policy: &RiskPolicy) -> RiskAssessment {
+        let others = self.modules.len().saturating_sub(1);
+        let dependents = self
+            .dependency_edges()
+            .iter()
+            .filter(|e| e.to == module.id && e.from != module.id)
+            .map(|e| e.from.as_str())
+            .collect::<BTreeSet<_>>()
+            .len();
+        let centrality = if others == 0 {
+            0.0
+        } else {
+            dependents as f64 / others as f64
+        };
+
+        let critical_count = module
+            .known_issues
+            .iter()
+            .filter(|i| self.effective_severity(i, module) == IssueSeverity::Critical)
+            .count();
+        let critical = if policy.critical_issue_cap == 0 {
+            0.0
+        } else {
+            (critical_count as f64 / policy.critical_issue_cap as f64).min(1.0)
+        };
+
+        let sensitive = policy.sensitive_modules.contains(&module.id);
+        let security_issues = module
+            .known_issues
+            .iter()
+            .filter(|i| i.category == IssueCategory::Security)
+            .count();
+        let security = if sensitive {
+            1.0
+        } else if security_issues > 0 {
+            0.5
+        } else {
+            0.0
+        };
+        let churn = policy.churn.get(&module.id).copied();
+
+        let factors = [
+            (RiskFactorKind::Churn, churn.unwrap_or(0.0)),
+            (RiskFactorKind::Centrality, centrality),
+            (RiskFactorKind::CriticalIssues, critical),
+            (
+                RiskFactorKind::CoverageGap,
+                1.0 - module.metrics.coverage_ratio.get(),
+            ),
+            (RiskFactorKind::SecurityClassification, security),
+        ]
+        .into_iter()
+        .map(|(kind, value)| RiskFactor {
+            kind,
+            value,
+            weight: policy.weight(kind),
+        })
+        .collect();
+
+        let explanations = if policy.explain {
+            vec![
+                match churn {
+                    Some(churn) => Explanation::new(format!("churn {churn:.2} supplied by caller"))
+                        .with_data("churn", churn),
+                    None => Explanation::new("no churn data; churn counted as 0"),
+                },
+                Explanation::new(format!(
+                    "{dependents} of {others} other modules depend on '{}'",
+                    module.id
+                ))
+                .with_data("dependents", dependents)
+                .with_data("other_modules", others)
+                .with_data("centrality", centrality),
+                Explanation::new(format!(
+                    "{critical_count} critical issue(s), saturating at {}",
+                    policy.critical_issue_cap
+                ))
+                .with_data("critical_issues", critical_count)
+                .with_data("cap", policy.critical_issue_cap),
+                Explanation::new(format!(
+                    "test coverage {:.0}%",
+                    module.metrics.coverage_ratio.get() * 100.0
+                ))
+                .with_data("coverage_ratio", module.metrics.coverage_ratio.get()),
+                Explanation::new(if sensitive {
+                    "listed as a sensitive module".to_string()
+                } else {
+                    format!("{security_issues} open security issue(s)")
+                })
+                .with_data("sensitive", sensitive)
+                .with_data("security_issues", security_issues),
+            ]
+        } else {
+            Vec::new()
+        };
+        RiskAssessment {
+            factors,
+            explanations,
+        }
+    }
+
+    /// Stores an assessment on every module and sets `risk_score` from it.
+    pub fn compute_risk(&mut self, policy: &RiskPolicy) {
+        let assessments: Vec<RiskAssessment> = self
+            .modules
+            .iter()
+            .map(|m| self.assess_risk(m, policy))
+            .collect();
+        for (module, assessment) in self.modules.iter_mut().zip(assessments) {
+            module.metrics.risk_score = Score::clamped(assessment.score());
+            module.metrics.risk_assessment = Some(assessment);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        GeneratorInfo, KnownIssue, ModuleDependency, ModuleMetrics, ProjectMetadata, TechStack,
+    };
+
+    fn module(id: &str, dependencies: &[&str], coverage: f64) -> Module {
+        Module::new(id, id, vec![format!("src/{id}/")])
+            .with_dependencies(
+                dependencies
+                    .iter()
+                    .map(|d| ModuleDependency::runtime(*d))
+                    .collect(),
+            )
+            .with_primary_language("rust")
+            .with_metrics(ModuleMetrics::new(coverage, 0.5, 0.0))
+    }
+
+    #[test]
+    fn test_compute_risk() {
+        let mut map = ModuleMap::new(
+            GeneratorInfo::new("test", "1.0.0"),
+            ProjectMetadata::new("shop", TechStack::new("rust")),
+            vec![
+                module("auth", &[], 0.4),
+                module("api", &["auth"], 1.0),
+                module("web", &["auth", "api"], 1.0),
+            ],
+            vec![],
+        );
+        map.modules[0].known_issues.push(KnownIssue::new(
+            "token-leak",
+            "Tokens logged",
+            IssueSeverity::Critical,
+            IssueCategory::Security,
+        ));
+
+        let policy = RiskPolicy::default().with_churn("auth", 0.8);
+        map.compute_risk(&policy);
+
+        let auth = &map.modules[0].metrics;
+        let assessment = auth.risk_assessment.as_ref().unwrap();
+        // churn .8×.25 + centrality 1×.2 + critical ⅓×.25 + gap .6×.2 + security .5×.1
+        let expected = 0.2 + 0.2 + 0.25 / 3.0 + 0.12 + 0.05;
+        assert!((auth.risk_score.get() - expected).abs() < 1e-9);
+        assert_eq!(assessment.ranked()[0].kind, RiskFactorKind::Churn);
+        assert_eq!(assessment.explain()[0], "churn 0.80 × 0.25");
+        assert_eq!(map.modules[2].metrics.risk_score, Score::ZERO);
+
+        let json = serde_json::to_value(&map.modules[0]).unwrap();
+        assert_eq!(
+            json["risk_assessment"]["factors"][0]["kind"],
+            serde_json::json!("churn")
+        );
+        let restored: Module = serde_json::from_value(json).unwrap();
+        assert_eq!(restored.metrics.risk_assessment.as_ref(), Some(assessment));
+    }
+
+    #[test]
+    fn test_policy_weights() {
+        let policy = RiskPolicy::default()
+            .with_weight(RiskFactorKind::Churn, 0.0)
+            .with_sensitive_modules(vec!["auth".into()]);
+        let map = ModuleMap::new(
+            GeneratorInfo::new("test", "1.0.0"),
+            ProjectMetadata::new("shop", TechStack::new("rust")),
+            vec![module("auth", &[], 1.0)],
+            vec![],
+        );
+        let assessment = map.assess_risk(&map.modules[0], &policy);
+        assert_eq!(
+            assessment.explain(),
+            vec!["security classification 1.00 × 0.10"]
+        );
+        assert!((assessment.score() - 0.1 / 0.75).abs() < 1e-9);
+        assert_eq!(RiskAssessment::default().score(), 0.0);
+        assert!(assessment.explanations.is_empty());
+
+        let explained = map.assess_risk(&map.modules[0], &policy.with_explanations());
+        assert_eq!(explained.factors, assessment.factors);
+        assert_eq!(
+            explained.explanations[1].to_string(),
+            "0 of 0 other modules depend on 'auth' (centrality=0.0, dependents=0, other_modules=0)"
+        );
+        assert_eq!(
+            explained.explanations[4].reason,
+            "listed as a sensitive module"
+        );
+    }
+}
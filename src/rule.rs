@@ -3,8 +3,12 @@
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
+use crate::entity::EntityRef;
+use crate::types::Localized;
+use crate::visibility::Visibility;
+
 /// Rule category for hierarchical organization
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, JsonSchema, Default)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, JsonSchema, Default)]
 #[serde(rename_all = "snake_case")]
 pub enum RuleCategory {
     /// Project-wide rules (priority 100, always inject)
@@ -20,10 +24,14 @@ pub enum RuleCategory {
     Group,
     /// Domain-specific rules (priority 60, by keyword trigger)
     Domain,
+    /// Value written by a newer generator, kept verbatim
+    #[serde(untagged)]
+    Unknown(String),
 }
 
 impl RuleCategory {
-    pub const fn default_priority(self) -> u8 {
+    /// Unknown categories rank below all known ones.
+    pub fn default_priority(&self) -> u8 {
         match self {
             Self::Project => 100,
             Self::Tech => 90,
@@ -31,10 +39,11 @@ impl RuleCategory {
             Self::Module => 80,
             Self::Group => 70,
             Self::Domain => 60,
+            Self::Unknown(_) => 50,
         }
     }
 
-    pub const fn subdirectory(self) -> &'static str {
+    pub fn subdirectory(&self) -> &str {
         match self {
             Self::Project => "",
             Self::Tech => "tech",
@@ -42,6 +51,7 @@ impl RuleCategory {
             Self::Module => "modules",
             Self::Group => "groups",
             Self::Domain => "domains",
+            Self::Unknown(other) => other,
         }
     }
 }
@@ -55,6 +65,7 @@ impl std::fmt::Display for RuleCategory {
             Self::Module => write!(f, "module"),
             Self::Group => write!(f, "group"),
             Self::Domain => write!(f, "domain"),
+            Self::Unknown(other) => write!(f, "{other}"),
         }
     }
 }
@@ -80,7 +91,24 @@ pub struct Rule {
     #[serde(default)]
     pub always_inject: bool,
     /// Markdown content lines
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub content: Vec<String>,
+    /// Content stored in `ContentStore` instead of inline; `content` stays
+    /// empty until `ContentStore::load_rule` or `resolve_rule` fills it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content_hash: Option<String>,
+    /// Translated content lines keyed by language tag
+    #[serde(default, skip_serializing_if = "Localized::is_empty")]
+    pub localized_content: Localized<Vec<String>>,
+    /// Vector id in an external embedding store
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub embedding_ref: Option<String>,
+    /// Map facts this rule was generated from
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub derived_from: Vec<EntityRef>,
+    /// Lowest access level allowed to receive this rule
+    #[serde(default, skip_serializing_if = "Visibility::is_public")]
+    pub visibility: Visibility,
 }
 
 fn default_priority() -> u8 {
@@ -97,6 +125,11 @@ impl Rule {
             category: RuleCategory::default(),
             always_inject: false,
             content,
+            content_hash: None,
+            localized_content: Localized::new(),
+            embedding_ref: None,
+            derived_from: Vec::new(),
+            visibility: Visibility::Public,
         }
     }
 
@@ -109,6 +142,11 @@ impl Rule {
             category: RuleCategory::Project,
             always_inject: true,
             content,
+            content_hash: None,
+            localized_content: Localized::new(),
+            embedding_ref: None,
+            derived_from: Vec::new(),
+            visibility: Visibility::Public,
         }
     }
 
@@ -121,6 +159,11 @@ impl Rule {
             category: RuleCategory::Tech,
             always_inject: false,
             content,
+            content_hash: None,
+            localized_content: Localized::new(),
+            embedding_ref: None,
+            derived_from: Vec::new(),
+            visibility: Visibility::Public,
         }
     }
 
@@ -138,6 +181,11 @@ impl Rule {
             category: RuleCategory::Framework,
             always_inject: false,
             content,
+            content_hash: None,
+            localized_content: Localized::new(),
+            embedding_ref: None,
+            derived_from: Vec::new(),
+            visibility: Visibility::Public,
         }
     }
 
@@ -150,6 +198,11 @@ impl Rule {
             category: RuleCategory::Module,
             always_inject: false,
             content,
+            content_hash: None,
+            localized_content: Localized::new(),
+            embedding_ref: None,
+            derived_from: Vec::new(),
+            visibility: Visibility::Public,
         }
     }
 
@@ -162,6 +215,11 @@ impl Rule {
             category: RuleCategory::Group,
             always_inject: false,
             content,
+            content_hash: None,
+            localized_content: Localized::new(),
+            embedding_ref: None,
+            derived_from: Vec::new(),
+            visibility: Visibility::Public,
         }
     }
 
@@ -174,6 +232,11 @@ impl Rule {
             category: RuleCategory::Domain,
             always_inject: false,
             content,
+            content_hash: None,
+            localized_content: Localized::new(),
+            embedding_ref: None,
+            derived_from: Vec::new(),
+            visibility: Visibility::Public,
         }
     }
 
@@ -198,6 +261,36 @@ impl Rule {
         self
     }
 
+    pub fn with_localized_content(
+        mut self,
+        locale: impl Into<String>,
+        content: Vec<String>,
+    ) -> Self {
+        self.localized_content.insert(locale, content);
+        self
+    }
+
+    pub fn with_derived_from(mut self, sources: Vec<EntityRef>) -> Self {
+        self.derived_from = sources;
+        self
+    }
+
+    pub fn with_visibility(mut self, visibility: Visibility) -> Self {
+        self.visibility = visibility;
+        self
+    }
+
+    pub fn is_derived_from(&self, entity: &EntityRef) -> bool {
+        self.derived_from.contains(entity)
+    }
+
+    /// Content in the first available preferred locale, else the default.
+    pub fn content_for(&self, locales: &[&str]) -> &[String] {
+        self.localized_content
+            .resolve(locales)
+            .map_or(&self.content, |c| c)
+    }
+
     pub fn output_path(&self) -> String {
         let subdir = self.category.subdirectory();
         if subdir.is_empty() {
@@ -255,6 +348,15 @@ mod tests {
         assert_eq!(rule.category, RuleCategory::Project);
     }
 
+    #[test]
+    fn test_localized_content() {
+        let rule = Rule::project("proj", vec!["Use English".into()])
+            .with_localized_content("ko", vec!["한국어 사용".into()]);
+
+        assert_eq!(rule.content_for(&["ko-KR"]), ["한국어 사용"]);
+        assert_eq!(rule.content_for(&["en"]), ["Use English"]);
+    }
+
     #[test]
     fn test_rule_serialization() {
         let rule = Rule::tech("rust", vec!["**/*.rs".into()], vec!["# Rust".into()]);
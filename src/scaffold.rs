@@ -0,0 +1,272 @@
+//! Scaffolding descriptors for creating new modules
+
+use std::path::Path;
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::module_map::ModuleMap;
+use crate::paths::{PathError, WorkspacePaths};
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum ScaffoldError {
+    #[error("Unknown scaffold {name} in group {group_id}")]
+    UnknownScaffold { group_id: String, name: String },
+    #[error("No value for placeholder {{{{{placeholder}}}}} in {path}")]
+    MissingValue { path: String, placeholder: String },
+    #[error("Invalid scaffold path: {0}")]
+    InvalidPath(#[from] PathError),
+}
+
+/// One file a scaffold creates. `path` and `template` may use `{{name}}` placeholders.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct ScaffoldFile {
+    pub path: String,
+    pub template: String,
+    /// Boilerplate lines (e.g. `## Responsibility`) the file must keep
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub required_sections: Vec<String>,
+}
+
+impl ScaffoldFile {
+    pub fn new(path: impl Into<String>, template: impl Into<String>) -> Self {
+        Self {
+            path: path.into(),
+            template: template.into(),
+            required_sections: Vec::new(),
+        }
+    }
+
+    pub fn with_required_sections(mut self, sections: Vec<String>) -> Self {
+        self.required_sections = sections;
+        self
+    }
+
+    /// Required sections with no matching line in `content`.
+    pub fn missing_sections<'a>(&'a self, content: &str) -> Vec<&'a str> {
+        self.required_sections
+            .iter()
+            .filter(|s| !content.lines().any(|l| l.trim() == s.trim()))
+            .map(String::as_str)
+            .collect()
+    }
+}
+
+/// How to create a new module of one kind, attached to a group
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct Scaffold {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub description: String,
+    pub files: Vec<ScaffoldFile>,
+    /// Shell commands to run after the files are written, in order
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub post_create: Vec<String>,
+}
+
+/// A scaffold with every placeholder filled in
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct RenderedScaffold {
+    /// `(path, content)` pairs
+    pub files: Vec<(String, String)>,
+    pub post_create: Vec<String>,
+}
+
+impl Scaffold {
+    pub fn new(name: impl Into<String>, files: Vec<ScaffoldFile>) -> Self {
+        Self {
+            name: name.into(),
+            description: String::new(),
+            files,
+            post_create: Vec::new(),
+        }
+    }
+
+    pub fn with_description(mut self, description: impl Into<String>) -> Self {
+        self.description = description.into();
+        self
+    }
+
+    pub fn with_post_create(mut self, commands: Vec<String>) -> Self {
+        self.post_create = commands;
+        self
+    }
+
+    /// Fills `{{key}}` placeholders in paths, templates and commands.
+    /// Every placeholder must have a value.
+    ///
+    /// Values are shell-quoted where they land in `post_create` commands, and
+    /// rendered paths must stay relative and inside the workspace.
+    pub fn render(&self, values: &[(&str, &str)]) -> Result<RenderedScaffold, ScaffoldError> {
+        let mut files = Vec::new();
+        for file in &self.files {
+            let path = fill(&file.path, values, &file.path)?;
+            WorkspacePaths::join_within(Path::new(""), &path)?;
+            let content = fill(&file.template, values, &file.path)?;
+            files.push((path, content));
+        }
+        let quoted: Vec<(&str, String)> =
+            values.iter().map(|(k, v)| (*k, shell_quote(v))).collect();
+        let quoted: Vec<(&str, &str)> = quoted.iter().map(|(k, v)| (*k, v.as_str())).collect();
+        let post_create = self
+            .post_create
+            .iter()
+            .map(|c| fill(c, &quoted, c))
+            .collect::<Result<_, _>>()?;
+        Ok(RenderedScaffold { files, post_create })
+    }
+}
+
+/// `value` as a single shell word; plain words are left as they are.
+fn shell_quote(value: &str) -> String {
+    let plain = !value.is_empty()
+        && value
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "-_./=:,+@%".contains(c));
+    if plain {
+        value.to_string()
+    } else {
+        format!("'{}'", value.replace('\'', "'\\''"))
+    }
+}
+
+fn fill(text: &str, values: &[(&str, &str)], context: &str) -> Result<String, ScaffoldError> {
+    let mut out = String::new();
+    let mut rest = text;
+    while let Some(start) = rest.find("{{") {
+        let Some(len) = rest[start + 2..].find("}}") else {
+            break;
+        };
+        let key = rest[start + 2..start + 2 + len].trim();
+        let value =
+            values
+                .iter()
+                .find(|(k, _)| *k == key)
+                .ok_or_else(|| ScaffoldError::MissingValue {
+                    path: context.to_string(),
+                    placeholder: key.to_string(),
+                })?;
+        out.push_str(&rest[..start]);
+        out.push_str(value.1);
+        rest = &rest[start + 2 + len + 2..];
+    }
+    out.push_str(rest);
+    Ok(out)
+}
+
+impl ModuleMap {
+    /// Renders scaffold `name` of group `group_id`. `group` and `group_id`
+    /// values are always available to templates.
+    pub fn scaffold(
+        &self,
+        group_id: &str,
+        name: &str,
+        values: &[(&str, &str)],
+    ) -> Result<RenderedScaffold, ScaffoldError> {
+        let unknown = || ScaffoldError::UnknownScaffold {
+            group_id: group_id.to_string(),
+            name: name.to_string(),
+        };
+        let group = self.find_group(group_id).ok_or_else(unknown)?;
+        let scaffold = group
+            .scaffolds
+            .iter()
+            .find(|s| s.name == name)
+            .ok_or_else(unknown)?;
+        let mut values = values.to_vec();
+        values.push(("group", &group.name));
+        values.push(("group_id", &group.id));
+        scaffold.render(&values)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{GeneratorInfo, ModuleGroup, ProjectMetadata, TechStack};
+
+    fn sample() -> ModuleMap {
+        let service = Scaffold::new(
+            "service",
+            vec![
+                ScaffoldFile::new(
+                    "services/{{name}}/README.md",
+                    "# {{name}}\n\nPart of {{group}}.\n\n## Responsibility\n",
+                )
+                .with_required_sections(vec!["## Responsibility".into()]),
+                ScaffoldFile::new("services/{{name}}/src/lib.rs", "//! {{ name }} service\n"),
+            ],
+        )
+        .with_post_create(vec!["cargo new --lib services/{{name}}".into()]);
+        ModuleMap::new(
+            GeneratorInfo::new("test", "1.0.0"),
+            ProjectMetadata::new("shop", TechStack::new("rust")),
+            vec![],
+            vec![ModuleGroup::new("payments", "Payments", vec![]).with_scaffolds(vec![service])],
+        )
+    }
+
+    #[test]
+    fn test_scaffold_render() {
+        let map = sample();
+        let rendered = map
+            .scaffold("payments", "service", &[("name", "refunds")])
+            .unwrap();
+        assert_eq!(
+            rendered.files[0],
+            (
+                "services/refunds/README.md".to_string(),
+                "# refunds\n\nPart of Payments.\n\n## Responsibility\n".to_string()
+            )
+        );
+        assert_eq!(rendered.files[1].1, "//! refunds service\n");
+        assert_eq!(
+            rendered.post_create,
+            vec!["cargo new --lib services/refunds"]
+        );
+
+        let readme = &map.find_group("payments").unwrap().scaffolds[0].files[0];
+        assert!(readme.missing_sections(&rendered.files[0].1).is_empty());
+        assert_eq!(
+            readme.missing_sections("# refunds"),
+            vec!["## Responsibility"]
+        );
+    }
+
+    #[test]
+    fn test_scaffold_errors() {
+        let map = sample();
+        assert_eq!(
+            map.scaffold("payments", "service", &[]).unwrap_err(),
+            ScaffoldError::MissingValue {
+                path: "services/{{name}}/README.md".into(),
+                placeholder: "name".into(),
+            }
+        );
+        assert!(matches!(
+            map.scaffold("payments", "worker", &[]),
+            Err(ScaffoldError::UnknownScaffold { .. })
+        ));
+        assert_eq!(
+            map.scaffold("payments", "service", &[("name", "../../etc")])
+                .unwrap_err(),
+            ScaffoldError::InvalidPath(PathError::EscapesBase(
+                "services/../../etc/README.md".into()
+            ))
+        );
+    }
+
+    #[test]
+    fn test_scaffold_quotes_command_values() {
+        let rendered = sample()
+            .scaffold("payments", "service", &[("name", "x; rm -rf ~")])
+            .unwrap();
+        assert_eq!(
+            rendered.post_create,
+            ["cargo new --lib services/'x; rm -rf ~'"]
+        );
+        assert_eq!(rendered.files[1].0, "services/x; rm -rf ~/src/lib.rs");
+        assert_eq!(shell_quote("it's"), "'it'\\''s'");
+    }
+}
@@ -0,0 +1,202 @@
+//! JSON Schemas of the file formats, and their field docs for editor tooltips
+
+use schemars::{JsonSchema, Schema, schema_for};
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+use crate::agent::Agent;
+use crate::manifest::ProjectManifest;
+use crate::module_map::ModuleMap;
+use crate::policy::PolicyConfig;
+use crate::rule::Rule;
+use crate::skill::Skill;
+use crate::taxonomy::Taxonomy;
+
+/// Types with a standalone schema; every type they reference is described too
+pub const ROOT_TYPES: &[&str] = &[
+    "ProjectManifest",
+    "ModuleMap",
+    "Rule",
+    "Skill",
+    "Agent",
+    "PolicyConfig",
+    "Taxonomy",
+];
+
+/// Schema of a root type, with `$defs` for everything it references.
+pub fn json_schema(type_name: &str) -> Option<Schema> {
+    Some(match type_name {
+        "ProjectManifest" => schema_for!(ProjectManifest),
+        "ModuleMap" => schema_for!(ModuleMap),
+        "Rule" => schema_for!(Rule),
+        "Skill" => schema_for!(Skill),
+        "Agent" => schema_for!(Agent),
+        "PolicyConfig" => schema_for!(PolicyConfig),
+        "Taxonomy" => schema_for!(Taxonomy),
+        _ => return None,
+    })
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct FieldDoc {
+    /// Serialized name
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    pub required: bool,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub examples: Vec<Value>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct VariantDoc {
+    /// Serialized value
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
+/// Documentation of one schema type, from its doc comments and examples
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TypeDoc {
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Struct fields, sorted by name
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub fields: Vec<FieldDoc>,
+    /// Unit variants of string enums
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub variants: Vec<VariantDoc>,
+}
+
+fn description(schema: &Map<String, Value>) -> Option<String> {
+    schema
+        .get("description")
+        .and_then(Value::as_str)
+        .map(String::from)
+}
+
+impl TypeDoc {
+    fn from_schema(name: &str, schema: &Map<String, Value>) -> Self {
+        let required: Vec<&str> = schema
+            .get("required")
+            .and_then(Value::as_array)
+            .map(|r| r.iter().filter_map(Value::as_str).collect())
+            .unwrap_or_default();
+        let fields = schema
+            .get("properties")
+            .and_then(Value::as_object)
+            .map(|properties| {
+                properties
+                    .iter()
+                    .map(|(field, prop)| {
+                        let prop = prop.as_object();
+                        FieldDoc {
+                            name: field.clone(),
+                            description: prop.and_then(description),
+                            required: required.contains(&field.as_str()),
+                            examples: prop
+                                .and_then(|p| p.get("examples"))
+                                .and_then(Value::as_array)
+                                .cloned()
+                                .unwrap_or_default(),
+                        }
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        // Documented variants get their own `oneOf` entry; the rest share one `enum`
+        let entries: Vec<&Map<String, Value>> = match schema.get("oneOf") {
+            Some(Value::Array(one_of)) => one_of.iter().filter_map(Value::as_object).collect(),
+            _ => vec![schema],
+        };
+        let mut variants = Vec::new();
+        for entry in entries {
+            let values = match (entry.get("const"), entry.get("enum")) {
+                (Some(value), _) => std::slice::from_ref(value),
+                (None, Some(Value::Array(values))) => values.as_slice(),
+                _ => continue,
+            };
+            let single = values.len() == 1;
+            variants.extend(values.iter().filter_map(Value::as_str).map(|v| VariantDoc {
+                name: v.to_string(),
+                description: description(entry).filter(|_| single && !std::ptr::eq(entry, schema)),
+            }));
+        }
+
+        Self {
+            name: name.to_string(),
+            description: description(schema),
+            fields,
+            variants,
+        }
+    }
+}
+
+/// Docs of any type appearing in the file formats, by Rust type name.
+pub fn describe(type_name: &str) -> Option<TypeDoc> {
+    ROOT_TYPES.iter().find_map(|root| {
+        let schema = json_schema(root)?;
+        let schema = schema.as_object()?;
+        if *root == type_name {
+            return Some(TypeDoc::from_schema(type_name, schema));
+        }
+        let def = schema.get("$defs")?.get(type_name)?.as_object()?;
+        Some(TypeDoc::from_schema(type_name, def))
+    })
+}
+
+/// Like `describe`, for any `JsonSchema` type.
+pub fn describe_type<T: JsonSchema>() -> TypeDoc {
+    let schema = schema_for!(T);
+    let name = T::schema_name();
+    TypeDoc::from_schema(&name, schema.as_object().unwrap_or(&Map::new()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_describe() {
+        let module = describe("Module").unwrap();
+        assert_eq!(
+            module.description.as_deref(),
+            Some("A unit of code ownership: a set of paths with one responsibility")
+        );
+        let id = module.fields.iter().find(|f| f.name == "id").unwrap();
+        assert!(id.required);
+        assert_eq!(
+            id.description.as_deref(),
+            Some("Unique identifier (kebab-case)")
+        );
+        assert_eq!(id.examples, [serde_json::json!("auth")]);
+        let issues = module
+            .fields
+            .iter()
+            .find(|f| f.name == "known_issues")
+            .unwrap();
+        assert!(!issues.required);
+
+        let visibility = describe("Visibility").unwrap();
+        let names: Vec<&str> = visibility
+            .variants
+            .iter()
+            .map(|v| v.name.as_str())
+            .collect();
+        assert_eq!(names, ["public", "team", "maintainers"]);
+        assert_eq!(
+            visibility.variants[2].description.as_deref(),
+            Some("e.g. exploitable security bugs")
+        );
+        assert_eq!(describe("Rule").unwrap().name, "Rule");
+        assert_eq!(describe_type::<Rule>(), describe("Rule").unwrap());
+        assert!(describe("Nope").is_none());
+
+        for root in ROOT_TYPES {
+            assert!(describe(root).unwrap().description.is_some(), "{root}");
+        }
+    }
+}
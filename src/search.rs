@@ -0,0 +1,265 @@
+//! In-memory full-text search over map and rule text
+
+use std::collections::HashMap;
+
+use serde::Serialize;
+
+use crate::module_map::ModuleMap;
+use crate::query::{Page, PageRequest};
+use crate::rule::Rule;
+
+/// Where a search hit was found
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum HitLocation {
+    Responsibility { module_id: String },
+    Convention { module_id: String, name: String },
+    Issue { module_id: String, issue_id: String },
+    Rule { name: String },
+}
+
+impl HitLocation {
+    /// Stable key used for cursors and tie-breaking
+    pub fn key(&self) -> String {
+        match self {
+            Self::Responsibility { module_id } => format!("responsibility:{module_id}"),
+            Self::Convention { module_id, name } => format!("convention:{module_id}/{name}"),
+            Self::Issue {
+                module_id,
+                issue_id,
+            } => format!("issue:{module_id}/{issue_id}"),
+            Self::Rule { name } => format!("rule:{name}"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SearchHit {
+    pub location: HitLocation,
+    pub score: f64,
+    pub text: String,
+}
+
+#[derive(Debug, Clone)]
+struct Document {
+    location: HitLocation,
+    text: String,
+    length: usize,
+}
+
+/// Inverted index with TF-IDF ranking
+#[derive(Debug, Clone, Default)]
+pub struct SearchIndex {
+    documents: Vec<Document>,
+    postings: HashMap<String, Vec<(usize, u32)>>,
+}
+
+fn tokenize(text: &str) -> impl Iterator<Item = String> + '_ {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|t| !t.is_empty())
+        .map(str::to_lowercase)
+}
+
+impl SearchIndex {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Indexes responsibilities, conventions and issue descriptions.
+    pub fn build(map: &ModuleMap) -> Self {
+        let mut index = Self::new();
+        index.add_map(map);
+        index
+    }
+
+    pub fn add_map(&mut self, map: &ModuleMap) {
+        for module in &map.modules {
+            self.add(
+                HitLocation::Responsibility {
+                    module_id: module.id.clone(),
+                },
+                format!("{} {}", module.name, module.responsibility),
+            );
+            for convention in &module.conventions {
+                let mut text = convention.to_string();
+                if let Some(rationale) = &convention.rationale {
+                    text.push(' ');
+                    text.push_str(rationale);
+                }
+                self.add(
+                    HitLocation::Convention {
+                        module_id: module.id.clone(),
+                        name: convention.name.clone(),
+                    },
+                    text,
+                );
+            }
+            for issue in &module.known_issues {
+                self.add(
+                    HitLocation::Issue {
+                        module_id: module.id.clone(),
+                        issue_id: issue.id.clone(),
+                    },
+                    format!("{} {}", issue.id, issue.description),
+                );
+            }
+        }
+    }
+
+    pub fn add_rules(&mut self, rules: &[Rule]) {
+        for rule in rules {
+            self.add(
+                HitLocation::Rule {
+                    name: rule.name.clone(),
+                },
+                rule.content.join("\n"),
+            );
+        }
+    }
+
+    pub fn add(&mut self, location: HitLocation, text: String) {
+        let doc = self.documents.len();
+        let mut counts: HashMap<String, u32> = HashMap::new();
+        for token in tokenize(&text) {
+            *counts.entry(token).or_default() += 1;
+        }
+        let length = counts.values().sum::<u32>() as usize;
+        for (token, count) in counts {
+            self.postings.entry(token).or_default().push((doc, count));
+        }
+        self.documents.push(Document {
+            location,
+            text,
+            length,
+        });
+    }
+
+    pub fn len(&self) -> usize {
+        self.documents.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.documents.is_empty()
+    }
+
+    /// Hits ranked by TF-IDF; documents matching every query term rank first.
+    pub fn search(&self, query: &str) -> Vec<SearchHit> {
+        let terms: Vec<String> = tokenize(query).collect();
+        if terms.is_empty() {
+            return Vec::new();
+        }
+
+        let total = self.documents.len() as f64;
+        let mut scores: HashMap<usize, (f64, usize)> = HashMap::new();
+        for term in &terms {
+            let Some(postings) = self.postings.get(term) else {
+                continue;
+            };
+            let idf = (1.0 + total / postings.len() as f64).ln();
+            for &(doc, count) in postings {
+                let tf = count as f64 / self.documents[doc].length.max(1) as f64;
+                let entry = scores.entry(doc).or_default();
+                entry.0 += tf * idf;
+                entry.1 += 1;
+            }
+        }
+
+        let mut hits: Vec<SearchHit> = scores
+            .into_iter()
+            .map(|(doc, (score, matched))| {
+                let coverage = matched as f64 / terms.len() as f64;
+                let document = &self.documents[doc];
+                SearchHit {
+                    location: document.location.clone(),
+                    score: score * coverage,
+                    text: document.text.clone(),
+                }
+            })
+            .collect();
+        hits.sort_by(|a, b| {
+            b.score
+                .total_cmp(&a.score)
+                .then_with(|| a.location.key().cmp(&b.location.key()))
+        });
+        hits
+    }
+
+    /// Paginated search; cursors are rank positions within the result set.
+    pub fn search_page(&self, query: &str, request: &PageRequest) -> Page<SearchHit> {
+        let ranked: Vec<(usize, SearchHit)> = self.search(query).into_iter().enumerate().collect();
+        Page::from_sorted_by(ranked, |(rank, _)| format!("{rank:08}"), request).map(|(_, hit)| hit)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        Convention, GeneratorInfo, IssueCategory, IssueSeverity, KnownIssue, Module,
+        ProjectMetadata, TechStack,
+    };
+
+    fn module(id: &str, responsibility: &str) -> Module {
+        Module::new(id, id, vec![])
+            .with_responsibility(responsibility)
+            .with_primary_language("rust")
+    }
+
+    fn sample_index() -> SearchIndex {
+        let mut gateway = module("gateway", "HTTP gateway with rate limiting and retry logic");
+        gateway.conventions = vec![Convention::new("retry", "Exponential backoff on retry")];
+        let mut billing = module("billing", "Invoice generation");
+        billing.known_issues = vec![KnownIssue::new(
+            "rate-bug",
+            "Currency rate cache is never refreshed",
+            IssueSeverity::High,
+            IssueCategory::Correctness,
+        )];
+        let map = ModuleMap::new(
+            GeneratorInfo::new("test", "1.0.0"),
+            ProjectMetadata::new("test", TechStack::new("rust")),
+            vec![gateway, billing],
+            vec![],
+        );
+
+        let mut index = SearchIndex::build(&map);
+        index.add_rules(&[Rule::tech(
+            "limits",
+            vec![],
+            vec!["Apply rate limiting to every public endpoint".into()],
+        )]);
+        index
+    }
+
+    #[test]
+    fn test_ranked_search() {
+        let index = sample_index();
+        let hits = index.search("rate limiting");
+
+        assert_eq!(hits.len(), 3);
+        assert!(matches!(
+            hits.last().unwrap().location,
+            HitLocation::Issue { .. }
+        ));
+        assert!(hits[..2].iter().any(|h| h.location
+            == HitLocation::Responsibility {
+                module_id: "gateway".into()
+            }));
+        assert!(index.search("nonexistent").is_empty());
+    }
+
+    #[test]
+    fn test_search_page() {
+        let index = sample_index();
+        let first = index.search_page("retry rate", &PageRequest::first(2));
+        assert_eq!(first.total, 4);
+        assert_eq!(first.items.len(), 2);
+
+        let rest = index.search_page(
+            "retry rate",
+            &PageRequest::after(first.next_cursor.unwrap(), 2),
+        );
+        assert_eq!(rest.items.len(), 2);
+        assert!(rest.next_cursor.is_none());
+    }
+}
@@ -0,0 +1,220 @@
+//! Read-only REST API over a manifest (feature: server)
+
+use std::io;
+use std::sync::Arc;
+
+use axum::extract::{Path, Query, State};
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use tokio::net::{TcpListener, ToSocketAddrs};
+
+use crate::context::ContextResolver;
+use crate::manifest::ProjectManifest;
+
+type SharedManifest = Arc<ProjectManifest>;
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct PathQuery {
+    pub path: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImpactRequest {
+    pub paths: Vec<String>,
+}
+
+/// Routes: `/health`, `/modules/{id}`, `/lookup?path=`, `/context?path=`, `POST /impact`
+/// and, with the `graphql` feature, `POST /graphql`
+pub fn router(manifest: SharedManifest) -> Router {
+    let router = Router::new()
+        .route("/health", get(health))
+        .route("/modules/{id}", get(module))
+        .route("/lookup", get(lookup))
+        .route("/context", get(context))
+        .route("/impact", post(impact));
+    #[cfg(feature = "graphql")]
+    let router = router.route(
+        "/graphql",
+        post(graphql).with_state(crate::graphql::schema(Arc::clone(&manifest))),
+    );
+    router.with_state(manifest)
+}
+
+pub async fn serve(manifest: ProjectManifest, addr: impl ToSocketAddrs) -> io::Result<()> {
+    let listener = TcpListener::bind(addr).await?;
+    axum::serve(listener, router(Arc::new(manifest))).await
+}
+
+/// 404 with the closest known ids, so callers can retry with a real one
+fn not_found(message: String, suggestions: Vec<&str>) -> Response {
+    let body = json!({ "error": message, "suggestions": suggestions });
+    (StatusCode::NOT_FOUND, Json(body)).into_response()
+}
+
+async fn health(State(manifest): State<SharedManifest>) -> Json<serde_json::Value> {
+    Json(json!({
+        "status": "ok",
+        "schema_version": manifest.project.schema_version,
+        "modules": manifest.project.modules.len(),
+    }))
+}
+
+async fn module(State(manifest): State<SharedManifest>, Path(id): Path<String>) -> Response {
+    match manifest.project.find_module(&id) {
+        Some(module) => Json(module).into_response(),
+        None => {
+            let suggestions: Vec<&str> = manifest
+                .project
+                .find_module_fuzzy(&id)
+                .iter()
+                .map(|m| m.item.id.as_str())
+                .collect();
+            not_found(format!("unknown module: {id}"), suggestions)
+        }
+    }
+}
+
+async fn lookup(
+    State(manifest): State<SharedManifest>,
+    Query(query): Query<PathQuery>,
+) -> Response {
+    let modules: Vec<_> = manifest
+        .project
+        .modules
+        .iter()
+        .filter(|m| m.contains_file(&query.path))
+        .collect();
+    Json(json!({ "path": query.path, "modules": modules })).into_response()
+}
+
+async fn context(
+    State(manifest): State<SharedManifest>,
+    Query(query): Query<PathQuery>,
+) -> Response {
+    Json(ContextResolver::new(&manifest).resolve(&query.path)).into_response()
+}
+
+async fn impact(
+    State(manifest): State<SharedManifest>,
+    Json(request): Json<ImpactRequest>,
+) -> Json<serde_json::Value> {
+    let paths: Vec<&str> = request.paths.iter().map(String::as_str).collect();
+    let modules: Vec<&str> = manifest
+        .project
+        .impacted_modules(&paths)
+        .iter()
+        .map(|m| m.id.as_str())
+        .collect();
+    Json(json!({ "modules": modules }))
+}
+
+#[cfg(feature = "graphql")]
+async fn graphql(
+    State(schema): State<crate::graphql::ModmapSchema>,
+    Json(request): Json<async_graphql::Request>,
+) -> Json<async_graphql::Response> {
+    Json(schema.execute(request).await)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::{Body, to_bytes};
+    use axum::http::Request;
+    use tower::ServiceExt;
+
+    use crate::{
+        GeneratorInfo, Module, ModuleContext, ModuleDependency, ModuleMap, ProjectMetadata,
+        TechStack,
+    };
+
+    fn module(id: &str, dependencies: &[&str]) -> Module {
+        Module::new(id, id, vec![format!("src/{id}/")])
+            .with_dependencies(
+                dependencies
+                    .iter()
+                    .map(|d| ModuleDependency::runtime(*d))
+                    .collect(),
+            )
+            .with_primary_language("rust")
+    }
+
+    fn app() -> Router {
+        let map = ModuleMap::new(
+            GeneratorInfo::new("test", "1.0.0"),
+            ProjectMetadata::new("test", TechStack::new("rust")),
+            vec![module("auth", &[]), module("api", &["auth"])],
+            vec![],
+        );
+        let manifest = ProjectManifest::new(map).with_modules(
+            [(
+                "auth".to_string(),
+                ModuleContext::new().with_rules(vec!["auth-rules".into()]),
+            )]
+            .into(),
+        );
+        router(Arc::new(manifest))
+    }
+
+    async fn send(request: Request<Body>) -> (StatusCode, serde_json::Value) {
+        let response = app().oneshot(request).await.unwrap();
+        let status = response.status();
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        (status, serde_json::from_slice(&body).unwrap())
+    }
+
+    async fn get_json(uri: &str) -> (StatusCode, serde_json::Value) {
+        send(Request::get(uri).body(Body::empty()).unwrap()).await
+    }
+
+    #[tokio::test]
+    async fn test_read_endpoints() {
+        let (status, body) = get_json("/health").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["modules"], 2);
+
+        let (status, body) = get_json("/modules/auth").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["id"], "auth");
+
+        let (status, _) = get_json("/modules/ghost").await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        let (_, body) = get_json("/modules/Auth").await;
+        assert_eq!(body["suggestions"][0], "auth");
+
+        let (_, body) = get_json("/lookup?path=src/api/routes.rs").await;
+        assert_eq!(body["modules"][0]["id"], "api");
+
+        let (_, body) = get_json("/context?path=src/auth/token.rs").await;
+        assert_eq!(body["rules"][0], "auth-rules");
+    }
+
+    #[tokio::test]
+    async fn test_impact() {
+        let request = Request::post("/impact")
+            .header("content-type", "application/json")
+            .body(Body::from(r#"{"paths": ["src/auth/lib.rs"]}"#))
+            .unwrap();
+        let (status, body) = send(request).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["modules"], json!(["auth", "api"]));
+    }
+
+    #[cfg(feature = "graphql")]
+    #[tokio::test]
+    async fn test_graphql_route() {
+        let request = Request::post("/graphql")
+            .header("content-type", "application/json")
+            .body(Body::from(
+                r#"{"query": "{ module(id: \"api\") { dependencies { id } } }"}"#,
+            ))
+            .unwrap();
+        let (status, body) = send(request).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["data"]["module"]["dependencies"][0]["id"], "auth");
+    }
+}
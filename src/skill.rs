@@ -77,7 +77,12 @@ pub struct Skill {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub disable_model_invocation: Option<bool>,
     /// Markdown body content
+    #[serde(default, skip_serializing_if = "String::is_empty")]
     pub body: String,
+    /// Body stored in `ContentStore` instead of inline; `body` stays empty
+    /// until `ContentStore::load_skill` or `resolve_skill` fills it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub body_hash: Option<String>,
     /// Additional files bundled with skill
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub additional_files: Vec<SkillFile>,
@@ -105,6 +110,7 @@ impl Skill {
             argument_hint: None,
             disable_model_invocation: None,
             body: body.into(),
+            body_hash: None,
             additional_files: Vec::new(),
         }
     }
@@ -0,0 +1,204 @@
+//! Self-contained sub-maps of one domain or group, for scoped context bundles
+
+use std::collections::BTreeSet;
+
+use crate::module_map::ModuleMap;
+
+impl ModuleMap {
+    /// The domain with its groups, their nested groups and member modules.
+    pub fn slice_domain(&self, domain_id: &str) -> Option<ModuleMap> {
+        let domain = self.find_domain(domain_id)?;
+        let roots = domain
+            .group_ids
+            .iter()
+            .filter_map(|id| self.find_group(id))
+            .chain(self.groups.iter().filter(|g| {
+                g.domain_id
+                    .as_deref()
+                    .and_then(|id| self.find_domain(id))
+                    .is_some_and(|d| d.id == domain.id)
+            }));
+        let groups = self.with_descendants(roots.map(|g| g.id.as_str()));
+        let mut slice = self.slice(&groups);
+        slice.domains.retain(|d| d.id == domain.id);
+        Some(slice)
+    }
+
+    /// The group with its nested groups and member modules, and the domain
+    /// it belongs to, trimmed to those groups.
+    pub fn slice_group(&self, group_id: &str) -> Option<ModuleMap> {
+        let group = self.find_group(group_id)?;
+        let groups = self.with_descendants([group.id.as_str()]);
+        Some(self.slice(&groups))
+    }
+
+    /// `roots` and every group nested under them, by `parent_group_id`.
+    fn with_descendants<'a>(
+        &'a self,
+        roots: impl IntoIterator<Item = &'a str>,
+    ) -> BTreeSet<&'a str> {
+        let mut groups: BTreeSet<&str> = BTreeSet::new();
+        let mut pending: Vec<&str> = roots.into_iter().collect();
+        while let Some(id) = pending.pop() {
+            if groups.insert(id) {
+                pending.extend(self.find_child_groups(id).iter().map(|g| g.id.as_str()));
+            }
+        }
+        groups
+    }
+
+    /// Copy holding only `group_ids` and their member modules. References to
+    /// anything left out are dropped, so every remaining id resolves.
+    fn slice(&self, group_ids: &BTreeSet<&str>) -> ModuleMap {
+        let mut map = self.clone();
+        map.groups.retain(|g| group_ids.contains(g.id.as_str()));
+        let module_ids: BTreeSet<String> = map
+            .groups
+            .iter()
+            .flat_map(|g| &g.module_ids)
+            .filter_map(|id| self.find_module(id))
+            .map(|m| m.id.clone())
+            .collect();
+        let keep = |id: &String| {
+            module_ids.contains(id)
+                || self
+                    .find_module(id)
+                    .is_some_and(|m| module_ids.contains(&m.id))
+        };
+
+        map.modules.retain(|m| module_ids.contains(&m.id));
+        for module in &mut map.modules {
+            module.dependencies.retain(|d| keep(&d.module_id));
+            module.dependents.retain(keep);
+        }
+        let kept_groups: BTreeSet<String> = map.groups.iter().map(|g| g.id.clone()).collect();
+        for group in &mut map.groups {
+            group.module_ids.retain(keep);
+            group.leader_module = group.leader_module.take().filter(keep);
+            if !group
+                .parent_group_id
+                .as_deref()
+                .and_then(|p| self.find_group(p))
+                .is_some_and(|p| kept_groups.contains(&p.id))
+            {
+                group.parent_group_id = None;
+            }
+        }
+
+        for domain in &mut map.domains {
+            domain.group_ids.retain(|id| {
+                self.find_group(id)
+                    .is_some_and(|g| kept_groups.contains(&g.id))
+            });
+        }
+        let domain_ids: BTreeSet<&str> = map
+            .groups
+            .iter()
+            .filter_map(|g| {
+                g.domain_id
+                    .as_deref()
+                    .and_then(|id| self.find_domain(id))
+                    .or_else(|| self.find_domain_containing_group(&g.id))
+            })
+            .map(|d| d.id.as_str())
+            .collect();
+        map.domains.retain(|d| domain_ids.contains(d.id.as_str()));
+        for group in &mut map.groups {
+            if group.domain_id.as_deref().is_some_and(|d| {
+                !self
+                    .find_domain(d)
+                    .is_some_and(|d| domain_ids.contains(d.id.as_str()))
+            }) {
+                group.domain_id = None;
+            }
+        }
+
+        map.shared_paths.retain_mut(|shared| {
+            let everyone = shared.module_ids.is_empty();
+            shared.module_ids.retain(keep);
+            everyone || !shared.module_ids.is_empty()
+        });
+        if let Some(graph) = &mut map.dependency_graph {
+            graph.edges.retain(|e| keep(&e.from) && keep(&e.to));
+            for layer in &mut graph.layers {
+                layer.modules.retain(keep);
+            }
+            graph.layers.retain(|l| !l.modules.is_empty());
+        }
+        map
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        DependencyEdge, DependencyGraph, Domain, GeneratorInfo, Module, ModuleDependency,
+        ModuleGroup, ProjectMetadata, TechStack,
+    };
+
+    fn module(id: &str, deps: &[&str]) -> Module {
+        Module::new(id, id, vec![format!("src/{id}/")])
+            .with_dependencies(deps.iter().map(|d| ModuleDependency::new(*d)).collect())
+            .with_primary_language("rust")
+    }
+
+    #[test]
+    fn test_slice_domain_and_group() {
+        let map = ModuleMap::new(
+            GeneratorInfo::new("test", "1.0.0"),
+            ProjectMetadata::new("shop", TechStack::new("rust")),
+            vec![
+                module("auth", &["db"]),
+                module("session", &["auth"]),
+                module("cart", &["auth", "db"]),
+                module("db", &[]),
+            ],
+            vec![
+                ModuleGroup::new("identity-core", "Identity", vec!["auth".into()]),
+                ModuleGroup::new("sessions", "Sessions", vec!["session".into()])
+                    .with_parent("identity-core", 1),
+                ModuleGroup::new("shop", "Shop", vec!["cart".into()]).with_domain("commerce"),
+                ModuleGroup::new("storage", "Storage", vec!["db".into()]),
+            ],
+        )
+        .with_domains(vec![
+            Domain::new("identity", "Identity", vec!["identity-core".into()]),
+            Domain::new("commerce", "Commerce", vec![]),
+        ])
+        .with_dependency_graph(DependencyGraph {
+            edges: vec![
+                DependencyEdge {
+                    from: "session".into(),
+                    to: "auth".into(),
+                    edge_type: Default::default(),
+                },
+                DependencyEdge {
+                    from: "auth".into(),
+                    to: "db".into(),
+                    edge_type: Default::default(),
+                },
+            ],
+            layers: vec![],
+        });
+
+        let identity = map.slice_domain("identity").unwrap();
+        let ids: Vec<&str> = identity.modules.iter().map(|m| m.id.as_str()).collect();
+        assert_eq!(ids, ["auth", "session"]);
+        assert!(identity.modules[0].dependencies.is_empty(), "db is outside");
+        assert_eq!(identity.groups.len(), 2);
+        assert_eq!(identity.domains.len(), 1);
+        assert_eq!(identity.dependency_graph.unwrap().edges.len(), 1);
+
+        let shop = map.slice_group("shop").unwrap();
+        assert_eq!(shop.modules.len(), 1);
+        assert_eq!(shop.modules[0].dependencies.len(), 0);
+        assert_eq!(shop.domains[0].id, "commerce");
+        assert_eq!(map.slice_domain("commerce").unwrap().modules[0].id, "cart");
+
+        let sessions = map.slice_group("sessions").unwrap();
+        assert_eq!(sessions.groups[0].parent_group_id, None);
+        assert!(sessions.domains.is_empty());
+        assert!(map.slice_group("missing").is_none());
+    }
+}
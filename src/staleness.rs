@@ -0,0 +1,261 @@
+//! How out of date a loaded map is
+
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use chrono::{DateTime, Utc};
+
+use crate::manifest::ProjectManifest;
+use crate::module_map::ModuleMap;
+use crate::registry::{SchemaError, SchemaRegistry};
+use crate::store::ContentStore;
+
+/// Limits beyond which a map is reported stale
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StalenessThresholds {
+    pub max_age_days: i64,
+    /// Fraction of tracked files changed or missing, 0.0–1.0
+    pub max_drift_ratio: f64,
+}
+
+impl Default for StalenessThresholds {
+    fn default() -> Self {
+        Self {
+            max_age_days: 30,
+            max_drift_ratio: 0.2,
+        }
+    }
+}
+
+impl StalenessThresholds {
+    pub fn with_max_age_days(mut self, days: i64) -> Self {
+        self.max_age_days = days;
+        self
+    }
+
+    pub fn with_max_drift_ratio(mut self, ratio: f64) -> Self {
+        self.max_drift_ratio = ratio;
+        self
+    }
+}
+
+/// Tracked files whose content no longer matches the recorded hash
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FileDrift {
+    pub changed: Vec<String>,
+    pub missing: Vec<String>,
+    pub total: usize,
+}
+
+impl FileDrift {
+    pub fn ratio(&self) -> f64 {
+        if self.total == 0 {
+            0.0
+        } else {
+            (self.changed.len() + self.missing.len()) as f64 / self.total as f64
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Staleness {
+    pub generated_at: DateTime<Utc>,
+    pub age_days: i64,
+    /// Present only when tracked files were checked against disk
+    pub drift: Option<FileDrift>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum StalenessWarning {
+    TooOld { age_days: i64, max_age_days: i64 },
+    Drifted { ratio: f64, max_ratio: f64 },
+}
+
+impl fmt::Display for StalenessWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::TooOld {
+                age_days,
+                max_age_days,
+            } => write!(
+                f,
+                "map generated {age_days} days ago (limit {max_age_days}); regenerate it"
+            ),
+            Self::Drifted { ratio, max_ratio } => write!(
+                f,
+                "{:.0}% of tracked files changed since generation (limit {:.0}%)",
+                ratio * 100.0,
+                max_ratio * 100.0
+            ),
+        }
+    }
+}
+
+impl Staleness {
+    pub fn warnings(&self, thresholds: &StalenessThresholds) -> Vec<StalenessWarning> {
+        let mut warnings = Vec::new();
+        if self.age_days > thresholds.max_age_days {
+            warnings.push(StalenessWarning::TooOld {
+                age_days: self.age_days,
+                max_age_days: thresholds.max_age_days,
+            });
+        }
+        if let Some(drift) = &self.drift
+            && drift.ratio() > thresholds.max_drift_ratio
+        {
+            warnings.push(StalenessWarning::Drifted {
+                ratio: drift.ratio(),
+                max_ratio: thresholds.max_drift_ratio,
+            });
+        }
+        warnings
+    }
+
+    pub fn is_stale(&self, thresholds: &StalenessThresholds) -> bool {
+        !self.warnings(thresholds).is_empty()
+    }
+}
+
+impl ModuleMap {
+    /// Age of the map at `now`; drift needs the tracked files of a manifest.
+    pub fn staleness(&self, now: DateTime<Utc>) -> Staleness {
+        Staleness {
+            generated_at: self.generated_at,
+            age_days: (now - self.generated_at).num_days(),
+            drift: None,
+        }
+    }
+}
+
+impl ProjectManifest {
+    /// Compares tracked files under `root` against their recorded SHA-256 hashes.
+    ///
+    /// Files are hashed as raw bytes, so non-UTF-8 content compares fine. Only
+    /// files that do not exist count as missing; one that cannot be read
+    /// counts as changed.
+    pub fn tracked_drift(&self, root: &Path) -> FileDrift {
+        let mut drift = FileDrift {
+            total: self.tracked.len(),
+            ..Default::default()
+        };
+        for file in &self.tracked {
+            match fs::read(root.join(&file.path)) {
+                Ok(content) if ContentStore::hash_bytes(&content) == file.hash => {}
+                Err(err) if err.kind() == io::ErrorKind::NotFound => {
+                    drift.missing.push(file.path.clone())
+                }
+                _ => drift.changed.push(file.path.clone()),
+            }
+        }
+        drift
+    }
+
+    /// Map age plus tracked-file drift when `root` is given.
+    pub fn staleness(&self, now: DateTime<Utc>, root: Option<&Path>) -> Staleness {
+        Staleness {
+            drift: root.map(|root| self.tracked_drift(root)),
+            ..self.project.staleness(now)
+        }
+    }
+}
+
+impl SchemaRegistry {
+    /// `load` plus age warnings against `thresholds` at `now`.
+    pub fn load_with_diagnostics(
+        &self,
+        data: &str,
+        thresholds: &StalenessThresholds,
+        now: DateTime<Utc>,
+    ) -> Result<(ProjectManifest, Vec<StalenessWarning>), SchemaError> {
+        let manifest = self.load(data)?;
+        let warnings = manifest.staleness(now, None).warnings(thresholds);
+        Ok((manifest, warnings))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    use crate::{GeneratorInfo, ProjectMetadata, TechStack, TrackedFile};
+
+    fn manifest(age_days: i64) -> ProjectManifest {
+        let mut map = ModuleMap::new(
+            GeneratorInfo::new("test", "1.0.0"),
+            ProjectMetadata::new("test", TechStack::new("rust")),
+            vec![],
+            vec![],
+        );
+        map.generated_at = Utc::now() - Duration::days(age_days);
+        ProjectManifest::new(map)
+    }
+
+    #[test]
+    fn test_age_warning() {
+        let thresholds = StalenessThresholds::default().with_max_age_days(7);
+        assert!(
+            !manifest(3)
+                .project
+                .staleness(Utc::now())
+                .is_stale(&thresholds)
+        );
+
+        let warnings = manifest(90)
+            .project
+            .staleness(Utc::now())
+            .warnings(&thresholds);
+        assert_eq!(
+            warnings,
+            vec![StalenessWarning::TooOld {
+                age_days: 90,
+                max_age_days: 7
+            }]
+        );
+        assert_eq!(
+            warnings[0].to_string(),
+            "map generated 90 days ago (limit 7); regenerate it"
+        );
+    }
+
+    #[test]
+    fn test_tracked_drift() {
+        let root = std::env::temp_dir().join(format!("modmap-staleness-{}", std::process::id()));
+        fs::create_dir_all(&root).unwrap();
+        fs::write(root.join("a.rs"), "fn a() {}").unwrap();
+        fs::write(root.join("b.rs"), "fn b() { changed }").unwrap();
+        fs::write(root.join("logo.png"), [0x89, 0xff, 0xfe]).unwrap();
+        fs::create_dir_all(root.join("d.rs")).unwrap();
+
+        let manifest = manifest(0).with_tracked(vec![
+            TrackedFile::new("a.rs", ContentStore::hash("fn a() {}"), 0),
+            TrackedFile::new("b.rs", ContentStore::hash("fn b() {}"), 0),
+            TrackedFile::new("c.rs", ContentStore::hash("fn c() {}"), 0),
+            TrackedFile::new("d.rs", ContentStore::hash("fn d() {}"), 0),
+            TrackedFile::new("logo.png", ContentStore::hash_bytes(&[0x89, 0xff, 0xfe]), 0),
+        ]);
+        let staleness = manifest.staleness(Utc::now(), Some(&root));
+        fs::remove_dir_all(&root).unwrap();
+
+        let drift = staleness.drift.as_ref().unwrap();
+        assert_eq!(drift.changed, vec!["b.rs", "d.rs"]);
+        assert_eq!(drift.missing, vec!["c.rs"]);
+        assert!((drift.ratio() - 3.0 / 5.0).abs() < 1e-9);
+        assert!(matches!(
+            staleness.warnings(&StalenessThresholds::default())[..],
+            [StalenessWarning::Drifted { .. }]
+        ));
+    }
+
+    #[test]
+    fn test_load_with_diagnostics() {
+        let json = serde_json::to_string(&manifest(45)).unwrap();
+        let (manifest, warnings) = SchemaRegistry::new()
+            .load_with_diagnostics(&json, &StalenessThresholds::default(), Utc::now())
+            .unwrap();
+        assert_eq!(manifest.project.project.name, "test");
+        assert_eq!(warnings.len(), 1);
+    }
+}
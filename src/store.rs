@@ -0,0 +1,249 @@
+//! Content-addressed storage for large rule and skill bodies
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use sha2::{Digest, Sha256};
+
+use crate::registry::SchemaError;
+use crate::rule::Rule;
+use crate::skill::Skill;
+
+/// Object directory relative to the manifest directory
+pub const OBJECTS_DIR: &str = ".modmap/objects";
+
+/// Stores bodies once under `.modmap/objects/<sha256>` so identical content
+/// shared by many rules or skills is written a single time.
+#[derive(Debug, Clone)]
+pub struct ContentStore {
+    objects: PathBuf,
+    min_size: usize,
+}
+
+impl ContentStore {
+    pub fn new(base: impl AsRef<Path>) -> Self {
+        Self {
+            objects: base.as_ref().join(OBJECTS_DIR),
+            min_size: 0,
+        }
+    }
+
+    /// The store next to the manifest at `manifest_path`.
+    pub fn for_manifest(manifest_path: impl AsRef<Path>) -> Self {
+        Self::new(manifest_path.as_ref().parent().unwrap_or(Path::new("")))
+    }
+
+    /// Only externalize bodies of at least `min_size` bytes.
+    pub fn with_min_size(mut self, min_size: usize) -> Self {
+        self.min_size = min_size;
+        self
+    }
+
+    pub fn hash(content: &str) -> String {
+        Self::hash_bytes(content.as_bytes())
+    }
+
+    pub fn hash_bytes(content: &[u8]) -> String {
+        Sha256::digest(content)
+            .iter()
+            .map(|b| format!("{b:02x}"))
+            .collect()
+    }
+
+    /// Rejects anything but a SHA-256 hex digest, so a hash read from a rule
+    /// or skill file cannot point outside the object directory.
+    pub fn object_path(&self, hash: &str) -> io::Result<PathBuf> {
+        let valid = hash.len() == 64
+            && hash
+                .bytes()
+                .all(|b| b.is_ascii_digit() || (b'a'..=b'f').contains(&b));
+        if !valid {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("invalid object hash {hash:?}"),
+            ));
+        }
+        Ok(self.objects.join(hash))
+    }
+
+    pub fn contains(&self, hash: &str) -> bool {
+        self.object_path(hash).is_ok_and(|path| path.is_file())
+    }
+
+    pub fn put(&self, content: &str) -> io::Result<String> {
+        let hash = Self::hash(content);
+        let path = self.object_path(&hash)?;
+        if !path.is_file() {
+            fs::create_dir_all(&self.objects)?;
+            let tmp = path.with_extension(format!("tmp.{}", std::process::id()));
+            fs::write(&tmp, content)?;
+            fs::rename(&tmp, &path)?;
+        }
+        Ok(hash)
+    }
+
+    pub fn get(&self, hash: &str) -> io::Result<String> {
+        let content = fs::read_to_string(self.object_path(hash)?)?;
+        if Self::hash(&content) != hash {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("object {hash} is corrupted"),
+            ));
+        }
+        Ok(content)
+    }
+
+    /// Moves rule content into the store, leaving `content_hash` behind.
+    ///
+    /// Lines are stored joined by `\n` exactly as given, so trailing empty
+    /// lines and `\r` endings survive. `load_rule` resolves the hash when
+    /// reading a rule file; a rule deserialized any other way needs
+    /// `resolve_rule` before its `content` is read.
+    pub fn externalize_rule(&self, rule: &mut Rule) -> io::Result<bool> {
+        let body = rule.content.join("\n");
+        if rule.content.is_empty() || body.len() < self.min_size {
+            return Ok(false);
+        }
+        rule.content_hash = Some(self.put(&body)?);
+        rule.content.clear();
+        Ok(true)
+    }
+
+    pub fn resolve_rule(&self, rule: &mut Rule) -> io::Result<()> {
+        if let Some(hash) = rule.content_hash.take() {
+            rule.content = self.get(&hash)?.split('\n').map(String::from).collect();
+        }
+        Ok(())
+    }
+
+    /// Moves the skill body into the store, leaving `body_hash` behind.
+    /// `load_skill` resolves it again; other callers use `resolve_skill`.
+    pub fn externalize_skill(&self, skill: &mut Skill) -> io::Result<bool> {
+        if skill.body.is_empty() || skill.body.len() < self.min_size {
+            return Ok(false);
+        }
+        skill.body_hash = Some(self.put(&skill.body)?);
+        skill.body.clear();
+        Ok(true)
+    }
+
+    pub fn resolve_skill(&self, skill: &mut Skill) -> io::Result<()> {
+        if let Some(hash) = skill.body_hash.take() {
+            skill.body = self.get(&hash)?;
+        }
+        Ok(())
+    }
+
+    /// Reads a rule JSON file, pulling externalized content from the store.
+    pub fn load_rule(&self, path: impl AsRef<Path>) -> Result<Rule, SchemaError> {
+        let mut rule: Rule = crate::key_style::from_json_any(&fs::read_to_string(path)?)?;
+        self.resolve_rule(&mut rule)?;
+        Ok(rule)
+    }
+
+    /// Reads a skill JSON file, pulling an externalized body from the store.
+    pub fn load_skill(&self, path: impl AsRef<Path>) -> Result<Skill, SchemaError> {
+        let mut skill: Skill = crate::key_style::from_json_any(&fs::read_to_string(path)?)?;
+        self.resolve_skill(&mut skill)?;
+        Ok(skill)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_store(name: &str) -> (PathBuf, ContentStore) {
+        let dir = std::env::temp_dir().join(format!("modmap-store-{name}-{}", std::process::id()));
+        let store = ContentStore::new(&dir);
+        (dir, store)
+    }
+
+    #[test]
+    fn test_put_is_deduplicated() {
+        let (dir, store) = temp_store("dedup");
+        let a = store.put("# Rust\nUse ?").unwrap();
+        let b = store.put("# Rust\nUse ?").unwrap();
+
+        assert_eq!(a, b);
+        assert_eq!(a.len(), 64);
+        assert_eq!(store.get(&a).unwrap(), "# Rust\nUse ?");
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_rule_and_skill_roundtrip() {
+        let (dir, store) = temp_store("roundtrip");
+        let mut rule = Rule::module("auth", vec![], vec!["# Auth".into(), "Use bcrypt".into()]);
+        let mut skill = Skill::new("review", "desc", "# Review");
+
+        assert!(store.externalize_rule(&mut rule).unwrap());
+        assert!(store.externalize_skill(&mut skill).unwrap());
+        assert!(rule.content.is_empty());
+        assert!(rule.content_hash.is_some());
+
+        store.resolve_rule(&mut rule).unwrap();
+        store.resolve_skill(&mut skill).unwrap();
+        assert_eq!(rule.content, vec!["# Auth", "Use bcrypt"]);
+        assert_eq!(skill.body, "# Review");
+        assert!(skill.body_hash.is_none());
+
+        let content: Vec<String> = vec!["# Auth\r".into(), "Use bcrypt\r".into(), "".into()];
+        let mut rule = Rule::module("auth", vec![], content.clone());
+        store.externalize_rule(&mut rule).unwrap();
+        store.resolve_rule(&mut rule).unwrap();
+        assert_eq!(rule.content, content);
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_load_resolves_from_store_next_to_manifest() {
+        let (dir, _) = temp_store("load");
+        let store = ContentStore::for_manifest(dir.join("manifest.json"));
+        let mut rule = Rule::module("auth", vec![], vec!["# Auth".into()]);
+        store.externalize_rule(&mut rule).unwrap();
+        let mut skill = Skill::new("review", "desc", "# Review");
+        store.externalize_skill(&mut skill).unwrap();
+        fs::write(dir.join("auth.json"), serde_json::to_string(&rule).unwrap()).unwrap();
+        fs::write(
+            dir.join("review.json"),
+            serde_json::to_string(&skill).unwrap(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            store.load_rule(dir.join("auth.json")).unwrap().content,
+            ["# Auth"]
+        );
+        assert_eq!(
+            store.load_skill(dir.join("review.json")).unwrap().body,
+            "# Review"
+        );
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_rejects_malformed_hashes() {
+        let (_, store) = temp_store("hashes");
+        for hash in ["../../etc/passwd", "ABC", &"A".repeat(64), &"0".repeat(63)] {
+            let err = store.get(hash).unwrap_err();
+            assert_eq!(err.kind(), io::ErrorKind::InvalidInput, "{hash}");
+        }
+        assert!(!store.contains("../manifest.json"));
+    }
+
+    #[test]
+    fn test_min_size_keeps_small_bodies_inline() {
+        let (_, store) = temp_store("small");
+        let mut rule = Rule::project("proj", vec!["short".into()]);
+
+        assert!(
+            !store
+                .with_min_size(1024)
+                .externalize_rule(&mut rule)
+                .unwrap()
+        );
+        assert_eq!(rule.content, vec!["short"]);
+    }
+}
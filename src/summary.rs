@@ -0,0 +1,457 @@
+//! Condensed architecture overviews for prompt injection
+
+use std::fmt::{self, Write};
+use std::ptr;
+
+use serde::Serialize;
+
+use crate::diff::MapDiff;
+use crate::manifest::ProjectManifest;
+use crate::module_map::{Domain, Module, ModuleGroup, ModuleMap};
+
+/// How much of the map a summary includes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SummaryLevel {
+    /// Domains only
+    Domains,
+    /// Domains and their groups
+    Groups,
+    /// Domains, groups and the `n` highest-risk modules
+    RiskyModules(usize),
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct GroupSummary {
+    pub id: String,
+    pub name: String,
+    pub responsibility: String,
+    pub module_count: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DomainSummary {
+    pub id: String,
+    pub name: String,
+    pub responsibility: String,
+    pub module_count: usize,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub groups: Vec<GroupSummary>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ModuleSummary {
+    pub id: String,
+    pub name: String,
+    pub responsibility: String,
+    pub risk_score: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct MapSummary {
+    pub project: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    pub primary_language: String,
+    pub module_count: usize,
+    pub domains: Vec<DomainSummary>,
+    /// Groups outside any domain (included from `SummaryLevel::Groups`)
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub standalone_groups: Vec<GroupSummary>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub risky_modules: Vec<ModuleSummary>,
+}
+
+impl ModuleMap {
+    pub fn summarize(&self, level: SummaryLevel) -> MapSummary {
+        let with_groups = level != SummaryLevel::Domains;
+
+        let domains = self
+            .domains
+            .iter()
+            .map(|domain| self.summarize_domain(domain, with_groups))
+            .collect();
+
+        let standalone_groups = if with_groups {
+            self.groups
+                .iter()
+                .filter(|g| self.domain_of_group(g).is_none())
+                .map(summarize_group)
+                .collect()
+        } else {
+            Vec::new()
+        };
+
+        let risky_modules = match level {
+            SummaryLevel::RiskyModules(n) => {
+                let mut modules: Vec<_> = self.modules.iter().collect();
+                modules.sort_by(|a, b| {
+                    b.metrics
+                        .risk_score
+                        .get()
+                        .total_cmp(&a.metrics.risk_score.get())
+                        .then_with(|| a.id.cmp(&b.id))
+                });
+                modules
+                    .into_iter()
+                    .take(n)
+                    .map(|m| ModuleSummary {
+                        id: m.id.clone(),
+                        name: m.name.clone(),
+                        responsibility: m.responsibility.clone(),
+                        risk_score: m.metrics.risk_score.get(),
+                    })
+                    .collect()
+            }
+            _ => Vec::new(),
+        };
+
+        MapSummary {
+            project: self.project.name.clone(),
+            description: self.project.description.clone(),
+            primary_language: self.project.tech_stack.primary_language.clone(),
+            module_count: self.modules.len(),
+            domains,
+            standalone_groups,
+            risky_modules,
+        }
+    }
+
+    /// The group's `domain_id` when set, else the first domain listing it.
+    fn domain_of_group(&self, group: &ModuleGroup) -> Option<&Domain> {
+        match &group.domain_id {
+            Some(domain_id) => self.find_domain(domain_id),
+            None => self.find_domain_containing_group(&group.id),
+        }
+    }
+
+    fn summarize_domain(&self, domain: &Domain, with_groups: bool) -> DomainSummary {
+        let groups: Vec<&ModuleGroup> = self
+            .groups
+            .iter()
+            .filter(|g| self.domain_of_group(g).is_some_and(|d| ptr::eq(d, domain)))
+            .collect();
+        DomainSummary {
+            id: domain.id.clone(),
+            name: domain.name.clone(),
+            responsibility: domain.responsibility.clone(),
+            module_count: groups.iter().map(|g| g.module_ids.len()).sum(),
+            groups: if with_groups {
+                groups.into_iter().map(summarize_group).collect()
+            } else {
+                Vec::new()
+            },
+        }
+    }
+}
+
+fn summarize_group(group: &ModuleGroup) -> GroupSummary {
+    GroupSummary {
+        id: group.id.clone(),
+        name: group.name.clone(),
+        responsibility: group.responsibility.clone(),
+        module_count: group.module_ids.len(),
+    }
+}
+
+fn describe(responsibility: &str) -> String {
+    if responsibility.is_empty() {
+        String::new()
+    } else {
+        format!(": {responsibility}")
+    }
+}
+
+impl MapSummary {
+    pub fn to_markdown(&self) -> String {
+        let mut out = String::new();
+        let _ = writeln!(out, "# {}", self.project);
+        if let Some(description) = &self.description {
+            let _ = writeln!(out, "\n{description}");
+        }
+        let _ = writeln!(
+            out,
+            "\n{} modules, primary language: {}",
+            self.module_count, self.primary_language
+        );
+
+        if !self.domains.is_empty() {
+            let _ = writeln!(out, "\n## Domains\n");
+            for domain in &self.domains {
+                let _ = writeln!(
+                    out,
+                    "- **{}** ({} modules){}",
+                    domain.name,
+                    domain.module_count,
+                    describe(&domain.responsibility)
+                );
+                for group in &domain.groups {
+                    let _ = writeln!(
+                        out,
+                        "  - {} ({} modules){}",
+                        group.name,
+                        group.module_count,
+                        describe(&group.responsibility)
+                    );
+                }
+            }
+        }
+
+        if !self.standalone_groups.is_empty() {
+            let _ = writeln!(out, "\n## Groups\n");
+            for group in &self.standalone_groups {
+                let _ = writeln!(
+                    out,
+                    "- {} ({} modules){}",
+                    group.name,
+                    group.module_count,
+                    describe(&group.responsibility)
+                );
+            }
+        }
+
+        if !self.risky_modules.is_empty() {
+            let _ = writeln!(out, "\n## Highest-Risk Modules\n");
+            for module in &self.risky_modules {
+                let _ = writeln!(
+                    out,
+                    "- `{}` (risk {:.2}){}",
+                    module.id,
+                    module.risk_score,
+                    describe(&module.responsibility)
+                );
+            }
+        }
+
+        out
+    }
+}
+
+/// `1 module`, `3 modules`
+fn count(n: usize, noun: &str) -> String {
+    if n == 1 {
+        format!("{n} {noun}")
+    } else {
+        format!("{n} {noun}s")
+    }
+}
+
+impl Module {
+    /// One line, e.g. `auth (rust): 2 paths, 1 dependency, 3 issues`.
+    pub fn summary(&self) -> String {
+        format!(
+            "{} ({}): {}, {}, {}",
+            self.id,
+            self.primary_language,
+            count(self.paths.len(), "path"),
+            if self.dependencies.len() == 1 {
+                "1 dependency".to_string()
+            } else {
+                format!("{} dependencies", self.dependencies.len())
+            },
+            count(self.known_issues.len(), "issue")
+        )
+    }
+}
+
+impl ModuleMap {
+    /// One line, e.g. `42 modules, 7 groups, 3 domains, 12 known issues`.
+    pub fn summary(&self) -> String {
+        let issues: usize = self.modules.iter().map(|m| m.known_issues.len()).sum();
+        format!(
+            "{}, {}, {}, {}",
+            count(self.modules.len(), "module"),
+            count(self.groups.len(), "group"),
+            count(self.domains.len(), "domain"),
+            count(issues, "known issue")
+        )
+    }
+}
+
+impl ProjectManifest {
+    /// Project name, map summary and resource counts on one line.
+    pub fn summary(&self) -> String {
+        format!(
+            "{}: {}; {}, {}, {}",
+            self.project.project.name,
+            self.project.summary(),
+            count(self.rules.len(), "rule"),
+            count(self.skills.len(), "skill"),
+            count(self.agents.len(), "agent")
+        )
+    }
+}
+
+impl MapDiff {
+    /// Non-zero counts only, e.g. `modules +2 -1 ~3, groups +1`, renames counting
+    /// as changes; `no changes` when empty.
+    pub fn summary(&self) -> String {
+        let parts: Vec<String> = [
+            (
+                "modules",
+                self.added_modules.len(),
+                self.removed_modules.len(),
+                self.changed_modules.len()
+                    + self
+                        .renamed_modules
+                        .iter()
+                        .filter(|r| !self.changed_modules.iter().any(|c| c.module_id == r.new))
+                        .count(),
+            ),
+            (
+                "groups",
+                self.added_groups.len(),
+                self.removed_groups.len(),
+                self.changed_groups.len(),
+            ),
+            (
+                "domains",
+                self.added_domains.len(),
+                self.removed_domains.len(),
+                0,
+            ),
+        ]
+        .into_iter()
+        .filter(|(_, added, removed, changed)| added + removed + changed > 0)
+        .map(|(kind, added, removed, changed)| {
+            let mut part = kind.to_string();
+            for (sign, n) in [('+', added), ('-', removed), ('~', changed)] {
+                if n > 0 {
+                    let _ = write!(part, " {sign}{n}");
+                }
+            }
+            part
+        })
+        .collect();
+        if parts.is_empty() {
+            "no changes".into()
+        } else {
+            parts.join(", ")
+        }
+    }
+}
+
+impl fmt::Display for Module {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.summary())
+    }
+}
+
+impl fmt::Display for ModuleMap {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.summary())
+    }
+}
+
+impl fmt::Display for ProjectManifest {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.summary())
+    }
+}
+
+impl fmt::Display for MapDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.summary())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{GeneratorInfo, Module, ModuleMetrics, ProjectMetadata, TechStack};
+
+    fn module(id: &str, risk: f64) -> Module {
+        Module::new(id, id, vec![])
+            .with_responsibility(format!("{id} module"))
+            .with_primary_language("rust")
+            .with_metrics(ModuleMetrics::new(0.5, 0.5, risk))
+    }
+
+    fn sample_map() -> ModuleMap {
+        ModuleMap::new(
+            GeneratorInfo::new("test", "1.0.0"),
+            ProjectMetadata::new("shop", TechStack::new("rust")),
+            vec![module("auth", 0.9), module("cart", 0.2), module("cli", 0.5)],
+            vec![
+                ModuleGroup::new("identity-core", "Identity Core", vec!["auth".into()])
+                    .with_domain("identity"),
+                ModuleGroup::new("tools", "Tools", vec!["cli".into()]),
+            ],
+        )
+        .with_domains(vec![
+            Domain::new("identity", "Identity", vec!["identity-core".into()])
+                .with_responsibility("Who the user is"),
+        ])
+    }
+
+    #[test]
+    fn test_summary_levels() {
+        let map = sample_map();
+
+        let domains = map.summarize(SummaryLevel::Domains);
+        assert_eq!(domains.domains[0].module_count, 1);
+        assert!(domains.domains[0].groups.is_empty());
+        assert!(domains.standalone_groups.is_empty());
+
+        let groups = map.summarize(SummaryLevel::Groups);
+        assert_eq!(groups.domains[0].groups.len(), 1);
+        assert_eq!(groups.standalone_groups[0].id, "tools");
+
+        let mut unlisted = map.clone();
+        unlisted.groups.push(
+            ModuleGroup::new("sessions", "Sessions", vec!["cart".into()]).with_domain("identity"),
+        );
+        let groups = unlisted.summarize(SummaryLevel::Groups);
+        let ids: Vec<_> = groups.domains[0]
+            .groups
+            .iter()
+            .map(|g| g.id.as_str())
+            .collect();
+        assert_eq!(ids, vec!["identity-core", "sessions"]);
+        assert_eq!(groups.domains[0].module_count, 2);
+        assert_eq!(groups.standalone_groups.len(), 1);
+
+        let risky = map.summarize(SummaryLevel::RiskyModules(2));
+        let ids: Vec<_> = risky.risky_modules.iter().map(|m| m.id.as_str()).collect();
+        assert_eq!(ids, vec!["auth", "cli"]);
+    }
+
+    #[test]
+    fn test_summary_markdown() {
+        let markdown = sample_map()
+            .summarize(SummaryLevel::RiskyModules(1))
+            .to_markdown();
+
+        assert!(markdown.starts_with("# shop\n"));
+        assert!(markdown.contains("- **Identity** (1 modules): Who the user is"));
+        assert!(markdown.contains("  - Identity Core (1 modules)"));
+        assert!(markdown.contains("- `auth` (risk 0.90): auth module"));
+    }
+
+    #[test]
+    fn test_one_line_summaries() {
+        let map = sample_map();
+        assert_eq!(
+            map.to_string(),
+            "3 modules, 2 groups, 1 domain, 0 known issues"
+        );
+        assert_eq!(
+            map.modules[0].to_string(),
+            "auth (rust): 0 paths, 0 dependencies, 0 issues"
+        );
+
+        let manifest = ProjectManifest::new(map.clone()).with_rules(vec!["rules/a.md".into()]);
+        assert_eq!(
+            manifest.to_string(),
+            "shop: 3 modules, 2 groups, 1 domain, 0 known issues; 1 rule, 0 skills, 0 agents"
+        );
+
+        let mut smaller = map.clone();
+        smaller.modules.pop();
+        smaller.domains.clear();
+        assert_eq!(
+            ModuleMap::diff(&map, &smaller).to_string(),
+            "modules -1, domains -1"
+        );
+        assert_eq!(ModuleMap::diff(&map, &map).to_string(), "no changes");
+    }
+}
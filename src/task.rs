@@ -0,0 +1,215 @@
+//! Planned work items tied to modules, domains and known issues
+
+use std::fmt;
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::entity::EntityRef;
+use crate::manifest::ProjectManifest;
+use crate::types::KnownIssue;
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, JsonSchema, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum TaskStatus {
+    #[default]
+    Todo,
+    InProgress,
+    Blocked,
+    Done,
+}
+
+impl fmt::Display for TaskStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TaskStatus::Todo => write!(f, "todo"),
+            TaskStatus::InProgress => write!(f, "in_progress"),
+            TaskStatus::Blocked => write!(f, "blocked"),
+            TaskStatus::Done => write!(f, "done"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct Task {
+    pub id: String,
+    pub title: String,
+    #[serde(default)]
+    pub status: TaskStatus,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub module_ids: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub domain_ids: Vec<String>,
+    /// `KnownIssue::id`s this task addresses
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub issue_ids: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub suggested_skill: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub suggested_agent: Option<String>,
+}
+
+impl Task {
+    pub fn new(id: impl Into<String>, title: impl Into<String>) -> Self {
+        Self {
+            id: id.into(),
+            title: title.into(),
+            status: TaskStatus::default(),
+            module_ids: Vec::new(),
+            domain_ids: Vec::new(),
+            issue_ids: Vec::new(),
+            suggested_skill: None,
+            suggested_agent: None,
+        }
+    }
+
+    /// Modules, domains and issues the task refers to, as kinded references.
+    pub fn entities(&self) -> Vec<EntityRef> {
+        let modules = self.module_ids.iter().cloned().map(EntityRef::Module);
+        let domains = self.domain_ids.iter().cloned().map(EntityRef::Domain);
+        let issues = self.issue_ids.iter().cloned().map(EntityRef::Issue);
+        modules.chain(domains).chain(issues).collect()
+    }
+
+    pub fn with_status(mut self, status: TaskStatus) -> Self {
+        self.status = status;
+        self
+    }
+
+    pub fn with_modules(mut self, module_ids: Vec<String>) -> Self {
+        self.module_ids = module_ids;
+        self
+    }
+
+    pub fn with_domains(mut self, domain_ids: Vec<String>) -> Self {
+        self.domain_ids = domain_ids;
+        self
+    }
+
+    pub fn with_issues(mut self, issue_ids: Vec<String>) -> Self {
+        self.issue_ids = issue_ids;
+        self
+    }
+
+    pub fn with_suggested_skill(mut self, skill: impl Into<String>) -> Self {
+        self.suggested_skill = Some(skill.into());
+        self
+    }
+
+    pub fn with_suggested_agent(mut self, agent: impl Into<String>) -> Self {
+        self.suggested_agent = Some(agent.into());
+        self
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.status != TaskStatus::Done
+    }
+}
+
+impl ProjectManifest {
+    pub fn get_task(&self, id: &str) -> Option<&Task> {
+        self.tasks.iter().find(|t| t.id == id)
+    }
+
+    /// Tasks referencing `module_id` directly or through its domain.
+    pub fn tasks_for_module(&self, module_id: &str) -> Vec<&Task> {
+        let domain_id = self
+            .project
+            .find_module(module_id)
+            .and_then(|m| self.project.find_domain_containing_module(&m.id))
+            .map(|d| d.id.as_str());
+        self.tasks
+            .iter()
+            .filter(|t| {
+                t.module_ids.iter().any(|id| id == module_id)
+                    || domain_id.is_some_and(|d| t.domain_ids.iter().any(|id| id == d))
+            })
+            .collect()
+    }
+
+    pub fn tasks_with_status(&self, status: TaskStatus) -> Vec<&Task> {
+        self.tasks.iter().filter(|t| t.status == status).collect()
+    }
+
+    /// Known issues linked from `task`, searched across all modules.
+    pub fn task_issues(&self, task: &Task) -> Vec<&KnownIssue> {
+        self.project
+            .modules
+            .iter()
+            .flat_map(|m| &m.known_issues)
+            .filter(|issue| task.issue_ids.contains(&issue.id))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        Domain, GeneratorInfo, IssueCategory, IssueSeverity, Module, ModuleGroup, ModuleMap,
+        ProjectMetadata, TechStack,
+    };
+
+    fn module(id: &str) -> Module {
+        Module::new(id, id, vec![format!("src/{id}/")]).with_primary_language("rust")
+    }
+
+    #[test]
+    fn test_task_queries() {
+        let mut map = ModuleMap::new(
+            GeneratorInfo::new("test", "1.0.0"),
+            ProjectMetadata::new("shop", TechStack::new("rust")),
+            vec![module("auth"), module("billing")],
+            vec![ModuleGroup::new(
+                "identity-core",
+                "Identity",
+                vec!["auth".into()],
+            )],
+        )
+        .with_domains(vec![Domain::new(
+            "identity",
+            "Identity",
+            vec!["identity-core".into()],
+        )]);
+        map.modules[0].known_issues.push(KnownIssue::new(
+            "token-leak",
+            "Tokens logged",
+            IssueSeverity::High,
+            IssueCategory::Security,
+        ));
+
+        let mut manifest = ProjectManifest::new(map).with_tasks(vec![
+            Task::new("t1", "Scrub token logs")
+                .with_modules(vec!["auth".into()])
+                .with_issues(vec!["token-leak".into()])
+                .with_suggested_skill("security-review"),
+            Task::new("t2", "Identity audit")
+                .with_domains(vec!["identity".into()])
+                .with_status(TaskStatus::InProgress),
+            Task::new("t3", "Invoice export")
+                .with_modules(vec!["billing".into()])
+                .with_status(TaskStatus::Done),
+        ]);
+
+        let ids = |tasks: Vec<&Task>| tasks.iter().map(|t| t.id.clone()).collect::<Vec<_>>();
+        assert_eq!(ids(manifest.tasks_for_module("auth")), vec!["t1", "t2"]);
+        assert_eq!(ids(manifest.tasks_for_module("billing")), vec!["t3"]);
+        assert_eq!(
+            ids(manifest.tasks_with_status(TaskStatus::Done)),
+            vec!["t3"]
+        );
+        assert!(!manifest.get_task("t3").unwrap().is_open());
+
+        let t1 = manifest.get_task("t1").unwrap();
+        assert_eq!(manifest.task_issues(t1)[0].id, "token-leak");
+        let refs: Vec<String> = t1.entities().iter().map(|e| e.to_string()).collect();
+        assert_eq!(refs, ["module:auth", "issue:token-leak"]);
+
+        manifest.tasks[0].status = TaskStatus::Blocked;
+        let json = serde_json::to_value(&manifest).unwrap();
+        assert_eq!(json["tasks"][0]["status"], "blocked");
+        assert!(json["tasks"][1].get("issue_ids").is_none());
+        let restored: ProjectManifest = serde_json::from_value(json).unwrap();
+        assert_eq!(restored.tasks, manifest.tasks);
+    }
+}
@@ -0,0 +1,392 @@
+//! Org-configurable vocabularies for issue categories, conventions and tags
+
+use std::fmt;
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::module_map::ModuleMap;
+use crate::types::IssueCategory;
+
+/// Vocabulary term with accepted aliases
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct TaxonomyEntry {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub aliases: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
+impl TaxonomyEntry {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            aliases: Vec::new(),
+            description: None,
+        }
+    }
+
+    pub fn with_aliases(mut self, aliases: Vec<String>) -> Self {
+        self.aliases = aliases;
+        self
+    }
+
+    pub fn with_description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    fn matches(&self, term: &str) -> bool {
+        self.name.eq_ignore_ascii_case(term)
+            || self.aliases.iter().any(|a| a.eq_ignore_ascii_case(term))
+    }
+}
+
+/// Org-specific issue category refining a built-in `IssueCategory`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct CategoryExtension {
+    #[serde(flatten)]
+    pub entry: TaxonomyEntry,
+    pub parent: IssueCategory,
+}
+
+impl CategoryExtension {
+    pub fn new(name: impl Into<String>, parent: IssueCategory) -> Self {
+        Self {
+            entry: TaxonomyEntry::new(name),
+            parent,
+        }
+    }
+
+    pub fn with_aliases(mut self, aliases: Vec<String>) -> Self {
+        self.entry.aliases = aliases;
+        self
+    }
+}
+
+/// Controlled vocabulary that map contents are checked against
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct Taxonomy {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub issue_categories: Vec<CategoryExtension>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub convention_names: Vec<TaxonomyEntry>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<TaxonomyEntry>,
+    /// Accept subcategories not listed in `issue_categories`
+    #[serde(default)]
+    pub free_form_categories: bool,
+    /// Accept convention names not listed in `convention_names`
+    #[serde(default)]
+    pub free_form_conventions: bool,
+    /// Accept tags not listed in `tags`
+    #[serde(default)]
+    pub free_form_tags: bool,
+}
+
+/// Map content that does not conform to the taxonomy
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TaxonomyViolation {
+    UnknownCategory {
+        module_id: String,
+        issue_id: String,
+        subcategory: String,
+    },
+    /// Subcategory extends a different built-in category than the issue's
+    CategoryParentMismatch {
+        module_id: String,
+        issue_id: String,
+        subcategory: String,
+        expected: IssueCategory,
+    },
+    UnknownConvention {
+        module_id: String,
+        name: String,
+    },
+    UnknownTag {
+        module_id: String,
+        tag: String,
+    },
+    /// Known term spelled as an alias instead of its canonical name
+    NonCanonical {
+        module_id: String,
+        found: String,
+        canonical: String,
+    },
+}
+
+impl fmt::Display for TaxonomyViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnknownCategory {
+                module_id,
+                issue_id,
+                subcategory,
+            } => write!(
+                f,
+                "{module_id}/{issue_id}: unknown issue category '{subcategory}'"
+            ),
+            Self::CategoryParentMismatch {
+                module_id,
+                issue_id,
+                subcategory,
+                expected,
+            } => write!(
+                f,
+                "{module_id}/{issue_id}: '{subcategory}' belongs to category '{expected}'"
+            ),
+            Self::UnknownConvention { module_id, name } => {
+                write!(f, "{module_id}: unknown convention '{name}'")
+            }
+            Self::UnknownTag { module_id, tag } => write!(f, "{module_id}: unknown tag '{tag}'"),
+            Self::NonCanonical {
+                module_id,
+                found,
+                canonical,
+            } => write!(f, "{module_id}: use '{canonical}' instead of '{found}'"),
+        }
+    }
+}
+
+enum Lookup<'a> {
+    Canonical,
+    Alias(&'a str),
+    Unknown,
+}
+
+fn lookup<'a>(entries: &'a [TaxonomyEntry], term: &str) -> Lookup<'a> {
+    match entries.iter().find(|e| e.matches(term)) {
+        Some(entry) if entry.name == term => Lookup::Canonical,
+        Some(entry) => Lookup::Alias(&entry.name),
+        None => Lookup::Unknown,
+    }
+}
+
+impl Taxonomy {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_issue_categories(mut self, categories: Vec<CategoryExtension>) -> Self {
+        self.issue_categories = categories;
+        self
+    }
+
+    pub fn with_convention_names(mut self, names: Vec<TaxonomyEntry>) -> Self {
+        self.convention_names = names;
+        self
+    }
+
+    pub fn with_tags(mut self, tags: Vec<TaxonomyEntry>) -> Self {
+        self.tags = tags;
+        self
+    }
+
+    /// Allows terms outside the vocabulary for categories, conventions and tags.
+    pub fn allow_free_form(mut self) -> Self {
+        self.free_form_categories = true;
+        self.free_form_conventions = true;
+        self.free_form_tags = true;
+        self
+    }
+
+    /// Canonical tag name for a tag or one of its aliases.
+    pub fn canonical_tag(&self, tag: &str) -> Option<&str> {
+        self.tags
+            .iter()
+            .find(|e| e.matches(tag))
+            .map(|e| e.name.as_str())
+    }
+
+    /// Canonical subcategory and its parent for a name or alias.
+    pub fn resolve_category(&self, name: &str) -> Option<&CategoryExtension> {
+        self.issue_categories.iter().find(|c| c.entry.matches(name))
+    }
+
+    pub fn validate(&self, map: &ModuleMap) -> Vec<TaxonomyViolation> {
+        let mut violations = Vec::new();
+
+        for module in &map.modules {
+            let module_id = &module.id;
+
+            for issue in &module.known_issues {
+                if let Some(subcategory) = &issue.subcategory {
+                    match self.resolve_category(subcategory) {
+                        Some(ext) => {
+                            if ext.entry.name != *subcategory {
+                                violations.push(TaxonomyViolation::NonCanonical {
+                                    module_id: module_id.clone(),
+                                    found: subcategory.clone(),
+                                    canonical: ext.entry.name.clone(),
+                                });
+                            }
+                            if ext.parent != issue.category {
+                                violations.push(TaxonomyViolation::CategoryParentMismatch {
+                                    module_id: module_id.clone(),
+                                    issue_id: issue.id.clone(),
+                                    subcategory: subcategory.clone(),
+                                    expected: ext.parent.clone(),
+                                });
+                            }
+                        }
+                        None if !self.free_form_categories => {
+                            violations.push(TaxonomyViolation::UnknownCategory {
+                                module_id: module_id.clone(),
+                                issue_id: issue.id.clone(),
+                                subcategory: subcategory.clone(),
+                            });
+                        }
+                        None => {}
+                    }
+                }
+                self.check_tags(module_id, &issue.tags, &mut violations);
+            }
+
+            for convention in &module.conventions {
+                match lookup(&self.convention_names, &convention.name) {
+                    Lookup::Canonical => {}
+                    Lookup::Alias(canonical) => violations.push(TaxonomyViolation::NonCanonical {
+                        module_id: module_id.clone(),
+                        found: convention.name.clone(),
+                        canonical: canonical.to_string(),
+                    }),
+                    Lookup::Unknown if !self.free_form_conventions => {
+                        violations.push(TaxonomyViolation::UnknownConvention {
+                            module_id: module_id.clone(),
+                            name: convention.name.clone(),
+                        })
+                    }
+                    Lookup::Unknown => {}
+                }
+                self.check_tags(module_id, &convention.tags, &mut violations);
+            }
+        }
+
+        violations
+    }
+
+    fn check_tags(
+        &self,
+        module_id: &str,
+        tags: &[String],
+        violations: &mut Vec<TaxonomyViolation>,
+    ) {
+        for tag in tags {
+            match lookup(&self.tags, tag) {
+                Lookup::Canonical => {}
+                Lookup::Alias(canonical) => violations.push(TaxonomyViolation::NonCanonical {
+                    module_id: module_id.to_string(),
+                    found: tag.clone(),
+                    canonical: canonical.to_string(),
+                }),
+                Lookup::Unknown if !self.free_form_tags => {
+                    violations.push(TaxonomyViolation::UnknownTag {
+                        module_id: module_id.to_string(),
+                        tag: tag.clone(),
+                    })
+                }
+                Lookup::Unknown => {}
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        Convention, GeneratorInfo, IssueSeverity, KnownIssue, Module, ProjectMetadata, TechStack,
+    };
+
+    fn sample_map(conventions: Vec<Convention>, known_issues: Vec<KnownIssue>) -> ModuleMap {
+        let module = Module::new("api", "api", vec!["src/api/".into()])
+            .with_primary_language("rust")
+            .with_conventions(conventions)
+            .with_known_issues(known_issues);
+        ModuleMap::new(
+            GeneratorInfo::new("test", "1.0.0"),
+            ProjectMetadata::new("test", TechStack::new("rust")),
+            vec![module],
+            vec![],
+        )
+    }
+
+    fn sample_taxonomy() -> Taxonomy {
+        Taxonomy::new()
+            .with_issue_categories(vec![
+                CategoryExtension::new("n-plus-one", IssueCategory::Performance)
+                    .with_aliases(vec!["n+1".into()]),
+            ])
+            .with_convention_names(vec![TaxonomyEntry::new("error-handling")])
+            .with_tags(vec![
+                TaxonomyEntry::new("performance").with_aliases(vec!["perf".into()]),
+            ])
+    }
+
+    #[test]
+    fn test_conforming_map() {
+        let map = sample_map(
+            vec![Convention::new("error-handling", "Use ?").with_tags(vec!["performance".into()])],
+            vec![
+                KnownIssue::new(
+                    "q",
+                    "Query loop",
+                    IssueSeverity::High,
+                    IssueCategory::Performance,
+                )
+                .with_subcategory("n-plus-one"),
+            ],
+        );
+        assert!(sample_taxonomy().validate(&map).is_empty());
+    }
+
+    #[test]
+    fn test_violations() {
+        let map = sample_map(
+            vec![Convention::new("naming", "snake_case").with_tags(vec!["perf".into()])],
+            vec![
+                KnownIssue::new(
+                    "q",
+                    "Query loop",
+                    IssueSeverity::High,
+                    IssueCategory::Security,
+                )
+                .with_subcategory("n+1"),
+                KnownIssue::new("x", "Other", IssueSeverity::Low, IssueCategory::Security)
+                    .with_subcategory("xss"),
+            ],
+        );
+        let violations = sample_taxonomy().validate(&map);
+
+        assert!(violations.contains(&TaxonomyViolation::NonCanonical {
+            module_id: "api".into(),
+            found: "perf".into(),
+            canonical: "performance".into(),
+        }));
+        assert!(violations.contains(&TaxonomyViolation::UnknownConvention {
+            module_id: "api".into(),
+            name: "naming".into(),
+        }));
+        assert!(
+            violations.contains(&TaxonomyViolation::CategoryParentMismatch {
+                module_id: "api".into(),
+                issue_id: "q".into(),
+                subcategory: "n+1".into(),
+                expected: IssueCategory::Performance,
+            })
+        );
+        assert!(violations.contains(&TaxonomyViolation::UnknownCategory {
+            module_id: "api".into(),
+            issue_id: "x".into(),
+            subcategory: "xss".into(),
+        }));
+
+        let lenient = sample_taxonomy().allow_free_form().validate(&map);
+        assert!(!lenient.iter().any(|v| matches!(
+            v,
+            TaxonomyViolation::UnknownConvention { .. } | TaxonomyViolation::UnknownCategory { .. }
+        )));
+        assert_eq!(sample_taxonomy().canonical_tag("PERF"), Some("performance"));
+    }
+}
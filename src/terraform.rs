@@ -0,0 +1,286 @@
+//! Terraform stacks as modules: providers, state backend and module sources
+
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::Path;
+
+use crate::module_map::ModuleMap;
+use crate::registry::SchemaError;
+use crate::types::{InfrastructureInfo, ModuleDependency};
+
+/// What a directory of `.tf` files declares
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TerraformConfig {
+    pub providers: BTreeSet<String>,
+    pub backend: Option<String>,
+    /// `source` of every `module` block, as written
+    pub module_sources: Vec<String>,
+}
+
+impl TerraformConfig {
+    pub fn is_empty(&self) -> bool {
+        self.providers.is_empty() && self.backend.is_none() && self.module_sources.is_empty()
+    }
+
+    pub fn merge(&mut self, other: TerraformConfig) {
+        self.providers.extend(other.providers);
+        self.backend = self.backend.take().or(other.backend);
+        for source in other.module_sources {
+            if !self.module_sources.contains(&source) {
+                self.module_sources.push(source);
+            }
+        }
+    }
+
+    pub fn to_infrastructure(&self) -> InfrastructureInfo {
+        let info = InfrastructureInfo::new("terraform")
+            .with_providers(self.providers.iter().cloned().collect());
+        match &self.backend {
+            Some(backend) => info.with_state_backend(backend),
+            None => info,
+        }
+    }
+}
+
+/// `provider "aws"` → `("provider", Some("aws"))`, `aws =` → `("aws", None)`
+fn block_header(line: &str) -> Option<(&str, Option<&str>)> {
+    let line = line.trim();
+    if let Some(key) = line.strip_suffix('=') {
+        return Some((key.trim(), None));
+    }
+    let mut parts = line.split_whitespace();
+    let kind = parts.next()?;
+    Some((kind, parts.next().map(|label| label.trim_matches('"'))))
+}
+
+/// Line-based HCL scan; good enough for the `provider`, `backend`,
+/// `required_providers` and `module` blocks, not a general HCL parser.
+pub fn parse_terraform(text: &str) -> TerraformConfig {
+    let mut config = TerraformConfig::default();
+    let mut blocks: Vec<&str> = Vec::new();
+    for line in text.lines() {
+        let line = line.split('#').next().unwrap_or_default().trim();
+        if line.starts_with("//") || line.is_empty() {
+            continue;
+        }
+
+        let (header, opens) = match line.strip_suffix("{}") {
+            Some(header) => (Some(header), false),
+            None => (line.strip_suffix('{'), true),
+        };
+        if let Some((kind, label)) = header.and_then(block_header) {
+            match (blocks.last().copied(), kind, label) {
+                (None, "provider", Some(name)) => {
+                    config.providers.insert(name.to_string());
+                }
+                (Some("terraform"), "backend", Some(name)) => {
+                    config.backend = Some(name.to_string());
+                }
+                (Some("required_providers"), name, None) => {
+                    config.providers.insert(name.to_string());
+                }
+                _ => {}
+            }
+            if opens {
+                blocks.push(kind);
+            }
+            continue;
+        }
+        if line.starts_with('}') {
+            blocks.pop();
+            continue;
+        }
+
+        let Some((key, value)) = line.split_once('=') else {
+            continue;
+        };
+        let (key, value) = (key.trim(), value.trim().trim_matches('"'));
+        match blocks.last().copied() {
+            Some("module") if key == "source" => config.module_sources.push(value.to_string()),
+            Some("required_providers") => {
+                config.providers.insert(key.to_string());
+            }
+            _ => {}
+        }
+    }
+    config
+}
+
+/// Parses every `.tf` file directly in `dir` (stacks are not recursive).
+pub fn scan_terraform(dir: &Path) -> Result<TerraformConfig, SchemaError> {
+    let mut config = TerraformConfig::default();
+    if !dir.is_dir() {
+        return Ok(config);
+    }
+    let mut files: Vec<_> = fs::read_dir(dir)?
+        .filter_map(|e| e.ok().map(|e| e.path()))
+        .filter(|p| p.extension().is_some_and(|e| e == "tf"))
+        .collect();
+    files.sort();
+    for file in files {
+        config.merge(parse_terraform(&fs::read_to_string(file)?));
+    }
+    Ok(config)
+}
+
+/// Resolves a local module source against the directory using it:
+/// `("stacks/prod/", "../../modules/vpc")` → `modules/vpc/`.
+fn resolve_source(base: &str, source: &str) -> Option<String> {
+    if !source.starts_with("./") && !source.starts_with("../") {
+        return None;
+    }
+    let mut parts: Vec<&str> = base.split('/').filter(|p| !p.is_empty()).collect();
+    for segment in source.split('/') {
+        match segment {
+            "" | "." => {}
+            ".." => {
+                parts.pop()?;
+            }
+            other => parts.push(other),
+        }
+    }
+    Some(format!("{}/", parts.join("/")))
+}
+
+impl ModuleMap {
+    /// Reads the Terraform files of each module under `root`, adding
+    /// dependencies for local `module` sources and recording providers and
+    /// the state backend in the project tech stack.
+    ///
+    /// Registry and git sources are not modules of this map and are ignored.
+    pub fn link_terraform_modules(&mut self, root: &Path) -> Result<TerraformConfig, SchemaError> {
+        let mut project = TerraformConfig::default();
+        let mut links = Vec::new();
+        for module in &self.modules {
+            for path in &module.paths {
+                let config = scan_terraform(&root.join(path))?;
+                for source in &config.module_sources {
+                    let Some(target) = resolve_source(path, source) else {
+                        continue;
+                    };
+                    if let Some(dep) = self.modules.iter().find(|m| {
+                        m.id != module.id && m.paths.iter().any(|p| target.starts_with(p.as_str()))
+                    }) {
+                        links.push((module.id.clone(), dep.id.clone()));
+                    }
+                }
+                project.merge(config);
+            }
+        }
+
+        for (from, to) in links {
+            if let Some(module) = self.modules.iter_mut().find(|m| m.id == from)
+                && !module.dependencies.iter().any(|d| d.module_id == to)
+            {
+                module.dependencies.push(ModuleDependency::runtime(&to));
+            }
+            if let Some(module) = self.modules.iter_mut().find(|m| m.id == to)
+                && !module.dependents.contains(&from)
+            {
+                module.dependents.push(from);
+            }
+        }
+
+        if !project.is_empty() {
+            self.project.tech_stack.infrastructure = Some(project.to_infrastructure());
+        }
+        Ok(project)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{GeneratorInfo, Module, ProjectMetadata, TechStack};
+
+    const MAIN_TF: &str = r#"
+terraform {
+  required_providers {
+    aws = {
+      source  = "hashicorp/aws"
+      version = "~> 5.0"
+    }
+    random = "~> 3.0"
+  }
+  backend "s3" {
+    bucket = "tfstate"
+  }
+}
+
+provider "google" {}
+
+module "vpc" {
+  source = "../../modules/vpc"
+  cidr   = "10.0.0.0/16"
+}
+
+# module "old" { source = "./old" }
+module "eks" {
+  source  = "terraform-aws-modules/eks/aws"
+}
+"#;
+
+    #[test]
+    fn test_parse_terraform() {
+        let config = parse_terraform(MAIN_TF);
+        assert_eq!(
+            config.providers.iter().collect::<Vec<_>>(),
+            vec!["aws", "google", "random"]
+        );
+        assert_eq!(config.backend.as_deref(), Some("s3"));
+        assert_eq!(
+            config.module_sources,
+            vec!["../../modules/vpc", "terraform-aws-modules/eks/aws"]
+        );
+    }
+
+    #[test]
+    fn test_resolve_source() {
+        assert_eq!(
+            resolve_source("stacks/prod/", "../../modules/vpc").as_deref(),
+            Some("modules/vpc/")
+        );
+        assert_eq!(resolve_source("a/", "./b").as_deref(), Some("a/b/"));
+        assert_eq!(resolve_source("a/", "../../x"), None);
+        assert_eq!(resolve_source("a/", "hashicorp/consul/aws"), None);
+    }
+
+    fn module(id: &str, path: &str) -> Module {
+        Module::new(id, id, vec![path.into()]).with_primary_language("hcl")
+    }
+
+    #[test]
+    fn test_link_terraform_modules() {
+        let root = std::env::temp_dir().join(format!("modmap-terraform-{}", std::process::id()));
+        fs::create_dir_all(root.join("stacks/prod")).unwrap();
+        fs::create_dir_all(root.join("modules/vpc")).unwrap();
+        fs::write(root.join("stacks/prod/main.tf"), MAIN_TF).unwrap();
+        fs::write(
+            root.join("modules/vpc/main.tf"),
+            "resource \"aws_vpc\" \"this\" {}\n",
+        )
+        .unwrap();
+
+        let mut map = ModuleMap::new(
+            GeneratorInfo::new("test", "1.0.0"),
+            ProjectMetadata::new("infra", TechStack::new("hcl")),
+            vec![
+                module("prod", "stacks/prod/"),
+                module("vpc", "modules/vpc/"),
+            ],
+            vec![],
+        );
+        let result = map.link_terraform_modules(&root);
+        fs::remove_dir_all(&root).unwrap();
+        result.unwrap();
+
+        assert_eq!(
+            map.find_module("prod").unwrap().dependencies,
+            vec![ModuleDependency::runtime("vpc")]
+        );
+        assert_eq!(map.find_module("vpc").unwrap().dependents, vec!["prod"]);
+        let infra = map.project.tech_stack.infrastructure.unwrap();
+        assert_eq!(infra.state_backend.as_deref(), Some("s3"));
+        assert_eq!(infra.providers.len(), 3);
+    }
+}
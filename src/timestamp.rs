@@ -0,0 +1,136 @@
+//! Lenient timestamp input: RFC 3339, epoch seconds or millis, and date-only
+
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Deserializer};
+
+/// Epoch values at or above this magnitude are read as milliseconds
+const MILLIS_THRESHOLD: i64 = 100_000_000_000;
+
+/// Parses RFC 3339, `YYYY-MM-DD` (midnight UTC) or a decimal epoch value.
+pub fn parse_timestamp(text: &str) -> Option<DateTime<Utc>> {
+    let text = text.trim();
+    if let Ok(parsed) = DateTime::parse_from_rfc3339(text) {
+        return Some(parsed.with_timezone(&Utc));
+    }
+    if let Ok(date) = NaiveDate::parse_from_str(text, "%Y-%m-%d") {
+        return Some(date.and_hms_opt(0, 0, 0)?.and_utc());
+    }
+    match text.parse() {
+        Ok(value) => from_epoch(value),
+        Err(_) => text.parse().ok().and_then(from_epoch_float),
+    }
+}
+
+/// Epoch seconds, or milliseconds for values too large to be plausible seconds.
+pub fn from_epoch(value: i64) -> Option<DateTime<Utc>> {
+    if value.abs() >= MILLIS_THRESHOLD {
+        DateTime::from_timestamp_millis(value)
+    } else {
+        DateTime::from_timestamp(value, 0)
+    }
+}
+
+/// Fractional epoch value under the same seconds/millis rule; `None` for NaN and infinities.
+fn from_epoch_float(value: f64) -> Option<DateTime<Utc>> {
+    if !value.is_finite() {
+        return None;
+    }
+    let millis = if value.abs() >= MILLIS_THRESHOLD as f64 {
+        value
+    } else {
+        value * 1000.0
+    };
+    DateTime::from_timestamp_millis(millis.round() as i64)
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum RawTimestamp {
+    Int(i64),
+    Float(f64),
+    Text(String),
+}
+
+impl RawTimestamp {
+    fn resolve<E: serde::de::Error>(self) -> Result<DateTime<Utc>, E> {
+        let parsed = match &self {
+            Self::Int(value) => from_epoch(*value),
+            Self::Float(value) => from_epoch_float(*value),
+            Self::Text(text) => parse_timestamp(text),
+        };
+        parsed.ok_or_else(|| {
+            E::custom(match self {
+                Self::Int(value) => format!("timestamp {value} is out of range"),
+                Self::Float(value) => format!("timestamp {value} is out of range"),
+                Self::Text(text) => format!("unrecognized timestamp '{text}'"),
+            })
+        })
+    }
+}
+
+/// `deserialize_with` for `DateTime<Utc>` fields; serialization stays RFC 3339.
+pub fn deserialize_timestamp<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<DateTime<Utc>, D::Error> {
+    RawTimestamp::deserialize(deserializer)?.resolve()
+}
+
+/// `deserialize_with` for epoch-second fields, accepting the same inputs.
+pub fn deserialize_epoch_seconds<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<i64, D::Error> {
+    match RawTimestamp::deserialize(deserializer)? {
+        RawTimestamp::Int(value) if value.abs() < MILLIS_THRESHOLD => Ok(value),
+        raw => raw.resolve().map(|t: DateTime<Utc>| t.timestamp()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Deserialize)]
+    struct Stamp {
+        #[serde(deserialize_with = "deserialize_timestamp")]
+        at: DateTime<Utc>,
+    }
+
+    fn parse(json: &str) -> Result<DateTime<Utc>, serde_json::Error> {
+        serde_json::from_str::<Stamp>(&format!(r#"{{"at": {json}}}"#)).map(|s| s.at)
+    }
+
+    #[test]
+    fn test_timestamp_formats() {
+        let expected: DateTime<Utc> = "2026-01-29T00:00:00Z".parse().unwrap();
+        for input in [
+            r#""2026-01-29T00:00:00Z""#,
+            r#""2026-01-29T09:00:00+09:00""#,
+            r#""2026-01-29""#,
+            "1769644800",
+            "1769644800000",
+            "1769644800.0",
+            "1769644800000.0",
+            r#""1769644800""#,
+            r#""1769644800000.4""#,
+        ] {
+            assert_eq!(parse(input).unwrap(), expected, "{input}");
+        }
+        assert!(
+            parse(r#""yesterday""#)
+                .unwrap_err()
+                .to_string()
+                .contains("unrecognized timestamp 'yesterday'")
+        );
+        assert_eq!(
+            parse("1769644800.25").unwrap().timestamp_subsec_millis(),
+            250
+        );
+        assert!(parse(r#""NaN""#).is_err());
+        assert!(parse(r#""inf""#).is_err());
+        assert!(parse("1e300").is_err());
+        assert_eq!(
+            serde_json::to_string(&expected).unwrap(),
+            r#""2026-01-29T00:00:00Z""#
+        );
+    }
+}
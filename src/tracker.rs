@@ -0,0 +1,294 @@
+//! Known issues as GitHub / GitLab issue creation payloads
+
+use std::fmt::Write;
+use std::fs;
+use std::path::PathBuf;
+
+use serde::Serialize;
+use serde_json::{Value, json};
+use sha2::{Digest, Sha256};
+
+use crate::module_map::{Module, ModuleMap};
+use crate::paths::WorkspacePaths;
+use crate::types::{IssueSeverity, KnownIssue};
+
+/// Marker prefix embedded in issue bodies so existing tracker issues can be found again
+pub const FINGERPRINT_MARKER: &str = "modmap-fingerprint:";
+
+/// Issue tracker the payload is shaped for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TrackerFormat {
+    /// `POST /repos/{owner}/{repo}/issues`
+    GitHub,
+    /// `POST /projects/{id}/issues`
+    GitLab,
+}
+
+/// How titles are built and where evidence snippets are read from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IssueTemplate {
+    /// Placeholders: `{id}`, `{module}`, `{severity}`, `{category}`, `{description}`
+    pub title: String,
+    /// Workspace root for reading evidence lines; locations only when `None`
+    pub snippet_root: Option<PathBuf>,
+    /// Longest snippet included per evidence location
+    pub max_snippet_lines: usize,
+}
+
+impl Default for IssueTemplate {
+    fn default() -> Self {
+        Self {
+            title: "[{severity}] {module}: {description}".to_string(),
+            snippet_root: None,
+            max_snippet_lines: 20,
+        }
+    }
+}
+
+impl IssueTemplate {
+    pub fn new(title: impl Into<String>) -> Self {
+        Self {
+            title: title.into(),
+            ..Default::default()
+        }
+    }
+
+    pub fn with_snippet_root(mut self, root: impl Into<PathBuf>) -> Self {
+        self.snippet_root = Some(root.into());
+        self
+    }
+
+    pub fn with_max_snippet_lines(mut self, lines: usize) -> Self {
+        self.max_snippet_lines = lines;
+        self
+    }
+
+    fn snippet(&self, file: &str, start: u32, end: u32) -> Option<String> {
+        let root = self.snippet_root.as_ref()?;
+        let path = WorkspacePaths::join_within(root, file).ok()?;
+        let content = fs::read_to_string(path).ok()?;
+        let start = start.max(1) as usize;
+        let len = (end.max(start as u32) as usize + 1 - start).min(self.max_snippet_lines);
+        let lines: Vec<&str> = content.lines().skip(start - 1).take(len).collect();
+        (!lines.is_empty()).then(|| lines.join("\n"))
+    }
+}
+
+/// Tracker-neutral issue ready to be rendered for one platform
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct IssuePayload {
+    /// Stable across regenerations; use it to skip issues already filed
+    pub idempotency_key: String,
+    pub title: String,
+    pub body: String,
+    pub labels: Vec<String>,
+}
+
+impl IssuePayload {
+    /// JSON body for the tracker's issue creation endpoint.
+    pub fn render(&self, format: TrackerFormat) -> Value {
+        match format {
+            TrackerFormat::GitHub => json!({
+                "title": self.title,
+                "body": self.body,
+                "labels": self.labels,
+            }),
+            TrackerFormat::GitLab => json!({
+                "title": self.title,
+                "description": self.body,
+                "labels": self.labels.join(","),
+            }),
+        }
+    }
+}
+
+impl KnownIssue {
+    /// Hex SHA-256 of the owning module and issue id, independent of wording.
+    pub fn fingerprint(&self, module_id: &str) -> String {
+        Sha256::digest(format!("{module_id}/{}", self.id).as_bytes())
+            .iter()
+            .map(|b| format!("{b:02x}"))
+            .collect()
+    }
+}
+
+fn severity_label(severity: IssueSeverity) -> String {
+    severity.to_string().to_lowercase()
+}
+
+impl ModuleMap {
+    /// Payload for one known issue of `module`, using its effective severity.
+    pub fn issue_payload(
+        &self,
+        module: &Module,
+        issue: &KnownIssue,
+        template: &IssueTemplate,
+    ) -> IssuePayload {
+        let severity = severity_label(self.effective_severity(issue, module));
+        let title = template
+            .title
+            .replace("{id}", &issue.id)
+            .replace("{module}", &module.name)
+            .replace("{severity}", &severity)
+            .replace("{category}", &issue.category.to_string())
+            .replace("{description}", &issue.description);
+
+        let group = self.find_group_containing(&module.id);
+        let domain = self.find_domain_containing_module(&module.id);
+        let fingerprint = issue.fingerprint(&module.id);
+
+        let mut body = format!("{}\n\n## Module\n\n", issue.description);
+        let _ = writeln!(body, "- Module: `{}` ({})", module.id, module.name);
+        if let Some(group) = group {
+            let _ = writeln!(body, "- Group: `{}`", group.id);
+        }
+        if let Some(domain) = domain {
+            let _ = writeln!(body, "- Domain: `{}`", domain.id);
+        }
+        if !module.responsibility.is_empty() {
+            let _ = writeln!(body, "- Responsibility: {}", module.responsibility);
+        }
+        if !module.paths.is_empty() {
+            let _ = writeln!(body, "- Paths: `{}`", module.paths.join("`, `"));
+        }
+        if !issue.evidence.is_empty() {
+            body.push_str("\n## Evidence\n");
+            for location in &issue.evidence {
+                let _ = writeln!(body, "\n`{}`", location.to_reference());
+                if location.is_line_level()
+                    && let Some(snippet) =
+                        template.snippet(&location.file, location.start_line, location.end_line)
+                {
+                    let _ = writeln!(body, "\n```\n{snippet}\n```");
+                }
+            }
+        }
+        if let Some(prevention) = &issue.prevention {
+            let _ = writeln!(body, "\n## Prevention\n\n{prevention}");
+        }
+        let _ = write!(body, "\n<!-- {FINGERPRINT_MARKER}{fingerprint} -->\n");
+
+        let mut labels = vec![
+            format!("severity:{severity}"),
+            format!("category:{}", issue.category),
+        ];
+        if let Some(subcategory) = &issue.subcategory {
+            labels.push(format!("subcategory:{subcategory}"));
+        }
+        labels.push(format!("module:{}", module.id));
+        if let Some(domain) = domain {
+            labels.push(format!("domain:{}", domain.id));
+        }
+
+        IssuePayload {
+            idempotency_key: fingerprint,
+            title,
+            body,
+            labels,
+        }
+    }
+
+    /// Payloads for every known issue, in module order.
+    pub fn issue_payloads(&self, template: &IssueTemplate) -> Vec<IssuePayload> {
+        self.issues_iter()
+            .map(|r| self.issue_payload(r.module, r.issue, template))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        Domain, EvidenceLocation, GeneratorInfo, IssueCategory, ModuleGroup, ProjectMetadata,
+        TechStack,
+    };
+
+    fn module(id: &str) -> Module {
+        Module::new(id, "Payments", vec![format!("src/{id}/")])
+            .with_responsibility("Charges cards")
+            .with_primary_language("rust")
+    }
+
+    #[test]
+    fn test_issue_payloads() {
+        let root = std::env::temp_dir().join(format!("modmap-tracker-{}", std::process::id()));
+        fs::create_dir_all(root.join("src/payments")).unwrap();
+        fs::write(
+            root.join("src/payments/charge.rs"),
+            "fn a() {}\nfn retry() {}\n",
+        )
+        .unwrap();
+
+        let mut payments = module("payments");
+        let mut issue = KnownIssue::new(
+            "double-charge",
+            "Retries may charge twice",
+            IssueSeverity::Critical,
+            IssueCategory::Correctness,
+        );
+        issue
+            .evidence
+            .push(EvidenceLocation::new("src/payments/charge.rs", 2));
+        payments.known_issues.push(issue);
+        let map = ModuleMap::new(
+            GeneratorInfo::new("test", "1.0.0"),
+            ProjectMetadata::new("shop", TechStack::new("rust")),
+            vec![payments],
+            vec![ModuleGroup::new(
+                "billing",
+                "Billing",
+                vec!["payments".into()],
+            )],
+        )
+        .with_domains(vec![Domain::new(
+            "commerce",
+            "Commerce",
+            vec!["billing".into()],
+        )]);
+
+        let template = IssueTemplate::default().with_snippet_root(&root);
+        let payloads = map.issue_payloads(&template);
+        let nested = IssueTemplate::default().with_snippet_root(root.join("src/payments"));
+        assert!(nested.snippet("charge.rs", 1, 1).is_some());
+        assert!(nested.snippet("../payments/charge.rs", 1, 1).is_none());
+        fs::remove_dir_all(&root).unwrap();
+
+        let payload = &payloads[0];
+        assert_eq!(
+            payload.title,
+            "[critical] Payments: Retries may charge twice"
+        );
+        assert_eq!(
+            payload.labels,
+            [
+                "severity:critical",
+                "category:correctness",
+                "module:payments",
+                "domain:commerce"
+            ]
+        );
+        assert!(payload.body.contains("- Group: `billing`\n"));
+        assert!(
+            payload
+                .body
+                .contains("`src/payments/charge.rs:2`\n\n```\nfn retry() {}\n```")
+        );
+        assert!(payload.body.contains(&payload.idempotency_key));
+        assert_eq!(
+            payload.idempotency_key,
+            map.modules[0].known_issues[0].fingerprint("payments")
+        );
+
+        let github = payload.render(TrackerFormat::GitHub);
+        assert_eq!(github["labels"][0], "severity:critical");
+        let gitlab = payload.render(TrackerFormat::GitLab);
+        assert_eq!(gitlab["description"], payload.body);
+        assert!(
+            gitlab["labels"]
+                .as_str()
+                .unwrap()
+                .contains(",module:payments,")
+        );
+    }
+}
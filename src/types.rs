@@ -1,8 +1,11 @@
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::fmt;
 use std::path::Path;
 
+use crate::visibility::Visibility;
+
 #[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
 #[serde(rename_all = "snake_case")]
 pub enum WorkspaceType {
@@ -21,9 +24,16 @@ pub enum ProjectType {
     Library,
     Service,
     Cli,
+    /// Terraform or other infrastructure-as-code
+    Infrastructure,
+    /// Value written by a newer generator, kept verbatim
+    #[serde(untagged)]
+    Unknown(String),
 }
 
-#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, JsonSchema, PartialEq, Eq, Hash)]
+#[derive(
+    Debug, Clone, Default, Serialize, Deserialize, JsonSchema, PartialEq, Eq, PartialOrd, Ord, Hash,
+)]
 #[serde(rename_all = "snake_case")]
 pub enum DependencyType {
     #[default]
@@ -31,12 +41,16 @@ pub enum DependencyType {
     Build,
     Test,
     Optional,
+    /// Value written by a newer generator, kept verbatim
+    #[serde(untagged)]
+    Unknown(String),
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq, Eq, Hash)]
 pub struct ModuleDependency {
+    #[serde(alias = "moduleId")]
     pub module_id: String,
-    #[serde(default)]
+    #[serde(default, alias = "dependencyType", alias = "dependency-type")]
     pub dependency_type: DependencyType,
 }
 
@@ -77,7 +91,10 @@ impl ModuleDependency {
     }
 }
 
-#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema, PartialEq)]
+/// Ordered by file, then start line, end line and columns
+#[derive(
+    Debug, Clone, Default, Serialize, Deserialize, JsonSchema, PartialEq, Eq, PartialOrd, Ord,
+)]
 #[serde(default)]
 pub struct EvidenceLocation {
     pub file: String,
@@ -127,10 +144,77 @@ impl EvidenceLocation {
         }
     }
 
+    pub fn with_columns(mut self, start_column: u32, end_column: u32) -> Self {
+        self.start_column = Some(start_column);
+        self.end_column = Some(end_column);
+        self
+    }
+
     pub fn is_file_level(&self) -> bool {
         self.start_line == 0 && self.end_line == 0
     }
 
+    /// Whether `line` falls in the span; a file-level location contains every line.
+    pub fn contains(&self, line: u32) -> bool {
+        self.is_file_level()
+            || (self.start_line..=self.end_line.max(self.start_line)).contains(&line)
+    }
+
+    /// Same file and at least one shared line (columns are ignored).
+    pub fn overlaps(&self, other: &Self) -> bool {
+        self.file == other.file
+            && (self.is_file_level()
+                || other.is_file_level()
+                || (self.start_line <= other.end_line.max(other.start_line)
+                    && other.start_line <= self.end_line.max(self.start_line)))
+    }
+
+    /// Smallest span covering both, if they overlap or are adjacent in the same file.
+    pub fn merge(&self, other: &Self) -> Option<Self> {
+        if self.file != other.file {
+            return None;
+        }
+        if self.is_file_level() || other.is_file_level() {
+            return Some(Self::file_level(self.file.clone()));
+        }
+        let (first, second) = if self <= other {
+            (self, other)
+        } else {
+            (other, self)
+        };
+        let first_end = first.end_line.max(first.start_line);
+        if second.start_line > first_end + 1 {
+            return None;
+        }
+        let second_end = second.end_line.max(second.start_line);
+        let (end_line, end_column) = match first_end.cmp(&second_end) {
+            std::cmp::Ordering::Greater => (first_end, first.end_column),
+            std::cmp::Ordering::Less => (second_end, second.end_column),
+            std::cmp::Ordering::Equal => (
+                first_end,
+                first
+                    .end_column
+                    .zip(second.end_column)
+                    .map(|(a, b)| a.max(b)),
+            ),
+        };
+        let start_column = if first.start_line == second.start_line {
+            first
+                .start_column
+                .zip(second.start_column)
+                .map(|(a, b)| a.min(b))
+        } else {
+            first.start_column
+        };
+        Some(Self {
+            file: self.file.clone(),
+            start_line: first.start_line,
+            end_line,
+            start_column,
+            end_column,
+        })
+    }
+
     pub fn is_line_level(&self) -> bool {
         self.start_line > 0
     }
@@ -146,10 +230,81 @@ impl EvidenceLocation {
     }
 }
 
+/// Translations keyed by BCP 47 language tag (e.g. "ko", "en-US").
+///
+/// The untranslated field next to it stays the default; lookups fall back
+/// from a regional tag to its primary language.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(transparent)]
+pub struct Localized<T> {
+    pub translations: BTreeMap<String, T>,
+}
+
+pub type LocalizedText = Localized<String>;
+
+impl<T> Default for Localized<T> {
+    fn default() -> Self {
+        Self {
+            translations: BTreeMap::new(),
+        }
+    }
+}
+
+impl<T> Localized<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with(mut self, locale: impl Into<String>, value: T) -> Self {
+        self.translations.insert(locale.into(), value);
+        self
+    }
+
+    pub fn insert(&mut self, locale: impl Into<String>, value: T) {
+        self.translations.insert(locale.into(), value);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.translations.is_empty()
+    }
+
+    /// First translation matching the preference list, in order.
+    pub fn resolve(&self, locales: &[&str]) -> Option<&T> {
+        locales.iter().find_map(|locale| self.lookup(locale))
+    }
+
+    fn lookup(&self, locale: &str) -> Option<&T> {
+        let exact = self
+            .translations
+            .iter()
+            .find(|(tag, _)| tag.eq_ignore_ascii_case(locale));
+        if let Some((_, value)) = exact {
+            return Some(value);
+        }
+        let primary = primary_language(locale);
+        self.translations
+            .iter()
+            .find(|(tag, _)| primary_language(tag).eq_ignore_ascii_case(primary))
+            .map(|(_, value)| value)
+    }
+}
+
+fn primary_language(tag: &str) -> &str {
+    tag.split(['-', '_']).next().unwrap_or(tag)
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct GeneratorInfo {
     pub name: String,
     pub version: String,
+    /// Sections the generator populated (e.g. "dependency-edges", "llm-responsibilities")
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub capabilities: Vec<String>,
+    /// Digest of the generator configuration used for this run
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub config_digest: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub run_id: Option<String>,
 }
 
 impl GeneratorInfo {
@@ -157,8 +312,30 @@ impl GeneratorInfo {
         Self {
             name: name.into(),
             version: version.into(),
+            capabilities: Vec::new(),
+            config_digest: None,
+            run_id: None,
         }
     }
+
+    pub fn with_capabilities(mut self, capabilities: Vec<String>) -> Self {
+        self.capabilities = capabilities;
+        self
+    }
+
+    pub fn with_config_digest(mut self, digest: impl Into<String>) -> Self {
+        self.config_digest = Some(digest.into());
+        self
+    }
+
+    pub fn with_run_id(mut self, run_id: impl Into<String>) -> Self {
+        self.run_id = Some(run_id.into());
+        self
+    }
+
+    pub fn has_capability(&self, capability: &str) -> bool {
+        self.capabilities.iter().any(|c| c == capability)
+    }
 }
 
 #[derive(
@@ -172,7 +349,7 @@ pub enum IssueSeverity {
     Low,
 }
 
-#[derive(Debug, Clone, Copy, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq, Eq, Hash)]
 #[serde(rename_all = "snake_case")]
 pub enum IssueCategory {
     Security,
@@ -181,16 +358,100 @@ pub enum IssueCategory {
     Maintainability,
     Concurrency,
     Compatibility,
+    /// Value written by a newer generator, kept verbatim
+    #[serde(untagged)]
+    Unknown(String),
+}
+
+/// Enum value not known to this version, reported by `ModuleMap::unknown_variants`
+/// and `ProjectManifest::unknown_variants`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnknownVariant {
+    /// Enum type name, e.g. `DependencyType`
+    pub kind: &'static str,
+    pub value: String,
+    /// Where it was found, e.g. `module 'auth'`
+    pub location: String,
+}
+
+impl fmt::Display for UnknownVariant {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "unknown {} '{}' in {}",
+            self.kind, self.value, self.location
+        )
+    }
+}
+
+/// Remaps an issue severity for gating, optionally limited to one category
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
+pub struct SeverityRule {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub category: Option<IssueCategory>,
+    pub severity: IssueSeverity,
+    pub effective: IssueSeverity,
 }
 
+impl SeverityRule {
+    pub fn new(severity: IssueSeverity, effective: IssueSeverity) -> Self {
+        Self {
+            category: None,
+            severity,
+            effective,
+        }
+    }
+
+    pub fn for_category(mut self, category: IssueCategory) -> Self {
+        self.category = Some(category);
+        self
+    }
+
+    pub fn matches(&self, issue: &KnownIssue) -> bool {
+        self.severity == issue.severity
+            && self.category.as_ref().is_none_or(|c| *c == issue.category)
+    }
+}
+
+/// Severity remapping applied to issues of modules within a domain
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
+pub struct SeverityPolicy {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub rules: Vec<SeverityRule>,
+}
+
+impl SeverityPolicy {
+    pub fn new(rules: Vec<SeverityRule>) -> Self {
+        Self { rules }
+    }
+
+    /// Severity from the first matching rule, else the issue's own.
+    pub fn apply(&self, issue: &KnownIssue) -> IssueSeverity {
+        self.rules
+            .iter()
+            .find(|r| r.matches(issue))
+            .map_or(issue.severity, |r| r.effective)
+    }
+}
+
+/// Coding convention observed in a module
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq)]
 pub struct Convention {
+    /// Unique name within the module
+    #[schemars(example = &"error-handling")]
     pub name: String,
+    /// What the convention prescribes
+    #[schemars(example = &"thiserror enums per module")]
     pub pattern: String,
+    /// Why the convention exists
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub rationale: Option<String>,
+    /// Where the convention can be seen
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub evidence: Vec<EvidenceLocation>,
+    /// Free-form labels
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
 }
 
 impl Convention {
@@ -200,6 +461,7 @@ impl Convention {
             pattern: pattern.into(),
             rationale: None,
             evidence: Vec::new(),
+            tags: Vec::new(),
         }
     }
 
@@ -212,6 +474,11 @@ impl Convention {
         self.evidence = evidence;
         self
     }
+
+    pub fn with_tags(mut self, tags: Vec<String>) -> Self {
+        self.tags = tags;
+        self
+    }
 }
 
 impl fmt::Display for Convention {
@@ -220,16 +487,37 @@ impl fmt::Display for Convention {
     }
 }
 
+/// Known problem in a module, worth knowing before editing it
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq)]
 pub struct KnownIssue {
+    /// Unique identifier (kebab-case)
+    #[schemars(example = &"token-leak")]
     pub id: String,
+    /// What is wrong
+    #[schemars(example = &"Session tokens are logged at debug level")]
     pub description: String,
+    /// How bad the issue is; domain policies may override it
     pub severity: IssueSeverity,
+    /// Kind of issue
     pub category: IssueCategory,
+    /// Org-defined refinement of `category` (see `Taxonomy`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub subcategory: Option<String>,
+    /// Free-form labels
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    /// How to avoid making the issue worse
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub prevention: Option<String>,
+    /// Where the issue can be seen
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub evidence: Vec<EvidenceLocation>,
+    /// Translated `description` keyed by language tag
+    #[serde(default, skip_serializing_if = "Localized::is_empty")]
+    pub localized_description: LocalizedText,
+    /// Lowest access level allowed to see this issue
+    #[serde(default, skip_serializing_if = "Visibility::is_public")]
+    pub visibility: Visibility,
 }
 
 impl KnownIssue {
@@ -244,11 +532,47 @@ impl KnownIssue {
             description: description.into(),
             severity,
             category,
+            subcategory: None,
+            tags: Vec::new(),
             prevention: None,
             evidence: Vec::new(),
+            localized_description: LocalizedText::new(),
+            visibility: Visibility::Public,
         }
     }
 
+    pub fn with_visibility(mut self, visibility: Visibility) -> Self {
+        self.visibility = visibility;
+        self
+    }
+
+    pub fn with_localized_description(
+        mut self,
+        locale: impl Into<String>,
+        description: impl Into<String>,
+    ) -> Self {
+        self.localized_description
+            .insert(locale, description.into());
+        self
+    }
+
+    /// Description in the first available preferred locale, else the default.
+    pub fn description_for(&self, locales: &[&str]) -> &str {
+        self.localized_description
+            .resolve(locales)
+            .map_or(&self.description, |s| s)
+    }
+
+    pub fn with_subcategory(mut self, subcategory: impl Into<String>) -> Self {
+        self.subcategory = Some(subcategory.into());
+        self
+    }
+
+    pub fn with_tags(mut self, tags: Vec<String>) -> Self {
+        self.tags = tags;
+        self
+    }
+
     pub fn with_prevention(mut self, prevention: impl Into<String>) -> Self {
         self.prevention = Some(prevention.into());
         self
@@ -266,6 +590,20 @@ impl fmt::Display for KnownIssue {
     }
 }
 
+impl fmt::Display for IssueCategory {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            IssueCategory::Security => write!(f, "security"),
+            IssueCategory::Performance => write!(f, "performance"),
+            IssueCategory::Correctness => write!(f, "correctness"),
+            IssueCategory::Maintainability => write!(f, "maintainability"),
+            IssueCategory::Concurrency => write!(f, "concurrency"),
+            IssueCategory::Compatibility => write!(f, "compatibility"),
+            IssueCategory::Unknown(other) => write!(f, "{other}"),
+        }
+    }
+}
+
 impl fmt::Display for IssueSeverity {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -290,6 +628,8 @@ pub struct TechStack {
     pub test_frameworks: Vec<String>,
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub key_libraries: Vec<LibraryInfo>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub infrastructure: Option<InfrastructureInfo>,
 }
 
 impl TechStack {
@@ -324,6 +664,127 @@ impl TechStack {
         self.key_libraries.push(library);
         self
     }
+
+    pub fn with_infrastructure(mut self, infrastructure: InfrastructureInfo) -> Self {
+        self.infrastructure = Some(infrastructure);
+        self
+    }
+}
+
+/// Infrastructure-as-code metadata (providers, state backend)
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct InfrastructureInfo {
+    /// e.g. `terraform`, `opentofu`
+    pub tool: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub providers: Vec<String>,
+    /// Where state lives, e.g. `s3`, `gcs`, `local`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub state_backend: Option<String>,
+}
+
+impl InfrastructureInfo {
+    pub fn new(tool: impl Into<String>) -> Self {
+        Self {
+            tool: tool.into(),
+            ..Default::default()
+        }
+    }
+
+    pub fn with_providers(mut self, providers: Vec<String>) -> Self {
+        self.providers = providers;
+        self
+    }
+
+    pub fn with_state_backend(mut self, backend: impl Into<String>) -> Self {
+        self.state_backend = Some(backend.into());
+        self
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, JsonSchema, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum ArtifactKind {
+    DockerImage,
+    NpmPackage,
+    Crate,
+    Binary,
+}
+
+/// Something a module or group builds and publishes
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct ArtifactInfo {
+    pub kind: ArtifactKind,
+    pub name: String,
+    /// e.g. `ghcr.io/acme`, `npmjs`, `crates.io`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub registry: Option<String>,
+    /// Dockerfile, package directory or build output path
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub build_path: Option<String>,
+}
+
+impl ArtifactInfo {
+    pub fn new(kind: ArtifactKind, name: impl Into<String>) -> Self {
+        Self {
+            kind,
+            name: name.into(),
+            registry: None,
+            build_path: None,
+        }
+    }
+
+    pub fn with_registry(mut self, registry: impl Into<String>) -> Self {
+        self.registry = Some(registry.into());
+        self
+    }
+
+    pub fn with_build_path(mut self, build_path: impl Into<String>) -> Self {
+        self.build_path = Some(build_path.into());
+        self
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, JsonSchema, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum FlagStatus {
+    #[default]
+    Active,
+    /// Enabled for everyone; the flag and its dead branch can be removed
+    RolledOut,
+    /// Not evaluated or changed for a long time
+    Stale,
+}
+
+/// Feature flag evaluated in a module's code
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct FlagRef {
+    pub key: String,
+    /// e.g. `launchdarkly`, `unleash`, `env`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub provider: Option<String>,
+    #[serde(default)]
+    pub status: FlagStatus,
+}
+
+impl FlagRef {
+    pub fn new(key: impl Into<String>) -> Self {
+        Self {
+            key: key.into(),
+            provider: None,
+            status: FlagStatus::default(),
+        }
+    }
+
+    pub fn with_provider(mut self, provider: impl Into<String>) -> Self {
+        self.provider = Some(provider.into());
+        self
+    }
+
+    pub fn with_status(mut self, status: FlagStatus) -> Self {
+        self.status = status;
+        self
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
@@ -417,6 +878,72 @@ impl DetectedLanguage {
     }
 }
 
+/// Score outside `[0, 1]` or NaN
+#[derive(Debug, Clone, Copy, PartialEq, thiserror::Error)]
+#[error("score {0} is outside [0, 1]")]
+pub struct ScoreError(pub f64);
+
+/// A ratio or score in `[0, 1]`, checked on construction and deserialization
+#[derive(Debug, Clone, Copy, Default, PartialEq, PartialOrd, Serialize, JsonSchema)]
+#[serde(transparent)]
+pub struct Score(#[schemars(range(min = 0.0, max = 1.0))] f64);
+
+impl Score {
+    pub const ZERO: Self = Self(0.0);
+    pub const ONE: Self = Self(1.0);
+
+    pub fn new(value: f64) -> Result<Self, ScoreError> {
+        if (0.0..=1.0).contains(&value) {
+            Ok(Self(value))
+        } else {
+            Err(ScoreError(value))
+        }
+    }
+
+    /// Clamps into `[0, 1]`; NaN becomes 0.
+    pub fn clamped(value: f64) -> Self {
+        if value.is_nan() {
+            Self::ZERO
+        } else {
+            Self(value.clamp(0.0, 1.0))
+        }
+    }
+
+    pub fn get(self) -> f64 {
+        self.0
+    }
+
+    pub fn max(self, other: Self) -> Self {
+        Self(self.0.max(other.0))
+    }
+}
+
+impl TryFrom<f64> for Score {
+    type Error = ScoreError;
+
+    fn try_from(value: f64) -> Result<Self, ScoreError> {
+        Self::new(value)
+    }
+}
+
+impl From<Score> for f64 {
+    fn from(score: Score) -> f64 {
+        score.0
+    }
+}
+
+impl fmt::Display for Score {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl<'de> Deserialize<'de> for Score {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Self::new(f64::deserialize(deserializer)?).map_err(serde::de::Error::custom)
+    }
+}
+
 pub fn is_path_in_scope<P: AsRef<Path>>(path: &Path, allowed_paths: &[P]) -> bool {
     allowed_paths
         .iter()
@@ -427,6 +954,39 @@ pub fn is_path_in_scope<P: AsRef<Path>>(path: &Path, allowed_paths: &[P]) -> boo
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_score_range() {
+        assert_eq!(Score::new(0.5).unwrap().get(), 0.5);
+        assert_eq!(Score::new(1.2), Err(ScoreError(1.2)));
+        assert!(Score::new(f64::NAN).is_err());
+        assert_eq!(Score::clamped(-0.3), Score::ZERO);
+        assert_eq!(Score::clamped(f64::NAN), Score::ZERO);
+
+        assert_eq!(serde_json::from_str::<Score>("0.25").unwrap().get(), 0.25);
+        let err = serde_json::from_str::<Score>("1.5").unwrap_err();
+        assert!(err.to_string().contains("score 1.5 is outside [0, 1]"));
+    }
+
+    #[test]
+    fn test_evidence_location_ranges() {
+        let a = EvidenceLocation::new_range("src/lib.rs", 10, 20).with_columns(4, 1);
+        let b = EvidenceLocation::new_range("src/lib.rs", 21, 30);
+        let c = EvidenceLocation::new_range("src/lib.rs", 40, 45);
+        assert!(a.contains(15) && !a.contains(21));
+        assert!(!a.overlaps(&b));
+        assert!(a.overlaps(&EvidenceLocation::file_level("src/lib.rs")));
+        assert!(!a.overlaps(&EvidenceLocation::new("src/main.rs", 15)));
+
+        let merged = b.merge(&a).unwrap();
+        assert_eq!(merged.to_reference(), "src/lib.rs:10-30");
+        assert_eq!((merged.start_column, merged.end_column), (Some(4), None));
+        assert_eq!(a.merge(&c), None);
+
+        let mut spans = vec![c.clone(), b.clone(), a.clone()];
+        spans.sort();
+        assert_eq!(spans, vec![a, b, c]);
+    }
+
     #[test]
     fn test_evidence_location_reference() {
         let loc = EvidenceLocation::new("src/main.rs", 42);
@@ -478,6 +1038,76 @@ mod tests {
         assert_eq!(stack.build_tools, vec!["cargo"]);
     }
 
+    #[test]
+    fn test_localized_text_resolution() {
+        let text = LocalizedText::new()
+            .with("ko", "인증 처리".to_string())
+            .with("en-US", "Handles auth".to_string());
+
+        assert_eq!(
+            text.resolve(&["ko-KR"]).map(String::as_str),
+            Some("인증 처리")
+        );
+        assert_eq!(
+            text.resolve(&["EN-us"]).map(String::as_str),
+            Some("Handles auth")
+        );
+        assert_eq!(
+            text.resolve(&["ja", "en"]).map(String::as_str),
+            Some("Handles auth")
+        );
+        assert!(text.resolve(&["ja"]).is_none());
+
+        let issue = KnownIssue::new(
+            "leak",
+            "Memory leak",
+            IssueSeverity::Low,
+            IssueCategory::Performance,
+        )
+        .with_localized_description("ko", "메모리 누수");
+        assert_eq!(issue.description_for(&["ko"]), "메모리 누수");
+        assert_eq!(issue.description_for(&["fr"]), "Memory leak");
+    }
+
+    #[test]
+    fn test_generator_info_metadata() {
+        let info = GeneratorInfo::new("claudegen", "1.0.0")
+            .with_capabilities(vec!["dependency-edges".into()])
+            .with_config_digest("sha256:abc")
+            .with_run_id("run-42");
+
+        assert!(info.has_capability("dependency-edges"));
+        assert!(!info.has_capability("llm-responsibilities"));
+
+        let json = serde_json::to_string(&GeneratorInfo::new("bare", "0.1.0")).unwrap();
+        assert_eq!(json, r#"{"name":"bare","version":"0.1.0"}"#);
+        let parsed: GeneratorInfo = serde_json::from_str(&json).unwrap();
+        assert!(parsed.capabilities.is_empty());
+    }
+
+    #[test]
+    fn test_severity_policy() {
+        let policy = SeverityPolicy::new(vec![
+            SeverityRule::new(IssueSeverity::Medium, IssueSeverity::High)
+                .for_category(IssueCategory::Security),
+        ]);
+        let security = KnownIssue::new(
+            "csrf",
+            "Missing CSRF token",
+            IssueSeverity::Medium,
+            IssueCategory::Security,
+        );
+        let perf = KnownIssue::new(
+            "slow",
+            "Slow query",
+            IssueSeverity::Medium,
+            IssueCategory::Performance,
+        );
+
+        assert_eq!(policy.apply(&security), IssueSeverity::High);
+        assert_eq!(policy.apply(&perf), IssueSeverity::Medium);
+    }
+
     #[test]
     fn test_issue_severity_ordering() {
         assert!(IssueSeverity::Critical < IssueSeverity::High);
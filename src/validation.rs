@@ -0,0 +1,550 @@
+use std::fmt;
+use std::path::Path;
+
+use crate::agent::Agent;
+use crate::entity::EntityRef;
+use crate::manifest::ProjectManifest;
+use crate::skill::Skill;
+
+/// Agents provided by Claude Code itself, valid as `Skill.agent` without a definition
+pub const BUILTIN_AGENTS: &[&str] = &["general-purpose", "Explore", "Plan"];
+
+/// Drift between the manifest context maps and the embedded module map
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ContextIssue {
+    /// Context entry for a module that is not in the map
+    UnknownModule { module_id: String },
+    /// Context entry for a group that is not in the map
+    UnknownGroup { group_id: String },
+    /// Context entry for a domain that is not in the map
+    UnknownDomain { domain_id: String },
+    /// Module context points at a group that does not list the module
+    GroupMismatch { module_id: String, group_id: String },
+    /// Context points at a domain that does not list the group
+    DomainMismatch { owner: EntityRef, domain_id: String },
+    /// Context references a rule not present in the flat rule list
+    UnlistedRule { owner: EntityRef, rule: String },
+    /// Context references a skill not present in the flat skill list
+    UnlistedSkill { owner: EntityRef, skill: String },
+    /// Listed resource path does not exist under the manifest root
+    MissingFile { path: String },
+    /// Map module has no context entry (add an empty one to mark it context-free)
+    MissingContext { module_id: String },
+}
+
+impl ContextIssue {
+    /// Stable check id, as used by `PolicyConfig`
+    pub fn check_id(&self) -> &'static str {
+        match self {
+            Self::UnknownModule { .. } => "unknown-module",
+            Self::UnknownGroup { .. } => "unknown-group",
+            Self::UnknownDomain { .. } => "unknown-domain",
+            Self::GroupMismatch { .. } => "group-mismatch",
+            Self::DomainMismatch { .. } => "domain-mismatch",
+            Self::UnlistedRule { .. } => "unlisted-rule",
+            Self::UnlistedSkill { .. } => "unlisted-skill",
+            Self::MissingFile { .. } => "missing-file",
+            Self::MissingContext { .. } => "missing-context",
+        }
+    }
+}
+
+impl fmt::Display for ContextIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnknownModule { module_id } => {
+                write!(f, "context for unknown module '{module_id}'")
+            }
+            Self::UnknownGroup { group_id } => write!(f, "context for unknown group '{group_id}'"),
+            Self::UnknownDomain { domain_id } => {
+                write!(f, "context for unknown domain '{domain_id}'")
+            }
+            Self::GroupMismatch {
+                module_id,
+                group_id,
+            } => write!(
+                f,
+                "module '{module_id}' is not a member of group '{group_id}'"
+            ),
+            Self::DomainMismatch { owner, domain_id } => {
+                write!(f, "'{owner}' is not part of domain '{domain_id}'")
+            }
+            Self::UnlistedRule { owner, rule } => {
+                write!(f, "'{owner}' references unlisted rule '{rule}'")
+            }
+            Self::UnlistedSkill { owner, skill } => {
+                write!(f, "'{owner}' references unlisted skill '{skill}'")
+            }
+            Self::MissingFile { path } => write!(f, "missing file '{path}'"),
+            Self::MissingContext { module_id } => {
+                write!(f, "module '{module_id}' has no context")
+            }
+        }
+    }
+}
+
+/// Broken name reference between skills, agents and contexts
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ReferenceIssue {
+    /// `Skill.agent` names no defined or built-in agent
+    UnknownSkillAgent { skill: String, agent: String },
+    /// `Agent.skills` entry names no defined skill
+    UnknownAgentSkill { agent: String, skill: String },
+    /// Module context skill names no defined skill
+    UnknownContextSkill { module_id: String, skill: String },
+    /// Agent with a consensus role is not listed in the manifest's agents
+    UnlistedConsensusMember { agent: String },
+}
+
+impl fmt::Display for ReferenceIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnknownSkillAgent { skill, agent } => {
+                write!(f, "skill '{skill}' runs in unknown agent '{agent}'")
+            }
+            Self::UnknownAgentSkill { agent, skill } => {
+                write!(f, "agent '{agent}' preloads unknown skill '{skill}'")
+            }
+            Self::UnknownContextSkill { module_id, skill } => {
+                write!(
+                    f,
+                    "module '{module_id}' context references unknown skill '{skill}'"
+                )
+            }
+            Self::UnlistedConsensusMember { agent } => {
+                write!(
+                    f,
+                    "consensus member '{agent}' is not listed in the manifest"
+                )
+            }
+        }
+    }
+}
+
+impl ProjectManifest {
+    /// Checks names across skill and agent definitions and the manifest.
+    ///
+    /// Context skills may name a skill directly or by its `SKILL.md` path.
+    pub fn check_references(&self, skills: &[Skill], agents: &[Agent]) -> Vec<ReferenceIssue> {
+        let skill_defined = |name: &str| {
+            skills
+                .iter()
+                .any(|s| s.name == name || resource_name(name) == Some(s.name.as_str()))
+        };
+        let mut issues = Vec::new();
+
+        for skill in skills {
+            if let Some(agent) = &skill.agent
+                && !BUILTIN_AGENTS.contains(&agent.as_str())
+                && !agents.iter().any(|a| &a.name == agent)
+            {
+                issues.push(ReferenceIssue::UnknownSkillAgent {
+                    skill: skill.name.clone(),
+                    agent: agent.clone(),
+                });
+            }
+        }
+
+        for agent in agents {
+            for skill in agent.skills.iter().filter(|s| !skill_defined(s)) {
+                issues.push(ReferenceIssue::UnknownAgentSkill {
+                    agent: agent.name.clone(),
+                    skill: skill.clone(),
+                });
+            }
+            if agent.consensus.is_some()
+                && !self
+                    .agents
+                    .iter()
+                    .any(|path| path == &agent.name || resource_name(path) == Some(&agent.name))
+            {
+                issues.push(ReferenceIssue::UnlistedConsensusMember {
+                    agent: agent.name.clone(),
+                });
+            }
+        }
+
+        for (module_id, ctx) in sorted(&self.modules) {
+            for skill in ctx.skills.iter().filter(|s| !skill_defined(s)) {
+                issues.push(ReferenceIssue::UnknownContextSkill {
+                    module_id: module_id.clone(),
+                    skill: skill.clone(),
+                });
+            }
+        }
+        issues
+    }
+
+    /// Checks context maps against the module map and flat resource lists.
+    ///
+    /// When `root` is given, every listed rule, skill and agent path is also
+    /// checked for existence relative to it. A module with an empty context
+    /// entry is treated as explicitly context-free. Checks disabled by the
+    /// manifest policy are skipped.
+    pub fn validate_contexts(&self, root: Option<&Path>) -> Vec<ContextIssue> {
+        let map = &self.project;
+        let mut issues = Vec::new();
+
+        for module in &map.modules {
+            if self.get_module_context(&module.id).is_none() {
+                issues.push(ContextIssue::MissingContext {
+                    module_id: module.id.clone(),
+                });
+            }
+        }
+
+        for (module_id, ctx) in sorted(&self.modules) {
+            if map.find_module(module_id).is_none() {
+                issues.push(ContextIssue::UnknownModule {
+                    module_id: module_id.clone(),
+                });
+            }
+            if let Some(group_id) = &ctx.group_id {
+                match map.find_group(group_id) {
+                    None => issues.push(ContextIssue::UnknownGroup {
+                        group_id: group_id.clone(),
+                    }),
+                    Some(group) if !map.group_lists_module(group, module_id) => {
+                        issues.push(ContextIssue::GroupMismatch {
+                            module_id: module_id.clone(),
+                            group_id: group_id.clone(),
+                        })
+                    }
+                    Some(_) => {}
+                }
+            }
+            if let Some(domain_id) = &ctx.domain_id {
+                self.check_domain(
+                    EntityRef::Module(module_id.clone()),
+                    domain_id,
+                    ctx.group_id.as_deref(),
+                    &mut issues,
+                );
+            }
+            self.check_rules(
+                EntityRef::Module(module_id.clone()),
+                &ctx.rules,
+                &mut issues,
+            );
+            for skill in &ctx.skills {
+                if !self.lists_skill(skill) {
+                    issues.push(ContextIssue::UnlistedSkill {
+                        owner: EntityRef::Module(module_id.clone()),
+                        skill: skill.clone(),
+                    });
+                }
+            }
+        }
+
+        for (group_id, ctx) in sorted(&self.groups) {
+            if map.find_group(group_id).is_none() {
+                issues.push(ContextIssue::UnknownGroup {
+                    group_id: group_id.clone(),
+                });
+            }
+            for member in &ctx.member_modules {
+                if map.find_module(member).is_none() {
+                    issues.push(ContextIssue::UnknownModule {
+                        module_id: member.clone(),
+                    });
+                }
+            }
+            if let Some(domain_id) = &ctx.domain_id {
+                self.check_domain(
+                    EntityRef::Group(group_id.clone()),
+                    domain_id,
+                    Some(group_id),
+                    &mut issues,
+                );
+            }
+            self.check_rules(EntityRef::Group(group_id.clone()), &ctx.rules, &mut issues);
+        }
+
+        for (domain_id, ctx) in sorted(&self.domains) {
+            if map.find_domain(domain_id).is_none() {
+                issues.push(ContextIssue::UnknownDomain {
+                    domain_id: domain_id.clone(),
+                });
+            }
+            for member in &ctx.member_groups {
+                if map.find_group(member).is_none() {
+                    issues.push(ContextIssue::UnknownGroup {
+                        group_id: member.clone(),
+                    });
+                }
+            }
+            self.check_rules(
+                EntityRef::Domain(domain_id.clone()),
+                &ctx.rules,
+                &mut issues,
+            );
+        }
+
+        if let Some(root) = root {
+            for path in self.rules.iter().chain(&self.skills).chain(&self.agents) {
+                if !root.join(path).exists() {
+                    issues.push(ContextIssue::MissingFile { path: path.clone() });
+                }
+            }
+        }
+
+        if let Some(policy) = &self.policy {
+            issues.retain(|i| policy.is_enabled(i.check_id()));
+        }
+        issues
+    }
+
+    fn check_domain(
+        &self,
+        owner: EntityRef,
+        domain_id: &str,
+        group_id: Option<&str>,
+        issues: &mut Vec<ContextIssue>,
+    ) {
+        match self.project.find_domain(domain_id) {
+            None => issues.push(ContextIssue::UnknownDomain {
+                domain_id: domain_id.to_string(),
+            }),
+            Some(domain) => {
+                let in_domain = |group_id: &str| {
+                    self.project.domain_lists_group(domain, group_id)
+                        || self
+                            .project
+                            .find_group(group_id)
+                            .and_then(|g| g.domain_id.as_deref())
+                            .and_then(|id| self.project.find_domain(id))
+                            .is_some_and(|d| d.id == domain.id)
+                };
+                if let Some(group_id) = group_id
+                    && !in_domain(group_id)
+                {
+                    issues.push(ContextIssue::DomainMismatch {
+                        owner,
+                        domain_id: domain_id.to_string(),
+                    });
+                }
+            }
+        }
+    }
+
+    fn check_rules(&self, owner: EntityRef, rules: &[String], issues: &mut Vec<ContextIssue>) {
+        for rule in rules {
+            if !self.rules.contains(rule) {
+                issues.push(ContextIssue::UnlistedRule {
+                    owner: owner.clone(),
+                    rule: rule.clone(),
+                });
+            }
+        }
+    }
+
+    /// Skills are referenced by name in contexts but listed by path
+    fn lists_skill(&self, skill: &str) -> bool {
+        self.skills.iter().any(|path| {
+            path == skill
+                || Path::new(path)
+                    .parent()
+                    .and_then(|p| p.file_name())
+                    .is_some_and(|name| name == skill)
+        })
+    }
+}
+
+/// `skills/<name>/SKILL.md` → `<name>`, `agents/<name>.md` → `<name>`
+fn resource_name(path: &str) -> Option<&str> {
+    let path = Path::new(path);
+    let target = if path.file_name().is_some_and(|n| n == "SKILL.md") {
+        path.parent()?.file_name()
+    } else {
+        path.file_stem()
+    };
+    target?.to_str()
+}
+
+fn sorted<V>(map: &std::collections::HashMap<String, V>) -> Vec<(&String, &V)> {
+    let mut entries: Vec<_> = map.iter().collect();
+    entries.sort_by(|a, b| a.0.cmp(b.0));
+    entries
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+    use crate::{
+        ConsensusRole, Domain, DomainContext, GeneratorInfo, GroupContext, Module, ModuleContext,
+        ModuleGroup, ModuleMap, ProjectMetadata, TechStack,
+    };
+
+    fn sample_module(id: &str) -> Module {
+        Module::new(id, id, vec![format!("src/{}/", id)]).with_primary_language("rust")
+    }
+
+    fn sample_manifest() -> ProjectManifest {
+        let map = ModuleMap::new(
+            GeneratorInfo::new("test", "1.0.0"),
+            ProjectMetadata::new("test", TechStack::new("rust")),
+            vec![sample_module("auth"), sample_module("api")],
+            vec![ModuleGroup::new("core", "Core", vec!["auth".into()]).with_domain("identity")],
+        )
+        .with_domains(vec![Domain::new(
+            "identity",
+            "Identity",
+            vec!["core".into()],
+        )]);
+
+        ProjectManifest::new(map)
+            .with_rules(vec!["rules/modules/auth.md".into()])
+            .with_skills(vec!["skills/code-review/SKILL.md".into()])
+    }
+
+    #[test]
+    fn test_consistent_contexts() {
+        let mut modules = HashMap::new();
+        modules.insert(
+            "auth".to_string(),
+            ModuleContext::new()
+                .with_rules(vec!["rules/modules/auth.md".into()])
+                .with_skills(vec!["code-review".into()])
+                .with_group("core")
+                .with_domain("identity"),
+        );
+        modules.insert("api".to_string(), ModuleContext::new());
+
+        let mut groups = HashMap::new();
+        groups.insert(
+            "core".to_string(),
+            GroupContext::new()
+                .with_members(vec!["auth".into()])
+                .with_domain("identity"),
+        );
+
+        let mut domains = HashMap::new();
+        domains.insert(
+            "identity".to_string(),
+            DomainContext::new().with_groups(vec!["core".into()]),
+        );
+
+        let manifest = sample_manifest()
+            .with_modules(modules)
+            .with_groups(groups)
+            .with_domains(domains);
+
+        assert!(manifest.validate_contexts(None).is_empty());
+
+        // Renamed entries still match contexts keyed by their former ids
+        let mut manifest = manifest;
+        let map = &mut manifest.project;
+        map.modules[0].id = "identity-auth".into();
+        map.modules[0].aliases = vec!["auth".into()];
+        map.groups[0].id = "identity-core".into();
+        map.groups[0].aliases = vec!["core".into()];
+        map.domains[0].id = "iam".into();
+        map.domains[0].aliases = vec!["identity".into()];
+        map.groups[0].domain_id = None;
+        assert!(manifest.validate_contexts(None).is_empty());
+    }
+
+    #[test]
+    fn test_detects_drift() {
+        let mut modules = HashMap::new();
+        modules.insert(
+            "auth".to_string(),
+            ModuleContext::new()
+                .with_rules(vec!["rules/modules/missing.md".into()])
+                .with_skills(vec!["deploy".into()])
+                .with_group("ghost"),
+        );
+        modules.insert(
+            "removed".to_string(),
+            ModuleContext::new().with_domain("identity"),
+        );
+
+        let manifest = sample_manifest().with_modules(modules);
+        let issues = manifest.validate_contexts(None);
+
+        assert!(issues.contains(&ContextIssue::MissingContext {
+            module_id: "api".into()
+        }));
+        assert!(issues.contains(&ContextIssue::UnknownModule {
+            module_id: "removed".into()
+        }));
+        assert!(issues.contains(&ContextIssue::UnknownGroup {
+            group_id: "ghost".into()
+        }));
+        assert!(issues.contains(&ContextIssue::UnlistedRule {
+            owner: EntityRef::Module("auth".into()),
+            rule: "rules/modules/missing.md".into()
+        }));
+        assert!(issues.contains(&ContextIssue::UnlistedSkill {
+            owner: EntityRef::Module("auth".into()),
+            skill: "deploy".into()
+        }));
+    }
+
+    #[test]
+    fn test_policy_disables_checks() {
+        let manifest = sample_manifest().with_policy(crate::PolicyConfig::default().with_check(
+            "missing-context",
+            crate::CheckPolicy {
+                enabled: false,
+                severity: None,
+            },
+        ));
+        assert!(manifest.validate_contexts(None).is_empty());
+        assert_eq!(sample_manifest().validate_contexts(None).len(), 2);
+    }
+
+    #[test]
+    fn test_missing_files_on_disk() {
+        let manifest = sample_manifest();
+        let issues = manifest.validate_contexts(Some(Path::new("/nonexistent-root")));
+
+        assert!(issues.contains(&ContextIssue::MissingFile {
+            path: "rules/modules/auth.md".into()
+        }));
+        assert!(issues.contains(&ContextIssue::MissingFile {
+            path: "skills/code-review/SKILL.md".into()
+        }));
+    }
+
+    #[test]
+    fn test_check_references() {
+        let mut modules = HashMap::new();
+        modules.insert(
+            "auth".to_string(),
+            ModuleContext::new()
+                .with_skills(vec!["code-review".into(), "skills/deploy/SKILL.md".into()]),
+        );
+        let manifest = sample_manifest()
+            .with_modules(modules)
+            .with_agents(vec!["agents/reviewer.md".into()]);
+        let skills = vec![
+            Skill::new("code-review", "Review", "").with_agent("reviewer"),
+            Skill::new("explore", "Explore", "").with_agent("Explore"),
+            Skill::new("release", "Release", "").with_agent("releaser"),
+        ];
+        let agents = vec![
+            Agent::new("reviewer", "Reviews", "")
+                .with_skills(vec!["code-review".into(), "lint".into()])
+                .with_consensus(ConsensusRole::new(80)),
+            Agent::new("auditor", "Audits", "").with_consensus(ConsensusRole::new(60)),
+        ];
+
+        let issues: Vec<String> = manifest
+            .check_references(&skills, &agents)
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        assert_eq!(
+            issues,
+            vec![
+                "skill 'release' runs in unknown agent 'releaser'",
+                "agent 'reviewer' preloads unknown skill 'lint'",
+                "consensus member 'auditor' is not listed in the manifest",
+                "module 'auth' context references unknown skill 'skills/deploy/SKILL.md'",
+            ]
+        );
+    }
+}
@@ -0,0 +1,327 @@
+//! Per-agent manifest views trimmed to the agent's scope and a size budget
+
+use std::collections::BTreeSet;
+
+use crate::agent::Agent;
+use crate::manifest::ProjectManifest;
+use crate::module_map::Module;
+use crate::paths::glob_match;
+
+/// Tools whose `Tool(pattern)` specifier is a file path pattern
+const PATH_TOOLS: &[&str] = &["Read", "Edit", "MultiEdit", "Write", "Glob", "Grep"];
+
+/// Consensus priority from which an agent without explicit scope reviews everything
+const REVIEWER_PRIORITY: u8 = 80;
+
+/// Why a module is in an agent's view, most specific first
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ViewReason {
+    /// A path-scoped tool of the agent covers the module
+    ToolPath,
+    /// The module context lists one of the agent's skills
+    Skill,
+    /// An open task suggests this agent for the module
+    Task,
+    /// Veto or high-priority consensus role with no narrower scope
+    Reviewer,
+}
+
+/// Filtered manifest for one agent
+#[derive(Debug, Clone)]
+pub struct ManifestView {
+    pub manifest: ProjectManifest,
+    /// Modules in scope, in inclusion order, with the reason each was included
+    pub included: Vec<(String, ViewReason)>,
+    /// In-scope modules left out to stay within the budget
+    pub dropped: Vec<String>,
+}
+
+/// Pattern inside `Edit(src/auth/**)`; `None` for unscoped or non-path tools.
+fn tool_path(tool: &str) -> Option<&str> {
+    let (name, rest) = tool.split_once('(')?;
+    let pattern = rest.strip_suffix(')')?;
+    PATH_TOOLS.contains(&name.trim()).then_some(pattern.trim())
+}
+
+fn covers(pattern: &str, module: &Module) -> bool {
+    let literal = &pattern[..pattern.find(['*', '?']).unwrap_or(pattern.len())];
+    module.paths.iter().any(|path| {
+        glob_match(pattern, path) || path.starts_with(literal) || literal.starts_with(path.as_str())
+    })
+}
+
+fn json_len<T: serde::Serialize>(value: &T) -> usize {
+    serde_json::to_vec(value).map_or(0, |v| v.len())
+}
+
+impl ProjectManifest {
+    /// Modules relevant to `agent`, most relevant first: modules covered by
+    /// its path-scoped tools, then modules whose contexts list its skills,
+    /// then modules of open tasks suggesting it. An agent with none of these
+    /// but a veto or high-priority consensus role sees every module, ordered
+    /// by risk.
+    pub fn agent_scope<'a>(&'a self, agent: &Agent) -> Vec<(&'a Module, ViewReason)> {
+        let map = &self.project;
+        let mut scope: Vec<(&Module, ViewReason)> = Vec::new();
+        let push = |scope: &mut Vec<(&'a Module, ViewReason)>, module: &'a Module, reason| {
+            if !scope.iter().any(|(m, _)| m.id == module.id) {
+                scope.push((module, reason));
+            }
+        };
+
+        let patterns: Vec<&str> = agent.tools.iter().filter_map(|t| tool_path(t)).collect();
+        for module in &map.modules {
+            if patterns.iter().any(|p| covers(p, module)) {
+                push(&mut scope, module, ViewReason::ToolPath);
+            }
+        }
+        for module in &map.modules {
+            let listed = self
+                .modules
+                .get(&module.id)
+                .is_some_and(|ctx| ctx.skills.iter().any(|s| agent.skills.contains(s)));
+            if listed {
+                push(&mut scope, module, ViewReason::Skill);
+            }
+        }
+        for task in self.tasks.iter().filter(|t| t.is_open()) {
+            if task.suggested_agent.as_deref() == Some(agent.name.as_str()) {
+                for id in &task.module_ids {
+                    if let Some(module) = map.find_module(id) {
+                        push(&mut scope, module, ViewReason::Task);
+                    }
+                }
+            }
+        }
+
+        let reviewer = agent
+            .consensus
+            .as_ref()
+            .is_some_and(|c| c.can_veto || c.priority >= REVIEWER_PRIORITY);
+        if scope.is_empty() && reviewer {
+            let mut modules: Vec<&Module> = map.modules.iter().collect();
+            modules.sort_by(|a, b| {
+                b.metrics
+                    .risk_score
+                    .get()
+                    .total_cmp(&a.metrics.risk_score.get())
+            });
+            for module in modules {
+                push(&mut scope, module, ViewReason::Reviewer);
+            }
+        }
+        scope
+    }
+
+    /// Manifest restricted to `agent_scope(agent)`: those modules with their
+    /// groups, domains, contexts, tasks and refactor plans, the rules listed
+    /// by their contexts plus rules no context lists, and the agent's skills.
+    /// Modules are dropped from the least relevant end until the JSON form
+    /// fits in `max_bytes`.
+    pub fn view_for(&self, agent: &Agent, max_bytes: usize) -> ManifestView {
+        let scope = self.agent_scope(agent);
+        let mut included: Vec<(String, ViewReason)> = Vec::new();
+        let mut dropped: Vec<String> = Vec::new();
+        let mut size = json_len(&self.restrict(agent, &[]));
+        for (module, reason) in scope {
+            let cost = json_len(module)
+                + self.modules.get(&module.id).map_or(0, json_len)
+                + module.id.len() * 4;
+            if size + cost <= max_bytes {
+                size += cost;
+                included.push((module.id.clone(), reason));
+            } else {
+                dropped.push(module.id.clone());
+            }
+        }
+
+        // Per-module costs are estimates; trim further if the whole is still too large.
+        let mut manifest = self.restrict(agent, &included);
+        while json_len(&manifest) > max_bytes
+            && let Some((id, _)) = included.pop()
+        {
+            dropped.insert(0, id);
+            manifest = self.restrict(agent, &included);
+        }
+
+        ManifestView {
+            manifest,
+            included,
+            dropped,
+        }
+    }
+
+    fn restrict(&self, agent: &Agent, included: &[(String, ViewReason)]) -> ProjectManifest {
+        let keep = |id: &String| included.iter().any(|(m, _)| m == id);
+        let mut view = self.clone();
+        view.agents.retain(|a| *a == agent.name);
+        let map = &mut view.project;
+        map.modules.retain(|m| keep(&m.id));
+        for group in &mut map.groups {
+            group.module_ids.retain(keep);
+        }
+        map.groups.retain(|g| !g.module_ids.is_empty());
+        let group_ids: Vec<String> = map.groups.iter().map(|g| g.id.clone()).collect();
+        for domain in &mut map.domains {
+            domain.group_ids.retain(|g| group_ids.contains(g));
+        }
+        map.domains.retain(|d| !d.group_ids.is_empty());
+        let domain_ids: Vec<String> = map.domains.iter().map(|d| d.id.clone()).collect();
+        if let Some(graph) = &mut map.dependency_graph {
+            graph.edges.retain(|e| keep(&e.from) && keep(&e.to));
+        }
+
+        view.modules.retain(|id, _| keep(id));
+        view.groups.retain(|id, _| group_ids.contains(id));
+        view.domains.retain(|id, _| domain_ids.contains(id));
+
+        let listed_anywhere: BTreeSet<&String> = self
+            .modules
+            .values()
+            .flat_map(|c| &c.rules)
+            .chain(self.groups.values().flat_map(|c| &c.rules))
+            .chain(self.domains.values().flat_map(|c| &c.rules))
+            .collect();
+        let listed_in_view: BTreeSet<String> = view
+            .modules
+            .values()
+            .flat_map(|c| &c.rules)
+            .chain(view.groups.values().flat_map(|c| &c.rules))
+            .chain(view.domains.values().flat_map(|c| &c.rules))
+            .cloned()
+            .collect();
+        view.rules
+            .retain(|r| !listed_anywhere.contains(r) || listed_in_view.contains(r));
+
+        let context_skills: BTreeSet<String> = view
+            .modules
+            .values()
+            .flat_map(|c| c.skills.iter().cloned())
+            .collect();
+        view.skills
+            .retain(|s| agent.skills.contains(s) || context_skills.contains(s));
+
+        view.tasks.retain(|t| {
+            t.suggested_agent.as_deref() == Some(agent.name.as_str())
+                || t.module_ids.iter().any(keep)
+                || t.domain_ids.iter().any(|d| domain_ids.contains(d))
+        });
+        view.refactor_plans
+            .retain(|p| p.affected_modules.iter().any(keep));
+        view.tracked.retain(|f| {
+            view.project
+                .modules
+                .iter()
+                .any(|m| m.contains_file(&f.path))
+        });
+        view.redacted(agent.access, &[])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        ConsensusRole, GeneratorInfo, ModuleContext, ModuleGroup, ModuleMap, ModuleMetrics,
+        ProjectMetadata, Task, TechStack,
+    };
+
+    fn module(id: &str, risk: f64) -> Module {
+        Module::new(id, id, vec![format!("src/{id}/")])
+            .with_responsibility("x".repeat(200))
+            .with_primary_language("rust")
+            .with_metrics(ModuleMetrics::new(0.5, 0.5, risk))
+    }
+
+    fn manifest() -> ProjectManifest {
+        let map = ModuleMap::new(
+            GeneratorInfo::new("test", "1.0.0"),
+            ProjectMetadata::new("shop", TechStack::new("rust")),
+            vec![
+                module("auth", 0.2),
+                module("billing", 0.9),
+                module("search", 0.5),
+            ],
+            vec![
+                ModuleGroup::new("identity", "Identity", vec!["auth".into()]),
+                ModuleGroup::new("commerce", "Commerce", vec!["billing".into()]),
+            ],
+        );
+        ProjectManifest::new(map)
+            .with_rules(vec![
+                "project".into(),
+                "auth-rules".into(),
+                "billing-rules".into(),
+            ])
+            .with_skills(vec!["invoice-debug".into(), "search-tuning".into()])
+            .with_agents(vec!["auth-dev".into(), "billing-dev".into()])
+            .with_modules(
+                [
+                    (
+                        "auth".to_string(),
+                        ModuleContext::new().with_rules(vec!["auth-rules".into()]),
+                    ),
+                    (
+                        "billing".to_string(),
+                        ModuleContext::new()
+                            .with_rules(vec!["billing-rules".into()])
+                            .with_skills(vec!["invoice-debug".into()]),
+                    ),
+                ]
+                .into(),
+            )
+            .with_tasks(vec![
+                Task::new("t1", "Tune ranking")
+                    .with_modules(vec!["search".into()])
+                    .with_suggested_agent("auth-dev"),
+            ])
+    }
+
+    #[test]
+    fn test_view_for_agent_scope() {
+        let manifest = manifest();
+        let agent = Agent::new("auth-dev", "Auth work", "prompt")
+            .with_tools(vec!["Read".into(), "Edit(src/auth/**)".into()]);
+
+        let view = manifest.view_for(&agent, usize::MAX);
+        assert_eq!(
+            view.included,
+            [
+                ("auth".to_string(), ViewReason::ToolPath),
+                ("search".to_string(), ViewReason::Task)
+            ]
+        );
+        let map = &view.manifest.project;
+        assert_eq!(map.modules.len(), 2);
+        assert_eq!(map.groups.len(), 1);
+        assert_eq!(view.manifest.rules, ["project", "auth-rules"]);
+        assert!(view.manifest.skills.is_empty());
+        assert_eq!(view.manifest.agents, ["auth-dev"]);
+        assert_eq!(view.manifest.tasks.len(), 1);
+
+        let billing = Agent::new("billing-dev", "Billing", "prompt")
+            .with_skills(vec!["invoice-debug".into()]);
+        let view = manifest.view_for(&billing, usize::MAX);
+        assert_eq!(view.included, [("billing".to_string(), ViewReason::Skill)]);
+        assert_eq!(view.manifest.skills, ["invoice-debug"]);
+        assert!(view.manifest.tasks.is_empty());
+    }
+
+    #[test]
+    fn test_view_for_budget() {
+        let manifest = manifest();
+        let reviewer = Agent::new("architect", "Reviews", "prompt")
+            .with_consensus(ConsensusRole::new(50).with_veto());
+        let full = manifest.view_for(&reviewer, usize::MAX);
+        assert_eq!(full.included[0].0, "billing");
+        assert_eq!(full.included.len(), 3);
+
+        let budget = serde_json::to_vec(&full.manifest).unwrap().len() - 100;
+        let trimmed = manifest.view_for(&reviewer, budget);
+        assert_eq!(trimmed.dropped, ["auth"]);
+        assert!(serde_json::to_vec(&trimmed.manifest).unwrap().len() <= budget);
+
+        let nobody = Agent::new("helper", "No scope", "prompt");
+        assert!(manifest.view_for(&nobody, usize::MAX).included.is_empty());
+    }
+}
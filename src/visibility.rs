@@ -0,0 +1,181 @@
+//! Access levels on rules and known issues, and redaction for low-trust agents
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::manifest::{ProjectManifest, issue_key};
+use crate::module_map::ModuleMap;
+use crate::rule::Rule;
+
+/// Who may see a rule or known issue; ordered from least to most trusted
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Default,
+    PartialEq,
+    Eq,
+    Hash,
+    PartialOrd,
+    Ord,
+    Serialize,
+    Deserialize,
+    JsonSchema,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum Visibility {
+    #[default]
+    Public,
+    Team,
+    /// e.g. exploitable security bugs
+    Maintainers,
+}
+
+impl Visibility {
+    pub fn is_public(&self) -> bool {
+        *self == Self::Public
+    }
+
+    /// Whether an agent declaring `level` may see something with this visibility.
+    pub fn visible_to(&self, level: Visibility) -> bool {
+        *self <= level
+    }
+}
+
+/// Visibility of the named rule; rules missing from `rules` are public.
+pub(crate) fn rule_visibility(rules: &[Rule], name: &str) -> Visibility {
+    rules
+        .iter()
+        .find(|r| r.name == name)
+        .map_or(Visibility::Public, |r| r.visibility)
+}
+
+impl ModuleMap {
+    /// Drops known issues `level` may not see.
+    pub fn redact(&mut self, level: Visibility) {
+        for module in &mut self.modules {
+            module
+                .known_issues
+                .retain(|issue| issue.visibility.visible_to(level));
+        }
+    }
+}
+
+impl ProjectManifest {
+    /// Copy of the manifest as seen at `level`: hidden known issues are removed
+    /// from the map and module contexts, hidden rules from every rule list.
+    pub fn redacted(&self, level: Visibility, rules: &[Rule]) -> ProjectManifest {
+        let mut manifest = self.clone();
+        let hidden_issues: Vec<(String, String)> = self
+            .project
+            .issues_iter()
+            .filter(|r| !r.issue.visibility.visible_to(level))
+            .map(|r| (r.module.id.clone(), r.issue.id.clone()))
+            .collect();
+        manifest.project.redact(level);
+
+        let visible = |name: &String| rule_visibility(rules, name).visible_to(level);
+        manifest.rules.retain(visible);
+        for (module_id, ctx) in &mut manifest.modules {
+            ctx.rules.retain(visible);
+            ctx.issues.retain(|entry| {
+                let id = issue_key(entry);
+                !hidden_issues.iter().any(|(m, i)| m == module_id && i == id)
+            });
+        }
+        for ctx in manifest.groups.values_mut() {
+            ctx.rules.retain(visible);
+        }
+        for ctx in manifest.domains.values_mut() {
+            ctx.rules.retain(visible);
+        }
+        manifest
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+    use crate::{
+        Agent, ContextResolver, GeneratorInfo, IssueCategory, IssueSeverity, KnownIssue, Module,
+        ModuleContext, ProjectMetadata, TechStack,
+    };
+
+    fn module(id: &str) -> Module {
+        Module::new(id, id, vec![format!("src/{id}/")])
+            .with_primary_language("rust")
+            .with_known_issues(vec![
+                KnownIssue::new(
+                    "slow-login",
+                    "Login takes 2s",
+                    IssueSeverity::Low,
+                    IssueCategory::Performance,
+                ),
+                KnownIssue::new(
+                    "token-replay",
+                    "Refresh tokens can be replayed",
+                    IssueSeverity::Critical,
+                    IssueCategory::Security,
+                )
+                .with_visibility(Visibility::Maintainers),
+            ])
+    }
+
+    #[test]
+    fn test_redaction_by_access_level() {
+        let map = ModuleMap::new(
+            GeneratorInfo::new("test", "1.0.0"),
+            ProjectMetadata::new("shop", TechStack::new("rust")),
+            vec![module("auth")],
+            vec![],
+        );
+        let rules = vec![
+            Rule::new("auth-style", vec![]),
+            Rule::new("auth-threats", vec![]).with_visibility(Visibility::Team),
+        ];
+        let manifest = ProjectManifest::new(map)
+            .with_rules(vec!["auth-style".into(), "auth-threats".into()])
+            .with_modules(HashMap::from([(
+                "auth".to_string(),
+                ModuleContext::new()
+                    .with_rules(vec!["auth-style".into(), "auth-threats".into()])
+                    .with_issues(vec![
+                        "[LOW] slow-login: Login takes 2s".into(),
+                        "[CRITICAL] token-replay: Refresh tokens can be replayed".into(),
+                    ]),
+            )]));
+
+        let public = ContextResolver::new(&manifest)
+            .with_access(Visibility::Public, &rules)
+            .resolve("src/auth/mod.rs");
+        assert_eq!(public.rules, ["auth-style"]);
+        let team = ContextResolver::new(&manifest)
+            .with_access(Visibility::Team, &rules)
+            .resolve("src/auth/mod.rs");
+        assert_eq!(team.rules, ["auth-style", "auth-threats"]);
+
+        let redacted = manifest.redacted(Visibility::Team, &rules);
+        assert_eq!(redacted.rules, ["auth-style", "auth-threats"]);
+        assert_eq!(redacted.project.modules[0].known_issues.len(), 1);
+        assert_eq!(
+            redacted.modules["auth"].issues,
+            ["[LOW] slow-login: Login takes 2s"]
+        );
+        let full = manifest.redacted(Visibility::Maintainers, &rules);
+        assert_eq!(full.modules["auth"].issues.len(), 2);
+
+        let bot = Agent::new("bot", "Automation", "prompt").with_tools(vec!["Edit(src/**)".into()]);
+        let view = manifest.view_for(&bot, usize::MAX);
+        assert_eq!(view.manifest.project.modules[0].known_issues.len(), 1);
+        let json = serde_json::to_value(&rules[1]).unwrap();
+        assert_eq!(json["visibility"], "team");
+        assert!(
+            serde_json::to_value(&rules[0])
+                .unwrap()
+                .get("visibility")
+                .is_none()
+        );
+    }
+}
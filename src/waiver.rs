@@ -0,0 +1,334 @@
+//! Waivers: approved, expiring exceptions to enforcement checks
+
+use chrono::NaiveDate;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::boundaries::{BoundaryViolation, ImportEdge, ImportViolation};
+use crate::entity::EntityRef;
+use crate::fitness::{FitnessReport, FitnessRunner};
+use crate::manifest::ProjectManifest;
+
+/// Exception to one check for one target
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct Waiver {
+    /// Check id (`layering`, `undeclared-dependency`, ...) or `*`
+    pub check: String,
+    /// What the check reports on, written `kind:id`; `None`, written `*`,
+    /// covers every target. A bare id from older manifests reads as a module.
+    #[serde(with = "target_repr")]
+    #[schemars(with = "String")]
+    pub target: Option<EntityRef>,
+    pub justification: String,
+    /// Last day the waiver applies; open-ended when unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires_on: Option<NaiveDate>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub approver: Option<String>,
+}
+
+mod target_repr {
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    use crate::entity::EntityRef;
+
+    pub fn serialize<S: Serializer>(
+        target: &Option<EntityRef>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        match target {
+            Some(entity) => serializer.collect_str(entity),
+            None => serializer.serialize_str("*"),
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<EntityRef>, D::Error> {
+        let text = String::deserialize(deserializer)?;
+        if text == "*" {
+            return Ok(None);
+        }
+        if !text.contains(':') {
+            return Ok(Some(EntityRef::Module(text)));
+        }
+        text.parse().map(Some).map_err(serde::de::Error::custom)
+    }
+}
+
+impl Waiver {
+    pub fn new(
+        check: impl Into<String>,
+        target: EntityRef,
+        justification: impl Into<String>,
+    ) -> Self {
+        Self {
+            check: check.into(),
+            target: Some(target),
+            justification: justification.into(),
+            expires_on: None,
+            approver: None,
+        }
+    }
+
+    /// Waiver of `check` for every target.
+    pub fn for_all(check: impl Into<String>, justification: impl Into<String>) -> Self {
+        Self {
+            check: check.into(),
+            target: None,
+            justification: justification.into(),
+            expires_on: None,
+            approver: None,
+        }
+    }
+
+    pub fn with_expiry(mut self, expires_on: NaiveDate) -> Self {
+        self.expires_on = Some(expires_on);
+        self
+    }
+
+    pub fn with_approver(mut self, approver: impl Into<String>) -> Self {
+        self.approver = Some(approver.into());
+        self
+    }
+
+    pub fn is_expired(&self, today: NaiveDate) -> bool {
+        self.expires_on.is_some_and(|date| date < today)
+    }
+
+    /// Whether this waiver, still active on `today`, covers `check` on
+    /// `target`. Targets match on kind and id, so `group:auth` does not cover
+    /// module `auth`; findings without a target need a `*` waiver.
+    pub fn covers(&self, check: &str, target: Option<&EntityRef>, today: NaiveDate) -> bool {
+        !self.is_expired(today)
+            && (self.check == "*" || self.check == check)
+            && match (&self.target, target) {
+                (None, _) => true,
+                (Some(waived), Some(target)) => waived == target,
+                (Some(_), None) => false,
+            }
+    }
+}
+
+impl ProjectManifest {
+    fn check_enabled(&self, check: &str) -> bool {
+        self.policy.as_ref().is_none_or(|p| p.is_enabled(check))
+    }
+
+    /// Active waiver covering `check` on `target` that meets the policy's
+    /// waiver requirements, if a policy is set.
+    pub fn waiver_for(
+        &self,
+        check: &str,
+        target: Option<&EntityRef>,
+        today: NaiveDate,
+    ) -> Option<&Waiver> {
+        self.waivers.iter().find(|w| {
+            w.covers(check, target, today)
+                && self
+                    .policy
+                    .as_ref()
+                    .is_none_or(|p| p.accepts_waiver(w, today))
+        })
+    }
+
+    /// Waivers past their expiry date; their checks are enforced again.
+    pub fn expired_waivers(&self, today: NaiveDate) -> Vec<&Waiver> {
+        self.waivers
+            .iter()
+            .filter(|w| w.is_expired(today))
+            .collect()
+    }
+
+    /// `check_boundaries` and `verify_consumers` findings of enabled checks
+    /// not covered by a waiver.
+    pub fn boundary_violations(&self, today: NaiveDate) -> Vec<BoundaryViolation> {
+        let mut violations = self.project.check_boundaries();
+        violations.extend(self.project.verify_consumers());
+        violations.retain(|v| {
+            self.check_enabled(v.check_id())
+                && self
+                    .waiver_for(v.check_id(), Some(&v.target()), today)
+                    .is_none()
+        });
+        violations
+    }
+
+    /// `check_imports` findings not covered by a waiver on the importing module.
+    pub fn import_violations(
+        &self,
+        edges: &[ImportEdge],
+        today: NaiveDate,
+    ) -> Vec<ImportViolation> {
+        let mut violations = self.project.check_imports(edges);
+        violations.retain(|v| {
+            self.check_enabled(v.check_id())
+                && self
+                    .waiver_for(
+                        v.check_id(),
+                        Some(&EntityRef::Module(v.from_module.clone())),
+                        today,
+                    )
+                    .is_none()
+        });
+        violations
+    }
+
+    /// Runs fitness functions, moving waived diagnostics out of each result.
+    /// The manifest policy applies unless the runner has its own.
+    pub fn run_fitness(&self, runner: &FitnessRunner, today: NaiveDate) -> FitnessReport {
+        let mut report = match &self.policy {
+            Some(policy) if !runner.has_policy() => {
+                runner.run_with_policy(&self.project, Some(policy))
+            }
+            _ => runner.run(&self.project),
+        };
+        for result in &mut report.results {
+            let (waived, kept) = std::mem::take(&mut result.diagnostics)
+                .into_iter()
+                .partition(|d| {
+                    self.waiver_for(&result.check, d.target.as_ref(), today)
+                        .is_some()
+                });
+            result.diagnostics = kept;
+            result.waived = waived;
+        }
+        report
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        Domain, GeneratorInfo, Layering, Module, ModuleGroup, ModuleMap, NoCycles,
+        OwnershipCoverage, ProjectMetadata, SharedPath, TechStack,
+    };
+
+    fn date(s: &str) -> NaiveDate {
+        s.parse().unwrap()
+    }
+
+    fn module(id: &str, paths: &[&str], dependencies: &[&str]) -> Module {
+        Module::new(id, id, paths.iter().map(|p| p.to_string()).collect())
+            .with_dependencies(
+                dependencies
+                    .iter()
+                    .map(|d| crate::ModuleDependency::runtime(*d))
+                    .collect(),
+            )
+            .with_primary_language("rust")
+    }
+
+    fn sample() -> ProjectManifest {
+        let map = ModuleMap::new(
+            GeneratorInfo::new("test", "1.0.0"),
+            ProjectMetadata::new("shop", TechStack::new("rust")),
+            vec![
+                module("api", &["src/api/", "proto/api/"], &["core"]),
+                module("core", &["src/core/"], &["api"]),
+                module("legacy", &["proto/legacy/"], &[]),
+            ],
+            vec![ModuleGroup::new(
+                "backend",
+                "Backend",
+                vec!["api".into(), "core".into()],
+            )],
+        )
+        .with_domains(vec![
+            Domain::new("platform", "Platform", vec!["backend".into()]).with_owner("@acme/core"),
+        ])
+        .with_shared_paths(vec![SharedPath::new("proto/", vec![])]);
+        ProjectManifest::new(map).with_waivers(vec![
+            Waiver::new(
+                "claimed-shared-path",
+                EntityRef::Module("api".into()),
+                "Moving protos in Q3",
+            )
+            .with_approver("@acme/architects")
+            .with_expiry(date("2026-09-30")),
+            Waiver::new(
+                "no-cycles",
+                EntityRef::Module("api".into()),
+                "Untangling in progress",
+            )
+            .with_approver("@lead"),
+            Waiver::for_all("ownership-coverage", "Owners assigned next sprint")
+                .with_expiry(date("2026-01-31")),
+        ])
+    }
+
+    #[test]
+    fn test_waived_boundary_violations() {
+        let manifest = sample();
+        let targets = |today| -> Vec<String> {
+            manifest
+                .boundary_violations(date(today))
+                .iter()
+                .map(|v| v.target().id().to_string())
+                .collect()
+        };
+        assert_eq!(targets("2026-06-01"), vec!["legacy"]);
+        assert_eq!(targets("2026-10-01"), vec!["api", "legacy"]);
+    }
+
+    #[test]
+    fn test_waived_fitness_and_expiry() {
+        let manifest = sample();
+        let runner = FitnessRunner::new()
+            .with(NoCycles)
+            .with(Layering)
+            .with(OwnershipCoverage);
+        let report = manifest.run_fitness(&runner, date("2026-06-01"));
+        assert!(!report.has_errors());
+        assert_eq!(report.results[0].waived.len(), 1);
+        assert_eq!(report.results[2].diagnostics.len(), 1);
+        assert_eq!(
+            report.results[2].diagnostics[0].target,
+            Some(EntityRef::Module("legacy".into()))
+        );
+
+        let expired = manifest.expired_waivers(date("2026-06-01"));
+        assert_eq!(expired.len(), 1);
+        assert_eq!(expired[0].check, "ownership-coverage");
+        let module = |id: &str| EntityRef::Module(id.into());
+        assert!(
+            manifest
+                .waiver_for("no-cycles", Some(&module("api")), date("2099-01-01"))
+                .is_some()
+        );
+        assert!(
+            manifest
+                .waiver_for("no-cycles", Some(&module("core")), date("2026-06-01"))
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_waiver_targets_match_kind() {
+        let waiver = Waiver::new("layering", EntityRef::Group("auth".into()), "Splitting");
+        let today = date("2026-06-01");
+        assert!(waiver.covers("layering", Some(&EntityRef::Group("auth".into())), today));
+        assert!(!waiver.covers("layering", Some(&EntityRef::Module("auth".into())), today));
+        assert!(!waiver.covers("layering", None, today));
+        assert!(Waiver::for_all("layering", "Later").covers("layering", None, today));
+
+        let json = serde_json::to_value(&waiver).unwrap();
+        assert_eq!(json["target"], "group:auth");
+        let legacy: Waiver = serde_json::from_value(serde_json::json!({
+            "check": "*",
+            "target": "api",
+            "justification": "Imported",
+        }))
+        .unwrap();
+        assert_eq!(legacy.target, Some(EntityRef::Module("api".into())));
+        let all: Waiver = serde_json::from_value(serde_json::json!({
+            "check": "layering",
+            "target": "*",
+            "justification": "Imported",
+        }))
+        .unwrap();
+        assert_eq!(all.target, None);
+        assert_eq!(serde_json::to_value(&all).unwrap()["target"], "*");
+    }
+}
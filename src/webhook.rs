@@ -0,0 +1,289 @@
+//! Notification payloads for map regeneration
+
+use std::fmt;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+
+use crate::diff::MapDiff;
+use crate::module_map::ModuleMap;
+use crate::types::IssueSeverity;
+
+pub const MAP_CHANGED_EVENT: &str = "map.changed";
+
+/// How disruptive a regeneration is, ordered from least to most
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum ChangeSeverity {
+    #[default]
+    None,
+    /// Responsibility, dependency or non-critical issue changes
+    Minor,
+    /// Module, group or domain boundaries changed
+    Major,
+    /// New critical issues or removed domains
+    Critical,
+}
+
+impl fmt::Display for ChangeSeverity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::None => write!(f, "none"),
+            Self::Minor => write!(f, "minor"),
+            Self::Major => write!(f, "major"),
+            Self::Critical => write!(f, "critical"),
+        }
+    }
+}
+
+/// Counts of each change kind in a `MapDiff`
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DiffSummary {
+    pub added_modules: usize,
+    pub removed_modules: usize,
+    #[serde(default)]
+    pub renamed_modules: usize,
+    pub changed_modules: usize,
+    pub added_groups: usize,
+    pub removed_groups: usize,
+    pub changed_groups: usize,
+    pub added_domains: usize,
+    pub removed_domains: usize,
+    /// `module_id/issue_id` of added issues whose effective severity is critical
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub new_critical_issues: Vec<String>,
+}
+
+impl DiffSummary {
+    fn boundaries_changed(&self) -> bool {
+        self.added_modules
+            + self.removed_modules
+            + self.renamed_modules
+            + self.added_groups
+            + self.removed_groups
+            + self.changed_groups
+            + self.added_domains
+            + self.removed_domains
+            > 0
+    }
+}
+
+/// Webhook target format for `MapChangedEvent::render`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WebhookFormat {
+    /// The event itself
+    Json,
+    /// Slack incoming webhook (`text` + markdown section block)
+    Slack,
+    /// Microsoft Teams incoming webhook (MessageCard)
+    Teams,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MapChangedEvent {
+    pub event: String,
+    pub project: String,
+    #[serde(deserialize_with = "crate::timestamp::deserialize_timestamp")]
+    pub timestamp: DateTime<Utc>,
+    pub severity: ChangeSeverity,
+    pub summary: DiffSummary,
+    /// Domain owners of every changed module, group or domain
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub affected_owners: Vec<String>,
+    pub diff: MapDiff,
+}
+
+impl MapChangedEvent {
+    pub fn new(old: &ModuleMap, new: &ModuleMap) -> Self {
+        Self::from_diff(ModuleMap::diff(old, new), old, new)
+    }
+
+    /// Builds the event from an existing diff of `old` → `new`.
+    pub fn from_diff(diff: MapDiff, old: &ModuleMap, new: &ModuleMap) -> Self {
+        let mut new_critical_issues = Vec::new();
+        for module in &new.modules {
+            let added: Option<&[String]> = if diff.added_modules.contains(&module.id) {
+                None
+            } else if let Some(change) = diff
+                .changed_modules
+                .iter()
+                .find(|c| c.module_id == module.id)
+            {
+                Some(&change.added_issues)
+            } else {
+                continue;
+            };
+            for issue in &module.known_issues {
+                let is_new = added.is_none_or(|ids| ids.contains(&issue.id));
+                if is_new && new.effective_severity(issue, module) == IssueSeverity::Critical {
+                    new_critical_issues.push(format!("{}/{}", module.id, issue.id));
+                }
+            }
+        }
+
+        let summary = DiffSummary {
+            added_modules: diff.added_modules.len(),
+            removed_modules: diff.removed_modules.len(),
+            renamed_modules: diff.renamed_modules.len(),
+            changed_modules: diff.changed_modules.len(),
+            added_groups: diff.added_groups.len(),
+            removed_groups: diff.removed_groups.len(),
+            changed_groups: diff.changed_groups.len(),
+            added_domains: diff.added_domains.len(),
+            removed_domains: diff.removed_domains.len(),
+            new_critical_issues,
+        };
+        let severity = if !summary.new_critical_issues.is_empty() || summary.removed_domains > 0 {
+            ChangeSeverity::Critical
+        } else if summary.boundaries_changed() {
+            ChangeSeverity::Major
+        } else if !diff.is_empty() {
+            ChangeSeverity::Minor
+        } else {
+            ChangeSeverity::None
+        };
+
+        Self {
+            event: MAP_CHANGED_EVENT.to_string(),
+            project: new.project.name.clone(),
+            timestamp: Utc::now(),
+            severity,
+            affected_owners: diff.route_by_owner(old, new).owners.into_keys().collect(),
+            summary,
+            diff,
+        }
+    }
+
+    /// One-line human summary, e.g. "shop: major map change (+1/-0 modules, ...)".
+    pub fn headline(&self) -> String {
+        let s = &self.summary;
+        let mut text = format!(
+            "{}: {} map change (+{}/-{} modules, {} changed, +{}/-{} groups, +{}/-{} domains)",
+            self.project,
+            self.severity,
+            s.added_modules,
+            s.removed_modules,
+            s.changed_modules,
+            s.added_groups,
+            s.removed_groups,
+            s.added_domains,
+            s.removed_domains
+        );
+        if !s.new_critical_issues.is_empty() {
+            text.push_str(&format!(
+                "; new critical issues: {}",
+                s.new_critical_issues.join(", ")
+            ));
+        }
+        if !self.affected_owners.is_empty() {
+            text.push_str(&format!("; owners: {}", self.affected_owners.join(", ")));
+        }
+        text
+    }
+
+    pub fn render(&self, format: WebhookFormat) -> Value {
+        match format {
+            WebhookFormat::Json => serde_json::to_value(self).unwrap_or(Value::Null),
+            WebhookFormat::Slack => json!({
+                "text": self.headline(),
+                "blocks": [{
+                    "type": "section",
+                    "text": { "type": "mrkdwn", "text": self.diff.to_markdown() },
+                }],
+            }),
+            WebhookFormat::Teams => json!({
+                "@type": "MessageCard",
+                "@context": "https://schema.org/extensions",
+                "summary": self.headline(),
+                "title": format!("{} module map changed", self.project),
+                "text": self.diff.to_markdown(),
+            }),
+        }
+    }
+
+    pub fn to_json(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string_pretty(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        Domain, GeneratorInfo, IssueCategory, KnownIssue, Module, ModuleGroup, ProjectMetadata,
+        TechStack,
+    };
+
+    fn module(id: &str) -> Module {
+        Module::new(id, id, vec![])
+            .with_responsibility(format!("{id} module"))
+            .with_primary_language("rust")
+    }
+
+    fn map(modules: Vec<Module>) -> ModuleMap {
+        let ids = modules.iter().map(|m| m.id.clone()).collect();
+        ModuleMap::new(
+            GeneratorInfo::new("test", "1.0.0"),
+            ProjectMetadata::new("shop", TechStack::new("rust")),
+            modules,
+            vec![ModuleGroup::new("core", "Core", ids)],
+        )
+        .with_domains(vec![
+            Domain::new("identity", "Identity", vec!["core".into()]).with_owner("team-id"),
+        ])
+    }
+
+    #[test]
+    fn test_severity_levels() {
+        let old = map(vec![module("auth")]);
+        assert_eq!(
+            MapChangedEvent::new(&old, &old).severity,
+            ChangeSeverity::None
+        );
+
+        let mut reworded = module("auth");
+        reworded.responsibility = "Sessions".into();
+        let event = MapChangedEvent::new(&old, &map(vec![reworded]));
+        assert_eq!(event.severity, ChangeSeverity::Minor);
+        assert_eq!(event.affected_owners, vec!["team-id"]);
+
+        let event = MapChangedEvent::new(&old, &map(vec![module("auth"), module("db")]));
+        assert_eq!(event.severity, ChangeSeverity::Major);
+
+        let mut vulnerable = module("auth");
+        vulnerable.known_issues = vec![KnownIssue::new(
+            "sqli",
+            "Unescaped query",
+            IssueSeverity::Critical,
+            IssueCategory::Security,
+        )];
+        let event = MapChangedEvent::new(&old, &map(vec![vulnerable]));
+        assert_eq!(event.severity, ChangeSeverity::Critical);
+        assert_eq!(event.summary.new_critical_issues, vec!["auth/sqli"]);
+    }
+
+    #[test]
+    fn test_render_formats() {
+        let old = map(vec![module("auth")]);
+        let event = MapChangedEvent::new(&old, &map(vec![module("auth"), module("db")]));
+
+        assert!(
+            event
+                .headline()
+                .starts_with("shop: major map change (+1/-0 modules")
+        );
+
+        let json = event.render(WebhookFormat::Json);
+        assert_eq!(json["event"], "map.changed");
+        assert_eq!(json["severity"], "major");
+        assert_eq!(json["diff"]["added_modules"][0], "db");
+
+        let slack = event.render(WebhookFormat::Slack);
+        assert_eq!(slack["text"], event.headline());
+        let teams = event.render(WebhookFormat::Teams);
+        assert_eq!(teams["@type"], "MessageCard");
+    }
+}
@@ -0,0 +1,256 @@
+//! Ranked "what to work on next" queue over modules
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+use serde::Serialize;
+
+use crate::manifest::ProjectManifest;
+use crate::module_map::{Module, ModuleMap};
+use crate::store::ContentStore;
+use crate::types::IssueSeverity;
+
+/// Weights of the work queue signals, plus per-module staleness inputs
+#[derive(Debug, Clone, PartialEq)]
+pub struct WorkQueuePolicy {
+    pub priority_weight: f64,
+    pub issue_weight: f64,
+    pub staleness_weight: f64,
+    /// Per-module staleness, 0.0–1.0 (see `ProjectManifest::module_drift`)
+    pub staleness: HashMap<String, f64>,
+}
+
+impl Default for WorkQueuePolicy {
+    fn default() -> Self {
+        Self {
+            priority_weight: 0.5,
+            issue_weight: 0.3,
+            staleness_weight: 0.2,
+            staleness: HashMap::new(),
+        }
+    }
+}
+
+impl WorkQueuePolicy {
+    pub fn with_weights(mut self, priority: f64, issues: f64, staleness: f64) -> Self {
+        self.priority_weight = priority;
+        self.issue_weight = issues;
+        self.staleness_weight = staleness;
+        self
+    }
+
+    pub fn with_staleness(mut self, staleness: HashMap<String, f64>) -> Self {
+        self.staleness = staleness;
+        self
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct WorkItem<'a> {
+    #[serde(serialize_with = "serialize_id")]
+    pub module: &'a Module,
+    pub score: f64,
+    /// Human-readable signals behind `score`, strongest first
+    pub reasons: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub suggested_skill: Option<String>,
+}
+
+fn serialize_id<S: serde::Serializer>(module: &&Module, s: S) -> Result<S::Ok, S::Error> {
+    s.serialize_str(&module.id)
+}
+
+fn severity_weight(severity: IssueSeverity) -> f64 {
+    match severity {
+        IssueSeverity::Critical => 1.0,
+        IssueSeverity::High => 0.6,
+        IssueSeverity::Medium => 0.3,
+        IssueSeverity::Low => 0.1,
+    }
+}
+
+impl ModuleMap {
+    /// Top `limit` modules by weighted priority, open issues and staleness.
+    pub fn work_queue(&self, policy: &WorkQueuePolicy, limit: usize) -> Vec<WorkItem<'_>> {
+        let mut items: Vec<WorkItem> = self
+            .modules
+            .iter()
+            .map(|module| {
+                let priority = module.metrics.priority_score();
+                let severities: Vec<IssueSeverity> = module
+                    .known_issues
+                    .iter()
+                    .map(|i| self.effective_severity(i, module))
+                    .collect();
+                let issues = severities
+                    .iter()
+                    .map(|s| severity_weight(*s))
+                    .sum::<f64>()
+                    .min(1.0);
+                let staleness = policy.staleness.get(&module.id).copied().unwrap_or(0.0);
+
+                let mut signals = vec![
+                    (
+                        policy.priority_weight * priority,
+                        format!("priority score {priority:.2}"),
+                    ),
+                    (
+                        policy.staleness_weight * staleness,
+                        format!("{:.0}% stale", staleness * 100.0),
+                    ),
+                ];
+                if !severities.is_empty() {
+                    let critical = severities
+                        .iter()
+                        .filter(|s| **s == IssueSeverity::Critical)
+                        .count();
+                    let mut reason = format!("{} open issue(s)", severities.len());
+                    if critical > 0 {
+                        reason.push_str(&format!(", {critical} critical"));
+                    }
+                    signals.push((policy.issue_weight * issues, reason));
+                }
+                signals.sort_by(|a, b| b.0.total_cmp(&a.0));
+
+                WorkItem {
+                    module,
+                    score: signals.iter().map(|(s, _)| s).sum(),
+                    reasons: signals
+                        .into_iter()
+                        .filter(|(s, _)| *s > 0.0)
+                        .map(|(_, r)| r)
+                        .collect(),
+                    suggested_skill: None,
+                }
+            })
+            .collect();
+        items.sort_by(|a, b| {
+            b.score
+                .total_cmp(&a.score)
+                .then_with(|| a.module.id.cmp(&b.module.id))
+        });
+        items.truncate(limit);
+        items
+    }
+}
+
+impl ProjectManifest {
+    /// `ModuleMap::work_queue` with each item's first context skill suggested.
+    pub fn work_queue(&self, policy: &WorkQueuePolicy, limit: usize) -> Vec<WorkItem<'_>> {
+        let mut items = self.project.work_queue(policy, limit);
+        for item in &mut items {
+            item.suggested_skill = self
+                .get_module_context(&item.module.id)
+                .and_then(|ctx| ctx.skills.first().cloned());
+        }
+        items
+    }
+
+    /// Share of each module's tracked files under `root` that changed or vanished.
+    /// Generated files are left out.
+    pub fn module_drift(&self, root: &Path) -> HashMap<String, f64> {
+        let mut counts: HashMap<&str, (usize, usize)> = HashMap::new();
+        for file in self
+            .tracked
+            .iter()
+            .filter(|f| !self.project.is_generated(&f.path))
+        {
+            let drifted = !fs::read_to_string(root.join(&file.path))
+                .is_ok_and(|content| ContentStore::hash(&content) == file.hash);
+            for module in self
+                .project
+                .modules
+                .iter()
+                .filter(|m| m.contains_file(&file.path))
+            {
+                let entry = counts.entry(&module.id).or_default();
+                entry.0 += usize::from(drifted);
+                entry.1 += 1;
+            }
+        }
+        counts
+            .into_iter()
+            .map(|(id, (drifted, total))| (id.to_string(), drifted as f64 / total as f64))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        GeneratorInfo, IssueCategory, KnownIssue, ModuleContext, ModuleMetrics, ProjectMetadata,
+        TechStack, TrackedFile,
+    };
+
+    fn module(id: &str, value: f64, risk: f64) -> Module {
+        Module::new(id, id, vec![format!("src/{id}/")])
+            .with_primary_language("rust")
+            .with_metrics(ModuleMetrics::new(0.5, value, risk))
+    }
+
+    fn manifest() -> ProjectManifest {
+        let mut map = ModuleMap::new(
+            GeneratorInfo::new("test", "1.0.0"),
+            ProjectMetadata::new("shop", TechStack::new("rust")),
+            vec![
+                module("auth", 0.2, 0.2),
+                module("billing", 0.8, 0.5),
+                module("docs", 0.1, 0.0),
+            ],
+            vec![],
+        );
+        map.modules[0].known_issues.push(KnownIssue::new(
+            "token-leak",
+            "Tokens logged",
+            IssueSeverity::Critical,
+            IssueCategory::Security,
+        ));
+        ProjectManifest::new(map).with_modules(HashMap::from([(
+            "auth".to_string(),
+            ModuleContext::new().with_skills(vec!["security-review".into()]),
+        )]))
+    }
+
+    #[test]
+    fn test_work_queue() {
+        let manifest = manifest();
+        let queue = manifest.work_queue(&WorkQueuePolicy::default(), 2);
+        let ids: Vec<&str> = queue.iter().map(|i| i.module.id.as_str()).collect();
+        // auth: .5×.2 + .3×1 = .4, billing: .5×.68 = .34
+        assert_eq!(ids, vec!["auth", "billing"]);
+        assert_eq!(
+            queue[0].reasons,
+            vec!["1 open issue(s), 1 critical", "priority score 0.20"]
+        );
+        assert_eq!(queue[0].suggested_skill.as_deref(), Some("security-review"));
+        assert!(queue[1].suggested_skill.is_none());
+
+        let policy = WorkQueuePolicy::default()
+            .with_staleness(HashMap::from([("docs".to_string(), 1.0)]))
+            .with_weights(0.0, 0.0, 1.0);
+        let queue = manifest.project.work_queue(&policy, 1);
+        assert_eq!(queue[0].module.id, "docs");
+        assert_eq!(queue[0].reasons, vec!["100% stale"]);
+    }
+
+    #[test]
+    fn test_module_drift() {
+        let root = std::env::temp_dir().join(format!("modmap-work-queue-{}", std::process::id()));
+        std::fs::create_dir_all(root.join("src/auth")).unwrap();
+        std::fs::write(root.join("src/auth/a.rs"), "fn a() {}").unwrap();
+
+        let manifest = manifest().with_tracked(vec![
+            TrackedFile::new("src/auth/a.rs", ContentStore::hash("fn a() {}"), 0),
+            TrackedFile::new("src/auth/b.rs", ContentStore::hash("fn b() {}"), 0),
+            TrackedFile::new("src/billing/c.rs", ContentStore::hash("fn c() {}"), 0),
+        ]);
+        let drift = manifest.module_drift(&root);
+        std::fs::remove_dir_all(&root).unwrap();
+
+        assert_eq!(drift["auth"], 0.5);
+        assert_eq!(drift["billing"], 1.0);
+        assert!(!drift.contains_key("docs"));
+    }
+}